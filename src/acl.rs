@@ -0,0 +1,589 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet, VecDeque};
+use std::fs;
+use std::path::PathBuf;
+
+use sha2::{Digest, Sha256};
+
+use crate::commands::{Command, ACL_CATEGORIES, COMMAND_TABLE};
+use crate::error::{RedisError, RedisResult};
+use crate::glob::string_match;
+use crate::log;
+use crate::models::value::Value;
+use crate::utilities::now_millis;
+
+pub const DEFAULT_USER: &str = "default";
+
+pub const ACL_LOG_MAX_LEN: usize = 128;
+
+// Denials of the same kind closer together than this share a log entry.
+const ACL_LOG_GROUPING_MILLIS: i64 = 60_000;
+
+pub fn hash_password(password: &str) -> String {
+    hex::encode(Sha256::digest(password.as_bytes()))
+}
+
+// Constant time, so response times don't leak how much of a guess is right.
+fn secure_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyPattern {
+    pub pattern: String,
+    pub read: bool,
+    pub write: bool,
+}
+
+impl KeyPattern {
+    fn allows(&self, key: &str, read: bool, write: bool) -> bool {
+        (!read || self.read) && (!write || self.write) && string_match(&self.pattern, key, false)
+    }
+
+    fn rule(&self) -> String {
+        let prefix = match (self.read, self.write) {
+            (true, false) => "%R~",
+            (false, true) => "%W~",
+            _ => "~",
+        };
+        format!("{}{}", prefix, self.pattern)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Denial {
+    Command(&'static str),
+    Key(String),
+}
+
+impl Denial {
+    fn reason(&self) -> &'static str {
+        match self {
+            Denial::Command(_) => "command",
+            Denial::Key(_) => "key",
+        }
+    }
+
+    fn object(&self) -> String {
+        match self {
+            Denial::Command(name) => name.to_string(),
+            Denial::Key(key) => key.clone(),
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Denial::Command(name) => {
+                format!("This user has no permissions to run the '{}' command", name)
+            }
+            Denial::Key(key) => format!("This user has no permissions to access the '{}' key", key),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct User {
+    pub name: String,
+    pub enabled: bool,
+    pub nopass: bool,
+    pub passwords: BTreeSet<String>,
+    pub keys: Vec<KeyPattern>,
+    pub channels: Vec<String>,
+    allowed: HashSet<&'static str>,
+    // The command rules in the order they were applied, for ACL LIST.
+    command_rules: Vec<String>,
+}
+
+impl User {
+    pub fn new(name: &str) -> Self {
+        User {
+            name: name.to_string(),
+            enabled: false,
+            nopass: false,
+            passwords: BTreeSet::new(),
+            keys: Vec::new(),
+            channels: Vec::new(),
+            allowed: HashSet::new(),
+            command_rules: Vec::new(),
+        }
+    }
+
+    fn default_user() -> Self {
+        let mut user = User::new(DEFAULT_USER);
+        for rule in ["on", "nopass", "allkeys", "allchannels", "allcommands"] {
+            user.apply_rule(rule).expect("default user rules are valid");
+        }
+        user
+    }
+
+    pub fn apply_rule(&mut self, rule: &str) -> Result<(), &'static str> {
+        match rule.to_lowercase().as_str() {
+            "on" => self.enabled = true,
+            "off" => self.enabled = false,
+            "nopass" => {
+                self.nopass = true;
+                self.passwords.clear();
+            }
+            "resetpass" => {
+                self.nopass = false;
+                self.passwords.clear();
+            }
+            "allkeys" => self.keys = vec![KeyPattern::parse("~*")?],
+            "resetkeys" => self.keys.clear(),
+            "allchannels" => self.channels = vec!["*".to_string()],
+            "resetchannels" => self.channels.clear(),
+            "allcommands" => self.apply_command_rule("+@all")?,
+            "nocommands" => self.apply_command_rule("-@all")?,
+            "reset" => {
+                for rule in [
+                    "resetpass",
+                    "resetkeys",
+                    "resetchannels",
+                    "nocommands",
+                    "off",
+                ] {
+                    self.apply_rule(rule)?;
+                }
+            }
+            _ => match rule.chars().next() {
+                Some('>') => {
+                    self.passwords.insert(hash_password(&rule[1..]));
+                    self.nopass = false;
+                }
+                Some('<') => {
+                    if !self.passwords.remove(&hash_password(&rule[1..])) {
+                        return Err(NO_SUCH_PASSWORD);
+                    }
+                }
+                Some('#') => {
+                    self.passwords.insert(parse_hash(&rule[1..])?);
+                    self.nopass = false;
+                }
+                Some('!') => {
+                    if !self.passwords.remove(&parse_hash(&rule[1..])?) {
+                        return Err(NO_SUCH_PASSWORD);
+                    }
+                }
+                Some('~') | Some('%') => self.keys.push(KeyPattern::parse(rule)?),
+                Some('&') => self.channels.push(rule[1..].to_string()),
+                Some('+') | Some('-') => self.apply_command_rule(rule)?,
+                _ => return Err(SYNTAX_ERROR),
+            },
+        }
+        Ok(())
+    }
+
+    fn apply_command_rule(&mut self, rule: &str) -> Result<(), &'static str> {
+        let rule = rule.to_lowercase();
+        let (allow, target) = rule.split_at(1);
+        let commands: Vec<&'static str> = match target.strip_prefix('@') {
+            Some("all") => COMMAND_TABLE.values().map(|command| command.name).collect(),
+            Some(category) if ACL_CATEGORIES.contains(&category) => COMMAND_TABLE
+                .values()
+                .filter(|command| command.acl_categories.contains(&category))
+                .map(|command| command.name)
+                .collect(),
+            Some(_) => return Err(UNKNOWN_COMMAND),
+            None => match COMMAND_TABLE.get(target.to_uppercase().as_str()) {
+                Some(command) => vec![command.name],
+                None => return Err(UNKNOWN_COMMAND),
+            },
+        };
+
+        if allow == "+" {
+            self.allowed.extend(commands);
+        } else {
+            for name in commands {
+                self.allowed.remove(name);
+            }
+        }
+        if target == "@all" {
+            self.command_rules.clear();
+        }
+        self.command_rules.push(rule);
+        Ok(())
+    }
+
+    pub fn command_rules(&self) -> String {
+        let mut rules = self.command_rules.clone();
+        if !matches!(rules.first().map(String::as_str), Some("+@all" | "-@all")) {
+            rules.insert(0, "-@all".to_string());
+        }
+        rules.join(" ")
+    }
+
+    pub fn key_rules(&self) -> String {
+        let rules: Vec<String> = self.keys.iter().map(KeyPattern::rule).collect();
+        rules.join(" ")
+    }
+
+    pub fn channel_rules(&self) -> String {
+        let rules: Vec<String> = self.channels.iter().map(|c| format!("&{}", c)).collect();
+        rules.join(" ")
+    }
+
+    pub fn describe(&self) -> String {
+        let mut rules = vec![
+            format!("user {}", self.name),
+            if self.enabled { "on" } else { "off" }.to_string(),
+        ];
+        if self.nopass {
+            rules.push("nopass".to_string());
+        }
+        rules.extend(self.passwords.iter().map(|hash| format!("#{}", hash)));
+        rules.extend(self.keys.iter().map(KeyPattern::rule));
+        if self.channels.is_empty() {
+            rules.push("resetchannels".to_string());
+        } else {
+            rules.push(self.channel_rules());
+        }
+        rules.push(self.command_rules());
+        rules.join(" ")
+    }
+
+    fn check_password(&self, password: &str) -> bool {
+        let hash = hash_password(password);
+        self.nopass
+            || self
+                .passwords
+                .iter()
+                .any(|stored| secure_eq(stored.as_bytes(), hash.as_bytes()))
+    }
+
+    // SORT needs it to follow BY and GET patterns, whose keys are only known
+    // once the elements are read.
+    pub fn can_read_all_keys(&self) -> bool {
+        self.keys
+            .iter()
+            .any(|pattern| pattern.read && pattern.pattern == "*")
+    }
+
+    // A disabled user may only run the commands that need no authentication.
+    pub fn check(&self, command: &Command, args: &[Value]) -> Result<(), Denial> {
+        if command.has_flag("no-auth") {
+            return Ok(());
+        }
+        if !self.enabled || !self.allowed.contains(command.name) {
+            return Err(Denial::Command(command.name));
+        }
+
+        for (index, key) in command.keys(args).into_iter().enumerate() {
+            let access = command.key_access(index);
+            if !self
+                .keys
+                .iter()
+                .any(|pattern| pattern.allows(&key, access.reads(), access.writes()))
+            {
+                return Err(Denial::Key(key));
+            }
+        }
+        Ok(())
+    }
+}
+
+const SYNTAX_ERROR: &str = "Syntax error";
+const UNKNOWN_COMMAND: &str = "Unknown command or category name in ACL";
+const NO_SUCH_PASSWORD: &str = "The password you are trying to remove from the user does not exist";
+const BAD_HASH: &str = "The password hash must be exactly 64 characters and contain only \
+                        lowercase hexadecimal characters";
+
+fn parse_hash(hash: &str) -> Result<String, &'static str> {
+    let valid = hash.len() == 64 && hash.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'));
+    if valid {
+        Ok(hash.to_string())
+    } else {
+        Err(BAD_HASH)
+    }
+}
+
+impl KeyPattern {
+    fn parse(rule: &str) -> Result<Self, &'static str> {
+        if let Some(pattern) = rule.strip_prefix('~') {
+            return Ok(KeyPattern {
+                pattern: pattern.to_string(),
+                read: true,
+                write: true,
+            });
+        }
+        let (flags, pattern) = rule[1..].split_once('~').ok_or(SYNTAX_ERROR)?;
+        let (mut read, mut write) = (false, false);
+        for flag in flags.chars() {
+            match flag.to_ascii_uppercase() {
+                'R' => read = true,
+                'W' => write = true,
+                _ => return Err(SYNTAX_ERROR),
+            }
+        }
+        if !read && !write {
+            return Err(SYNTAX_ERROR);
+        }
+        Ok(KeyPattern {
+            pattern: pattern.to_string(),
+            read,
+            write,
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LogEntry {
+    pub count: u64,
+    pub reason: &'static str,
+    pub object: String,
+    pub username: String,
+    pub entry_id: u64,
+    pub created: i64,
+    pub updated: i64,
+}
+
+impl LogEntry {
+    pub fn to_value(&self, now: i64) -> Value {
+        let field = |name: &str| Value::BulkString(name.to_string());
+        Value::Array(vec![
+            field("count"),
+            Value::Integer(self.count as i64),
+            field("reason"),
+            field(self.reason),
+            field("context"),
+            field("toplevel"),
+            field("object"),
+            field(&self.object),
+            field("username"),
+            field(&self.username),
+            field("age-seconds"),
+            field(&format!("{:.3}", (now - self.created) as f64 / 1000.0)),
+            field("entry-id"),
+            Value::Integer(self.entry_id as i64),
+            field("timestamp-created"),
+            Value::Integer(self.created),
+            field("timestamp-last-updated"),
+            Value::Integer(self.updated),
+        ])
+    }
+}
+
+#[derive(Debug)]
+pub struct Acl {
+    users: BTreeMap<String, User>,
+    log: VecDeque<LogEntry>,
+    next_entry_id: u64,
+    file: Option<PathBuf>,
+}
+
+impl Default for Acl {
+    fn default() -> Self {
+        let mut users = BTreeMap::new();
+        users.insert(DEFAULT_USER.to_string(), User::default_user());
+        Acl {
+            users,
+            log: VecDeque::new(),
+            next_entry_id: 0,
+            file: None,
+        }
+    }
+}
+
+impl Acl {
+    pub fn new(requirepass: Option<&str>, file: Option<PathBuf>) -> Self {
+        let mut acl = Acl {
+            file,
+            ..Default::default()
+        };
+        if acl.file.as_ref().is_some_and(|file| file.exists()) {
+            if let Err(e) = acl.load() {
+                log!("Failed to load ACL file: {}", e);
+            }
+        } else if let Some(password) = requirepass {
+            acl.set_requirepass(password);
+        }
+        acl
+    }
+
+    pub fn set_requirepass(&mut self, password: &str) {
+        let user = self.users.get_mut(DEFAULT_USER).unwrap();
+        user.apply_rule("resetpass").unwrap();
+        let rule = match password {
+            "" => "nopass".to_string(),
+            password => format!(">{}", password),
+        };
+        user.apply_rule(&rule).unwrap();
+    }
+
+    pub fn user(&self, name: &str) -> Option<&User> {
+        self.users.get(name)
+    }
+
+    pub fn users(&self) -> impl Iterator<Item = &User> {
+        self.users.values()
+    }
+
+    // Either every rule applies or the user is left untouched.
+    pub fn set_user(&mut self, name: &str, rules: &[String]) -> RedisResult<()> {
+        let mut user = self
+            .users
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| User::new(name));
+        for rule in rules {
+            user.apply_rule(rule).map_err(|reason| {
+                RedisError::err(format!(
+                    "Error in ACL SETUSER modifier '{}': {}",
+                    rule, reason
+                ))
+            })?;
+        }
+        self.users.insert(name.to_string(), user);
+        Ok(())
+    }
+
+    pub fn delete_user(&mut self, name: &str) -> RedisResult<bool> {
+        if name == DEFAULT_USER {
+            return Err(RedisError::err("The 'default' user cannot be removed"));
+        }
+        Ok(self.users.remove(name).is_some())
+    }
+
+    pub fn requires_auth(&self) -> bool {
+        self.users
+            .get(DEFAULT_USER)
+            .is_none_or(|user| !(user.enabled && user.nopass))
+    }
+
+    pub fn authenticate(&mut self, username: &str, password: &str) -> RedisResult<()> {
+        let valid = self
+            .users
+            .get(username)
+            .is_some_and(|user| user.enabled && user.check_password(password));
+        if valid {
+            Ok(())
+        } else {
+            self.record("auth", "AUTH".to_string(), username);
+            Err(RedisError::WrongPass)
+        }
+    }
+
+    pub fn check(&mut self, username: &str, command: &Command, args: &[Value]) -> RedisResult<()> {
+        let denial = match self.users.get(username) {
+            Some(user) => match user.check(command, args) {
+                Ok(()) => return Ok(()),
+                Err(denial) => denial,
+            },
+            None => Denial::Command(command.name),
+        };
+        self.record(denial.reason(), denial.object(), username);
+        Err(RedisError::NoPerm(match denial {
+            Denial::Command(name) => format!(
+                "User {} has no permissions to run the '{}' command",
+                username, name
+            ),
+            Denial::Key(_) => "No permissions to access a key".to_string(),
+        }))
+    }
+
+    fn record(&mut self, reason: &'static str, object: String, username: &str) {
+        let now = now_millis();
+        let similar = self.log.iter().position(|entry| {
+            entry.reason == reason
+                && entry.object == object
+                && entry.username == username
+                && now - entry.updated < ACL_LOG_GROUPING_MILLIS
+        });
+        if let Some(position) = similar {
+            let mut entry = self.log.remove(position).unwrap();
+            entry.count += 1;
+            entry.updated = now;
+            self.log.push_front(entry);
+            return;
+        }
+
+        self.log.push_front(LogEntry {
+            count: 1,
+            reason,
+            object,
+            username: username.to_string(),
+            entry_id: self.next_entry_id,
+            created: now,
+            updated: now,
+        });
+        self.next_entry_id += 1;
+        self.log.truncate(ACL_LOG_MAX_LEN);
+    }
+
+    pub fn log(&self) -> impl Iterator<Item = &LogEntry> {
+        self.log.iter()
+    }
+
+    pub fn reset_log(&mut self) {
+        self.log.clear();
+    }
+
+    fn file(&self) -> RedisResult<&PathBuf> {
+        self.file.as_ref().ok_or_else(|| {
+            RedisError::err(
+                "This Redis instance is not configured to use an ACL file. You may want to \
+                 specify users via the ACL SETUSER command and then issue a CONFIG REWRITE \
+                 (assuming you have a Redis configuration file set) in order to store users in \
+                 the Redis configuration.",
+            )
+        })
+    }
+
+    pub fn save(&self) -> RedisResult<()> {
+        let file = self.file()?;
+        let mut contents = String::new();
+        for user in self.users.values() {
+            contents.push_str(&user.describe());
+            contents.push('\n');
+        }
+        let temp = file.with_extension("tmp");
+        fs::write(&temp, contents)
+            .and_then(|_| fs::rename(&temp, file))
+            .map_err(|e| {
+                RedisError::err(format!("There was an error trying to save the ACLs: {}", e))
+            })
+    }
+
+    // Nothing changes unless the whole file is valid.
+    pub fn load(&mut self) -> RedisResult<()> {
+        let file = self.file()?;
+        let contents = fs::read_to_string(file).map_err(|e| {
+            RedisError::err(format!(
+                "Error loading ACLs, opening file '{}': {}",
+                file.display(),
+                e
+            ))
+        })?;
+
+        let mut users = BTreeMap::new();
+        for (number, line) in contents.lines().enumerate() {
+            let error = |reason: &str| {
+                RedisError::err(format!(
+                    "{}:{}: {}. WARNING: ACL errors detected, no change to the previously \
+                     active ACL rules was performed",
+                    file.display(),
+                    number + 1,
+                    reason
+                ))
+            };
+            let mut words = line.split_whitespace();
+            match words.next() {
+                None => continue,
+                Some("user") => {}
+                Some(_) => return Err(error("should start with user keyword")),
+            }
+            let name = words.next().ok_or_else(|| error("user name missing"))?;
+            if users.contains_key(name) {
+                return Err(error(&format!("Duplicate user '{}' found", name)));
+            }
+            let mut user = User::new(name);
+            for rule in words {
+                user.apply_rule(rule).map_err(error)?;
+            }
+            users.insert(name.to_string(), user);
+        }
+        users
+            .entry(DEFAULT_USER.to_string())
+            .or_insert_with(User::default_user);
+        self.users = users;
+        Ok(())
+    }
+}
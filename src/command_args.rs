@@ -0,0 +1,166 @@
+use std::collections::VecDeque;
+
+use crate::error::{RedisError, RedisResult};
+use crate::models::value::Value;
+
+pub trait FromArgs: Sized {
+    fn from_args(args: &mut ArgParser) -> RedisResult<Self>;
+}
+
+pub fn parse_args<T: FromArgs>(command: &'static str, args: Vec<Value>) -> RedisResult<T> {
+    let mut parser = ArgParser::new(command, args);
+    let parsed = T::from_args(&mut parser)?;
+    parser.finish()?;
+    Ok(parsed)
+}
+
+pub trait FromArg: Sized {
+    fn from_arg(value: Value) -> RedisResult<Self>;
+}
+
+impl FromArg for String {
+    fn from_arg(value: Value) -> RedisResult<Self> {
+        match value {
+            Value::BulkString(s) | Value::SimpleString(s) => Ok(s),
+            Value::Integer(i) => Ok(i.to_string()),
+            _ => Err(RedisError::Syntax),
+        }
+    }
+}
+
+impl FromArg for i64 {
+    fn from_arg(value: Value) -> RedisResult<Self> {
+        match value {
+            Value::Integer(i) => Ok(i),
+            Value::BulkString(s) => s.parse().map_err(|_| RedisError::NotInteger),
+            _ => Err(RedisError::NotInteger),
+        }
+    }
+}
+
+impl FromArg for f64 {
+    fn from_arg(value: Value) -> RedisResult<Self> {
+        let parsed = match value {
+            Value::Integer(i) => Some(i as f64),
+            Value::BulkString(s) => match s.to_lowercase().as_str() {
+                "inf" | "+inf" => Some(f64::INFINITY),
+                "-inf" => Some(f64::NEG_INFINITY),
+                s => s.parse().ok(),
+            },
+            _ => None,
+        };
+        parsed
+            .filter(|f: &f64| !f.is_nan())
+            .ok_or(RedisError::NotFloat)
+    }
+}
+
+impl FromArg for Value {
+    fn from_arg(value: Value) -> RedisResult<Self> {
+        Ok(value)
+    }
+}
+
+pub struct ArgParser {
+    command: &'static str,
+    args: VecDeque<Value>,
+}
+
+impl ArgParser {
+    pub fn new(command: &'static str, args: Vec<Value>) -> Self {
+        ArgParser {
+            command,
+            args: args.into(),
+        }
+    }
+
+    pub fn arg<T: FromArg>(&mut self) -> RedisResult<T> {
+        match self.args.pop_front() {
+            Some(value) => T::from_arg(value),
+            None => Err(RedisError::wrong_arity(self.command)),
+        }
+    }
+
+    // Unlike a positional argument, a missing one is a syntax error.
+    pub fn value_of<T: FromArg>(&mut self) -> RedisResult<T> {
+        match self.args.pop_front() {
+            Some(value) => T::from_arg(value),
+            None => Err(RedisError::Syntax),
+        }
+    }
+
+    pub fn keyword(&mut self) -> RedisResult<Option<String>> {
+        match self.args.pop_front() {
+            Some(value) => Ok(Some(String::from_arg(value)?.to_uppercase())),
+            None => Ok(None),
+        }
+    }
+
+    pub fn flag(&mut self, keyword: &str) -> bool {
+        let matches = matches!(
+            self.args.front(),
+            Some(Value::BulkString(s)) if s.eq_ignore_ascii_case(keyword)
+        );
+        if matches {
+            self.args.pop_front();
+        }
+        matches
+    }
+
+    pub fn repeated<T>(
+        &mut self,
+        mut group: impl FnMut(&mut Self) -> RedisResult<T>,
+    ) -> RedisResult<Vec<T>> {
+        let mut groups = vec![group(self)?];
+        while !self.is_empty() {
+            groups.push(group(self)?);
+        }
+        Ok(groups)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.args.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.args.len()
+    }
+
+    pub fn finish(&self) -> RedisResult<()> {
+        if self.args.is_empty() {
+            Ok(())
+        } else {
+            Err(RedisError::Syntax)
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Exclusive<T> {
+    value: Option<T>,
+    error: RedisError,
+}
+
+impl<T> Exclusive<T> {
+    pub fn new(error: RedisError) -> Self {
+        Exclusive { value: None, error }
+    }
+
+    pub fn set(&mut self, value: T) -> RedisResult<()> {
+        if self.value.is_some() {
+            return Err(self.error.clone());
+        }
+        self.value = Some(value);
+        Ok(())
+    }
+
+    pub fn into_inner(self) -> Option<T> {
+        self.value
+    }
+}
+
+impl<T> Default for Exclusive<T> {
+    fn default() -> Self {
+        Exclusive::new(RedisError::Syntax)
+    }
+}
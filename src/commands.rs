@@ -1,122 +1,601 @@
 use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::Instant;
 
+use crate::error::RedisError;
+use crate::eviction::perform_evictions;
 use crate::handlers::*;
-
+use crate::log;
 use crate::models::value::Value;
 use crate::server::Server;
+use crate::utilities::{touches_key, unpack_bulk_str, unpack_integer_arg};
 use lazy_static::lazy_static;
 
 type CommandHandler = Box<dyn Fn(&mut Server, String, Vec<Value>) -> Option<Value> + Send + Sync>;
 
-fn wrap_no_args<F>(
-    f: F,
-) -> Box<dyn Fn(&mut Server, String, Vec<Value>) -> Option<Value> + Send + Sync>
+// For commands whose keys can't be described by first/last/step.
+type KeysFinder = fn(&[Value]) -> Vec<usize>;
+
+// Decides the ACL key permissions a command needs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyAccess {
+    Read,
+    Write,
+    ReadWrite,
+}
+
+impl KeyAccess {
+    pub fn reads(self) -> bool {
+        self != KeyAccess::Write
+    }
+
+    pub fn writes(self) -> bool {
+        self != KeyAccess::Read
+    }
+}
+
+fn wrap_no_args<F>(f: F) -> CommandHandler
 where
     F: Fn(&mut Server) -> Option<Value> + Send + Sync + 'static,
 {
     Box::new(move |server, _, _| f(server))
 }
 
-fn wrap_immutable_no_args<F>(
-    f: F,
-) -> Box<dyn Fn(&mut Server, String, Vec<Value>) -> Option<Value> + Send + Sync>
+fn wrap_immutable_no_args<F>(f: F) -> CommandHandler
 where
     F: Fn(&Server) -> Option<Value> + Send + Sync + 'static,
 {
     Box::new(move |server, _, _| f(server))
 }
 
-lazy_static! {
-    pub static ref DEPRECATED_COMMANDS: HashMap<&'static str, &'static str> = {
-        let mut m = HashMap::new();
-        m.insert("HMSET", "HSET");
-        m
-    };
+pub const ACL_CATEGORIES: &[&str] = &[
+    "keyspace",
+    "read",
+    "write",
+    "set",
+    "sortedset",
+    "list",
+    "hash",
+    "string",
+    "bitmap",
+    "hyperloglog",
+    "geo",
+    "stream",
+    "pubsub",
+    "admin",
+    "fast",
+    "slow",
+    "blocking",
+    "dangerous",
+    "connection",
+    "transaction",
+    "scripting",
+];
+
+pub struct Command {
+    pub name: &'static str,
+    // A negative arity -N means at least N arguments, the name included.
+    pub arity: i32,
+    pub flags: Vec<&'static str>,
+    // A negative last key counts from the end.
+    pub first_key: i32,
+    pub last_key: i32,
+    pub step: i32,
+    pub acl_categories: Vec<&'static str>,
+    pub group: &'static str,
+    pub summary: &'static str,
+    // The last entry applies to the remaining keys.
+    key_access: &'static [KeyAccess],
+    keys_finder: Option<KeysFinder>,
+    handler: CommandHandler,
 }
 
-lazy_static! {
-    pub static ref COMMAND_HANDLERS: HashMap<&'static str, CommandHandler> = {
-        let mut handlers: HashMap<&str, CommandHandler> = HashMap::new();
-        // Basic commands
-        handlers.insert("PING", Box::new(ping_handler));
-        handlers.insert("ECHO", Box::new(echo_handler));
-        handlers.insert("SET", Box::new(set_handler));
-        handlers.insert("GET", Box::new(get_handler));
-        handlers.insert("INFO", wrap_immutable_no_args(info_handler));
+impl Command {
+    pub fn has_flag(&self, flag: &str) -> bool {
+        self.flags.contains(&flag)
+    }
 
-        // Replication commands
-        handlers.insert("REPLCONF", Box::new(replconf_handler));
-        handlers.insert("PSYNC", wrap_no_args(psync_handler));
+    pub fn arity_matches(&self, argc: usize) -> bool {
+        let argc = argc as i32;
+        if self.arity >= 0 {
+            argc == self.arity
+        } else {
+            argc >= -self.arity
+        }
+    }
 
-        // Server commands
-        handlers.insert("FLUSHALL", Box::new(flushall_handler));
+    // Variadic and key-less commands get all arguments.
+    pub fn splits_key(&self) -> bool {
+        self.first_key == 1 && self.last_key > 0
+    }
 
+    pub fn key_positions(&self, args: &[Value]) -> Vec<usize> {
+        if let Some(finder) = self.keys_finder {
+            return finder(args);
+        }
+        if self.first_key <= 0 {
+            return Vec::new();
+        }
 
-        // Key management commands
-        // Returns all keys matching pattern.
-        handlers.insert("KEYS", Box::new(keys_handler));
+        let argc = args.len() as i32 + 1;
+        let last = if self.last_key < 0 {
+            argc + self.last_key
+        } else {
+            self.last_key
+        };
+        (self.first_key..=last.min(argc - 1))
+            .step_by(self.step.max(1) as usize)
+            .map(|position| position as usize - 1)
+            .collect()
+    }
 
-        // Returns the type of the value stored at key.
-        handlers.insert("TYPE", Box::new(type_handler));
+    pub fn keys(&self, args: &[Value]) -> Vec<String> {
+        self.key_positions(args)
+            .into_iter()
+            .filter_map(|position| match args.get(position) {
+                Some(Value::BulkString(key)) => Some(key.clone()),
+                _ => None,
+            })
+            .collect()
+    }
 
-        // Removes the specified keys. A key is ignored if it does not exist.
-        handlers.insert("DEL", Box::new(del_handler));
+    pub fn key_access(&self, index: usize) -> KeyAccess {
+        let access = self.key_access;
+        access.get(index).or(access.last()).copied().unwrap_or(KeyAccess::Write)
+    }
+}
 
-        // Removes the specified keys. A key is ignored if it does not exist.
-        handlers.insert("UNLINK", Box::new(unlink_handler));
+#[derive(Default)]
+struct CommandTable {
+    commands: HashMap<&'static str, Command>,
+    group: &'static str,
+}
 
-        // Set a key's time to live in seconds.
-        handlers.insert("EXPIRE", Box::new(expire_handler));
+impl CommandTable {
+    // Flags imply their ACL categories, and commands that aren't fast are @slow.
+    fn add(
+        &mut self,
+        name: &'static str,
+        arity: i32,
+        spec: &'static str,
+        (first_key, last_key, step): (i32, i32, i32),
+        summary: &'static str,
+        handler: CommandHandler,
+    ) -> &mut Command {
+        let mut flags = Vec::new();
+        let mut categories = Vec::new();
+        for token in spec.split_whitespace() {
+            match token.strip_prefix('@') {
+                Some(category) => categories.push(category),
+                None => flags.push(token),
+            }
+        }
+        for (flag, implied) in [
+            ("write", &["write"][..]),
+            ("readonly", &["read"]),
+            ("admin", &["admin", "dangerous"]),
+            ("pubsub", &["pubsub"]),
+            ("fast", &["fast"]),
+            ("blocking", &["blocking"]),
+        ] {
+            if flags.contains(&flag) {
+                categories.extend_from_slice(implied);
+            }
+        }
+        if !flags.contains(&"fast") {
+            categories.push("slow");
+        }
+        let acl_categories = ACL_CATEGORIES
+            .iter()
+            .copied()
+            .filter(|category| categories.contains(category))
+            .collect();
 
-        // Renames key to newkey. It returns an error if key does not exist.
-        handlers.insert("RENAME", Box::new(rename_handler));
+        let key_access = if flags.contains(&"readonly") {
+            &[KeyAccess::Read][..]
+        } else {
+            &[KeyAccess::Write]
+        };
+        let key = name.to_uppercase().leak();
+        self.commands.insert(
+            key,
+            Command {
+                name,
+                arity,
+                flags,
+                first_key,
+                last_key,
+                step,
+                acl_categories,
+                group: self.group,
+                summary,
+                key_access,
+                keys_finder: None,
+                handler,
+            },
+        );
+        self.commands.get_mut(&*key).unwrap()
+    }
+}
 
-        // List commands
-        // Returns the length of the list stored at key.
-        handlers.insert("LLEN", Box::new(llen_handler));
+// LMPOP numkeys key [key ...] LEFT|RIGHT [COUNT count]
+fn lmpop_keys(args: &[Value]) -> Vec<usize> {
+    let numkeys = match args.first().map(unpack_integer_arg) {
+        Some(Ok(numkeys)) if numkeys > 0 => numkeys as usize,
+        _ => return Vec::new(),
+    };
+    (1..=numkeys).filter(|&i| i < args.len()).collect()
+}
 
-        // Removes the first count occurrences of elements equal to element from the list stored at key.
-        handlers.insert("LREM", Box::new(lrem_handler));
+// MIGRATE host port key|"" db timeout [... KEYS key [key ...]]
+fn migrate_keys(args: &[Value]) -> Vec<usize> {
+    match args.get(2) {
+        Some(Value::BulkString(key)) if !key.is_empty() => vec![2],
+        _ => {
+            let keys_at = args.iter().skip(5).position(
+                |arg| matches!(arg, Value::BulkString(s) if s.eq_ignore_ascii_case("KEYS")),
+            );
+            match keys_at {
+                Some(at) => (5 + at + 1..args.len()).collect(),
+                None => Vec::new(),
+            }
+        }
+    }
+}
 
-        // Returns the element at index index in the list stored at key.
-        handlers.insert("LINDEX", Box::new(lindex_handler));
+// SORT key [... STORE destination]
+fn sort_keys(args: &[Value]) -> Vec<usize> {
+    let mut positions = vec![0];
+    let mut i = 1;
+    while i < args.len() {
+        let option = match &args[i] {
+            Value::BulkString(option) => option.to_uppercase(),
+            _ => break,
+        };
+        match option.as_str() {
+            "STORE" if i + 1 < args.len() => {
+                positions.push(i + 1);
+                i += 2;
+            }
+            "BY" | "GET" => i += 2,
+            "LIMIT" => i += 3,
+            _ => i += 1,
+        }
+    }
+    positions
+}
 
-        // Removes and returns the first element of the list stored at key.
-        handlers.insert("LPOP", Box::new(lpop_handler));
+lazy_static! {
+    pub static ref DEPRECATED_COMMANDS: HashMap<&'static str, &'static str> = {
+        let mut m = HashMap::new();
+        m.insert("HMSET", "HSET");
+        m
+    };
+}
+
+lazy_static! {
+    pub static ref COMMAND_TABLE: HashMap<&'static str, Command> = {
+        let mut table = CommandTable {
+            group: "connection",
+            ..Default::default()
+        };
+
+        table.add("ping", -1, "fast @connection", (0, 0, 0),
+            "Returns the server's liveliness response.", Box::new(ping_handler));
+        table.add("echo", 2, "fast @connection", (0, 0, 0),
+            "Returns the given string.", Box::new(echo_handler));
+        table.add("select", 2, "loading stale fast @connection", (0, 0, 0),
+            "Changes the selected database.", Box::new(select_handler));
+        table.add("auth", -2, "noscript loading stale fast no-auth allow-busy @connection",
+            (0, 0, 0), "Authenticates the connection.", Box::new(auth_handler));
+        table.add("hello", -1, "noscript loading stale fast no-auth allow-busy @connection",
+            (0, 0, 0), "Handshakes with the Redis server.", Box::new(hello_handler));
+        table.add("quit", -1, "noscript loading stale fast no-auth allow-busy @connection",
+            (0, 0, 0), "Closes the connection.", Box::new(quit_handler));
+
+        table.group = "server";
+        table.add("info", -1, "loading stale @dangerous", (0, 0, 0),
+            "Returns information and statistics about the server.", Box::new(info_handler));
+        table.add("acl", -2, "admin noscript loading stale", (0, 0, 0),
+            "Manages ACL users and inspects their permissions.", Box::new(acl_handler));
+        table.add("config", -2, "admin noscript loading stale", (0, 0, 0),
+            "Reads, changes and persists the configuration.", Box::new(config_handler));
+        table.add("memory", -2, "readonly", (2, 2, 1),
+            "Reports how memory is used and which keys use it.", Box::new(memory_handler));
+        table.add("command", -1, "loading stale @connection", (0, 0, 0),
+            "Returns detailed information about all commands.", Box::new(command_handler));
+        table.add("flushall", -1, "write @keyspace @dangerous", (0, 0, 0),
+            "Removes all keys from all databases.", Box::new(flushall_handler));
+        table.add("flushdb", -1, "write @keyspace @dangerous", (0, 0, 0),
+            "Removes all keys from the current database.", Box::new(flushdb_handler));
+        table.add("swapdb", 3, "write fast @keyspace @dangerous", (0, 0, 0),
+            "Swaps two databases.", Box::new(swapdb_handler));
+        table.add("dbsize", 1, "readonly fast @keyspace", (0, 0, 0),
+            "Returns the number of keys in the database.",
+            wrap_immutable_no_args(dbsize_handler));
 
-        // Removes and returns the last element of the list stored at key.
-        handlers.insert("RPOP", Box::new(rpop_handler));
+        // Replication commands
+        table.add("replconf", -1, "admin noscript loading stale", (0, 0, 0),
+            "An internal command for configuring the replication stream.",
+            Box::new(replconf_handler));
+        table.add("psync", -3, "admin noscript", (0, 0, 0),
+            "An internal command used in replication.", wrap_no_args(psync_handler));
+        table.add("fullresync", -1, "admin noscript", (0, 0, 0),
+            "An internal command that starts streaming the snapshot to a replica.",
+            wrap_no_args(fullresync_handler));
 
-        // Returns the specified elements of the list stored at key.
-        handlers.insert("LSET", Box::new(lset_handler));
-        handlers.insert("LPUSH", Box::new(lpush_handler));
-        handlers.insert("RPUSH", Box::new(rpush_handler));
+        table.group = "string";
+        table.add("set", -3, "write denyoom @string", (1, 1, 1),
+            "Sets the string value of a key.", Box::new(set_handler));
+        table.add("get", 2, "readonly fast @string", (1, 1, 1),
+            "Returns the string value of a key.", Box::new(get_handler));
+        table.add("incr", 2, "write denyoom fast @string", (1, 1, 1),
+            "Increments the integer value of a key by one.", Box::new(incr_handler))
+            .key_access = &[KeyAccess::ReadWrite];
+        table.add("decr", 2, "write denyoom fast @string", (1, 1, 1),
+            "Decrements the integer value of a key by one.", Box::new(decr_handler))
+            .key_access = &[KeyAccess::ReadWrite];
+        table.add("incrby", 3, "write denyoom fast @string", (1, 1, 1),
+            "Increments the integer value of a key by a number.", Box::new(incrby_handler))
+            .key_access = &[KeyAccess::ReadWrite];
+        table.add("decrby", 3, "write denyoom fast @string", (1, 1, 1),
+            "Decrements a number from the integer value of a key.", Box::new(decrby_handler))
+            .key_access = &[KeyAccess::ReadWrite];
 
+        table.group = "generic";
+        table.add("keys", 2, "readonly @keyspace @dangerous", (0, 0, 0),
+            "Returns all key names that match a pattern.", Box::new(keys_handler));
+        table.add("type", 2, "readonly fast @keyspace", (1, 1, 1),
+            "Determines the type of value stored at a key.", Box::new(type_handler));
+        table.add("scan", -2, "readonly @keyspace", (0, 0, 0),
+            "Iterates over the key names in the database.", Box::new(scan_handler));
+        table.add("exists", -2, "readonly fast @keyspace", (1, -1, 1),
+            "Determines whether one or more keys exist.", Box::new(exists_handler));
+        table.add("touch", -2, "readonly fast @keyspace", (1, -1, 1),
+            "Updates the last access time of keys.", Box::new(touch_handler));
+        table.add("copy", -3, "write denyoom @keyspace", (1, 2, 1),
+            "Copies the value of a key to a new key.", Box::new(copy_handler))
+            .key_access = &[KeyAccess::Read, KeyAccess::Write];
+        table.add("renamenx", 3, "write fast @keyspace", (1, 2, 1),
+            "Renames a key only when the target key name doesn't exist.",
+            Box::new(renamenx_handler))
+            .key_access = &[KeyAccess::ReadWrite, KeyAccess::Write];
+        table.add("randomkey", 1, "readonly @keyspace", (0, 0, 0),
+            "Returns a random key name from the database.", wrap_no_args(randomkey_handler));
+        table.add("move", 3, "write fast @keyspace", (1, 1, 1),
+            "Moves a key to another database.", Box::new(move_handler))
+            .key_access = &[KeyAccess::ReadWrite];
+        table.add("dump", 2, "readonly @keyspace", (1, 1, 1),
+            "Returns a serialized representation of the value stored at a key.",
+            Box::new(dump_handler));
+        table.add("restore", -4, "write denyoom @keyspace @dangerous", (1, 1, 1),
+            "Creates a key from the serialized representation of a value.",
+            Box::new(restore_handler));
+        let migrate = table.add("migrate", -6, "write movablekeys @keyspace @dangerous",
+            (3, 3, 1),
+            "Atomically transfers a key from one Redis instance to another.",
+            Box::new(migrate_handler));
+        migrate.key_access = &[KeyAccess::ReadWrite];
+        migrate.keys_finder = Some(migrate_keys);
+        let sort = table.add("sort", -2, "write denyoom movablekeys @set @sortedset @list @dangerous",
+            (1, 1, 1),
+            "Sorts the elements in a list, a set, or a sorted set, optionally storing the result.",
+            Box::new(sort_handler));
+        sort.key_access = &[KeyAccess::Read, KeyAccess::Write];
+        sort.keys_finder = Some(sort_keys);
+        table.add("sort_ro", -2, "readonly @set @sortedset @list @dangerous", (1, 1, 1),
+            "Returns the sorted elements of a list, a set, or a sorted set.",
+            Box::new(sort_ro_handler));
+        table.add("object", -2, "readonly @keyspace", (2, 2, 1),
+            "Inspects the internal representation of a key.", Box::new(object_handler));
+        table.add("del", -2, "write @keyspace", (1, -1, 1),
+            "Deletes one or more keys.", Box::new(del_handler));
+        table.add("unlink", -2, "write fast @keyspace", (1, -1, 1),
+            "Asynchronously deletes one or more keys.", Box::new(unlink_handler));
+        table.add("expire", -3, "write fast @keyspace", (1, 1, 1),
+            "Sets the expiration time of a key in seconds.", Box::new(expire_handler));
+        table.add("rename", 3, "write @keyspace", (1, 2, 1),
+            "Renames a key and overwrites the destination.", Box::new(rename_handler))
+            .key_access = &[KeyAccess::ReadWrite, KeyAccess::Write];
 
-        // Hash commands
-        // Returns the value associated with field in the hash stored at key.
-        handlers.insert("HGET", Box::new(hget_handler));
+        table.group = "list";
+        table.add("llen", 2, "readonly fast @list", (1, 1, 1),
+            "Returns the length of a list.", Box::new(llen_handler));
+        table.add("lrem", 4, "write @list", (1, 1, 1),
+            "Removes elements from a list.", Box::new(lrem_handler));
+        table.add("lindex", 3, "readonly @list", (1, 1, 1),
+            "Returns an element from a list by its index.", Box::new(lindex_handler));
+        table.add("lpop", -2, "write fast @list", (1, 1, 1),
+            "Returns the first elements in a list after removing them.", Box::new(lpop_handler))
+            .key_access = &[KeyAccess::ReadWrite];
+        table.add("rpop", -2, "write fast @list", (1, 1, 1),
+            "Returns and removes the last elements of a list.", Box::new(rpop_handler))
+            .key_access = &[KeyAccess::ReadWrite];
+        table.add("lset", 4, "write denyoom @list", (1, 1, 1),
+            "Sets the value of an element in a list by its index.", Box::new(lset_handler));
+        table.add("lpush", -3, "write denyoom fast @list", (1, 1, 1),
+            "Prepends one or more elements to a list.", Box::new(lpush_handler));
+        table.add("rpush", -3, "write denyoom fast @list", (1, 1, 1),
+            "Appends one or more elements to a list.", Box::new(rpush_handler));
+        table.add("lpushx", -3, "write denyoom fast @list", (1, 1, 1),
+            "Prepends one or more elements to a list only when the list exists.",
+            Box::new(lpushx_handler));
+        table.add("rpushx", -3, "write denyoom fast @list", (1, 1, 1),
+            "Appends an element to a list only when the list exists.", Box::new(rpushx_handler));
+        table.add("lrange", 4, "readonly @list", (1, 1, 1),
+            "Returns a range of elements from a list.", Box::new(lrange_handler));
+        table.add("ltrim", 4, "write @list", (1, 1, 1),
+            "Removes elements from both ends of a list.", Box::new(ltrim_handler));
+        table.add("linsert", 5, "write denyoom @list", (1, 1, 1),
+            "Inserts an element before or after another element in a list.",
+            Box::new(linsert_handler));
+        table.add("lpos", -3, "readonly @list", (1, 1, 1),
+            "Returns the index of matching elements in a list.", Box::new(lpos_handler));
+        table.add("lmove", 5, "write denyoom @list", (1, 2, 1),
+            "Returns an element after popping it from one list and pushing it to another.",
+            Box::new(lmove_handler))
+            .key_access = &[KeyAccess::ReadWrite, KeyAccess::Write];
+        table.add("rpoplpush", 3, "write denyoom @list", (1, 2, 1),
+            "Returns the last element of a list after removing and pushing it to another list.",
+            Box::new(rpoplpush_handler))
+            .key_access = &[KeyAccess::ReadWrite, KeyAccess::Write];
+        let lmpop = table.add("lmpop", -4, "write movablekeys @list", (0, 0, 0),
+            "Returns multiple elements from a list after removing them.", Box::new(lmpop_handler));
+        lmpop.key_access = &[KeyAccess::ReadWrite];
+        lmpop.keys_finder = Some(lmpop_keys);
 
-        // Returns the value associated with field in the hash stored at key.
-        handlers.insert("HEXISTS", Box::new(hexists_handler));
+        table.group = "hash";
+        table.add("hget", 3, "readonly fast @hash", (1, 1, 1),
+            "Returns the value of a field in a hash.", Box::new(hget_handler));
+        table.add("hexists", 3, "readonly fast @hash", (1, 1, 1),
+            "Determines whether a field exists in a hash.", Box::new(hexists_handler));
+        table.add("hdel", -3, "write fast @hash", (1, 1, 1),
+            "Deletes one or more fields and their values from a hash.", Box::new(hdel_handler));
+        table.add("hgetall", 2, "readonly @hash", (1, 1, 1),
+            "Returns all fields and values in a hash.", Box::new(hgetall_handler));
+        table.add("hkeys", 2, "readonly @hash", (1, 1, 1),
+            "Returns all fields in a hash.", Box::new(hkeys_handler));
+        table.add("hlen", 2, "readonly fast @hash", (1, 1, 1),
+            "Returns the number of fields in a hash.", Box::new(hlen_handler));
+        table.add("hset", -4, "write denyoom fast @hash", (1, 1, 1),
+            "Creates or modifies the value of a field in a hash.", Box::new(hset_handler));
+        table.add("hvals", 2, "readonly @hash", (1, 1, 1),
+            "Returns all values in a hash.", Box::new(hvals_handler));
+        table.add("hmset", -4, "write denyoom fast @hash", (1, 1, 1),
+            "Sets the values of multiple fields.", Box::new(hmset_handler));
+        table.add("hsetnx", 4, "write denyoom fast @hash", (1, 1, 1),
+            "Sets the value of a field in a hash only when the field doesn't exist.",
+            Box::new(hsetnx_handler));
+        table.add("hincrby", 4, "write denyoom fast @hash", (1, 1, 1),
+            "Increments the integer value of a field in a hash by a number.",
+            Box::new(hincrby_handler))
+            .key_access = &[KeyAccess::ReadWrite];
+        table.add("hincrbyfloat", 4, "write denyoom fast @hash", (1, 1, 1),
+            "Increments the floating point value of a field by a number.",
+            Box::new(hincrbyfloat_handler))
+            .key_access = &[KeyAccess::ReadWrite];
+        table.add("hmget", -3, "readonly fast @hash", (1, 1, 1),
+            "Returns the values of all fields in a hash.", Box::new(hmget_handler));
+        table.add("hstrlen", 3, "readonly fast @hash", (1, 1, 1),
+            "Returns the length of the value of a field.", Box::new(hstrlen_handler));
+        table.add("hrandfield", -2, "readonly @hash", (1, 1, 1),
+            "Returns one or more random fields from a hash.", Box::new(hrandfield_handler));
+        table.add("hscan", -3, "readonly @hash", (1, 1, 1),
+            "Iterates over fields and values of a hash.", Box::new(hscan_handler));
+        table.add("hexpire", -6, "write fast @hash", (1, 1, 1),
+            "Set expiry for hash field using relative time to expire (seconds).",
+            Box::new(hexpire_handler));
+        table.add("hpexpire", -6, "write fast @hash", (1, 1, 1),
+            "Set expiry for hash field using relative time to expire (milliseconds).",
+            Box::new(hpexpire_handler));
+        table.add("hexpireat", -6, "write fast @hash", (1, 1, 1),
+            "Set expiry for hash field using an absolute Unix timestamp (seconds).",
+            Box::new(hexpireat_handler));
+        table.add("hpexpireat", -6, "write fast @hash", (1, 1, 1),
+            "Set expiry for hash field using an absolute Unix timestamp (milliseconds).",
+            Box::new(hpexpireat_handler));
+        table.add("httl", -5, "readonly fast @hash", (1, 1, 1),
+            "Returns the TTL in seconds of a hash field.", Box::new(httl_handler));
+        table.add("hpttl", -5, "readonly fast @hash", (1, 1, 1),
+            "Returns the TTL in milliseconds of a hash field.", Box::new(hpttl_handler));
+        table.add("hpersist", -5, "write fast @hash", (1, 1, 1),
+            "Removes the expiration time for each specified field.", Box::new(hpersist_handler));
+        table.add("hgetex", -5, "write fast @hash", (1, 1, 1),
+            "Get the value of one or more fields of a given hash key, and optionally set their expiration.",
+            Box::new(hgetex_handler))
+            .key_access = &[KeyAccess::ReadWrite];
+        table.add("hsetex", -6, "write denyoom fast @hash", (1, 1, 1),
+            "Set the value of one or more fields of a given hash key, and optionally set their expiration.",
+            Box::new(hsetex_handler));
 
-        // Removes the specified fields from the hash stored at key.
-        handlers.insert("HDEL", Box::new(hdel_handler));
+        table.group = "set";
+        table.add("sadd", -3, "write denyoom fast @set", (1, 1, 1),
+            "Adds one or more members to a set.", Box::new(sadd_handler));
+        table.add("sscan", -3, "readonly @set", (1, 1, 1),
+            "Iterates over members of a set.", Box::new(sscan_handler));
 
-        // Returns all field names in the hash stored at key.
-        handlers.insert("HGETALL", Box::new(hgetall_handler));
+        table.group = "sorted-set";
+        table.add("zadd", -4, "write denyoom fast @sortedset", (1, 1, 1),
+            "Adds one or more members to a sorted set, or updates their scores.",
+            Box::new(zadd_handler));
+        table.add("zscan", -3, "readonly @sortedset", (1, 1, 1),
+            "Iterates over members and scores of a sorted set.", Box::new(zscan_handler));
 
-        // Returns all field names in the hash stored at key.
-        handlers.insert("HKEYS", Box::new(hkeys_handler));
+        table.commands
+    };
+}
 
-        // Returns the number of fields contained in the hash stored at key.
-        handlers.insert("HLEN", Box::new(hlen_handler));
+// Catches a panic so one bad command can't kill the client's task.
+pub fn run_isolated<F>(server: &mut Server, f: F) -> Option<Value>
+where
+    F: FnOnce(&mut Server) -> Option<Value>,
+{
+    match panic::catch_unwind(AssertUnwindSafe(|| f(server))) {
+        Ok(response) => response,
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .copied()
+                .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("unknown panic");
+            log!("Command panicked: {}", message);
+            server.stats.record_internal_error();
+            Some(RedisError::Internal.into())
+        }
+    }
+}
 
-        // Sets field in the hash stored at key to value.
-        handlers.insert("HSET", Box::new(hset_handler));
+pub fn dispatch(server: &mut Server, name: &str, args: Vec<Value>) -> Option<Value> {
+    let response = call(server, name, args);
+    if let Some(Value::Error(message)) = &response {
+        server.stats.record_error_reply(message);
+    }
+    response
+}
 
-        // Returns all values in the hash stored at key.
-        handlers.insert("HVALS", Box::new(hvals_handler));
-        handlers
+fn call(server: &mut Server, name: &str, args: Vec<Value>) -> Option<Value> {
+    let upper = name.to_uppercase();
+    let command = match COMMAND_TABLE.get(upper.as_str()) {
+        Some(command) => command,
+        None => return Some(RedisError::unknown_command(name, &args).into()),
     };
+    if !command.arity_matches(args.len() + 1) {
+        server.stats.record_rejected_call(command.name);
+        return Some(RedisError::wrong_arity(command.name).into());
+    }
+
+    let rejected = run_isolated(server, |server| match perform_evictions(server) {
+        Err(e) if command.has_flag("denyoom") => Some(e.into()),
+        _ => None,
+    });
+    if rejected.is_some() {
+        server.stats.record_rejected_call(command.name);
+        return rejected;
+    }
+
+    let mut args = args;
+    let key = if command.splits_key() {
+        match unpack_bulk_str(args.remove(0)) {
+            Ok(key) => key,
+            Err(_) => return Some(RedisError::Syntax.into()),
+        }
+    } else {
+        String::new()
+    };
+
+    let handler = &command.handler;
+    let started = Instant::now();
+    let response = run_isolated(server, |server| {
+        let response = handler(server, key.clone(), args);
+        if !key.is_empty() && touches_key(&upper) {
+            server.touch_key(&key);
+        }
+        response
+    });
+    let failed = matches!(response, Some(Value::Error(_)));
+    server.stats.record_call(command.name, started.elapsed(), failed);
+    if command.has_flag("write") && !failed {
+        server.stats.record_write();
+    }
+    response
 }
@@ -0,0 +1,668 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use lazy_static::lazy_static;
+use structopt::clap::{self, AppSettings};
+use structopt::StructOpt;
+use thiserror::Error;
+
+use crate::error::{RedisError, RedisResult};
+use crate::eviction;
+use crate::glob::string_match;
+use crate::lazyfree::LazyFreeConfig;
+use crate::models::args::Args;
+use crate::server::Server;
+
+const PROGRAM: &str = "my_redis_server";
+
+const REWRITE_SIGNATURE: &str = "# Generated by CONFIG REWRITE";
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("Failed to read {0}: {1}")]
+    Io(PathBuf, io::Error),
+
+    #[error("*** FATAL CONFIG FILE ERROR *** line {line}: >>> '{text}' {reason}")]
+    File {
+        line: usize,
+        text: String,
+        reason: &'static str,
+    },
+
+    #[error("Bad directive or wrong number of arguments: '{0}'")]
+    UnknownDirective(String),
+
+    #[error("{0}")]
+    Args(#[from] clap::Error),
+}
+
+impl ConfigError {
+    // `--help` and `--version` end up here too, and exit successfully.
+    pub fn exit(self) -> ! {
+        match self {
+            ConfigError::Args(e) => e.exit(),
+            e => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub file: Option<PathBuf>,
+    pub args: Args,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            file: None,
+            args: DEFAULTS.clone(),
+        }
+    }
+}
+
+impl Deref for Config {
+    type Target = Args;
+
+    fn deref(&self) -> &Args {
+        &self.args
+    }
+}
+
+impl Config {
+    pub fn load<I: IntoIterator<Item = String>>(argv: I) -> Result<Config, ConfigError> {
+        let mut argv = argv.into_iter().skip(1).peekable();
+        let file = argv.next_if(|arg| !arg.starts_with('-')).map(PathBuf::from);
+
+        let mut directives = Directives::new();
+        if let Some(file) = &file {
+            let contents =
+                fs::read_to_string(file).map_err(|e| ConfigError::Io(file.clone(), e))?;
+            for (name, values) in parse_file(&contents)? {
+                directives.insert(name, values);
+            }
+        }
+        for (name, values) in parse_command_line(argv)? {
+            directives.insert(name, values);
+        }
+
+        Ok(Config {
+            file,
+            args: parse_directives(&directives)?,
+        })
+    }
+
+    pub fn get(&self, patterns: &[String]) -> Vec<(&'static str, String)> {
+        PARAMS
+            .iter()
+            .filter(|param| {
+                patterns
+                    .iter()
+                    .any(|pattern| string_match(pattern, param.name, true))
+            })
+            .map(|param| (param.name, (param.get)(&self.args)))
+            .collect()
+    }
+
+    // Either every value is valid or none is taken.
+    pub fn with_changes(&self, changes: &[(String, String)]) -> RedisResult<Config> {
+        let mut directives = self.directives();
+        let mut seen = Vec::new();
+        for (name, value) in changes {
+            let name = name.to_lowercase();
+            let param = find_param(&name).ok_or_else(|| {
+                RedisError::err(format!(
+                    "Unknown option or number of arguments for CONFIG SET - '{}'",
+                    name
+                ))
+            })?;
+            if !param.mutable {
+                return Err(set_failed(&name, "can't set immutable config"));
+            }
+            if seen.contains(&param.name) {
+                return Err(set_failed(&name, "duplicate parameter"));
+            }
+            seen.push(param.name);
+
+            directives.insert(name.clone(), vec![value.clone()]);
+            // Parsing after each change pins an invalid value on its name.
+            parse_directives(&directives).map_err(|e| set_failed(&name, &clap_reason(&e)))?;
+        }
+        Ok(Config {
+            file: self.file.clone(),
+            args: parse_directives(&directives).map_err(|e| RedisError::err(clap_reason(&e)))?,
+        })
+    }
+
+    pub fn rewrite(&self) -> RedisResult<()> {
+        let file = self
+            .file
+            .as_ref()
+            .ok_or_else(|| RedisError::err("The server is running without a config file"))?;
+        let contents = fs::read_to_string(file).unwrap_or_default();
+        let mut lines = Vec::new();
+        let mut written = Vec::new();
+        for line in contents.lines() {
+            if line.trim() == REWRITE_SIGNATURE {
+                continue;
+            }
+            let param = split_args(line).ok().and_then(|args| {
+                args.first()
+                    .and_then(|name| find_param(&name.to_lowercase()))
+            });
+            match param {
+                // Later occurrences of a directive are dropped: the first
+                // one carries the value in effect.
+                Some(param) if written.contains(&param.name) => {}
+                Some(param) => {
+                    lines.push(param.render(&self.args));
+                    written.push(param.name);
+                }
+                None => lines.push(line.to_string()),
+            }
+        }
+
+        let appended: Vec<String> = PARAMS
+            .iter()
+            .filter(|param| !written.contains(&param.name) && !param.is_default(&self.args))
+            .map(|param| param.render(&self.args))
+            .collect();
+        if !appended.is_empty() {
+            lines.push(REWRITE_SIGNATURE.to_string());
+            lines.extend(appended);
+        }
+
+        write_atomically(file, &(lines.join("\n") + "\n"))
+            .map_err(|e| RedisError::err(format!("Rewriting config file: {}", e)))
+    }
+
+    pub fn dump_path(&self) -> String {
+        self.dir.join(&self.dbfilename).display().to_string()
+    }
+
+    fn directives(&self) -> Directives {
+        let mut directives = Directives::new();
+        for param in PARAMS.iter().filter(|param| !param.is_default(&self.args)) {
+            directives.insert(param.name.to_string(), vec![(param.get)(&self.args)]);
+        }
+        directives
+    }
+}
+
+// When a change can't take effect, the ones already applied are rolled back.
+pub fn set(server: &Server, changes: &[(String, String)]) -> RedisResult<()> {
+    let old = server.config().clone();
+    let new = old.with_changes(changes)?;
+    *server.config_mut() = new.clone();
+
+    let mut applied: Vec<Apply> = Vec::new();
+    for (name, _) in changes {
+        let param = find_param(&name.to_lowercase()).expect("validated by with_changes");
+        if let Some(apply) = param.apply {
+            if let Err(reason) = apply(server, &new.args) {
+                *server.config_mut() = old.clone();
+                for apply in applied {
+                    let _ = apply(server, &old.args);
+                }
+                return Err(set_failed(param.name, &reason));
+            }
+            applied.push(apply);
+        }
+    }
+    Ok(())
+}
+
+fn set_failed(name: &str, reason: &str) -> RedisError {
+    RedisError::err(format!(
+        "CONFIG SET failed (possibly related to argument '{}') - {}",
+        name, reason
+    ))
+}
+
+fn clap_reason(error: &clap::Error) -> String {
+    let message = error.message.lines().next().unwrap_or_default();
+    message
+        .strip_prefix("error: ")
+        .unwrap_or(message)
+        .to_string()
+}
+
+// Renamed over `path` so a crash never leaves a truncated config behind.
+fn write_atomically(path: &Path, contents: &str) -> io::Result<()> {
+    let temp = path.with_extension("rewrite.tmp");
+    fs::write(&temp, contents)?;
+    fs::rename(&temp, path)
+}
+
+// Later insertions win: the command line over the file, CONFIG SET over both.
+type Directives = BTreeMap<String, Vec<String>>;
+
+fn parse_directives(directives: &Directives) -> Result<Args, clap::Error> {
+    let mut argv = vec![PROGRAM.to_string()];
+    for (name, values) in directives {
+        if name.starts_with('-') {
+            argv.push(name.clone());
+            argv.extend(values.iter().cloned());
+        } else if name == "replicaof" {
+            // The only option taking two values: host and port.
+            argv.push(format!("--{}", name));
+            argv.extend(
+                values
+                    .iter()
+                    .flat_map(|v| v.split_whitespace().map(String::from)),
+            );
+        } else {
+            let value = values.join(" ");
+            // An empty value clears an optional parameter, like requirepass.
+            if value.is_empty() && find_param(name).is_some_and(|p| (p.get)(&DEFAULTS).is_empty()) {
+                continue;
+            }
+            // `--name=value` keeps values starting with `-`, like `-::1`.
+            argv.push(format!("--{}={}", name, value));
+        }
+    }
+    let matches = Args::clap()
+        .setting(AppSettings::ColorNever)
+        .get_matches_from_safe(argv)?;
+    Ok(Args::from_clap(&matches))
+}
+
+fn parse_command_line<I: Iterator<Item = String>>(
+    argv: I,
+) -> Result<Vec<(String, Vec<String>)>, ConfigError> {
+    let mut directives: Vec<(String, Vec<String>)> = Vec::new();
+    for arg in argv {
+        let flag = match arg.strip_prefix("--") {
+            Some(name) => Some(name.to_lowercase()),
+            None if arg.len() == 2 && arg.starts_with('-') => Some(arg.clone()),
+            None => None,
+        };
+        match (flag, directives.last_mut()) {
+            (Some(name), _) if name == "help" || name == "version" => {
+                directives.push((format!("--{}", name), Vec::new()));
+            }
+            (Some(name), _) if name.starts_with('-') => directives.push((name, Vec::new())),
+            (Some(name), _) => {
+                if find_param(&name).is_none() {
+                    return Err(ConfigError::UnknownDirective(name));
+                }
+                directives.push((name, Vec::new()));
+            }
+            (None, Some((_, values))) => values.push(arg),
+            (None, None) => return Err(ConfigError::UnknownDirective(arg)),
+        }
+    }
+    Ok(directives)
+}
+
+fn parse_file(contents: &str) -> Result<Vec<(String, Vec<String>)>, ConfigError> {
+    let mut directives = Vec::new();
+    for (index, line) in contents.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let error = |reason| ConfigError::File {
+            line: index + 1,
+            text: trimmed.to_string(),
+            reason,
+        };
+        let mut args = split_args(trimmed).map_err(error)?;
+        let name = args.remove(0).to_lowercase();
+        if find_param(&name).is_none() || args.is_empty() {
+            return Err(error("Bad directive or wrong number of arguments"));
+        }
+        directives.push((name, args));
+    }
+    Ok(directives)
+}
+
+// Splits a config line into arguments like Redis' `sdssplitargs`.
+pub fn split_args(line: &str) -> Result<Vec<String>, &'static str> {
+    let mut args = Vec::new();
+    let mut chars = line.chars().peekable();
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        let Some(&first) = chars.peek() else {
+            return Ok(args);
+        };
+        let mut arg = String::new();
+        if first == '"' || first == '\'' {
+            chars.next();
+            loop {
+                match chars.next() {
+                    None => return Err("Unbalanced quotes in configuration line"),
+                    Some(c) if c == first => break,
+                    Some('\\') if first == '"' => arg.push(unescape(&mut chars)?),
+                    Some('\\') if chars.peek() == Some(&'\'') => {
+                        arg.push('\'');
+                        chars.next();
+                    }
+                    Some(c) => arg.push(c),
+                }
+            }
+            // A closing quote must end the argument.
+            if chars.peek().is_some_and(|c| !c.is_whitespace()) {
+                return Err("Unbalanced quotes in configuration line");
+            }
+        } else {
+            while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+                arg.push(c);
+            }
+        }
+        args.push(arg);
+    }
+}
+
+fn unescape(chars: &mut std::iter::Peekable<std::str::Chars>) -> Result<char, &'static str> {
+    Ok(match chars.next() {
+        Some('n') => '\n',
+        Some('r') => '\r',
+        Some('t') => '\t',
+        Some('b') => '\u{8}',
+        Some('a') => '\u{7}',
+        Some('x') => {
+            let hex: String = chars.take(2).collect();
+            u8::from_str_radix(&hex, 16)
+                .map(char::from)
+                .map_err(|_| "Invalid escape in configuration line")?
+        }
+        Some(c) => c,
+        None => return Err("Unbalanced quotes in configuration line"),
+    })
+}
+
+fn quote(value: &str) -> String {
+    let plain = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_graphic() && c != '"' && c != '\'' && c != '\\');
+    if plain {
+        return value.to_string();
+    }
+    let mut quoted = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\x{:02x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+type Apply = fn(&Server, &Args) -> Result<(), String>;
+
+pub struct Param {
+    pub name: &'static str,
+    get: fn(&Args) -> String,
+    pub mutable: bool,
+    // Several space-separated values, like `bind` and `save`, written unquoted.
+    list: bool,
+    // Parameters read through `Server::config` where they are used need
+    // nothing applied.
+    apply: Option<Apply>,
+}
+
+impl Param {
+    const fn new(name: &'static str, get: fn(&Args) -> String) -> Self {
+        Param {
+            name,
+            get,
+            mutable: false,
+            list: false,
+            apply: None,
+        }
+    }
+
+    const fn mutable(mut self) -> Self {
+        self.mutable = true;
+        self
+    }
+
+    const fn list(mut self) -> Self {
+        self.list = true;
+        self
+    }
+
+    const fn apply(mut self, apply: Apply) -> Self {
+        self.apply = Some(apply);
+        self.mutable()
+    }
+
+    fn is_default(&self, args: &Args) -> bool {
+        (self.get)(args) == (self.get)(&DEFAULTS)
+    }
+
+    fn render(&self, args: &Args) -> String {
+        let value = (self.get)(args);
+        let value = if self.list && !value.is_empty() {
+            value
+                .split_whitespace()
+                .map(quote)
+                .collect::<Vec<_>>()
+                .join(" ")
+        } else {
+            quote(&value)
+        };
+        format!("{} {}", self.name, value)
+    }
+}
+
+fn find_param(name: &str) -> Option<&'static Param> {
+    PARAMS.iter().find(|param| param.name == name)
+}
+
+fn yes_no(value: bool) -> String {
+    if value { "yes" } else { "no" }.to_string()
+}
+
+fn path(value: &Option<PathBuf>) -> String {
+    value
+        .as_ref()
+        .map(|path| path.display().to_string())
+        .unwrap_or_default()
+}
+
+fn apply_lazyfree(server: &Server, args: &Args) -> Result<(), String> {
+    server.lazyfree.set_config(LazyFreeConfig::from(args));
+    Ok(())
+}
+
+pub static PARAMS: &[Param] = &[
+    Param::new("port", |args| args.port.to_string()),
+    Param::new("bind", |args| args.bind.to_string()).list(),
+    Param::new("unixsocket", |args| path(&args.unixsocket)),
+    Param::new("unixsocketperm", |args| {
+        format!("{:o}", args.unixsocketperm)
+    }),
+    Param::new("protected-mode", |args| yes_no(args.protected_mode)).mutable(),
+    Param::new("replicaof", |args| {
+        args.replicaof.clone().unwrap_or_default().join(" ")
+    })
+    .list(),
+    Param::new("databases", |args| args.databases.to_string()),
+    Param::new("requirepass", |args| {
+        args.requirepass.clone().unwrap_or_default()
+    })
+    .apply(|server, args| {
+        let password = args.requirepass.as_deref().unwrap_or_default();
+        server.acl().set_requirepass(password);
+        Ok(())
+    }),
+    Param::new("masterauth", |args| {
+        args.masterauth.clone().unwrap_or_default()
+    })
+    .mutable(),
+    Param::new("aclfile", |args| path(&args.aclfile)),
+    Param::new("tls-port", |args| args.tls_port.to_string()),
+    Param::new("tls-cert-file", |args| path(&args.tls_cert_file)),
+    Param::new("tls-key-file", |args| path(&args.tls_key_file)),
+    Param::new("tls-ca-cert-file", |args| path(&args.tls_ca_cert_file)),
+    Param::new("tls-auth-clients", |args| args.tls_auth_clients.to_string()),
+    Param::new("tls-replication", |args| yes_no(args.tls_replication)),
+    Param::new("dir", |args| args.dir.display().to_string()).apply(|_, args| {
+        if args.dir.is_dir() {
+            Ok(())
+        } else {
+            Err(format!("No such directory: {}", args.dir.display()))
+        }
+    }),
+    Param::new("dbfilename", |args| args.dbfilename.clone()).mutable(),
+    Param::new("save", |args| args.save.to_string())
+        .list()
+        .mutable(),
+    Param::new("hz", |args| args.hz.to_string()).mutable(),
+    Param::new("client-query-buffer-limit", |args| {
+        args.client_query_buffer_limit.to_string()
+    })
+    .mutable(),
+    Param::new("maxmemory", |args| args.maxmemory.to_string()).apply(|server, _| {
+        // Lowering the limit evicts right away rather than on the next command.
+        let _ = eviction::perform_evictions(server);
+        Ok(())
+    }),
+    Param::new("maxmemory-policy", |args| args.maxmemory_policy.to_string()).mutable(),
+    Param::new("maxmemory-samples", |args| {
+        args.maxmemory_samples.to_string()
+    })
+    .mutable(),
+    Param::new("lfu-log-factor", |args| args.lfu_log_factor.to_string()).mutable(),
+    Param::new("lfu-decay-time", |args| args.lfu_decay_time.to_string()).mutable(),
+    Param::new("hash-max-listpack-entries", |args| {
+        args.hash_max_listpack_entries.to_string()
+    })
+    .mutable(),
+    Param::new("hash-max-listpack-value", |args| {
+        args.hash_max_listpack_value.to_string()
+    })
+    .mutable(),
+    Param::new("list-max-listpack-size", |args| {
+        args.list_max_listpack_size.to_string()
+    })
+    .mutable(),
+    Param::new("set-max-intset-entries", |args| {
+        args.set_max_intset_entries.to_string()
+    })
+    .mutable(),
+    Param::new("set-max-listpack-entries", |args| {
+        args.set_max_listpack_entries.to_string()
+    })
+    .mutable(),
+    Param::new("set-max-listpack-value", |args| {
+        args.set_max_listpack_value.to_string()
+    })
+    .mutable(),
+    Param::new("zset-max-listpack-entries", |args| {
+        args.zset_max_listpack_entries.to_string()
+    })
+    .mutable(),
+    Param::new("zset-max-listpack-value", |args| {
+        args.zset_max_listpack_value.to_string()
+    })
+    .mutable(),
+    Param::new("lazyfree-lazy-user-del", |args| {
+        yes_no(args.lazyfree_lazy_user_del)
+    })
+    .apply(apply_lazyfree),
+    Param::new("lazyfree-lazy-user-flush", |args| {
+        yes_no(args.lazyfree_lazy_user_flush)
+    })
+    .apply(apply_lazyfree),
+    Param::new("lazyfree-lazy-expire", |args| {
+        yes_no(args.lazyfree_lazy_expire)
+    })
+    .apply(apply_lazyfree),
+    Param::new("lazyfree-lazy-server-del", |args| {
+        yes_no(args.lazyfree_lazy_server_del)
+    })
+    .apply(apply_lazyfree),
+];
+
+lazy_static! {
+    static ref DEFAULTS: Args = Args::from_iter_safe([PROGRAM]).expect("defaults are valid");
+}
+
+// No rules disable automatic dumps.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SaveRules(pub Vec<(u64, u64)>);
+
+impl SaveRules {
+    pub fn due(&self, elapsed: u64, changes: u64) -> bool {
+        self.0
+            .iter()
+            .any(|&(seconds, min_changes)| elapsed >= seconds && changes >= min_changes)
+    }
+}
+
+impl FromStr for SaveRules {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let numbers = value
+            .split_whitespace()
+            .map(|n| {
+                n.parse::<u64>()
+                    .map_err(|_| format!("invalid save rule '{}'", value))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if numbers.len() % 2 != 0 {
+            return Err("save takes pairs of seconds and changes".to_string());
+        }
+        Ok(SaveRules(
+            numbers.chunks(2).map(|pair| (pair[0], pair[1])).collect(),
+        ))
+    }
+}
+
+impl fmt::Display for SaveRules {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rules: Vec<String> = self
+            .0
+            .iter()
+            .map(|(seconds, changes)| format!("{} {}", seconds, changes))
+            .collect();
+        write!(f, "{}", rules.join(" "))
+    }
+}
+
+// `k`, `m` and `g` are powers of 1000, `kb`, `mb` and `gb` powers of 1024.
+pub fn parse_memory(value: &str) -> Result<u64, String> {
+    let lower = value.to_ascii_lowercase();
+    let digits = lower.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    let multiplier: u64 = match &lower[digits.len()..] {
+        "" | "b" => 1,
+        "k" => 1000,
+        "kb" => 1024,
+        "m" => 1000 * 1000,
+        "mb" => 1024 * 1024,
+        "g" => 1000 * 1000 * 1000,
+        "gb" => 1024 * 1024 * 1024,
+        _ => return Err(format!("invalid memory size '{}'", value)),
+    };
+    digits
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(multiplier))
+        .ok_or_else(|| format!("invalid memory size '{}'", value))
+}
+
+pub fn parse_dbfilename(value: &str) -> Result<String, String> {
+    if value.is_empty() || value.contains('/') {
+        return Err("dbfilename can't be a path, just a filename".to_string());
+    }
+    Ok(value.to_string())
+}
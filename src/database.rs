@@ -1,17 +1,29 @@
 use bincode;
+use serde::Deserialize;
 use std::collections::HashMap;
 use std::fs::{self, File};
 use std::io::{BufReader, BufWriter, Read, Write};
 use std::path::Path;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, MutexGuard};
 use thiserror::Error;
 
+use crate::keyspace::Keyspace;
 use crate::log;
-use crate::models::redis_item::RedisItem;
+use crate::models::redis_item::{AccessStats, RedisItem};
+use crate::models::redis_type::RedisType;
+use crate::models::value::Value;
+use crate::server::Db;
+use crate::utilities::lock;
+
+// Files without the header are version 1. Version 2 holds a single keyspace,
+// version 3 a list of (db index, keyspace) pairs.
+const DUMP_MAGIC: &[u8; 8] = b"MYREDIS\0";
+const SINGLE_DB_VERSION: u16 = 2;
+const DUMP_VERSION: u16 = 3;
 
 #[derive(Clone)]
 pub struct Database {
-    pub cache: Arc<Mutex<HashMap<String, RedisItem>>>,
+    pub databases: Arc<Vec<Db>>,
     pub path: String,
 }
 
@@ -25,13 +37,44 @@ pub enum DatabaseError {
 
     #[error("Data Corruption Detected")]
     DataCorruption,
+
+    #[error("Unsupported dump version {0}")]
+    UnsupportedVersion(u16),
+}
+
+#[derive(Deserialize)]
+struct LegacyRedisItem {
+    value: Value,
+    created_at: i64,
+    expiration: Option<i64>,
+    redis_type: RedisType,
+}
+
+impl From<LegacyRedisItem> for RedisItem {
+    fn from(legacy: LegacyRedisItem) -> Self {
+        let mut item = RedisItem {
+            value: legacy.value,
+            created_at: legacy.created_at,
+            expiration: legacy.expiration,
+            redis_type: legacy.redis_type,
+            field_expirations: HashMap::new(),
+            access: AccessStats::default(),
+        };
+        item.upgrade_legacy_list();
+        item
+    }
 }
 
 impl Database {
-    /// Initializes the Database struct.
-    pub fn new(cache: Arc<Mutex<HashMap<String, RedisItem>>>, path: &str) -> Self {
+    /// Initializes the Database struct for a single keyspace, stored as db 0.
+    pub fn new(cache: Db, path: &str) -> Self {
+        Self::with_databases(Arc::new(vec![cache]), path)
+    }
+
+    /// Initializes the Database struct for all numbered databases.
+    pub fn with_databases(databases: Arc<Vec<Db>>, path: &str) -> Self {
         Database {
-            cache,
+            databases,
             path: path.to_string(),
         }
     }
@@ -42,13 +85,26 @@ impl Database {
         let file = File::create(temp_path)?;
         let mut writer = BufWriter::new(file);
 
-        let cache = self.cache.lock().unwrap();
-        log!("Dumping backup. Cache size: {}", cache.len());
-        for (key, item) in cache.iter() {
-            log!("Key: {}, Value type: {:?}", key, item.value);
+        // In index order, so the dump is consistent even while SWAPDB runs.
+        let guards: Vec<(u32, MutexGuard<Keyspace>)> = self
+            .databases
+            .iter()
+            .enumerate()
+            .map(|(index, db)| (index as u32, lock(db)))
+            .collect();
+        let snapshot: Vec<(u32, &Keyspace)> = guards
+            .iter()
+            .filter(|(_, cache)| !cache.is_empty())
+            .map(|(index, cache)| (*index, &**cache))
+            .collect();
+        for (index, cache) in &snapshot {
+            log!("Dumping backup. db{} size: {}", index, cache.len());
         }
-        let serialized = bincode::serialize(&*cache)?;
+        let serialized = bincode::serialize(&snapshot)?;
+        drop(guards);
 
+        writer.write_all(DUMP_MAGIC)?;
+        writer.write_all(&DUMP_VERSION.to_le_bytes())?;
         writer.write_all(&serialized)?;
         writer.flush()?;
         drop(writer);
@@ -70,16 +126,46 @@ impl Database {
         let mut buffer = Vec::new();
         reader.read_to_end(&mut buffer)?;
 
-        let deserialized: HashMap<String, RedisItem> = bincode::deserialize(&buffer)?;
-        log!("Read backup. Deserialized size: {}", deserialized.len());
-        for (key, item) in deserialized.iter() {
-            log!("Key: {}, Value type: {:?}", key, item.value);
+        for (index, deserialized) in Self::decode(&buffer)? {
+            log!(
+                "Read backup. db{} deserialized size: {}",
+                index,
+                deserialized.len()
+            );
+            match self.databases.get(index as usize) {
+                Some(db) => *lock(db) = deserialized,
+                None => log!(
+                    "Skipping db{}: only {} databases are configured",
+                    index,
+                    self.databases.len()
+                ),
+            }
         }
 
-        let mut cache = self.cache.lock().unwrap();
-        *cache = deserialized;
-
         log!("Backup loaded successfully.");
         Ok(())
     }
+
+    fn decode(buffer: &[u8]) -> Result<Vec<(u32, Keyspace)>, DatabaseError> {
+        let header_len = DUMP_MAGIC.len() + 2;
+        if !buffer.starts_with(DUMP_MAGIC) {
+            let legacy: HashMap<String, LegacyRedisItem> = bincode::deserialize(buffer)?;
+            let cache = legacy
+                .into_iter()
+                .map(|(key, item)| (key, item.into()))
+                .collect();
+            return Ok(vec![(0, cache)]);
+        }
+
+        if buffer.len() < header_len {
+            return Err(DatabaseError::DataCorruption);
+        }
+        let version = u16::from_le_bytes([buffer[header_len - 2], buffer[header_len - 1]]);
+        let body = &buffer[header_len..];
+        match version {
+            SINGLE_DB_VERSION => Ok(vec![(0, bincode::deserialize(body)?)]),
+            DUMP_VERSION => Ok(bincode::deserialize(body)?),
+            _ => Err(DatabaseError::UnsupportedVersion(version)),
+        }
+    }
 }
@@ -0,0 +1,137 @@
+use thiserror::Error;
+
+use crate::models::value::Value;
+
+// Client libraries match on these messages, so they must stay exactly as
+// Redis words them.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum RedisError {
+    #[error("ERR {0}")]
+    Err(String),
+
+    #[error("ERR internal error")]
+    Internal,
+
+    #[error("ERR syntax error")]
+    Syntax,
+
+    #[error("ERR wrong number of arguments for '{0}' command")]
+    WrongArity(String),
+
+    #[error("ERR unknown command '{0}', with args beginning with: {1}")]
+    UnknownCommand(String, String),
+
+    #[error("ERR value is not an integer or out of range")]
+    NotInteger,
+
+    #[error("ERR value is not a valid float")]
+    NotFloat,
+
+    #[error("ERR no such key")]
+    NoSuchKey,
+
+    #[error("WRONGTYPE Operation against a key holding the wrong kind of value")]
+    WrongType,
+
+    #[error("BUSYKEY Target key name already exists.")]
+    BusyKey,
+
+    #[error("NOSCRIPT No matching script. Please use EVAL.")]
+    NoScript,
+
+    #[error("READONLY You can't write against a read only replica.")]
+    ReadOnly,
+
+    #[error("MOVED {0} {1}")]
+    Moved(u16, String),
+
+    #[error("ASK {0} {1}")]
+    Ask(u16, String),
+
+    #[error(
+        "BUSY Redis is busy running a script. You can only call SCRIPT KILL or SHUTDOWN NOSAVE."
+    )]
+    Busy,
+
+    #[error("NOAUTH Authentication required.")]
+    NoAuth,
+
+    #[error("WRONGPASS invalid username-password pair or user is disabled.")]
+    WrongPass,
+
+    #[error("NOPROTO sorry, this protocol version is not supported.")]
+    NoProto,
+
+    #[error("NOPERM {0}")]
+    NoPerm(String),
+
+    #[error("OOM command not allowed when used memory > 'maxmemory'.")]
+    Oom,
+
+    #[error("EXECABORT Transaction discarded because of previous errors.")]
+    ExecAbort,
+
+    #[error("IOERR {0}")]
+    IoErr(String),
+}
+
+impl RedisError {
+    pub fn err(message: impl Into<String>) -> Self {
+        RedisError::Err(message.into())
+    }
+
+    pub fn wrong_arity(command: &str) -> Self {
+        RedisError::WrongArity(command.to_lowercase())
+    }
+
+    pub fn unknown_command(name: &str, args: &[Value]) -> Self {
+        let mut quoted = String::new();
+        for arg in args {
+            if quoted.len() >= 128 {
+                break;
+            }
+            let arg = match arg {
+                Value::BulkString(s) | Value::SimpleString(s) => s.clone(),
+                Value::Integer(i) => i.to_string(),
+                _ => String::new(),
+            };
+            quoted.push_str(&format!("'{}' ", truncate(&arg, 128)));
+        }
+        RedisError::UnknownCommand(truncate(name, 128).to_string(), quoted)
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            RedisError::WrongType => "WRONGTYPE",
+            RedisError::BusyKey => "BUSYKEY",
+            RedisError::NoScript => "NOSCRIPT",
+            RedisError::ReadOnly => "READONLY",
+            RedisError::Moved(..) => "MOVED",
+            RedisError::Ask(..) => "ASK",
+            RedisError::Busy => "BUSY",
+            RedisError::NoAuth => "NOAUTH",
+            RedisError::WrongPass => "WRONGPASS",
+            RedisError::NoProto => "NOPROTO",
+            RedisError::NoPerm(_) => "NOPERM",
+            RedisError::Oom => "OOM",
+            RedisError::ExecAbort => "EXECABORT",
+            RedisError::IoErr(_) => "IOERR",
+            _ => "ERR",
+        }
+    }
+}
+
+fn truncate(s: &str, max_chars: usize) -> &str {
+    match s.char_indices().nth(max_chars) {
+        Some((end, _)) => &s[..end],
+        None => s,
+    }
+}
+
+impl From<RedisError> for Value {
+    fn from(error: RedisError) -> Self {
+        Value::Error(error.to_string())
+    }
+}
+
+pub type RedisResult<T> = Result<T, RedisError>;
@@ -0,0 +1,234 @@
+use std::fmt;
+use std::str::FromStr;
+
+use rand::Rng;
+
+use crate::error::{RedisError, RedisResult};
+use crate::keyspace::Keyspace;
+use crate::log;
+use crate::memory::used_memory;
+use crate::server::Server;
+use crate::utilities::{lock, now_millis};
+
+const EVICTION_POOL_SIZE: usize = 16;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum MaxmemoryPolicy {
+    #[default]
+    NoEviction,
+    AllKeysLru,
+    VolatileLru,
+    AllKeysLfu,
+    VolatileLfu,
+    AllKeysRandom,
+    VolatileRandom,
+    VolatileTtl,
+}
+
+const POLICIES: &[(&str, MaxmemoryPolicy)] = &[
+    ("noeviction", MaxmemoryPolicy::NoEviction),
+    ("allkeys-lru", MaxmemoryPolicy::AllKeysLru),
+    ("volatile-lru", MaxmemoryPolicy::VolatileLru),
+    ("allkeys-lfu", MaxmemoryPolicy::AllKeysLfu),
+    ("volatile-lfu", MaxmemoryPolicy::VolatileLfu),
+    ("allkeys-random", MaxmemoryPolicy::AllKeysRandom),
+    ("volatile-random", MaxmemoryPolicy::VolatileRandom),
+    ("volatile-ttl", MaxmemoryPolicy::VolatileTtl),
+];
+
+impl MaxmemoryPolicy {
+    pub fn is_volatile(self) -> bool {
+        matches!(
+            self,
+            Self::VolatileLru | Self::VolatileLfu | Self::VolatileRandom | Self::VolatileTtl
+        )
+    }
+}
+
+impl FromStr for MaxmemoryPolicy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        POLICIES
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(value))
+            .map(|&(_, policy)| policy)
+            .ok_or_else(|| format!("invalid maxmemory policy '{}'", value))
+    }
+}
+
+impl fmt::Display for MaxmemoryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (name, _) = POLICIES.iter().find(|(_, policy)| policy == self).unwrap();
+        f.write_str(name)
+    }
+}
+
+// The higher `idle`, the better a victim: idle time for LRU, 255 minus the
+// frequency for LFU, closeness to expiry for volatile-ttl.
+#[derive(Debug)]
+struct Candidate {
+    idle: u64,
+    db: usize,
+    key: String,
+}
+
+// Sorted by ascending idle score. Candidates are checked again before they
+// are evicted, as they may have changed since they were sampled.
+#[derive(Debug, Default)]
+pub struct EvictionPool {
+    candidates: Vec<Candidate>,
+    next_db: usize,
+}
+
+impl EvictionPool {
+    fn insert(&mut self, candidate: Candidate) {
+        if self
+            .candidates
+            .iter()
+            .any(|c| c.db == candidate.db && c.key == candidate.key)
+        {
+            return;
+        }
+        let full = self.candidates.len() == EVICTION_POOL_SIZE;
+        if full && candidate.idle <= self.candidates[0].idle {
+            return;
+        }
+        let at = self.candidates.partition_point(|c| c.idle < candidate.idle);
+        self.candidates.insert(at, candidate);
+        if full {
+            self.candidates.remove(0);
+        }
+    }
+}
+
+pub fn perform_evictions(server: &Server) -> RedisResult<()> {
+    let (maxmemory, policy) = {
+        let config = server.config();
+        (config.maxmemory as usize, config.maxmemory_policy)
+    };
+    let used = used_memory();
+    if maxmemory == 0 || used <= maxmemory {
+        return Ok(());
+    }
+    if policy == MaxmemoryPolicy::NoEviction {
+        return Err(RedisError::Oom);
+    }
+
+    let to_free = used - maxmemory;
+    let mut freed = 0;
+    while freed < to_free {
+        match evict_key(server) {
+            Some(size) => freed += size,
+            None => return Err(RedisError::Oom),
+        }
+    }
+    Ok(())
+}
+
+pub fn evict_key(server: &Server) -> Option<usize> {
+    let (policy, samples, decay_time) = {
+        let config = server.config();
+        (
+            config.maxmemory_policy,
+            config.maxmemory_samples.max(1),
+            config.lfu_decay_time,
+        )
+    };
+    let mut pool = lock(&server.eviction_pool);
+    let (db, key) = match policy {
+        MaxmemoryPolicy::NoEviction => return None,
+        MaxmemoryPolicy::AllKeysRandom | MaxmemoryPolicy::VolatileRandom => {
+            random_victim(server, &mut pool, policy)?
+        }
+        _ => pooled_victim(server, &mut pool, policy, samples, decay_time)?,
+    };
+    drop(pool);
+
+    let item = lock(&server.databases[db]).remove(&key)?;
+    let size = key.len() + item.memory_usage();
+    server.stats.record_eviction();
+    log!("Evicted {} from db{} ({} bytes)", key, db, size);
+    Some(size)
+}
+
+fn pooled_victim(
+    server: &Server,
+    pool: &mut EvictionPool,
+    policy: MaxmemoryPolicy,
+    samples: usize,
+    decay_time: u64,
+) -> Option<(usize, String)> {
+    loop {
+        for (db, keyspace) in server.databases.iter().enumerate() {
+            let keyspace = lock(keyspace);
+            for (key, idle) in sample(&keyspace, samples, policy, decay_time) {
+                pool.insert(Candidate { idle, db, key });
+            }
+        }
+        let candidate = pool.candidates.pop()?;
+        let exists = lock(&server.databases[candidate.db])
+            .get(&candidate.key)
+            .is_some_and(|item| !policy.is_volatile() || item.expiration.is_some());
+        if exists {
+            return Some((candidate.db, candidate.key));
+        }
+    }
+}
+
+// Reads contiguous positions from a random start, like dictGetSomeKeys.
+fn sample(
+    keyspace: &Keyspace,
+    count: usize,
+    policy: MaxmemoryPolicy,
+    decay_time: u64,
+) -> Vec<(String, u64)> {
+    let len = if policy.is_volatile() {
+        keyspace.expires()
+    } else {
+        keyspace.len()
+    };
+    if len == 0 {
+        return Vec::new();
+    }
+    let start = rand::thread_rng().gen_range(0..len);
+    let now = now_millis();
+    (start..start + count.min(len))
+        .filter_map(|index| {
+            if policy.is_volatile() {
+                keyspace.get_volatile_index(index % len)
+            } else {
+                keyspace.get_index(index % len)
+            }
+        })
+        .map(|(key, item)| {
+            let idle = match policy {
+                MaxmemoryPolicy::AllKeysLfu | MaxmemoryPolicy::VolatileLfu => {
+                    (u8::MAX - item.access.decayed_frequency(decay_time)) as u64
+                }
+                MaxmemoryPolicy::VolatileTtl => {
+                    u64::MAX - item.expires_at().unwrap_or(i64::MAX).max(0) as u64
+                }
+                _ => (now - item.access.last_access).max(0) as u64,
+            };
+            (key.clone(), idle)
+        })
+        .collect()
+}
+
+fn random_victim(
+    server: &Server,
+    pool: &mut EvictionPool,
+    policy: MaxmemoryPolicy,
+) -> Option<(usize, String)> {
+    let count = server.databases.len();
+    for offset in 0..count {
+        let db = (pool.next_db + offset) % count;
+        let keyspace = lock(&server.databases[db]);
+        if let Some((key, _)) = sample(&keyspace, 1, policy, 0).pop() {
+            pool.next_db = db + 1;
+            return Some((db, key));
+        }
+    }
+    None
+}
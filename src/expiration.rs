@@ -0,0 +1,128 @@
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+
+use crate::keyspace::Keyspace;
+use crate::log;
+use crate::models::redis_item::RedisItem;
+use crate::models::value::Value;
+use crate::server::Server;
+use crate::utilities::{lock, now_millis};
+
+pub const KEYS_PER_LOOP: usize = 20;
+
+// A database gets another round while more than this percentage of its last
+// sample had expired.
+const STALE_PERCENT: usize = 25;
+
+const CYCLE_TIME_PERCENT: u32 = 25;
+
+#[derive(Debug, Default)]
+pub struct Sample {
+    pub sampled: usize,
+    pub expired: usize,
+    pub fields: usize,
+    pub removed: Vec<RedisItem>,
+}
+
+impl Sample {
+    fn is_stale(&self) -> bool {
+        self.expired * 100 > self.sampled * STALE_PERCENT
+    }
+}
+
+pub fn cycle_budget(hz: u32) -> Duration {
+    Duration::from_secs(1) * CYCLE_TIME_PERCENT / 100 / hz.max(1)
+}
+
+pub fn expire_keys_sample(cache: &mut Keyspace) -> Sample {
+    let keys: Vec<String> = random_window(cache.expires())
+        .filter_map(|index| cache.get_volatile_index(index))
+        .map(|(key, _)| key.clone())
+        .collect();
+    let mut sample = Sample {
+        sampled: keys.len(),
+        ..Default::default()
+    };
+    for key in keys {
+        if cache.get(&key).is_some_and(RedisItem::is_expired) {
+            sample.removed.extend(cache.remove(&key));
+            sample.expired += 1;
+        }
+    }
+    sample
+}
+
+pub fn expire_fields_sample(cache: &mut Keyspace, now_ms: i64) -> Sample {
+    let keys: Vec<String> = random_window(cache.field_expires())
+        .filter_map(|index| cache.get_volatile_fields_index(index))
+        .map(|(key, _)| key.clone())
+        .collect();
+    let mut sample = Sample {
+        sampled: keys.len(),
+        ..Default::default()
+    };
+    for key in keys {
+        let Some(item) = cache.get_mut(&key) else {
+            continue;
+        };
+        let purged = item.purge_expired_fields(now_ms);
+        let emptied = matches!(&item.value, Value::Hash(hash) if hash.is_empty());
+        if purged > 0 {
+            sample.expired += 1;
+            sample.fields += purged;
+        }
+        if purged > 0 && emptied {
+            sample.removed.extend(cache.remove(&key));
+        } else {
+            cache.track_field_expirations(&key);
+        }
+    }
+    sample
+}
+
+// Stops once `budget` is spent, leaving `*next_db` at the database the next
+// cycle resumes at.
+pub fn active_expire_cycle(server: &Server, next_db: &mut usize, budget: Duration) {
+    let started = Instant::now();
+    let count = server.databases.len();
+    for offset in 0..count {
+        let db = (*next_db + offset) % count;
+        loop {
+            if started.elapsed() >= budget {
+                *next_db = db;
+                return;
+            }
+            let (keys, fields) = {
+                let mut cache = lock(&server.databases[db]);
+                let keys = expire_keys_sample(&mut cache);
+                (keys, expire_fields_sample(&mut cache, now_millis()))
+            };
+            if !keys.removed.is_empty() {
+                log!("Expired {} keys in db{}", keys.removed.len(), db);
+                server.stats.record_expired(keys.removed.len());
+            }
+            if fields.fields > 0 {
+                log!("Expired {} hash fields in db{}", fields.fields, db);
+            }
+            let stale = keys.is_stale() || fields.is_stale();
+            let lazy = server.lazyfree.config().lazy_expire;
+            for item in keys.removed.into_iter().chain(fields.removed) {
+                server.lazyfree.release(item, lazy);
+            }
+            if !stale {
+                break;
+            }
+        }
+    }
+}
+
+// Consecutive positions from a random start, wrapping around.
+fn random_window(len: usize) -> impl Iterator<Item = usize> {
+    let start = if len == 0 {
+        0
+    } else {
+        rand::thread_rng().gen_range(0..len)
+    };
+    (start..start + len.min(KEYS_PER_LOOP)).map(move |index| index % len)
+}
@@ -0,0 +1,134 @@
+// Limit on nested `*` expansions, so a hostile pattern can't exhaust the stack.
+const MAX_NESTING: usize = 1000;
+
+// Same semantics as Redis' `stringmatchlen`.
+pub fn string_match(pattern: &str, string: &str, nocase: bool) -> bool {
+    let mut skip_longer_matches = false;
+    match_impl(
+        pattern.as_bytes(),
+        string.as_bytes(),
+        nocase,
+        &mut skip_longer_matches,
+        0,
+    )
+}
+
+fn fold(c: u8, nocase: bool) -> u8 {
+    if nocase {
+        c.to_ascii_lowercase()
+    } else {
+        c
+    }
+}
+
+fn match_impl(
+    pattern: &[u8],
+    string: &[u8],
+    nocase: bool,
+    skip_longer_matches: &mut bool,
+    nesting: usize,
+) -> bool {
+    if nesting > MAX_NESTING {
+        return false;
+    }
+
+    let (mut p, mut s) = (0, 0);
+    while p < pattern.len() && s < string.len() {
+        match pattern[p] {
+            b'*' => {
+                while p + 1 < pattern.len() && pattern[p + 1] == b'*' {
+                    p += 1;
+                }
+                if p + 1 == pattern.len() {
+                    return true;
+                }
+                while s < string.len() {
+                    if match_impl(
+                        &pattern[p + 1..],
+                        &string[s..],
+                        nocase,
+                        skip_longer_matches,
+                        nesting + 1,
+                    ) {
+                        return true;
+                    }
+                    // A deeper `*` already failed against every suffix, so
+                    // trying longer expansions of this one cannot succeed.
+                    if *skip_longer_matches {
+                        return false;
+                    }
+                    s += 1;
+                }
+                *skip_longer_matches = true;
+                return false;
+            }
+            b'?' => s += 1,
+            b'[' => {
+                p += 1;
+                let not = pattern.get(p) == Some(&b'^');
+                if not {
+                    p += 1;
+                }
+                let c = fold(string[s], nocase);
+                let mut matched = false;
+                loop {
+                    if p >= pattern.len() {
+                        // Unterminated class: treat the end of the pattern as
+                        // its closing bracket.
+                        p -= 1;
+                        break;
+                    }
+                    if pattern[p] == b'\\' && p + 1 < pattern.len() {
+                        p += 1;
+                        if pattern[p] == string[s] {
+                            matched = true;
+                        }
+                    } else if pattern[p] == b']' {
+                        break;
+                    } else if p + 2 < pattern.len() && pattern[p + 1] == b'-' {
+                        let mut start = fold(pattern[p], nocase);
+                        let mut end = fold(pattern[p + 2], nocase);
+                        if start > end {
+                            std::mem::swap(&mut start, &mut end);
+                        }
+                        p += 2;
+                        if (start..=end).contains(&c) {
+                            matched = true;
+                        }
+                    } else if fold(pattern[p], nocase) == c {
+                        matched = true;
+                    }
+                    p += 1;
+                }
+                if not {
+                    matched = !matched;
+                }
+                if !matched {
+                    return false;
+                }
+                s += 1;
+            }
+            literal => {
+                let literal = if literal == b'\\' && p + 1 < pattern.len() {
+                    p += 1;
+                    pattern[p]
+                } else {
+                    literal
+                };
+                if fold(literal, nocase) != fold(string[s], nocase) {
+                    return false;
+                }
+                s += 1;
+            }
+        }
+        p += 1;
+        if s == string.len() {
+            while p < pattern.len() && pattern[p] == b'*' {
+                p += 1;
+            }
+            break;
+        }
+    }
+
+    p == pattern.len() && s == string.len()
+}
@@ -0,0 +1,179 @@
+use crate::{
+    acl::User,
+    command_args::{ArgParser, FromArg},
+    commands::{ACL_CATEGORIES, COMMAND_TABLE},
+    error::{RedisError, RedisResult},
+    models::value::Value,
+    server::Server,
+    utilities::now_millis,
+};
+
+// Manages ACL users and inspects what they may do.
+// ACL SETUSER username [rule ...] | GETUSER username | DELUSER username
+//   [username ...] | LIST | USERS | WHOAMI | CAT [category]
+//   | DRYRUN username command [arg ...] | LOG [count | RESET] | SAVE | LOAD
+pub fn acl_handler(server: &mut Server, _key: String, args: Vec<Value>) -> Option<Value> {
+    let mut args = ArgParser::new("acl", args);
+    let subcommand = match args.arg::<String>() {
+        Ok(subcommand) => subcommand,
+        Err(e) => return Some(e.into()),
+    };
+    let result = match subcommand.to_uppercase().as_str() {
+        "SETUSER" if !args.is_empty() => setuser(server, args),
+        "GETUSER" if args.len() == 1 => getuser(server, args),
+        "DELUSER" if !args.is_empty() => deluser(server, args),
+        "LIST" if args.is_empty() => Ok(Value::Array(
+            server
+                .acl()
+                .users()
+                .map(|user| Value::BulkString(user.describe()))
+                .collect(),
+        )),
+        "USERS" if args.is_empty() => Ok(Value::Array(
+            server
+                .acl()
+                .users()
+                .map(|user| Value::BulkString(user.name.clone()))
+                .collect(),
+        )),
+        "WHOAMI" if args.is_empty() => Ok(Value::BulkString(server.user.clone())),
+        "CAT" if args.len() <= 1 => cat(args),
+        "DRYRUN" if args.len() >= 2 => dryrun(server, args),
+        "LOG" if args.len() <= 1 => log(server, args),
+        "SAVE" if args.is_empty() => server.acl().save().map(|_| ok()),
+        "LOAD" if args.is_empty() => server.acl().load().map(|_| ok()),
+        _ => Err(RedisError::err(format!(
+            "unknown subcommand or wrong number of arguments for '{}'. Try ACL HELP.",
+            subcommand
+        ))),
+    };
+    Some(result.unwrap_or_else(Value::from))
+}
+
+fn ok() -> Value {
+    Value::SimpleString("OK".to_string())
+}
+
+fn setuser(server: &Server, mut args: ArgParser) -> RedisResult<Value> {
+    let name: String = args.arg()?;
+    let mut rules = Vec::new();
+    while !args.is_empty() {
+        rules.push(args.arg()?);
+    }
+    server.acl().set_user(&name, &rules)?;
+    Ok(ok())
+}
+
+fn getuser(server: &Server, mut args: ArgParser) -> RedisResult<Value> {
+    let name: String = args.arg()?;
+    let acl = server.acl();
+    let Some(user) = acl.user(&name) else {
+        return Ok(Value::NullBulkString);
+    };
+    Ok(describe_user(user))
+}
+
+fn describe_user(user: &User) -> Value {
+    let field = |name: &str| Value::BulkString(name.to_string());
+    let mut flags = vec![field(if user.enabled { "on" } else { "off" })];
+    if user.nopass {
+        flags.push(field("nopass"));
+    }
+    Value::Array(vec![
+        field("flags"),
+        Value::Array(flags),
+        field("passwords"),
+        Value::Array(user.passwords.iter().map(|hash| field(hash)).collect()),
+        field("commands"),
+        field(&user.command_rules()),
+        field("keys"),
+        field(&user.key_rules()),
+        field("channels"),
+        field(&user.channel_rules()),
+        field("selectors"),
+        Value::Array(vec![]),
+    ])
+}
+
+fn deluser(server: &Server, mut args: ArgParser) -> RedisResult<Value> {
+    let mut acl = server.acl();
+    let mut deleted = 0;
+    while !args.is_empty() {
+        if acl.delete_user(&args.arg::<String>()?)? {
+            deleted += 1;
+        }
+    }
+    Ok(Value::Integer(deleted))
+}
+
+fn cat(mut args: ArgParser) -> RedisResult<Value> {
+    if args.is_empty() {
+        return Ok(Value::Array(
+            ACL_CATEGORIES
+                .iter()
+                .map(|category| Value::BulkString(category.to_string()))
+                .collect(),
+        ));
+    }
+
+    let category = args.arg::<String>()?.to_lowercase();
+    if !ACL_CATEGORIES.contains(&category.as_str()) {
+        return Err(RedisError::err(format!("Unknown category '{}'", category)));
+    }
+    let mut names: Vec<&str> = COMMAND_TABLE
+        .values()
+        .filter(|command| command.acl_categories.contains(&category.as_str()))
+        .map(|command| command.name)
+        .collect();
+    names.sort_unstable();
+    Ok(Value::Array(
+        names
+            .into_iter()
+            .map(|name| Value::BulkString(name.to_string()))
+            .collect(),
+    ))
+}
+
+fn dryrun(server: &Server, mut args: ArgParser) -> RedisResult<Value> {
+    let username: String = args.arg()?;
+    let name: String = args.arg()?;
+    let mut rest = Vec::new();
+    while !args.is_empty() {
+        rest.push(args.arg()?);
+    }
+
+    let acl = server.acl();
+    let user = acl
+        .user(&username)
+        .ok_or_else(|| RedisError::err(format!("User '{}' not found", username)))?;
+    let command = COMMAND_TABLE
+        .get(name.to_uppercase().as_str())
+        .ok_or_else(|| RedisError::err(format!("Command '{}' not found", name)))?;
+    if !command.arity_matches(rest.len() + 1) {
+        return Err(RedisError::wrong_arity(command.name));
+    }
+    Ok(match user.check(command, &rest) {
+        Ok(()) => ok(),
+        Err(denial) => Value::BulkString(denial.describe()),
+    })
+}
+
+fn log(server: &Server, mut args: ArgParser) -> RedisResult<Value> {
+    let count = match args.arg::<Value>() {
+        Err(_) => usize::MAX,
+        Ok(Value::BulkString(s)) if s.eq_ignore_ascii_case("RESET") => {
+            server.acl().reset_log();
+            return Ok(ok());
+        }
+        Ok(value) => usize::try_from(i64::from_arg(value)?).map_err(|_| RedisError::NotInteger)?,
+    };
+    let now = now_millis();
+    Ok(Value::Array(
+        server
+            .acl()
+            .log()
+            .take(count)
+            .map(|entry| entry.to_value(now))
+            .collect(),
+    ))
+}
@@ -1,34 +1,100 @@
+use crate::utilities::lock;
 use crate::{
+    error::RedisError,
     models::value::Value,
-    server::{Role, Server},
+    server::{Db, Server},
+    utilities::unpack_integer_arg,
 };
 
 pub fn ping_handler(_: &mut Server, _key: String, _: Vec<Value>) -> Option<Value> {
     Some(Value::SimpleString("PONG".to_string()))
 }
 
-pub fn echo_handler(_: &mut Server, arg: String, _: Vec<Value>) -> Option<Value> {
-    Some(Value::BulkString(arg))
+pub fn echo_handler(_: &mut Server, _key: String, args: Vec<Value>) -> Option<Value> {
+    match args.into_iter().next() {
+        Some(Value::BulkString(message)) => Some(Value::BulkString(message)),
+        _ => Some(RedisError::wrong_arity("echo").into()),
+    }
 }
 
-pub fn flushall_handler(server: &mut Server, _key: String, _: Vec<Value>) -> Option<Value> {
-    let mut cache = server.cache.lock().unwrap();
-    cache.clear();
+// Removes every key from every database.
+// FLUSHALL [ASYNC | SYNC]
+pub fn flushall_handler(server: &mut Server, _key: String, args: Vec<Value>) -> Option<Value> {
+    let lazy = match flush_async(server, &args) {
+        Ok(lazy) => lazy,
+        Err(e) => return Some(e),
+    };
+    for db in server.databases.iter() {
+        flush_db(server, db, lazy);
+    }
+    Some(Value::SimpleString("OK".to_string()))
+}
+
+// Removes every key from the selected database.
+// FLUSHDB [ASYNC | SYNC]
+pub fn flushdb_handler(server: &mut Server, _key: String, args: Vec<Value>) -> Option<Value> {
+    let lazy = match flush_async(server, &args) {
+        Ok(lazy) => lazy,
+        Err(e) => return Some(e),
+    };
+    flush_db(server, &server.cache, lazy);
     Some(Value::SimpleString("OK".to_string()))
 }
 
-pub fn info_handler(server: &Server) -> Option<Value> {
-    let mut info = format!("role:{}", server.role.to_string());
-    match &server.role {
-        Role::Main => {
-            info.push_str(&format!(
-                "nmaster_replid:8371b4fb1155b71f4a04d3e1bc3e18c4a990aeeb"
-            ));
-            info.push_str("master_repl_offset:0");
-        }
-        Role::Slave { host, port } => {
-            info.push_str(&format!("nmaster_host:{}nmaster_port:{}", host, port));
-        }
+fn flush_async(server: &Server, args: &[Value]) -> Result<bool, Value> {
+    match args {
+        [] => Ok(server.lazyfree.config().lazy_user_flush),
+        [Value::BulkString(mode)] if mode.eq_ignore_ascii_case("ASYNC") => Ok(true),
+        [Value::BulkString(mode)] if mode.eq_ignore_ascii_case("SYNC") => Ok(false),
+        _ => Err(RedisError::Syntax.into()),
+    }
+}
+
+// The keyspace is freed after the lock is released.
+fn flush_db(server: &Server, db: &Db, lazy: bool) {
+    let keyspace = std::mem::take(&mut *lock(db));
+    if lazy {
+        server.lazyfree.free_keyspace(keyspace);
+    }
+}
+
+// Selects the database used by the rest of this connection.
+pub fn select_handler(server: &mut Server, _key: String, args: Vec<Value>) -> Option<Value> {
+    let index = match args.first().map(unpack_integer_arg) {
+        Some(Ok(index)) => index,
+        _ => return Some(RedisError::NotInteger.into()),
     };
-    Some(Value::BulkString(info))
+
+    if index >= 0 && server.select_db(index as usize) {
+        Some(Value::SimpleString("OK".to_string()))
+    } else {
+        Some(RedisError::err("DB index is out of range").into())
+    }
+}
+
+// Swaps the contents of two databases.
+pub fn swapdb_handler(server: &mut Server, _key: String, args: Vec<Value>) -> Option<Value> {
+    let (first, second) = match args.as_slice() {
+        [first, second] => (first, second),
+        _ => return Some(RedisError::wrong_arity("swapdb").into()),
+    };
+    let first = match unpack_integer_arg(first) {
+        Ok(index) => index,
+        Err(_) => return Some(RedisError::err("invalid first DB index").into()),
+    };
+    let second = match unpack_integer_arg(second) {
+        Ok(index) => index,
+        Err(_) => return Some(RedisError::err("invalid second DB index").into()),
+    };
+
+    let count = server.databases.len() as i64;
+    if !(0..count).contains(&first) || !(0..count).contains(&second) {
+        return Some(RedisError::err("DB index is out of range").into());
+    }
+
+    if first != second {
+        let (mut a, mut b) = server.lock_db_pair(first as usize, second as usize);
+        std::mem::swap(&mut *a, &mut *b);
+    }
+    Some(Value::SimpleString("OK".to_string()))
 }
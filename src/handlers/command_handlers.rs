@@ -0,0 +1,135 @@
+use crate::{
+    commands::{Command, COMMAND_TABLE, DEPRECATED_COMMANDS},
+    error::RedisError,
+    models::value::Value,
+    server::Server,
+};
+
+// Describes the commands the server knows.
+// COMMAND [COUNT | INFO [command ...] | DOCS [command ...]
+//   | GETKEYS command [arg ...]]
+pub fn command_handler(_: &mut Server, _key: String, args: Vec<Value>) -> Option<Value> {
+    let mut args = args.into_iter();
+    let subcommand = match args.next() {
+        None => return Some(Value::Array(sorted_commands().map(command_info).collect())),
+        Some(Value::BulkString(subcommand)) => subcommand,
+        Some(_) => return Some(RedisError::Syntax.into()),
+    };
+    let args: Vec<Value> = args.collect();
+
+    match subcommand.to_uppercase().as_str() {
+        "COUNT" if args.is_empty() => Some(Value::Integer(COMMAND_TABLE.len() as i64)),
+        "INFO" if args.is_empty() => {
+            Some(Value::Array(sorted_commands().map(command_info).collect()))
+        }
+        "INFO" => Some(Value::Array(
+            args.iter()
+                .map(|name| match lookup(name) {
+                    Some(command) => command_info(command),
+                    None => Value::NullBulkString,
+                })
+                .collect(),
+        )),
+        "DOCS" => {
+            let commands: Vec<&Command> = if args.is_empty() {
+                sorted_commands().collect()
+            } else {
+                args.iter().filter_map(lookup).collect()
+            };
+            Some(Value::Array(
+                commands
+                    .into_iter()
+                    .flat_map(|command| {
+                        [
+                            Value::BulkString(command.name.to_string()),
+                            command_docs(command),
+                        ]
+                    })
+                    .collect(),
+            ))
+        }
+        "GETKEYS" if !args.is_empty() => Some(getkeys(args)),
+        _ => Some(
+            RedisError::err(format!(
+                "unknown subcommand or wrong number of arguments for '{}'. Try COMMAND HELP.",
+                subcommand
+            ))
+            .into(),
+        ),
+    }
+}
+
+fn lookup(name: &Value) -> Option<&'static Command> {
+    match name {
+        Value::BulkString(name) => COMMAND_TABLE.get(name.to_uppercase().as_str()),
+        _ => None,
+    }
+}
+
+fn sorted_commands() -> impl Iterator<Item = &'static Command> {
+    let mut commands: Vec<&Command> = COMMAND_TABLE.values().collect();
+    commands.sort_by_key(|command| command.name);
+    commands.into_iter()
+}
+
+fn command_info(command: &Command) -> Value {
+    Value::Array(vec![
+        Value::BulkString(command.name.to_string()),
+        Value::Integer(command.arity as i64),
+        Value::Array(
+            command
+                .flags
+                .iter()
+                .map(|flag| Value::SimpleString(flag.to_string()))
+                .collect(),
+        ),
+        Value::Integer(command.first_key as i64),
+        Value::Integer(command.last_key as i64),
+        Value::Integer(command.step as i64),
+        Value::Array(
+            command
+                .acl_categories
+                .iter()
+                .map(|category| Value::SimpleString(format!("@{}", category)))
+                .collect(),
+        ),
+        Value::Array(vec![]),
+        Value::Array(vec![]),
+        Value::Array(vec![]),
+    ])
+}
+
+fn command_docs(command: &Command) -> Value {
+    let mut docs = vec![
+        Value::BulkString("summary".to_string()),
+        Value::BulkString(command.summary.to_string()),
+        Value::BulkString("group".to_string()),
+        Value::BulkString(command.group.to_string()),
+    ];
+    if let Some(replacement) = DEPRECATED_COMMANDS.get(command.name.to_uppercase().as_str()) {
+        docs.extend([
+            Value::BulkString("doc_flags".to_string()),
+            Value::Array(vec![Value::SimpleString("deprecated".to_string())]),
+            Value::BulkString("replaced_by".to_string()),
+            Value::BulkString(format!("`{}`", replacement)),
+        ]);
+    }
+    Value::Array(docs)
+}
+
+// COMMAND GETKEYS command [arg ...]
+fn getkeys(mut args: Vec<Value>) -> Value {
+    let command = match lookup(&args.remove(0)) {
+        Some(command) => command,
+        None => return RedisError::err("Invalid command specified").into(),
+    };
+    if !command.arity_matches(args.len() + 1) {
+        return RedisError::err("Invalid number of arguments specified for command").into();
+    }
+
+    let keys = command.keys(&args);
+    if keys.is_empty() {
+        return RedisError::err("The command has no key arguments").into();
+    }
+    Value::Array(keys.into_iter().map(Value::BulkString).collect())
+}
@@ -0,0 +1,68 @@
+use crate::{
+    command_args::ArgParser,
+    config,
+    error::{RedisError, RedisResult},
+    memory,
+    models::value::Value,
+    server::Server,
+};
+
+// Reads, changes and persists the configuration.
+// CONFIG GET parameter [parameter ...] | SET parameter value [parameter value
+//   ...] | REWRITE | RESETSTAT
+pub fn config_handler(server: &mut Server, _key: String, args: Vec<Value>) -> Option<Value> {
+    let mut args = ArgParser::new("config", args);
+    let subcommand = match args.arg::<String>() {
+        Ok(subcommand) => subcommand,
+        Err(e) => return Some(e.into()),
+    };
+    let result = match subcommand.to_uppercase().as_str() {
+        "GET" if !args.is_empty() => get(server, args),
+        "SET" if !args.is_empty() && args.len().is_multiple_of(2) => set(server, args),
+        "REWRITE" if args.is_empty() => server.config().rewrite().map(|_| ok()),
+        "RESETSTAT" if args.is_empty() => {
+            server.stats.reset();
+            memory::reset_peak();
+            Ok(ok())
+        }
+        _ => Err(RedisError::err(format!(
+            "unknown subcommand or wrong number of arguments for '{}'. Try CONFIG HELP.",
+            subcommand
+        ))),
+    };
+    Some(result.unwrap_or_else(Value::from))
+}
+
+fn ok() -> Value {
+    Value::SimpleString("OK".to_string())
+}
+
+// Replies with a flat list of names and values.
+fn get(server: &Server, mut args: ArgParser) -> RedisResult<Value> {
+    let mut patterns = Vec::new();
+    while !args.is_empty() {
+        patterns.push(args.arg()?);
+    }
+    Ok(Value::Array(
+        server
+            .config()
+            .get(&patterns)
+            .into_iter()
+            .flat_map(|(name, value)| {
+                [
+                    Value::BulkString(name.to_string()),
+                    Value::BulkString(value),
+                ]
+            })
+            .collect(),
+    ))
+}
+
+fn set(server: &Server, mut args: ArgParser) -> RedisResult<Value> {
+    let mut changes = Vec::new();
+    while !args.is_empty() {
+        changes.push((args.arg()?, args.arg()?));
+    }
+    config::set(server, &changes)?;
+    Ok(ok())
+}
@@ -0,0 +1,121 @@
+use crate::{
+    acl::DEFAULT_USER,
+    command_args::{parse_args, ArgParser, FromArgs},
+    error::{RedisError, RedisResult},
+    models::value::Value,
+    server::{Server, REDIS_VERSION},
+};
+
+pub fn check_credentials(server: &mut Server, user: &str, password: &str) -> RedisResult<()> {
+    server.acl().authenticate(user, password)?;
+    server.user = user.to_string();
+    Ok(())
+}
+
+// Authenticates the connection.
+// AUTH [username] password
+pub fn auth_handler(server: &mut Server, _key: String, args: Vec<Value>) -> Option<Value> {
+    match authenticate(server, ArgParser::new("auth", args)) {
+        Ok(()) => Some(Value::SimpleString("OK".to_string())),
+        Err(e) => Some(e.into()),
+    }
+}
+
+fn authenticate(server: &mut Server, mut args: ArgParser) -> RedisResult<()> {
+    match args.len() {
+        1 => {
+            let acl = server.acl();
+            if acl.user(DEFAULT_USER).is_some_and(|user| user.nopass) {
+                return Err(RedisError::err(
+                    "AUTH <password> called without any password configured for the default \
+                     user. Are you sure your configuration is correct?",
+                ));
+            }
+            drop(acl);
+            check_credentials(server, DEFAULT_USER, &args.arg::<String>()?)
+        }
+        2 => {
+            let user: String = args.arg()?;
+            check_credentials(server, &user, &args.arg::<String>()?)
+        }
+        _ => Err(RedisError::Syntax),
+    }
+}
+
+struct HelloArgs {
+    protover: Option<i64>,
+    auth: Option<(String, String)>,
+}
+
+impl FromArgs for HelloArgs {
+    fn from_args(args: &mut ArgParser) -> RedisResult<Self> {
+        if args.is_empty() {
+            return Ok(HelloArgs {
+                protover: None,
+                auth: None,
+            });
+        }
+        let protover = args
+            .arg()
+            .map_err(|_| RedisError::err("Protocol version is not an integer or out of range"))?;
+        let mut auth = None;
+        while let Some(keyword) = args.keyword()? {
+            match keyword.as_str() {
+                "AUTH" => auth = Some((args.value_of()?, args.value_of()?)),
+                // Accepted so clients sending it still connect.
+                "SETNAME" => {
+                    args.value_of::<String>()?;
+                }
+                _ => return Err(RedisError::Syntax),
+            }
+        }
+        Ok(HelloArgs {
+            protover: Some(protover),
+            auth,
+        })
+    }
+}
+
+pub fn hello_authenticates(args: &[Value]) -> bool {
+    args.iter()
+        .any(|arg| matches!(arg, Value::BulkString(s) if s.eq_ignore_ascii_case("AUTH")))
+}
+
+// Handshakes with the server, optionally authenticating. Only RESP2 is spoken.
+// HELLO [protover [AUTH username password] [SETNAME clientname]]
+pub fn hello_handler(server: &mut Server, _key: String, args: Vec<Value>) -> Option<Value> {
+    let HelloArgs { protover, auth } = match parse_args("hello", args) {
+        Ok(args) => args,
+        Err(e) => return Some(e.into()),
+    };
+    if protover.is_some_and(|protover| protover != 2) {
+        return Some(RedisError::NoProto.into());
+    }
+    if let Some((user, password)) = auth {
+        if let Err(e) = check_credentials(server, &user, &password) {
+            return Some(e.into());
+        }
+    }
+
+    let field = |name: &str| Value::BulkString(name.to_string());
+    Some(Value::Array(vec![
+        field("server"),
+        field("redis"),
+        field("version"),
+        field(REDIS_VERSION),
+        field("proto"),
+        Value::Integer(2),
+        field("mode"),
+        field("standalone"),
+        field("role"),
+        field(&server.role.to_string()),
+        field("modules"),
+        Value::Array(vec![]),
+    ]))
+}
+
+// Asks the server to close the connection once the reply is sent.
+// QUIT
+pub fn quit_handler(_: &mut Server, _key: String, _: Vec<Value>) -> Option<Value> {
+    Some(Value::SimpleString("OK".to_string()))
+}
@@ -1,53 +1,523 @@
+use crate::utilities::lock;
 use crate::{
-    models::{redis_type::RedisType, value::Value},
+    error::{RedisError, RedisResult},
+    keyspace::Keyspace,
+    models::{
+        packed::{Entry, Listpack, PackLimits, Packed},
+        redis_item::RedisItem,
+        redis_type::RedisType,
+        value::Value,
+    },
     server::Server,
+    utilities::{now_millis, unpack_integer_arg},
 };
+use indexmap::IndexMap;
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::ops::Range;
 
 macro_rules! wrong_type_error {
     () => {
-        Some(Value::Error(
-            "ERR operation against a key holding the wrong kind of value".to_string(),
-        ))
+        Some(RedisError::WrongType.into())
     };
 }
 
 pub trait HashOperation {
     fn operate_on_hash<F, R>(&mut self, key: &str, f: F) -> Option<Value>
     where
-        F: FnOnce(&mut HashMap<String, Value>) -> R,
+        F: FnOnce(&mut IndexMap<String, Value>) -> R,
+        R: Into<Option<Value>>;
+
+    fn upsert_hash<F, R>(&mut self, key: &str, f: F) -> Option<Value>
+    where
+        F: FnOnce(&mut IndexMap<String, Value>) -> R,
+        R: Into<Option<Value>>;
+
+    fn operate_on_hash_with_ttls<F, R>(&mut self, key: &str, f: F) -> Option<Value>
+    where
+        F: FnOnce(&mut IndexMap<String, Value>, &mut HashMap<String, i64>) -> R,
+        R: Into<Option<Value>>;
+
+    fn upsert_hash_with_ttls<F, R>(&mut self, key: &str, f: F) -> Option<Value>
+    where
+        F: FnOnce(&mut IndexMap<String, Value>, &mut HashMap<String, i64>) -> R,
+        R: Into<Option<Value>>;
+
+    // A packed hash is read in place. Expired fields are still dropped first.
+    fn read_hash<F, R>(&self, key: &str, f: F) -> Option<Value>
+    where
+        F: FnOnce(HashRef<'_>) -> R,
         R: Into<Option<Value>>;
 }
 
 impl HashOperation for Server {
     fn operate_on_hash<F, R>(&mut self, key: &str, f: F) -> Option<Value>
     where
-        F: FnOnce(&mut HashMap<String, Value>) -> R,
+        F: FnOnce(&mut IndexMap<String, Value>) -> R,
+        R: Into<Option<Value>>,
+    {
+        let limits = self.pack_limits();
+        let mut cache = lock(&self.cache);
+        operate_on_hash_in(&mut cache, key, &limits, f)
+    }
+
+    fn upsert_hash<F, R>(&mut self, key: &str, f: F) -> Option<Value>
+    where
+        F: FnOnce(&mut IndexMap<String, Value>) -> R,
         R: Into<Option<Value>>,
     {
-        let mut cache = self.cache.lock().unwrap();
-        match cache.get_mut(key) {
-            Some(item) if item.redis_type == RedisType::Hash => {
-                if let Value::Hash(ref mut hash) = item.value {
-                    f(hash).into()
-                } else {
-                    wrong_type_error!()
+        self.upsert_hash_with_ttls(key, |hash, _| f(hash))
+    }
+
+    fn operate_on_hash_with_ttls<F, R>(&mut self, key: &str, f: F) -> Option<Value>
+    where
+        F: FnOnce(&mut IndexMap<String, Value>, &mut HashMap<String, i64>) -> R,
+        R: Into<Option<Value>>,
+    {
+        let limits = self.pack_limits();
+        let mut cache = lock(&self.cache);
+        operate_on_hash_with_ttls_in(&mut cache, key, &limits, f)
+    }
+
+    fn upsert_hash_with_ttls<F, R>(&mut self, key: &str, f: F) -> Option<Value>
+    where
+        F: FnOnce(&mut IndexMap<String, Value>, &mut HashMap<String, i64>) -> R,
+        R: Into<Option<Value>>,
+    {
+        let limits = self.pack_limits();
+        let mut cache = lock(&self.cache);
+        if let Some(item) = cache.get_mut(key) {
+            if item.purge_expired_fields(now_millis()) > 0 && is_empty_hash(item) {
+                cache.remove(key);
+            }
+        }
+        if !cache.contains_key(key) {
+            let mut item = RedisItem::new_hash(IndexMap::new());
+            item.pack(&limits);
+            cache.insert(key.to_string(), item);
+        }
+        operate_on_hash_with_ttls_in(&mut cache, key, &limits, f)
+    }
+
+    fn read_hash<F, R>(&self, key: &str, f: F) -> Option<Value>
+    where
+        F: FnOnce(HashRef<'_>) -> R,
+        R: Into<Option<Value>>,
+    {
+        let mut cache = lock(&self.cache);
+        if let Some(item) = cache.get_mut(key) {
+            if item.purge_expired_fields(now_millis()) > 0 {
+                if is_empty_hash(item) {
+                    cache.remove(key);
+                    return None;
                 }
+                cache.track_field_expirations(key);
             }
+        }
+        match cache.get(key).map(|item| &item.value) {
+            Some(Value::Hash(hash)) => f(HashRef::Full(hash)).into(),
+            Some(Value::Packed(Packed::Hash(listpack))) => f(HashRef::Packed(listpack)).into(),
             Some(_) => wrong_type_error!(),
             None => None,
         }
     }
 }
 
-pub fn parse_field_value_pairs(args: &[Value]) -> Result<Vec<(String, Value)>, String> {
+pub enum HashRef<'a> {
+    Full(&'a IndexMap<String, Value>),
+    Packed(&'a Listpack),
+}
+
+type FieldRef<'a> = (Cow<'a, str>, Cow<'a, Value>);
+
+impl<'a> HashRef<'a> {
+    pub fn len(&self) -> usize {
+        match self {
+            HashRef::Full(hash) => hash.len(),
+            HashRef::Packed(listpack) => listpack.len() / 2,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, field: &str) -> Option<Cow<'a, Value>> {
+        match self {
+            HashRef::Full(hash) => hash.get(field).map(Cow::Borrowed),
+            HashRef::Packed(listpack) => packed_pairs(listpack)
+                .find(|(name, _)| name.is_str(field))
+                .map(|(_, value)| Cow::Owned(value.to_value())),
+        }
+    }
+
+    pub fn contains_key(&self, field: &str) -> bool {
+        match self {
+            HashRef::Full(hash) => hash.contains_key(field),
+            HashRef::Packed(listpack) => packed_pairs(listpack).any(|(name, _)| name.is_str(field)),
+        }
+    }
+
+    pub fn keys(&self) -> Box<dyn Iterator<Item = Cow<'a, str>> + 'a> {
+        match *self {
+            HashRef::Full(hash) => Box::new(hash.keys().map(|field| Cow::Borrowed(field.as_str()))),
+            HashRef::Packed(listpack) => {
+                Box::new(packed_pairs(listpack).map(|(field, _)| field.as_str()))
+            }
+        }
+    }
+
+    pub fn iter(&self) -> Box<dyn Iterator<Item = FieldRef<'a>> + 'a> {
+        match *self {
+            HashRef::Full(hash) => Box::new(
+                hash.iter()
+                    .map(|(field, value)| (Cow::Borrowed(field.as_str()), Cow::Borrowed(value))),
+            ),
+            HashRef::Packed(listpack) => Box::new(
+                packed_pairs(listpack)
+                    .map(|(field, value)| (field.as_str(), Cow::Owned(value.to_value()))),
+            ),
+        }
+    }
+
+    pub fn rev_range(&self, positions: Range<usize>) -> Vec<FieldRef<'a>> {
+        match *self {
+            HashRef::Full(hash) => positions
+                .rev()
+                .filter_map(|position| hash.get_index(position))
+                .map(|(field, value)| (Cow::Borrowed(field.as_str()), Cow::Borrowed(value)))
+                .collect(),
+            HashRef::Packed(_) => {
+                let mut fields: Vec<FieldRef<'a>> = self
+                    .iter()
+                    .skip(positions.start)
+                    .take(positions.len())
+                    .collect();
+                fields.reverse();
+                fields
+            }
+        }
+    }
+}
+
+fn packed_pairs(listpack: &Listpack) -> impl Iterator<Item = (Entry<'_>, Entry<'_>)> {
+    let mut entries = listpack.iter();
+    std::iter::from_fn(move || Some((entries.next()?, entries.next()?)))
+}
+
+pub fn operate_on_hash_in<F, R>(
+    cache: &mut Keyspace,
+    key: &str,
+    limits: &PackLimits,
+    f: F,
+) -> Option<Value>
+where
+    F: FnOnce(&mut IndexMap<String, Value>) -> R,
+    R: Into<Option<Value>>,
+{
+    operate_on_hash_with_ttls_in(cache, key, limits, |hash, _| f(hash))
+}
+
+// Fields whose TTL has passed are dropped before `f` runs, so they are never
+// visible to a command.
+pub fn operate_on_hash_with_ttls_in<F, R>(
+    cache: &mut Keyspace,
+    key: &str,
+    limits: &PackLimits,
+    f: F,
+) -> Option<Value>
+where
+    F: FnOnce(&mut IndexMap<String, Value>, &mut HashMap<String, i64>) -> R,
+    R: Into<Option<Value>>,
+{
+    let (result, is_empty) = match cache.get_mut(key) {
+        Some(item) if item.redis_type == RedisType::Hash => {
+            if item.purge_expired_fields(now_millis()) > 0 && is_empty_hash(item) {
+                cache.remove(key);
+                return None;
+            }
+            let packed = item.unpack();
+            let RedisItem {
+                value,
+                field_expirations,
+                ..
+            } = &mut *item;
+            if let Value::Hash(hash) = value {
+                let result = f(hash, field_expirations).into();
+                field_expirations.retain(|field, _| hash.contains_key(field));
+                let is_empty = hash.is_empty();
+                if packed {
+                    item.pack(limits);
+                }
+                (result, is_empty)
+            } else {
+                return wrong_type_error!();
+            }
+        }
+        Some(_) => return wrong_type_error!(),
+        None => return None,
+    };
+
+    if is_empty {
+        cache.remove(key);
+    } else {
+        cache.track_field_expirations(key);
+    }
+    result
+}
+
+fn is_empty_hash(item: &RedisItem) -> bool {
+    matches!(&item.value, Value::Hash(hash) if hash.is_empty())
+}
+
+// `per_field` is the number of arguments each field takes: two for HSETEX.
+pub fn parse_fields_block(args: &[Value], per_field: usize) -> RedisResult<Vec<Value>> {
+    match args.first() {
+        Some(Value::BulkString(s)) if s.eq_ignore_ascii_case("FIELDS") => {}
+        _ => {
+            return Err(RedisError::err(
+                "Mandatory argument FIELDS is missing or not at the right position",
+            ))
+        }
+    }
+
+    let numfields = args
+        .get(1)
+        .ok_or_else(|| RedisError::err("wrong number of arguments"))
+        .and_then(unpack_integer_arg)?;
+    if numfields <= 0 {
+        return Err(RedisError::err(
+            "Parameter `numFields` should be greater than 0",
+        ));
+    }
+
+    let rest = &args[2..];
+    if (numfields as usize).checked_mul(per_field) != Some(rest.len()) {
+        return Err(RedisError::err(
+            "The `numfields` parameter must match the number of arguments",
+        ));
+    }
+    Ok(rest.to_vec())
+}
+
+pub fn parse_expire_at(
+    value: &Value,
+    unit_ms: i64,
+    absolute: bool,
+    command: &str,
+) -> RedisResult<i64> {
+    let time = unpack_integer_arg(value)?;
+    let invalid = || RedisError::err(format!("invalid expire time in '{}' command", command));
+    if time < 0 {
+        return Err(invalid());
+    }
+    let time = time.checked_mul(unit_ms).ok_or_else(invalid)?;
+    if absolute {
+        Ok(time)
+    } else {
+        now_millis().checked_add(time).ok_or_else(invalid)
+    }
+}
+
+// Replies per field: -2 missing, 0 condition not met, 1 set, 2 deleted as the
+// time is already past.
+pub fn expire_fields(
+    server: &mut Server,
+    key: &str,
+    at_ms: i64,
+    condition: Option<&str>,
+    fields: &[String],
+) -> Option<Value> {
+    let condition = condition.map(str::to_uppercase);
+    server
+        .operate_on_hash_with_ttls(key, |hash, ttls| {
+            let now = now_millis();
+            let replies = fields
+                .iter()
+                .map(|field| {
+                    if !hash.contains_key(field) {
+                        return Value::Integer(-2);
+                    }
+                    let current = ttls.get(field).copied();
+                    let allowed = match condition.as_deref() {
+                        Some("NX") => current.is_none(),
+                        Some("XX") => current.is_some(),
+                        Some("GT") => current.is_some_and(|current| at_ms > current),
+                        Some("LT") => current.is_none_or(|current| at_ms < current),
+                        _ => true,
+                    };
+                    if !allowed {
+                        Value::Integer(0)
+                    } else if at_ms <= now {
+                        hash.swap_remove(field);
+                        Value::Integer(2)
+                    } else {
+                        ttls.insert(field.clone(), at_ms);
+                        Value::Integer(1)
+                    }
+                })
+                .collect();
+            Some(Value::Array(replies))
+        })
+        .or_else(|| Some(missing_fields_reply(fields)))
+}
+
+pub fn missing_fields_reply(fields: &[String]) -> Value {
+    Value::Array(fields.iter().map(|_| Value::Integer(-2)).collect())
+}
+
+pub fn field_names(fields: Vec<Value>) -> RedisResult<Vec<String>> {
+    fields
+        .into_iter()
+        .map(|field| match field {
+            Value::BulkString(field) => Ok(field),
+            _ => Err(RedisError::err(
+                "arguments must contain a value for every field",
+            )),
+        })
+        .collect()
+}
+
+pub fn parse_field_value_pairs(args: &[Value]) -> RedisResult<Vec<(String, Value)>> {
     args.chunks(2)
         .map(|chunk| {
             if let [Value::BulkString(field), value] = chunk {
                 Ok((field.clone(), value.clone()))
             } else {
-                Err("ERR arguments must contain a value for every field".to_string())
+                Err(RedisError::err(
+                    "arguments must contain a value for every field",
+                ))
             }
         })
         .collect()
 }
+
+pub fn field_as_string(value: &Value) -> Option<String> {
+    match value {
+        Value::BulkString(s) | Value::SimpleString(s) => Some(s.clone()),
+        Value::Integer(i) => Some(i.to_string()),
+        _ => None,
+    }
+}
+
+// HEXPIRE, HPEXPIRE, HEXPIREAT and HPEXPIREAT.
+pub fn expire_command(
+    server: &mut Server,
+    key: &str,
+    args: &[Value],
+    unit_ms: i64,
+    absolute: bool,
+    command: &str,
+) -> Option<Value> {
+    let Some(time) = args.first() else {
+        return Some(RedisError::wrong_arity(command).into());
+    };
+    let (condition, rest) = match args.get(1) {
+        Some(Value::BulkString(s))
+            if ["NX", "XX", "GT", "LT"].contains(&s.to_uppercase().as_str()) =>
+        {
+            (Some(s.as_str()), &args[2..])
+        }
+        _ => (None, &args[1..]),
+    };
+
+    let parsed = parse_expire_at(time, unit_ms, absolute, command).and_then(|at_ms| {
+        let fields = field_names(parse_fields_block(rest, 1)?)?;
+        Ok((at_ms, fields))
+    });
+    match parsed {
+        Ok((at_ms, fields)) => expire_fields(server, key, at_ms, condition, &fields),
+        Err(e) => Some(e.into()),
+    }
+}
+
+// HTTL and HPTTL.
+pub fn ttl_command(server: &mut Server, key: &str, args: &[Value], unit_ms: i64) -> Option<Value> {
+    let fields = match parse_fields_block(args, 1).and_then(field_names) {
+        Ok(fields) => fields,
+        Err(e) => return Some(e.into()),
+    };
+
+    server
+        .operate_on_hash_with_ttls(key, |hash, ttls| {
+            let now = now_millis();
+            let replies = fields
+                .iter()
+                .map(|field| match (hash.contains_key(field), ttls.get(field)) {
+                    (false, _) => Value::Integer(-2),
+                    (true, None) => Value::Integer(-1),
+                    (true, Some(at)) => Value::Integer((at - now + unit_ms / 2) / unit_ms),
+                })
+                .collect();
+            Some(Value::Array(replies))
+        })
+        .or_else(|| Some(missing_fields_reply(&fields)))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FieldExpiry {
+    At(i64),
+    Persist,
+    KeepTtl,
+}
+
+pub struct FieldExpiryOptions<'a> {
+    pub expiry: Option<FieldExpiry>,
+    pub flag: Option<String>,
+    pub rest: &'a [Value],
+}
+
+pub fn parse_field_expiry_options<'a>(
+    args: &'a [Value],
+    allowed: &[&str],
+    command: &str,
+) -> RedisResult<FieldExpiryOptions<'a>> {
+    let mut expiry = None;
+    let mut flag = None;
+    let mut i = 0;
+
+    while let Some(Value::BulkString(option)) = args.get(i) {
+        let option = option.to_uppercase();
+        let timed = match option.as_str() {
+            "FIELDS" => break,
+            "EX" => Some((1000, false)),
+            "PX" => Some((1, false)),
+            "EXAT" => Some((1000, true)),
+            "PXAT" => Some((1, true)),
+            _ if allowed.contains(&option.as_str()) => None,
+            _ => return Err(RedisError::Syntax),
+        };
+
+        let parsed = match (timed, option.as_str()) {
+            (Some((unit_ms, absolute)), _) => {
+                i += 1;
+                let time = args.get(i).ok_or(RedisError::Syntax)?;
+                Some(FieldExpiry::At(parse_expire_at(
+                    time, unit_ms, absolute, command,
+                )?))
+            }
+            (None, "PERSIST") => Some(FieldExpiry::Persist),
+            (None, "KEEPTTL") => Some(FieldExpiry::KeepTtl),
+            (None, _) => {
+                if flag.is_some() {
+                    return Err(RedisError::Syntax);
+                }
+                flag = Some(option);
+                None
+            }
+        };
+        if parsed.is_some() {
+            if expiry.is_some() {
+                return Err(RedisError::Syntax);
+            }
+            expiry = parsed;
+        }
+        i += 1;
+    }
+
+    Ok(FieldExpiryOptions {
+        expiry,
+        flag,
+        rest: &args[i.min(args.len())..],
+    })
+}
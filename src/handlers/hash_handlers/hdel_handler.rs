@@ -1,4 +1,5 @@
 use super::hash_utils::HashOperation;
+use crate::error::RedisError;
 use crate::{models::value::Value, server::Server};
 
 pub fn hdel_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
@@ -9,13 +10,13 @@ pub fn hdel_handler(server: &mut Server, key: String, args: Vec<Value>) -> Optio
         let mut count = 0;
         for field in args {
             if let Value::BulkString(field) = field {
-                if hash.remove(&field).is_some() {
+                if hash.swap_remove(&field).is_some() {
                     count += 1;
                 }
             } else {
-                return Some(Value::Error(
-                    "ERR arguments must contain a value for every field".to_string(),
-                ));
+                return Some(
+                    RedisError::err("arguments must contain a value for every field").into(),
+                );
             }
         }
         Some(Value::Integer(count))
@@ -0,0 +1,7 @@
+use super::hash_utils::expire_command;
+use crate::{models::value::Value, server::Server};
+
+// Sets a timeout in seconds on one or more fields of the hash stored at key.
+pub fn hexpire_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    expire_command(server, &key, &args, 1000, false, "hexpire")
+}
@@ -0,0 +1,7 @@
+use super::hash_utils::expire_command;
+use crate::{models::value::Value, server::Server};
+
+// Sets the expiration of hash fields as a unix timestamp in seconds.
+pub fn hexpireat_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    expire_command(server, &key, &args, 1000, true, "hexpireat")
+}
@@ -1,19 +1,22 @@
+use std::borrow::Cow;
+
 use super::hash_utils::HashOperation;
+use crate::error::RedisError;
 use crate::{models::value::Value, server::Server};
 
 pub fn hget_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
-    match args.get(0) {
+    match args.first() {
         Some(Value::BulkString(field)) => {
             Some(
                 server
-                    .operate_on_hash(&key, |hash| {
-                        hash.get(field).cloned().or(Some(Value::NullBulkString))
+                    .read_hash(&key, |hash| {
+                        hash.get(field)
+                            .map(Cow::into_owned)
+                            .or(Some(Value::NullBulkString))
                     })
                     .unwrap_or(Value::NullBulkString),
             ) // Handle non-existent keys
         }
-        _ => Some(Value::Error(
-            "ERR arguments must contain a value for every field".to_string(),
-        )),
+        _ => Some(RedisError::err("arguments must contain a value for every field").into()),
     }
 }
@@ -3,16 +3,13 @@ use crate::{models::value::Value, server::Server};
 
 pub fn hgetall_handler(server: &mut Server, key: String, _: Vec<Value>) -> Option<Value> {
     server
-        .operate_on_hash(&key, |hash| {
-            let mut sorted_keys: Vec<_> = hash.keys().cloned().collect();
-            sorted_keys.sort();
+        .read_hash(&key, |hash| {
+            let mut fields: Vec<_> = hash.iter().collect();
+            fields.sort_by(|a, b| a.0.cmp(&b.0));
 
-            let hash_arr: Vec<Value> = sorted_keys
+            let hash_arr: Vec<Value> = fields
                 .into_iter()
-                .flat_map(|k| {
-                    let v = hash.get(&k).unwrap();
-                    vec![Value::BulkString(k), v.clone()]
-                })
+                .flat_map(|(k, v)| [Value::BulkString(k.into_owned()), v.into_owned()])
                 .collect();
 
             Some(Value::Array(hash_arr))
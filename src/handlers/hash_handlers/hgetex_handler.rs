@@ -0,0 +1,49 @@
+use super::hash_utils::{
+    field_names, parse_field_expiry_options, parse_fields_block, FieldExpiry, HashOperation,
+};
+use crate::{models::value::Value, server::Server, utilities::now_millis};
+
+// Returns the values of fields of a hash and optionally sets or removes their
+// expiration with EX, PX, EXAT, PXAT or PERSIST.
+pub fn hgetex_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    let parsed = parse_field_expiry_options(&args, &["PERSIST"], "hgetex").and_then(|options| {
+        let fields = field_names(parse_fields_block(options.rest, 1)?)?;
+        Ok((options.expiry, fields))
+    });
+    let (expiry, fields) = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => return Some(e.into()),
+    };
+
+    server
+        .operate_on_hash_with_ttls(&key, |hash, ttls| {
+            let now = now_millis();
+            let values = fields
+                .iter()
+                .map(|field| {
+                    let value = hash.get(field).cloned().unwrap_or(Value::NullBulkString);
+                    if value != Value::NullBulkString {
+                        match expiry {
+                            Some(FieldExpiry::At(at)) if at <= now => {
+                                hash.swap_remove(field);
+                            }
+                            Some(FieldExpiry::At(at)) => {
+                                ttls.insert(field.clone(), at);
+                            }
+                            Some(FieldExpiry::Persist) => {
+                                ttls.remove(field);
+                            }
+                            Some(FieldExpiry::KeepTtl) | None => {}
+                        }
+                    }
+                    value
+                })
+                .collect();
+            Some(Value::Array(values))
+        })
+        .or_else(|| {
+            Some(Value::Array(
+                fields.iter().map(|_| Value::NullBulkString).collect(),
+            ))
+        })
+}
@@ -0,0 +1,39 @@
+use super::hash_utils::{field_as_string, HashOperation};
+use crate::error::RedisError;
+use crate::{log, models::value::Value, server::Server, utilities::unpack_integer_arg};
+
+// Increments the number stored at field in the hash stored at key by increment.
+// Missing keys and fields are treated as holding 0.
+pub fn hincrby_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    log!(
+        "hincrby_handler called with key: {} and args: {:?}",
+        key,
+        args
+    );
+
+    let (field, increment) = match (args.first(), args.get(1)) {
+        (Some(Value::BulkString(field)), Some(increment)) => match unpack_integer_arg(increment) {
+            Ok(increment) => (field.clone(), increment),
+            Err(_) => return Some(RedisError::NotInteger.into()),
+        },
+        _ => return Some(RedisError::wrong_arity("hincrby").into()),
+    };
+
+    server.upsert_hash(&key, |hash| {
+        let current = match hash.get(&field) {
+            Some(value) => match field_as_string(value).and_then(|s| s.parse::<i64>().ok()) {
+                Some(current) => current,
+                None => return Some(RedisError::err("hash value is not an integer").into()),
+            },
+            None => 0,
+        };
+
+        match current.checked_add(increment) {
+            Some(updated) => {
+                hash.insert(field, Value::BulkString(updated.to_string()));
+                Some(Value::Integer(updated))
+            }
+            None => Some(RedisError::err("increment or decrement would overflow").into()),
+        }
+    })
+}
@@ -0,0 +1,42 @@
+use super::hash_utils::{field_as_string, HashOperation};
+use crate::error::RedisError;
+use crate::{log, models::value::Value, server::Server};
+
+// Increments the floating point number stored at field in the hash stored at
+// key by increment, replying with the new value as a bulk string.
+pub fn hincrbyfloat_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    log!(
+        "hincrbyfloat_handler called with key: {} and args: {:?}",
+        key,
+        args
+    );
+
+    let (field, increment) = match (args.first(), args.get(1)) {
+        (Some(Value::BulkString(field)), Some(increment)) => {
+            match field_as_string(increment).and_then(|s| s.parse::<f64>().ok()) {
+                Some(increment) if increment.is_finite() => (field.clone(), increment),
+                _ => return Some(RedisError::NotFloat.into()),
+            }
+        }
+        _ => return Some(RedisError::wrong_arity("hincrbyfloat").into()),
+    };
+
+    server.upsert_hash(&key, |hash| {
+        let current = match hash.get(&field) {
+            Some(value) => match field_as_string(value).and_then(|s| s.parse::<f64>().ok()) {
+                Some(current) => current,
+                None => return Some(RedisError::err("hash value is not a float").into()),
+            },
+            None => 0.0,
+        };
+
+        let updated = current + increment;
+        if !updated.is_finite() {
+            return Some(RedisError::err("increment would produce NaN or Infinity").into());
+        }
+
+        let updated = updated.to_string();
+        hash.insert(field, Value::BulkString(updated.clone()));
+        Some(Value::BulkString(updated))
+    })
+}
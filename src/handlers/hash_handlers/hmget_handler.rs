@@ -0,0 +1,38 @@
+use std::borrow::Cow;
+
+use super::hash_utils::HashOperation;
+use crate::error::RedisError;
+use crate::{models::value::Value, server::Server};
+
+// Returns the values associated with the specified fields in the hash stored at
+// key. Missing fields, or a missing key, reply with nil entries.
+pub fn hmget_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    if args.is_empty() {
+        return Some(RedisError::wrong_arity("hmget").into());
+    }
+
+    let fields: Vec<String> = args
+        .into_iter()
+        .filter_map(|arg| match arg {
+            Value::BulkString(field) => Some(field),
+            _ => None,
+        })
+        .collect();
+
+    server
+        .read_hash(&key, |hash| {
+            let values = fields
+                .iter()
+                .map(|field| {
+                    hash.get(field)
+                        .map_or(Value::NullBulkString, Cow::into_owned)
+                })
+                .collect();
+            Some(Value::Array(values))
+        })
+        .or_else(|| {
+            Some(Value::Array(
+                fields.iter().map(|_| Value::NullBulkString).collect(),
+            ))
+        })
+}
@@ -0,0 +1,16 @@
+use super::hset_handler::hset_handler;
+use crate::error::RedisError;
+use crate::{models::value::Value, server::Server};
+
+// Sets the specified fields to their respective values in the hash stored at
+// key. Deprecated in favour of HSET, but kept as a working alias that replies OK.
+pub fn hmset_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    if args.is_empty() || !args.len().is_multiple_of(2) {
+        return Some(RedisError::wrong_arity("hmset").into());
+    }
+
+    match hset_handler(server, key, args) {
+        Some(Value::Integer(_)) => Some(Value::SimpleString("OK".to_string())),
+        other => other,
+    }
+}
@@ -0,0 +1,29 @@
+use super::hash_utils::{field_names, missing_fields_reply, parse_fields_block, HashOperation};
+use crate::{models::value::Value, server::Server};
+
+// Removes the expiration of fields of a hash. Replies per field with 1 when a
+// TTL was removed, -1 when the field had none and -2 when it does not exist.
+pub fn hpersist_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    let fields = match parse_fields_block(&args, 1).and_then(field_names) {
+        Ok(fields) => fields,
+        Err(e) => return Some(e.into()),
+    };
+
+    server
+        .operate_on_hash_with_ttls(&key, |hash, ttls| {
+            let replies = fields
+                .iter()
+                .map(|field| {
+                    if !hash.contains_key(field) {
+                        Value::Integer(-2)
+                    } else if ttls.remove(field).is_some() {
+                        Value::Integer(1)
+                    } else {
+                        Value::Integer(-1)
+                    }
+                })
+                .collect();
+            Some(Value::Array(replies))
+        })
+        .or_else(|| Some(missing_fields_reply(&fields)))
+}
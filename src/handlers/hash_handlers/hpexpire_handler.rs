@@ -0,0 +1,7 @@
+use super::hash_utils::expire_command;
+use crate::{models::value::Value, server::Server};
+
+// Sets a timeout in milliseconds on one or more fields of the hash stored at key.
+pub fn hpexpire_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    expire_command(server, &key, &args, 1, false, "hpexpire")
+}
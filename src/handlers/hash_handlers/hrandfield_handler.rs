@@ -0,0 +1,75 @@
+use rand::seq::{IteratorRandom, SliceRandom};
+
+use super::hash_utils::HashOperation;
+use crate::error::RedisError;
+use crate::{models::value::Value, server::Server, utilities::unpack_integer_arg};
+
+// Most fields a negative count may ask for. Each repeat takes room in the
+// reply, so a larger one would exhaust memory instead of answering.
+const MAX_REPEATED_FIELDS: u64 = 10_000_000;
+
+// Returns random fields from the hash stored at key.
+// HRANDFIELD key [count [WITHVALUES]]
+// A positive count returns distinct fields, a negative count may repeat fields
+// and always returns exactly |count| of them.
+pub fn hrandfield_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    let count = match args.first().map(unpack_integer_arg) {
+        Some(Ok(count)) => Some(count),
+        Some(Err(_)) => return Some(RedisError::NotInteger.into()),
+        None => None,
+    };
+    if count.is_some_and(|count| count < 0 && count.unsigned_abs() > MAX_REPEATED_FIELDS) {
+        return Some(RedisError::err("value is out of range").into());
+    }
+
+    let with_values = match args.get(1) {
+        Some(Value::BulkString(option)) if option.eq_ignore_ascii_case("WITHVALUES") => true,
+        Some(_) => return Some(RedisError::Syntax.into()),
+        None => false,
+    };
+    if args.len() > 2 {
+        return Some(RedisError::Syntax.into());
+    }
+
+    let empty_reply = match count {
+        Some(_) => Value::Array(vec![]),
+        None => Value::NullBulkString,
+    };
+
+    server
+        .read_hash(&key, |hash| {
+            let mut rng = rand::thread_rng();
+            let count = match count {
+                None => {
+                    return hash
+                        .keys()
+                        .choose(&mut rng)
+                        .map(|field| Value::BulkString(field.into_owned()));
+                }
+                Some(count) => count,
+            };
+
+            let all: Vec<_> = hash.iter().collect();
+            let fields: Vec<_> = if count >= 0 {
+                let mut fields = all
+                    .iter()
+                    .choose_multiple(&mut rng, (count as usize).min(all.len()));
+                fields.shuffle(&mut rng);
+                fields
+            } else {
+                (0..count.unsigned_abs())
+                    .filter_map(|_| all.choose(&mut rng))
+                    .collect()
+            };
+
+            let mut reply = Vec::new();
+            for (field, value) in fields {
+                reply.push(Value::BulkString(field.to_string()));
+                if with_values {
+                    reply.push(value.as_ref().clone());
+                }
+            }
+            Some(Value::Array(reply))
+        })
+        .or(Some(empty_reply))
+}
@@ -0,0 +1,42 @@
+use super::hash_utils::HashOperation;
+use crate::{
+    log,
+    models::value::Value,
+    server::Server,
+    utilities::{scan_page, scan_reply, ScanOptions},
+};
+
+// Incrementally iterates the fields of the hash stored at key.
+// HSCAN key cursor [MATCH pattern] [COUNT count] [NOVALUES]
+pub fn hscan_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    log!(
+        "hscan_handler called with key: {} and args: {:?}",
+        key,
+        args
+    );
+
+    let options = match ScanOptions::parse(&args, "hscan", &["NOVALUES"]) {
+        Ok(options) => options,
+        Err(e) => return Some(e.into()),
+    };
+
+    let count = options.count_for(server, &key);
+    server
+        .read_hash(&key, |hash| {
+            let (next_cursor, positions) = scan_page(hash.len(), options.cursor, count);
+
+            let mut entries = Vec::new();
+            for (field, value) in hash.rev_range(positions) {
+                if !options.matches(&field) {
+                    continue;
+                }
+                entries.push(Value::BulkString(field.into_owned()));
+                if !options.no_values {
+                    entries.push(value.into_owned());
+                }
+            }
+
+            Some(scan_reply(next_cursor, entries))
+        })
+        .or(Some(scan_reply(0, vec![])))
+}
@@ -1,9 +1,11 @@
-use std::collections::HashMap;
+use indexmap::IndexMap;
 
+use crate::utilities::lock;
 use crate::{
-    log,
+    error::RedisError,
     models::{redis_item::RedisItem, redis_type::RedisType, value::Value},
     server::Server,
+    utilities::now_millis,
 };
 
 pub fn hset_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
@@ -13,44 +15,39 @@ pub fn hset_handler(server: &mut Server, key: String, args: Vec<Value>) -> Optio
             [Value::BulkString(_), Value::Integer(_)] => continue,
             [Value::BulkString(_), Value::Array(_)] => continue,
             _ => {
-                return Some(Value::Error(
-                    "ERR arguments must contain a value for every field".to_string(),
-                ))
+                return Some(
+                    RedisError::err("arguments must contain a value for every field").into(),
+                )
             }
         }
     }
-    let mut cache = server.cache.lock().unwrap();
+    let limits = server.pack_limits();
+    let mut cache = lock(&server.cache);
     match cache.get_mut(&key) {
         Some(item) => {
             if let RedisType::Hash = item.redis_type {
+                item.purge_expired_fields(now_millis());
+                let packed = item.unpack();
                 let mut count = 0;
                 if let Value::Hash(ref mut hash) = item.value {
                     for chunk in args.chunks(2) {
-                        match chunk {
-                            [Value::BulkString(field), value] => {
-                                log!("Field: {:?}, Value: {:?}", field, value);
-                                let entry = hash.entry(field.to_string());
-                                log!("Entry: {:?}", entry);
-                                let inserted_value = hash.insert(field.to_string(), value.clone());
-                                log!("Entry after insert: {:?}", inserted_value);
-
-                                count += 1;
-                                Some(Value::BulkString("Ok".to_string()))
-                            }
-                            _ => Some(Value::Error(
-                                "Arguments must contain a value for every field".to_string(),
-                            )),
-                        };
+                        if let [Value::BulkString(field), value] = chunk {
+                            hash.insert(field.to_string(), value.clone());
+                            item.field_expirations.remove(field);
+                            count += 1;
+                        }
                     }
                 }
+                if packed {
+                    item.pack(&limits);
+                }
+                cache.track_field_expirations(&key);
                 return Some(Value::Integer(count));
             }
-            Some(Value::Error(
-                "ERR operation against a key holding the wrong kind of value".to_string(),
-            ))
+            Some(RedisError::WrongType.into())
         }
         None => {
-            let mut hash = HashMap::new();
+            let mut hash = IndexMap::new();
             let mut count = 0;
             for chunk in args.chunks(2) {
                 if let [Value::BulkString(field), value] = chunk {
@@ -58,7 +55,8 @@ pub fn hset_handler(server: &mut Server, key: String, args: Vec<Value>) -> Optio
                     count += 1;
                 }
             }
-            let redis_item = RedisItem::new_hash(hash);
+            let mut redis_item = RedisItem::new_hash(hash);
+            redis_item.pack(&limits);
             cache.insert(key, redis_item);
             Some(Value::Integer(count))
         }
@@ -0,0 +1,54 @@
+use super::hash_utils::{
+    parse_field_expiry_options, parse_field_value_pairs, parse_fields_block, FieldExpiry,
+    HashOperation,
+};
+use crate::error::RedisError;
+use crate::{models::value::Value, server::Server, utilities::now_millis};
+
+// Sets fields of a hash together with their expiration. Replies 1 when the
+// fields were set and 0 otherwise.
+pub fn hsetex_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    let parsed = parse_field_expiry_options(&args, &["FNX", "FXX", "KEEPTTL"], "hsetex").and_then(
+        |options| {
+            let pairs = parse_field_value_pairs(&parse_fields_block(options.rest, 2)?)?;
+            Ok((options.expiry, options.flag, pairs))
+        },
+    );
+    let (expiry, flag, pairs) = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => return Some(e.into()),
+    };
+    if expiry == Some(FieldExpiry::Persist) {
+        return Some(RedisError::Syntax.into());
+    }
+
+    server.upsert_hash_with_ttls(&key, |hash, ttls| {
+        let allowed = match flag.as_deref() {
+            Some("FNX") => pairs.iter().all(|(field, _)| !hash.contains_key(field)),
+            Some("FXX") => pairs.iter().all(|(field, _)| hash.contains_key(field)),
+            _ => true,
+        };
+        if !allowed {
+            return Some(Value::Integer(0));
+        }
+
+        let now = now_millis();
+        for (field, value) in pairs {
+            match expiry {
+                Some(FieldExpiry::At(at)) if at <= now => {
+                    hash.swap_remove(&field);
+                    continue;
+                }
+                Some(FieldExpiry::At(at)) => {
+                    ttls.insert(field.clone(), at);
+                }
+                Some(FieldExpiry::KeepTtl) => {}
+                _ => {
+                    ttls.remove(&field);
+                }
+            }
+            hash.insert(field, value);
+        }
+        Some(Value::Integer(1))
+    })
+}
@@ -0,0 +1,23 @@
+use indexmap::map::Entry;
+
+use super::hash_utils::HashOperation;
+use crate::error::RedisError;
+use crate::{models::value::Value, server::Server};
+
+// Sets field in the hash stored at key to value, only if field does not yet exist.
+pub fn hsetnx_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    let (field, value) = match (args.first(), args.get(1)) {
+        (Some(Value::BulkString(field)), Some(value)) if args.len() == 2 => {
+            (field.clone(), value.clone())
+        }
+        _ => return Some(RedisError::wrong_arity("hsetnx").into()),
+    };
+
+    server.upsert_hash(&key, |hash| match hash.entry(field) {
+        Entry::Occupied(_) => Some(Value::Integer(0)),
+        Entry::Vacant(entry) => {
+            entry.insert(value);
+            Some(Value::Integer(1))
+        }
+    })
+}
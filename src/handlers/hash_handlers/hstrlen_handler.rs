@@ -0,0 +1,20 @@
+use super::hash_utils::{field_as_string, HashOperation};
+use crate::error::RedisError;
+use crate::{models::value::Value, server::Server};
+
+// Returns the string length of the value associated with field in the hash
+// stored at key, or 0 when either does not exist.
+pub fn hstrlen_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    match args.first() {
+        Some(Value::BulkString(field)) => server
+            .read_hash(&key, |hash| {
+                let len = hash
+                    .get(field)
+                    .and_then(|value| field_as_string(&value))
+                    .map_or(0, |s| s.len());
+                Some(Value::Integer(len as i64))
+            })
+            .or(Some(Value::Integer(0))),
+        _ => Some(RedisError::wrong_arity("hstrlen").into()),
+    }
+}
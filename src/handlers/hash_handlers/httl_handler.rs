@@ -0,0 +1,7 @@
+use super::hash_utils::ttl_command;
+use crate::{models::value::Value, server::Server};
+
+// Returns the remaining time to live, in seconds, of fields of a hash.
+pub fn httl_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    ttl_command(server, &key, &args, 1000)
+}
@@ -3,9 +3,9 @@ use crate::{models::value::Value, server::Server};
 
 pub fn hvals_handler(server: &mut Server, key: String, _: Vec<Value>) -> Option<Value> {
     server
-        .operate_on_hash(&key, |hash| {
-            let mut values: Vec<_> = hash.values().cloned().collect();
-            values.sort_by(|a, b| a.clone().serialize().cmp(&b.clone().serialize())); // Custom comparison
+        .read_hash(&key, |hash| {
+            let mut values: Vec<Value> = hash.iter().map(|(_, value)| value.into_owned()).collect();
+            values.sort_by_key(|v| v.clone().serialize()); // Custom comparison
             Some(Value::Array(values))
         })
         .or(Some(Value::Array(vec![])))
@@ -1,18 +1,52 @@
 pub mod hash_utils;
 pub mod hdel_handler;
 pub mod hexists_handler;
+pub mod hexpire_handler;
+pub mod hexpireat_handler;
 pub mod hget_handler;
 pub mod hgetall_handler;
+pub mod hgetex_handler;
+pub mod hincrby_handler;
+pub mod hincrbyfloat_handler;
 pub mod hkeys_handler;
 pub mod hlen_handler;
+pub mod hmget_handler;
+pub mod hmset_handler;
+pub mod hpersist_handler;
+pub mod hpexpire_handler;
+pub mod hpexpireat_handler;
+pub mod hpttl_handler;
+pub mod hrandfield_handler;
+pub mod hscan_handler;
 pub mod hset_handler;
+pub mod hsetex_handler;
+pub mod hsetnx_handler;
+pub mod hstrlen_handler;
+pub mod httl_handler;
 pub mod hvals_handler;
 
 pub use hdel_handler::hdel_handler;
 pub use hexists_handler::hexists_handler;
+pub use hexpire_handler::hexpire_handler;
+pub use hexpireat_handler::hexpireat_handler;
 pub use hget_handler::hget_handler;
 pub use hgetall_handler::hgetall_handler;
+pub use hgetex_handler::hgetex_handler;
+pub use hincrby_handler::hincrby_handler;
+pub use hincrbyfloat_handler::hincrbyfloat_handler;
 pub use hkeys_handler::hkeys_handler;
 pub use hlen_handler::hlen_handler;
+pub use hmget_handler::hmget_handler;
+pub use hmset_handler::hmset_handler;
+pub use hpersist_handler::hpersist_handler;
+pub use hpexpire_handler::hpexpire_handler;
+pub use hpexpireat_handler::hpexpireat_handler;
+pub use hpttl_handler::hpttl_handler;
+pub use hrandfield_handler::hrandfield_handler;
+pub use hscan_handler::hscan_handler;
 pub use hset_handler::hset_handler;
+pub use hsetex_handler::hsetex_handler;
+pub use hsetnx_handler::hsetnx_handler;
+pub use hstrlen_handler::hstrlen_handler;
+pub use httl_handler::httl_handler;
 pub use hvals_handler::hvals_handler;
@@ -0,0 +1,327 @@
+use std::collections::HashSet;
+use std::fmt::Display;
+
+use lazy_static::lazy_static;
+use uuid::Uuid;
+
+use crate::utilities::lock;
+use crate::{
+    command_args::ArgParser,
+    memory::{bytes_to_human, peak_memory, used_memory},
+    models::value::Value,
+    server::{Role, Server, REDIS_VERSION},
+    utilities::now_millis,
+};
+
+const ALL_SECTIONS: &[&str] = &[
+    "server",
+    "clients",
+    "memory",
+    "persistence",
+    "stats",
+    "replication",
+    "cpu",
+    "modules",
+    "commandstats",
+    "errorstats",
+    "latencystats",
+    "keyspace",
+];
+
+const DEFAULT_SECTIONS: &[&str] = &[
+    "server",
+    "clients",
+    "memory",
+    "persistence",
+    "stats",
+    "replication",
+    "cpu",
+    "modules",
+    "errorstats",
+    "keyspace",
+];
+
+const LATENCY_PERCENTILES: &[f64] = &[50.0, 99.0, 99.9];
+
+lazy_static! {
+    static ref RUN_ID: String = random_hex_id();
+    static ref MASTER_REPLID: String = random_hex_id();
+}
+
+type Fields = Vec<(String, String)>;
+
+fn field(name: impl Into<String>, value: impl Display) -> (String, String) {
+    (name.into(), value.to_string())
+}
+
+// Returns information and statistics about the server.
+// INFO [section [section ...]]
+pub fn info_handler(server: &mut Server, _key: String, args: Vec<Value>) -> Option<Value> {
+    let mut args = ArgParser::new("info", args);
+    let mut requested = HashSet::new();
+    if args.is_empty() {
+        requested.extend(DEFAULT_SECTIONS.iter().copied());
+    }
+    while !args.is_empty() {
+        let section = match args.arg::<String>() {
+            Ok(section) => section.to_lowercase(),
+            Err(e) => return Some(e.into()),
+        };
+        match section.as_str() {
+            "default" => requested.extend(DEFAULT_SECTIONS.iter().copied()),
+            "all" | "everything" => requested.extend(ALL_SECTIONS.iter().copied()),
+            name => {
+                if let Some(name) = ALL_SECTIONS.iter().find(|known| **known == name) {
+                    requested.insert(*name);
+                }
+            }
+        }
+    }
+
+    let sections: Vec<String> = ALL_SECTIONS
+        .iter()
+        .filter(|name| requested.contains(*name))
+        .map(|name| {
+            let (title, fields) = section(server, name);
+            let mut text = format!("# {}\r\n", title);
+            for (name, value) in fields {
+                text.push_str(&format!("{}:{}\r\n", name, value));
+            }
+            text
+        })
+        .collect();
+    Some(Value::BulkString(sections.join("\r\n")))
+}
+
+fn section(server: &Server, name: &str) -> (&'static str, Fields) {
+    match name {
+        "server" => ("Server", server_info(server)),
+        "clients" => ("Clients", clients_info(server)),
+        "memory" => ("Memory", memory_info(server)),
+        "persistence" => ("Persistence", persistence_info(server)),
+        "stats" => ("Stats", stats_info(server)),
+        "replication" => ("Replication", replication_info(server)),
+        "cpu" => ("CPU", cpu_info()),
+        "commandstats" => ("Commandstats", command_stats_info(server)),
+        "errorstats" => ("Errorstats", error_stats_info(server)),
+        "latencystats" => ("Latencystats", latency_stats_info(server)),
+        "keyspace" => ("Keyspace", keyspace_info(server)),
+        _ => ("Modules", Vec::new()),
+    }
+}
+
+fn server_info(server: &Server) -> Fields {
+    let config = server.config();
+    let uptime = server.stats.uptime().as_secs();
+    let executable = std::env::current_exe()
+        .map(|path| path.display().to_string())
+        .unwrap_or_default();
+    let config_file = config
+        .file
+        .as_ref()
+        .map(|path| path.display().to_string())
+        .unwrap_or_default();
+    vec![
+        field("redis_version", REDIS_VERSION),
+        field("redis_mode", "standalone"),
+        field(
+            "os",
+            format!("{} {}", std::env::consts::OS, std::env::consts::ARCH),
+        ),
+        field("arch_bits", usize::BITS),
+        field("process_id", std::process::id()),
+        field("run_id", RUN_ID.as_str()),
+        field("tcp_port", server.port),
+        field("server_time_usec", now_millis() * 1000),
+        field("uptime_in_seconds", uptime),
+        field("uptime_in_days", uptime / (24 * 60 * 60)),
+        field("hz", config.hz),
+        field("configured_hz", config.hz),
+        field("executable", executable),
+        field("config_file", config_file),
+    ]
+}
+
+fn clients_info(server: &Server) -> Fields {
+    vec![
+        field("connected_clients", server.stats.connected_clients()),
+        field("blocked_clients", 0),
+    ]
+}
+
+fn memory_info(server: &Server) -> Fields {
+    let (used, peak) = (used_memory(), peak_memory());
+    let config = server.config();
+    vec![
+        field("used_memory", used),
+        field("used_memory_human", bytes_to_human(used)),
+        field("used_memory_peak", peak),
+        field("used_memory_peak_human", bytes_to_human(peak)),
+        field(
+            "used_memory_peak_perc",
+            format!("{:.2}%", used as f64 * 100.0 / peak.max(1) as f64),
+        ),
+        field("maxmemory", config.maxmemory),
+        field("maxmemory_human", bytes_to_human(config.maxmemory as usize)),
+        field("maxmemory_policy", &config.maxmemory_policy),
+        field(
+            "lazyfree_pending_objects",
+            server.lazyfree.pending_objects(),
+        ),
+    ]
+}
+
+fn persistence_info(server: &Server) -> Fields {
+    let status = if server.stats.last_save_failed() {
+        "err"
+    } else {
+        "ok"
+    };
+    vec![
+        field("loading", 0),
+        field("rdb_changes_since_last_save", server.stats.dirty()),
+        field("rdb_bgsave_in_progress", 0),
+        field("rdb_last_save_time", server.stats.last_save()),
+        field("rdb_last_bgsave_status", status),
+        field("aof_enabled", 0),
+    ]
+}
+
+fn stats_info(server: &Server) -> Fields {
+    let stats = &server.stats;
+    vec![
+        field("total_connections_received", stats.total_connections()),
+        field("total_commands_processed", stats.total_commands()),
+        field("expired_keys", stats.expired_keys()),
+        field("evicted_keys", stats.evicted_keys()),
+        field("total_error_replies", stats.total_error_replies()),
+        field("internal_errors", stats.internal_errors()),
+    ]
+}
+
+fn replication_info(server: &Server) -> Fields {
+    let mut fields = vec![field("role", &server.role)];
+    match &server.role {
+        Role::Main => fields.extend([
+            field("master_replid", MASTER_REPLID.as_str()),
+            field("master_repl_offset", 0),
+        ]),
+        Role::Slave { host, port } => {
+            fields.extend([field("master_host", host), field("master_port", port)])
+        }
+    }
+    fields
+}
+
+fn cpu_info() -> Fields {
+    let [user, sys, user_children, sys_children] = cpu_times();
+    vec![
+        field("used_cpu_sys", format!("{:.6}", sys)),
+        field("used_cpu_user", format!("{:.6}", user)),
+        field("used_cpu_sys_children", format!("{:.6}", sys_children)),
+        field("used_cpu_user_children", format!("{:.6}", user_children)),
+    ]
+}
+
+// Read from /proc/self/stat; other platforms report zeros.
+fn cpu_times() -> [f64; 4] {
+    // Linux counts them in clock ticks, 1/100 s on every common platform.
+    const TICKS_PER_SEC: f64 = 100.0;
+    let stat = std::fs::read_to_string("/proc/self/stat").unwrap_or_default();
+    // After the command name, which may contain spaces.
+    let fields: Vec<&str> = match stat.rsplit_once(')') {
+        Some((_, rest)) => rest.split_whitespace().collect(),
+        None => return [0.0; 4],
+    };
+    let mut times = [0.0; 4];
+    for (time, ticks) in times.iter_mut().zip(fields.iter().skip(11)) {
+        *time = ticks.parse::<f64>().unwrap_or(0.0) / TICKS_PER_SEC;
+    }
+    times
+}
+
+fn command_stats_info(server: &Server) -> Fields {
+    server
+        .stats
+        .command_stats()
+        .into_iter()
+        .map(|(name, stats)| {
+            let per_call = if stats.calls == 0 {
+                0.0
+            } else {
+                stats.usec as f64 / stats.calls as f64
+            };
+            field(
+                format!("cmdstat_{}", name),
+                format!(
+                    "calls={},usec={},usec_per_call={:.2},rejected_calls={},failed_calls={}",
+                    stats.calls, stats.usec, per_call, stats.rejected_calls, stats.failed_calls
+                ),
+            )
+        })
+        .collect()
+}
+
+fn error_stats_info(server: &Server) -> Fields {
+    server
+        .stats
+        .error_stats()
+        .into_iter()
+        .map(|(prefix, count)| field(format!("errorstat_{}", prefix), format!("count={}", count)))
+        .collect()
+}
+
+fn latency_stats_info(server: &Server) -> Fields {
+    server
+        .stats
+        .command_stats()
+        .into_iter()
+        .filter(|(_, stats)| stats.calls > 0)
+        .map(|(name, stats)| {
+            let percentiles: Vec<String> = LATENCY_PERCENTILES
+                .iter()
+                .map(|p| format!("p{}={:.3}", p, stats.latency_percentile(*p) as f64))
+                .collect();
+            field(
+                format!("latency_percentiles_usec_{}", name),
+                percentiles.join(","),
+            )
+        })
+        .collect()
+}
+
+fn keyspace_info(server: &Server) -> Fields {
+    let now = now_millis();
+    let mut fields = Vec::new();
+    for (index, db) in server.databases.iter().enumerate() {
+        let cache = lock(db);
+        if cache.is_empty() {
+            continue;
+        }
+        let ttls: Vec<i64> = (0..cache.expires())
+            .filter_map(|index| cache.get_volatile_index(index))
+            .filter_map(|(_, item)| item.expires_at())
+            .map(|expires_at| (expires_at * 1000 - now).max(0))
+            .collect();
+        let avg_ttl = if ttls.is_empty() {
+            0
+        } else {
+            ttls.iter().sum::<i64>() / ttls.len() as i64
+        };
+        fields.push(field(
+            format!("db{}", index),
+            format!(
+                "keys={},expires={},avg_ttl={}",
+                cache.len(),
+                ttls.len(),
+                avg_ttl
+            ),
+        ));
+    }
+    fields
+}
+
+fn random_hex_id() -> String {
+    let id = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+    id[..40].to_string()
+}
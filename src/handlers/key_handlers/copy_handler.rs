@@ -0,0 +1,84 @@
+use crate::utilities::lock;
+use crate::{
+    error::RedisError,
+    keyspace::Keyspace,
+    lazyfree::LazyFree,
+    models::{
+        redis_item::{AccessStats, RedisItem},
+        value::Value,
+    },
+    server::Server,
+    utilities::unpack_integer_arg,
+};
+
+// Copies the value stored at the source key to the destination key, keeping
+// its TTL. Replies 1 when the value was copied and 0 when the destination
+// already exists and REPLACE was not given.
+// COPY source destination [DB destination-db] [REPLACE]
+pub fn copy_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    let destination = match args.first() {
+        Some(Value::BulkString(destination)) => destination.clone(),
+        _ => return Some(RedisError::wrong_arity("copy").into()),
+    };
+
+    let mut replace = false;
+    let mut db = server.db_index as i64;
+    let mut options = args[1..].iter();
+    while let Some(option) = options.next() {
+        match option {
+            Value::BulkString(s) if s.eq_ignore_ascii_case("REPLACE") => replace = true,
+            Value::BulkString(s) if s.eq_ignore_ascii_case("DB") => {
+                match options.next().map(unpack_integer_arg) {
+                    Some(Ok(index)) => db = index,
+                    Some(Err(e)) => return Some(e.into()),
+                    None => return Some(RedisError::Syntax.into()),
+                }
+            }
+            _ => return Some(RedisError::Syntax.into()),
+        }
+    }
+
+    if db < 0 || db as usize >= server.databases.len() {
+        return Some(RedisError::err("DB index is out of range").into());
+    }
+    let db = db as usize;
+    if db == server.db_index && key == destination {
+        return Some(RedisError::err("source and destination objects are the same").into());
+    }
+
+    if db == server.db_index {
+        let mut cache = lock(&server.cache);
+        let item = cache.get(&key).cloned();
+        copy_item(item, &mut cache, destination, replace, &server.lazyfree)
+    } else {
+        let (source, mut target) = server.lock_db_pair(server.db_index, db);
+        let item = source.get(&key).cloned();
+        copy_item(item, &mut target, destination, replace, &server.lazyfree)
+    }
+}
+
+fn copy_item(
+    item: Option<RedisItem>,
+    target: &mut Keyspace,
+    destination: String,
+    replace: bool,
+    lazyfree: &LazyFree,
+) -> Option<Value> {
+    let mut item = match item.filter(|item| !item.is_expired()) {
+        Some(item) => item,
+        None => return Some(Value::Integer(0)),
+    };
+    if !replace
+        && target
+            .get(&destination)
+            .is_some_and(|item| !item.is_expired())
+    {
+        return Some(Value::Integer(0));
+    }
+
+    item.access = AccessStats::default();
+    if let Some(old) = target.insert(destination, item) {
+        lazyfree.release(old, lazyfree.config().lazy_server_del);
+    }
+    Some(Value::Integer(1))
+}
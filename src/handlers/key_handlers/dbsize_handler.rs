@@ -0,0 +1,8 @@
+use crate::utilities::lock;
+use crate::{models::value::Value, server::Server};
+
+// Returns the number of keys in the database.
+pub fn dbsize_handler(server: &Server) -> Option<Value> {
+    let cache = lock(&server.cache);
+    Some(Value::Integer(cache.len() as i64))
+}
@@ -0,0 +1,8 @@
+use super::incr_handler::incr_by;
+use crate::{models::value::Value, server::Server};
+
+// Decrements the integer stored at key by one. A missing key counts as 0.
+// DECR key
+pub fn decr_handler(server: &mut Server, key: String, _args: Vec<Value>) -> Option<Value> {
+    Some(incr_by(server, &key, -1).unwrap_or_else(Value::from))
+}
@@ -0,0 +1,24 @@
+use super::incr_handler::incr_by;
+use crate::{
+    command_args::ArgParser,
+    error::{RedisError, RedisResult},
+    models::value::Value,
+    server::Server,
+};
+
+// Decrements the integer stored at key by decrement. A missing key counts as
+// 0.
+// DECRBY key decrement
+pub fn decrby_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    let result = ArgParser::new("decrby", args)
+        .arg()
+        .and_then(negate)
+        .and_then(|delta| incr_by(server, &key, delta));
+    Some(result.unwrap_or_else(Value::from))
+}
+
+fn negate(decrement: i64) -> RedisResult<i64> {
+    decrement
+        .checked_neg()
+        .ok_or_else(|| RedisError::err("decrement would overflow"))
+}
@@ -1,17 +1,21 @@
-use crate::{models::value::Value, server::Server, utilities::unpack_bulk_str};
+use crate::utilities::lock;
+use crate::{command_args::ArgParser, models::value::Value, server::Server};
 
 pub fn del_handler(server: &mut Server, _: String, args: Vec<Value>) -> Option<Value> {
-    let keys = args
-        .iter()
-        .map(|arg| unpack_bulk_str(arg.clone()).unwrap())
-        .collect::<Vec<String>>();
+    let keys: Vec<String> = match ArgParser::new("del", args).repeated(|args| args.arg()) {
+        Ok(keys) => keys,
+        Err(e) => return Some(e.into()),
+    };
 
-    let mut cache = server.cache.lock().unwrap();
+    let mut cache = lock(&server.cache);
 
     let mut count = 0;
 
     for key in keys {
-        if cache.remove(&key).is_some() {
+        if let Some(item) = cache.remove(&key) {
+            server
+                .lazyfree
+                .release(item, server.lazyfree.config().lazy_user_del);
             count += 1;
         }
     }
@@ -0,0 +1,22 @@
+use crate::utilities::lock;
+use crate::{
+    error::RedisError, models::value::Value, rdb::dump_payload, server::Server,
+    utilities::now_millis,
+};
+
+// Serializes the value stored at key in a format RESTORE accepts. The TTL is
+// not part of the payload.
+pub fn dump_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    if !args.is_empty() {
+        return Some(RedisError::wrong_arity("dump").into());
+    }
+
+    let cache = lock(&server.cache);
+    let mut item = match cache.get(&key).filter(|item| !item.is_expired()) {
+        Some(item) => item.clone(),
+        None => return Some(Value::NullBulkString),
+    };
+    item.purge_expired_fields(now_millis());
+
+    Some(Value::Bytes(dump_payload(&item)))
+}
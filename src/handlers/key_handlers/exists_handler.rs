@@ -0,0 +1,22 @@
+use crate::error::RedisError;
+use crate::utilities::lock;
+use crate::{models::value::Value, server::Server};
+
+// Returns how many of the given keys exist. A key mentioned several times is
+// counted every time.
+pub fn exists_handler(server: &mut Server, _: String, args: Vec<Value>) -> Option<Value> {
+    if args.is_empty() {
+        return Some(RedisError::wrong_arity("exists").into());
+    }
+
+    let cache = lock(&server.cache);
+    let count = args
+        .iter()
+        .filter(|arg| match arg {
+            Value::BulkString(key) => cache.get(key).is_some_and(|item| !item.is_expired()),
+            _ => false,
+        })
+        .count();
+
+    Some(Value::Integer(count as i64))
+}
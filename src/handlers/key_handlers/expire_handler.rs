@@ -1,39 +1,73 @@
+use crate::utilities::lock;
 use crate::{
+    command_args::{parse_args, ArgParser, Exclusive, FromArgs},
+    error::{RedisError, RedisResult},
     log,
     models::value::Value,
     server::Server,
-    utilities::{should_set_expiry, unpack_bulk_str, unpack_integer},
+    utilities::{now_millis, should_set_expiry},
 };
-use std::time::Instant;
 
-pub fn expire_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
-    let expiration_time = unpack_integer(args.get(1).unwrap().clone()).unwrap();
+struct ExpireArgs {
+    seconds: i64,
+    // NX, XX, GT or LT.
+    condition: Option<String>,
+}
 
-    let option = match args.get(2) {
-        Some(value) => unpack_bulk_str(value.clone()).unwrap_or_default(),
-        None => String::new(),
-    };
+impl FromArgs for ExpireArgs {
+    fn from_args(args: &mut ArgParser) -> RedisResult<Self> {
+        let seconds = args.arg()?;
+        let mut condition = Exclusive::new(RedisError::err(
+            "NX and XX, GT or LT options at the same time are not compatible",
+        ));
+        while let Some(option) = args.keyword()? {
+            match option.as_str() {
+                "NX" | "XX" | "GT" | "LT" => condition.set(option)?,
+                _ => return Err(RedisError::err(format!("Unsupported option {}", option))),
+            }
+        }
+        Ok(ExpireArgs {
+            seconds,
+            condition: condition.into_inner(),
+        })
+    }
+}
 
-    log!("option {:?}", option);
-    let mut cache = server.cache.lock().unwrap();
+// Sets a timeout on key, after which it is deleted. A timeout that is not
+// positive deletes the key right away. Replies 1 when the timeout was set and
+// 0 when the key doesn't exist or the condition was not met.
+// EXPIRE key seconds [NX | XX | GT | LT]
+pub fn expire_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    let args: ExpireArgs = match parse_args("expire", args) {
+        Ok(args) => args,
+        Err(e) => return Some(e.into()),
+    };
+    log!("option {:?}", args.condition);
+    let mut cache = lock(&server.cache);
+    let now = now_millis() / 1000;
 
-    match cache.get_mut(&key) {
-        Some(item) => {
-            log!("value {:?}", item);
-            if should_set_expiry(item, expiration_time, option) {
-                log!("setting expiration");
-                let now = Instant::now();
-                let new_expiration =
-                    now + std::time::Duration::from_secs(expiration_time.try_into().unwrap());
-                let new_expiration_secs =
-                    new_expiration.duration_since(Instant::now()).as_secs() as i64;
+    let item = match cache.get_mut(&key).filter(|item| !item.is_expired()) {
+        Some(item) => item,
+        None => return Some(Value::Integer(0)),
+    };
+    let at = match now.checked_add(args.seconds) {
+        Some(at) => at,
+        None => return Some(RedisError::err("invalid expire time in 'expire' command").into()),
+    };
+    if !should_set_expiry(item, at, args.condition.as_deref()) {
+        return Some(Value::Integer(0));
+    }
 
-                item.expiration = Some(new_expiration_secs);
-                Some(Value::Integer(1))
-            } else {
-                Some(Value::Integer(0))
-            }
+    log!("setting expiration");
+    if args.seconds <= 0 {
+        if let Some(item) = cache.remove(&key) {
+            server
+                .lazyfree
+                .release(item, server.lazyfree.config().lazy_server_del);
         }
-        None => Some(Value::Integer(0)),
+    } else {
+        let expiration = at - item.created_at;
+        cache.set_expiration(&key, Some(expiration));
     }
+    Some(Value::Integer(1))
 }
@@ -1,10 +1,11 @@
 use chrono::Utc;
 
-use crate::{log, models::value::Value, server::Server};
+use crate::utilities::lock;
+use crate::{error::RedisError, log, models::value::Value, server::Server};
 
 pub fn get_handler(server: &mut Server, key: String, _args: Vec<Value>) -> Option<Value> {
     log!("key {:?}", key);
-    let cache = server.cache.lock().unwrap();
+    let cache = lock(&server.cache);
     match cache.get(&key) {
         Some(item) => {
             log!("value {:?}", item);
@@ -15,7 +16,12 @@ pub fn get_handler(server: &mut Server, key: String, _args: Vec<Value>) -> Optio
                 }
             }
             log!("response {:?}", item.value);
-            Some(item.value.clone())
+            match item.value.unpacked().into_owned() {
+                value @ (Value::BulkString(_) | Value::SimpleString(_) | Value::Integer(_)) => {
+                    Some(value)
+                }
+                _ => Some(RedisError::WrongType.into()),
+            }
         }
         None => Some(Value::NullBulkString),
     }
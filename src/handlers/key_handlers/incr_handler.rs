@@ -0,0 +1,51 @@
+use crate::utilities::lock;
+use crate::{
+    error::{RedisError, RedisResult},
+    models::{
+        redis_item::RedisItem,
+        redis_string::{as_integer, RedisString},
+        redis_type::RedisType,
+        value::Value,
+    },
+    server::Server,
+};
+
+// Increments the integer stored at key by one. A missing key counts as 0.
+// INCR key
+pub fn incr_handler(server: &mut Server, key: String, _args: Vec<Value>) -> Option<Value> {
+    Some(incr_by(server, &key, 1).unwrap_or_else(Value::from))
+}
+
+// An int-encoded counter is updated in place rather than parsed again.
+pub fn incr_by(server: &Server, key: &str, delta: i64) -> RedisResult<Value> {
+    let mut cache = lock(&server.cache);
+    let item = match cache.get_mut(key).filter(|item| !item.is_expired()) {
+        Some(item) => item,
+        None => {
+            cache.insert(key.to_string(), RedisItem::new_string(delta.to_string()));
+            return Ok(Value::Integer(delta));
+        }
+    };
+    if item.redis_type != RedisType::String {
+        return Err(RedisError::WrongType);
+    }
+
+    if let Value::Str(RedisString::Int(counter)) = &mut item.value {
+        *counter = counter.checked_add(delta).ok_or_else(overflow)?;
+        return Ok(Value::Integer(*counter));
+    }
+    // Only a string from an older dump can still spell an integer here.
+    let current = match item.value.unpacked().as_ref() {
+        Value::BulkString(s) => as_integer(s),
+        Value::Integer(i) => Some(*i),
+        _ => None,
+    }
+    .ok_or(RedisError::NotInteger)?;
+    let updated = current.checked_add(delta).ok_or_else(overflow)?;
+    item.value = Value::Str(RedisString::Int(updated));
+    Ok(Value::Integer(updated))
+}
+
+fn overflow() -> RedisError {
+    RedisError::err("increment or decrement would overflow")
+}
@@ -0,0 +1,12 @@
+use super::incr_handler::incr_by;
+use crate::{command_args::ArgParser, models::value::Value, server::Server};
+
+// Increments the integer stored at key by increment. A missing key counts as
+// 0.
+// INCRBY key increment
+pub fn incrby_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    let result = ArgParser::new("incrby", args)
+        .arg()
+        .and_then(|increment| incr_by(server, &key, increment));
+    Some(result.unwrap_or_else(Value::from))
+}
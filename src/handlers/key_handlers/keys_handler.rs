@@ -1,29 +1,21 @@
-use crate::{log, models::value::Value, server::Server};
-use regex::Regex;
+use crate::error::RedisError;
+use crate::utilities::lock;
+use crate::{glob::string_match, log, models::value::Value, server::Server};
 
 pub fn keys_handler(server: &mut Server, _: String, args: Vec<Value>) -> Option<Value> {
     log!("keys_handler handler {:?}", args);
 
-    let pattern = match args.get(0) {
+    let pattern = match args.first() {
         Some(Value::BulkString(s)) => s,
-        _ => {
-            return Some(Value::Error(
-                "ERR wrong number of arguments for 'keys' command".into(),
-            ))
-        }
-    };
-
-    let regex_pattern = glob_to_regex(pattern);
-    let re = match Regex::new(&regex_pattern) {
-        Ok(re) => re,
-        Err(_) => return Some(Value::Error("ERR invalid pattern".into())),
+        _ => return Some(RedisError::wrong_arity("keys").into()),
     };
 
-    let cache = server.cache.lock().unwrap();
+    let all_keys = pattern == "*";
+    let cache = lock(&server.cache);
 
     let mut matching_keys: Vec<Value> = cache
         .keys()
-        .filter(|key| re.is_match(key))
+        .filter(|key| all_keys || string_match(pattern, key, false))
         .map(|key| Value::BulkString(key.clone()))
         .collect();
 
@@ -38,21 +30,3 @@ pub fn keys_handler(server: &mut Server, _: String, args: Vec<Value>) -> Option<
     // 5. Return matching keys as a BulkString array
     Some(Value::Array(matching_keys))
 }
-
-fn glob_to_regex(pattern: &str) -> String {
-    let mut regex_pattern = String::new();
-    regex_pattern.push('^');
-    for c in pattern.chars() {
-        match c {
-            '*' => regex_pattern.push_str(".*"),
-            '?' => regex_pattern.push('.'),
-            '.' | '(' | ')' | '+' | '|' | '^' | '$' | '@' | '%' => {
-                regex_pattern.push('\\');
-                regex_pattern.push(c);
-            }
-            _ => regex_pattern.push(c),
-        }
-    }
-    regex_pattern.push('$');
-    regex_pattern
-}
@@ -0,0 +1,350 @@
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use lazy_static::lazy_static;
+use tokio::runtime::{Handle, RuntimeFlavor};
+use tokio::task::block_in_place;
+
+use crate::utilities::lock;
+use crate::{
+    error::{RedisError, RedisResult},
+    log,
+    models::{redis_item::RedisItem, value::Value},
+    rdb::dump_payload,
+    server::Server,
+    utilities::{now_millis, unpack_integer_arg},
+};
+
+const MIGRATE_SOCKET_CACHE_ITEMS: usize = 64;
+const MIGRATE_SOCKET_CACHE_TTL: Duration = Duration::from_secs(10);
+
+struct MigrateConnection {
+    stream: BufReader<TcpStream>,
+    db: Option<i64>,
+    last_use: Instant,
+}
+
+lazy_static! {
+    // Keyed by "host:port".
+    static ref CONNECTIONS: Mutex<HashMap<String, MigrateConnection>> =
+        Mutex::new(HashMap::new());
+}
+
+struct MigrateOptions {
+    host: String,
+    port: u16,
+    db: i64,
+    timeout: Duration,
+    copy: bool,
+    replace: bool,
+    auth: Vec<String>,
+    keys: Vec<String>,
+}
+
+// Moves keys to another instance. The database isn't locked during the round
+// trips, so a key that changed in between is kept.
+// MIGRATE host port key|"" destination-db timeout [COPY] [REPLACE]
+//   [AUTH password | AUTH2 username password] [KEYS key [key ...]]
+pub fn migrate_handler(server: &mut Server, _: String, args: Vec<Value>) -> Option<Value> {
+    let options = match parse_options(&args) {
+        Ok(options) => options,
+        Err(e) => return Some(e.into()),
+    };
+
+    let now = now_millis();
+    let mut commands = Vec::new();
+    let mut sent = Vec::new();
+    {
+        let cache = lock(&server.cache);
+        for key in &options.keys {
+            let item = match cache.get(key).filter(|item| !item.is_expired()) {
+                Some(item) => item,
+                None => continue,
+            };
+            let ttl = item.expiration.map_or(0, |expiration| {
+                ((item.created_at + expiration) * 1000 - now).max(1)
+            });
+            let payload = live_payload(item, now);
+
+            let mut command = vec![
+                Value::BulkString("RESTORE".to_string()),
+                Value::BulkString(key.clone()),
+                Value::BulkString(ttl.to_string()),
+                Value::Bytes(payload.clone()),
+            ];
+            if options.replace {
+                command.push(Value::BulkString("REPLACE".to_string()));
+            }
+            commands.push(command);
+            sent.push((key.clone(), payload));
+        }
+    }
+    if sent.is_empty() {
+        return Some(Value::SimpleString("NOKEY".to_string()));
+    }
+
+    let replies = match blocking(|| send_to_target(&options, commands)) {
+        Ok(replies) => replies,
+        Err(e) => return Some(e.into()),
+    };
+
+    let mut cache = lock(&server.cache);
+    let mut error = None;
+    for ((key, payload), reply) in sent.into_iter().zip(replies) {
+        match reply {
+            Ok(()) if !options.copy => {
+                let unchanged = cache
+                    .get(&key)
+                    .is_some_and(|item| live_payload(item, now) == payload);
+                if !unchanged {
+                    continue;
+                }
+                if let Some(item) = cache.remove(&key) {
+                    server
+                        .lazyfree
+                        .release(item, server.lazyfree.config().lazy_server_del);
+                }
+            }
+            Ok(()) => {}
+            Err(e) => error = Some(e),
+        }
+    }
+
+    match error {
+        Some(e) => Some(target_error(e).into()),
+        None => Some(Value::SimpleString("OK".to_string())),
+    }
+}
+
+fn live_payload(item: &RedisItem, now: i64) -> Vec<u8> {
+    let mut item = item.clone();
+    item.purge_expired_fields(now);
+    dump_payload(&item)
+}
+
+// Keeps blocking I/O from stalling the other tasks on this tokio worker.
+fn blocking<T>(f: impl FnOnce() -> T) -> T {
+    match Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => block_in_place(f),
+        _ => f(),
+    }
+}
+
+fn parse_options(args: &[Value]) -> RedisResult<MigrateOptions> {
+    let strings = args
+        .iter()
+        .map(|arg| match arg {
+            Value::BulkString(s) => Ok(s.clone()),
+            Value::Integer(i) => Ok(i.to_string()),
+            _ => Err(RedisError::Syntax),
+        })
+        .collect::<RedisResult<Vec<String>>>()?;
+    if strings.len() < 5 {
+        return Err(RedisError::wrong_arity("migrate"));
+    }
+
+    let integer = |value: &str| unpack_integer_arg(&Value::BulkString(value.to_string()));
+    let port = u16::try_from(integer(&strings[1])?).map_err(|_| RedisError::err("Invalid port"))?;
+    let db = integer(&strings[3])?;
+    let timeout = match integer(&strings[4])? {
+        timeout if timeout <= 0 => 1000,
+        timeout => timeout,
+    };
+
+    let mut options = MigrateOptions {
+        host: strings[0].clone(),
+        port,
+        db,
+        timeout: Duration::from_millis(timeout as u64),
+        copy: false,
+        replace: false,
+        auth: Vec::new(),
+        keys: vec![strings[2].clone()],
+    };
+
+    let mut rest = strings[5..].iter();
+    while let Some(option) = rest.next() {
+        match option.to_uppercase().as_str() {
+            "COPY" => options.copy = true,
+            "REPLACE" => options.replace = true,
+            "AUTH" => {
+                let password = rest.next().ok_or(RedisError::Syntax)?;
+                options.auth = vec![password.clone()];
+            }
+            "AUTH2" => {
+                let username = rest.next().ok_or(RedisError::Syntax)?;
+                let password = rest.next().ok_or(RedisError::Syntax)?;
+                options.auth = vec![username.clone(), password.clone()];
+            }
+            "KEYS" => {
+                if !strings[2].is_empty() {
+                    return Err(RedisError::err(
+                        "When using MIGRATE KEYS option, the key argument must be set to the empty string",
+                    ));
+                }
+                options.keys = rest.by_ref().cloned().collect();
+            }
+            _ => return Err(RedisError::Syntax),
+        }
+    }
+    Ok(options)
+}
+
+// A dead cached connection is replaced once; only the RESTOREs that got no
+// reply are sent again, the rest were already applied.
+fn send_to_target(
+    options: &MigrateOptions,
+    restores: Vec<Vec<Value>>,
+) -> RedisResult<Vec<Result<(), String>>> {
+    let target = format!("{}:{}", options.host, options.port);
+    let mut replies = Vec::new();
+    let mut retried = false;
+    loop {
+        let (mut connection, cached) = match take_connection(&target, options.timeout) {
+            Some(cached) => (cached, true),
+            None => (connect(&target, options.timeout)?, false),
+        };
+
+        match run_commands(&mut connection, options, &restores, &mut replies) {
+            Ok(result) => {
+                connection.last_use = Instant::now();
+                return_connection(target, connection);
+                return result.map(|()| replies);
+            }
+            Err((e, writing)) => {
+                log!("MIGRATE to {} failed: {}", target, e);
+                let timed_out = matches!(
+                    e.kind(),
+                    io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock
+                );
+                if cached && !timed_out && !retried {
+                    retried = true;
+                    continue;
+                }
+                return Err(RedisError::IoErr(format!(
+                    "error or timeout {} to target instance",
+                    if writing { "writing" } else { "reading" }
+                )));
+            }
+        }
+    }
+}
+
+// The outer error is an I/O failure, flagged with whether it happened while
+// writing; the inner one an AUTH or SELECT the target rejected.
+fn run_commands(
+    connection: &mut MigrateConnection,
+    options: &MigrateOptions,
+    restores: &[Vec<Value>],
+    replies: &mut Vec<Result<(), String>>,
+) -> Result<RedisResult<()>, (io::Error, bool)> {
+    if !options.auth.is_empty() {
+        let mut auth = vec![Value::BulkString("AUTH".to_string())];
+        auth.extend(options.auth.iter().cloned().map(Value::BulkString));
+        if let Err(e) = send_command(connection, &auth)? {
+            return Ok(Err(target_error(e)));
+        }
+    }
+
+    if connection.db != Some(options.db) {
+        let select = vec![
+            Value::BulkString("SELECT".to_string()),
+            Value::BulkString(options.db.to_string()),
+        ];
+        if let Err(e) = send_command(connection, &select)? {
+            connection.db = None;
+            return Ok(Err(target_error(e)));
+        }
+        connection.db = Some(options.db);
+    }
+
+    for restore in &restores[replies.len()..] {
+        replies.push(send_command(connection, restore)?);
+    }
+    Ok(Ok(()))
+}
+
+fn send_command(
+    connection: &mut MigrateConnection,
+    command: &[Value],
+) -> Result<Result<(), String>, (io::Error, bool)> {
+    let request = Value::Array(command.to_vec()).serialize();
+    connection
+        .stream
+        .get_mut()
+        .write_all(&request)
+        .map_err(|e| (e, true))?;
+
+    let mut reply = String::new();
+    match connection.stream.read_line(&mut reply) {
+        Ok(0) => {
+            return Err((
+                io::Error::new(io::ErrorKind::UnexpectedEof, "connection closed"),
+                false,
+            ))
+        }
+        Ok(_) => {}
+        Err(e) => return Err((e, false)),
+    }
+    let reply = reply.trim_end();
+    match reply.strip_prefix('-') {
+        Some(error) => Ok(Err(error.to_string())),
+        None => Ok(Ok(())),
+    }
+}
+
+fn target_error(error: String) -> RedisError {
+    RedisError::err(format!("Target instance replied with error: {}", error))
+}
+
+fn connect(target: &str, timeout: Duration) -> RedisResult<MigrateConnection> {
+    let io_error = || RedisError::IoErr("error or timeout connecting to the client".to_string());
+    let address = target
+        .to_socket_addrs()
+        .ok()
+        .and_then(|mut addresses| addresses.next())
+        .ok_or_else(io_error)?;
+    let stream = TcpStream::connect_timeout(&address, timeout).map_err(|e| {
+        log!("MIGRATE could not connect to {}: {}", target, e);
+        io_error()
+    })?;
+    stream
+        .set_read_timeout(Some(timeout))
+        .and_then(|_| stream.set_write_timeout(Some(timeout)))
+        .map_err(|_| io_error())?;
+
+    Ok(MigrateConnection {
+        stream: BufReader::new(stream),
+        db: None,
+        last_use: Instant::now(),
+    })
+}
+
+fn take_connection(target: &str, timeout: Duration) -> Option<MigrateConnection> {
+    let mut connections = lock(&CONNECTIONS);
+    connections.retain(|_, connection| connection.last_use.elapsed() < MIGRATE_SOCKET_CACHE_TTL);
+    let connection = connections.remove(target)?;
+    let stream = connection.stream.get_ref();
+    stream
+        .set_read_timeout(Some(timeout))
+        .and_then(|_| stream.set_write_timeout(Some(timeout)))
+        .ok()?;
+    Some(connection)
+}
+
+fn return_connection(target: String, connection: MigrateConnection) {
+    let mut connections = lock(&CONNECTIONS);
+    if connections.len() >= MIGRATE_SOCKET_CACHE_ITEMS {
+        let oldest = connections
+            .iter()
+            .min_by_key(|(_, connection)| connection.last_use)
+            .map(|(target, _)| target.clone());
+        if let Some(oldest) = oldest {
+            connections.remove(&oldest);
+        }
+    }
+    connections.insert(target, connection);
+}
@@ -1,17 +1,53 @@
+pub mod copy_handler;
+pub mod dbsize_handler;
+pub mod decr_handler;
+pub mod decrby_handler;
 pub mod del_handler;
+pub mod dump_handler;
+pub mod exists_handler;
 pub mod expire_handler;
 pub mod get_handler;
+pub mod incr_handler;
+pub mod incrby_handler;
 pub mod keys_handler;
+pub mod migrate_handler;
+pub mod move_handler;
+pub mod object_handler;
+pub mod randomkey_handler;
 pub mod rename_handler;
+pub mod renamenx_handler;
+pub mod restore_handler;
+pub mod scan_handler;
 pub mod set_handler;
+pub mod sort_handler;
+pub mod sort_ro_handler;
+pub mod touch_handler;
 pub mod type_handler;
 pub mod unlink_handler;
 
+pub use copy_handler::copy_handler;
+pub use dbsize_handler::dbsize_handler;
+pub use decr_handler::decr_handler;
+pub use decrby_handler::decrby_handler;
 pub use del_handler::del_handler;
+pub use dump_handler::dump_handler;
+pub use exists_handler::exists_handler;
 pub use expire_handler::expire_handler;
 pub use get_handler::get_handler;
+pub use incr_handler::incr_handler;
+pub use incrby_handler::incrby_handler;
 pub use keys_handler::keys_handler;
+pub use migrate_handler::migrate_handler;
+pub use move_handler::move_handler;
+pub use object_handler::object_handler;
+pub use randomkey_handler::randomkey_handler;
 pub use rename_handler::rename_handler;
+pub use renamenx_handler::renamenx_handler;
+pub use restore_handler::restore_handler;
+pub use scan_handler::scan_handler;
 pub use set_handler::set_handler;
+pub use sort_handler::sort_handler;
+pub use sort_ro_handler::sort_ro_handler;
+pub use touch_handler::touch_handler;
 pub use type_handler::type_handler;
 pub use unlink_handler::unlink_handler;
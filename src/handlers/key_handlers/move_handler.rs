@@ -0,0 +1,31 @@
+use crate::error::RedisError;
+use crate::{models::value::Value, server::Server, utilities::unpack_integer_arg};
+
+// Moves key from the selected database to the given database. Replies 0 when
+// it does not exist or the destination already holds the key.
+pub fn move_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    let db = match args.first().map(unpack_integer_arg) {
+        Some(Ok(db)) if args.len() == 1 => db,
+        Some(Err(e)) => return Some(e.into()),
+        _ => return Some(RedisError::wrong_arity("move").into()),
+    };
+    if db < 0 || db as usize >= server.databases.len() {
+        return Some(RedisError::err("DB index is out of range").into());
+    }
+    let db = db as usize;
+    if db == server.db_index {
+        return Some(RedisError::err("source and destination objects are the same").into());
+    }
+
+    let (mut source, mut destination) = server.lock_db_pair(server.db_index, db);
+    if source.get(&key).is_none_or(|item| item.is_expired()) {
+        return Some(Value::Integer(0));
+    }
+    if destination.get(&key).is_some_and(|item| !item.is_expired()) {
+        return Some(Value::Integer(0));
+    }
+
+    let item = source.remove(&key).expect("source key checked above");
+    destination.insert(key, item);
+    Some(Value::Integer(1))
+}
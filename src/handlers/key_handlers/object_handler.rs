@@ -0,0 +1,72 @@
+use crate::error::RedisError;
+use crate::utilities::lock;
+use crate::{models::value::Value, server::Server};
+
+// Inspects the internals of the value stored at a key.
+// OBJECT ENCODING | IDLETIME | FREQ | REFCOUNT key, or OBJECT HELP
+pub fn object_handler(server: &mut Server, _: String, args: Vec<Value>) -> Option<Value> {
+    let subcommand = match args.first() {
+        Some(Value::BulkString(subcommand)) => subcommand.to_uppercase(),
+        _ => return Some(RedisError::wrong_arity("object").into()),
+    };
+
+    if subcommand == "HELP" {
+        return Some(Value::Array(
+            [
+                "OBJECT <subcommand> [<arg> [value] [opt] ...]. Subcommands are:",
+                "ENCODING <key>",
+                "    Return the kind of internal representation used in order to store the value",
+                "    associated with a <key>.",
+                "FREQ <key>",
+                "    Return the access frequency index of the <key>.",
+                "IDLETIME <key>",
+                "    Return the idle time of the <key>, that is the approximated number of",
+                "    seconds elapsed since the last access to the key.",
+                "REFCOUNT <key>",
+                "    Return the number of references of the value associated with the specified",
+                "    <key>.",
+                "HELP",
+                "    Print this help.",
+            ]
+            .iter()
+            .map(|line| Value::SimpleString(line.to_string()))
+            .collect(),
+        ));
+    }
+
+    let key = match (subcommand.as_str(), args.get(1), args.len()) {
+        ("ENCODING" | "IDLETIME" | "FREQ" | "REFCOUNT", Some(Value::BulkString(key)), 2) => key,
+        _ => {
+            let name = match &args[0] {
+                Value::BulkString(name) => name.clone(),
+                _ => subcommand.clone(),
+            };
+            return Some(
+                RedisError::err(format!(
+                    "unknown subcommand or wrong number of arguments for '{}'. Try OBJECT HELP.",
+                    name
+                ))
+                .into(),
+            );
+        }
+    };
+
+    let (_, decay_time) = server.lfu_params();
+    let cache = lock(&server.cache);
+    let item = match cache.get(key).filter(|item| !item.is_expired()) {
+        Some(item) => item,
+        None => return Some(Value::NullBulkString),
+    };
+
+    match subcommand.as_str() {
+        "ENCODING" => Some(Value::BulkString(item.encoding().to_string())),
+        "IDLETIME" => Some(Value::Integer(item.access.idle_seconds())),
+        "FREQ" => Some(Value::Integer(
+            item.access.decayed_frequency(decay_time) as i64
+        )),
+        _ => Some(Value::Integer(match &item.value {
+            Value::Str(s) if s.is_shared() => i32::MAX as i64,
+            _ => 1,
+        })),
+    }
+}
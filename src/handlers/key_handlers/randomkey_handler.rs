@@ -0,0 +1,19 @@
+use rand::seq::IteratorRandom;
+
+use crate::utilities::lock;
+use crate::{models::value::Value, server::Server};
+
+// Returns a random key from the keyspace, or nil when it is empty.
+pub fn randomkey_handler(server: &mut Server) -> Option<Value> {
+    let cache = lock(&server.cache);
+    let key = cache
+        .iter()
+        .filter(|(_, item)| !item.is_expired())
+        .map(|(key, _)| key)
+        .choose(&mut rand::thread_rng());
+
+    match key {
+        Some(key) => Some(Value::BulkString(key.clone())),
+        None => Some(Value::NullBulkString),
+    }
+}
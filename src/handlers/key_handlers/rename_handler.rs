@@ -1,26 +1,56 @@
-use crate::{log, models::value::Value, server::Server, utilities::unpack_bulk_str};
+use crate::utilities::lock;
+use crate::{
+    error::RedisError,
+    keyspace::Keyspace,
+    lazyfree::LazyFree,
+    log,
+    models::{redis_item::RedisItem, value::Value},
+    server::Server,
+};
 
 // Renames key to newkey. It returns an error when key does not exist. If newkey already exists it is overwritten, when this happens RENAME executes an implicit DEL operation, so if the deleted key contains a very big value it may cause high latency even if RENAME itself is usually a constant-time operation.
 
 pub fn rename_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
     log!("rename_handler key {:?}", key);
     log!("rename_handler handler {:?}", args);
-    let new_key = unpack_bulk_str(args.get(0).unwrap().clone()).unwrap();
+    let new_key = match args.first() {
+        Some(Value::BulkString(new_key)) => new_key,
+        _ => return Some(RedisError::wrong_arity("rename").into()),
+    };
 
-    let mut cache = server.cache.lock().unwrap();
+    let mut cache = lock(&server.cache);
+    match rename_key(&mut cache, &key, new_key, false, &server.lazyfree) {
+        Ok(_) => Some(Value::SimpleString("OK".to_string())),
+        Err(e) => Some(e),
+    }
+}
 
-    if !cache.contains_key(&key) {
-        return Some(Value::Error("ERR no such key".to_string()));
+// With `nx`, an existing `new_key` is kept and `Ok(false)` returned.
+pub fn rename_key(
+    cache: &mut Keyspace,
+    key: &str,
+    new_key: &str,
+    nx: bool,
+    lazyfree: &LazyFree,
+) -> Result<bool, Value> {
+    if cache.get(key).is_some_and(RedisItem::is_expired) {
+        cache.remove(key);
+    }
+    if !cache.contains_key(key) {
+        return Err(RedisError::NoSuchKey.into());
     }
 
-    if cache.contains_key(&new_key) {
-        cache.remove(&new_key);
+    let target_exists = cache.get(new_key).is_some_and(|item| !item.is_expired());
+    if nx && target_exists {
+        return Ok(false);
+    }
+    if key == new_key {
+        return Ok(true);
     }
 
-    if let Some(item) = cache.remove(&key) {
-        cache.insert(new_key, item);
-        Some(Value::SimpleString("OK".to_string()))
-    } else {
-        Some(Value::Error("ERR no such key".to_string()))
+    let item = cache.remove(key).expect("source key checked above");
+    if let Some(old) = cache.insert(new_key.to_string(), item) {
+        lazyfree.release(old, lazyfree.config().lazy_server_del);
     }
+    Ok(true)
 }
@@ -0,0 +1,19 @@
+use super::rename_handler::rename_key;
+use crate::error::RedisError;
+use crate::utilities::lock;
+use crate::{models::value::Value, server::Server};
+
+// Renames key to newkey only if newkey does not yet exist. Replies 1 when the
+// key was renamed and 0 when newkey already exists.
+pub fn renamenx_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    let new_key = match args.as_slice() {
+        [Value::BulkString(new_key)] => new_key,
+        _ => return Some(RedisError::wrong_arity("renamenx").into()),
+    };
+
+    let mut cache = lock(&server.cache);
+    match rename_key(&mut cache, &key, new_key, true, &server.lazyfree) {
+        Ok(renamed) => Some(Value::Integer(renamed as i64)),
+        Err(e) => Some(e),
+    }
+}
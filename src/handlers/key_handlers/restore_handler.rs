@@ -0,0 +1,109 @@
+use std::time::SystemTime;
+
+use crate::utilities::lock;
+use crate::{
+    error::RedisError,
+    models::value::Value,
+    rdb::restore_payload,
+    server::Server,
+    utilities::{now_millis, unpack_integer_arg},
+};
+
+// Creates a key from a payload produced by DUMP. The TTL is in milliseconds,
+// or a unix time in milliseconds with ABSTTL; 0 means no TTL.
+// RESTORE key ttl serialized-value [REPLACE] [ABSTTL] [IDLETIME seconds] [FREQ frequency]
+pub fn restore_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    let (ttl, payload) = match args.as_slice() {
+        [ttl, Value::BulkString(payload), ..] => (ttl, payload.as_bytes()),
+        [ttl, Value::Bytes(payload), ..] => (ttl, payload.as_slice()),
+        _ => return Some(RedisError::wrong_arity("restore").into()),
+    };
+    let ttl = match unpack_integer_arg(ttl) {
+        Ok(ttl) if ttl >= 0 => ttl,
+        Ok(_) => return Some(RedisError::err("Invalid TTL value, must be >= 0").into()),
+        Err(e) => return Some(e.into()),
+    };
+
+    let mut replace = false;
+    let mut absttl = false;
+    let mut idletime = None;
+    let mut freq = None;
+    let mut options = args[2..].iter();
+    while let Some(option) = options.next() {
+        let option = match option {
+            Value::BulkString(option) => option.to_uppercase(),
+            _ => return Some(RedisError::Syntax.into()),
+        };
+        match option.as_str() {
+            "REPLACE" => replace = true,
+            "ABSTTL" => absttl = true,
+            "IDLETIME" if freq.is_none() => match options.next().map(unpack_integer_arg) {
+                Some(Ok(idle)) if idle >= 0 => idletime = Some(idle),
+                Some(Ok(_)) => {
+                    return Some(RedisError::err("Invalid IDLETIME value, must be >= 0").into())
+                }
+                Some(Err(e)) => return Some(e.into()),
+                None => return Some(RedisError::Syntax.into()),
+            },
+            "FREQ" if idletime.is_none() => match options.next().map(unpack_integer_arg) {
+                Some(Ok(f)) if (0..=255).contains(&f) => freq = Some(f as u8),
+                Some(Ok(_)) => {
+                    return Some(
+                        RedisError::err("Invalid FREQ value, must be >= 0 and <= 255").into(),
+                    )
+                }
+                Some(Err(e)) => return Some(e.into()),
+                None => return Some(RedisError::Syntax.into()),
+            },
+            _ => return Some(RedisError::Syntax.into()),
+        }
+    }
+
+    let mut cache = lock(&server.cache);
+    if !replace && cache.get(&key).is_some_and(|item| !item.is_expired()) {
+        return Some(RedisError::BusyKey.into());
+    }
+
+    let mut item = match restore_payload(payload) {
+        Ok(item) => item,
+        Err(e) => return Some(RedisError::from(e).into()),
+    };
+    item.pack(&server.pack_limits());
+
+    let now = now_millis();
+    let remaining = match (ttl, absttl) {
+        (0, _) => None,
+        (at, true) => Some(at - now),
+        (ttl, false) => Some(ttl),
+    };
+    if remaining.is_some_and(|remaining| remaining <= 0) {
+        // Not created, but REPLACE still removes the old key.
+        if let Some(old) = cache.remove(&key) {
+            server
+                .lazyfree
+                .release(old, server.lazyfree.config().lazy_server_del);
+        }
+        return Some(Value::SimpleString("OK".to_string()));
+    }
+    if let Some(remaining) = remaining {
+        // Rounded up so the key never expires early.
+        item.created_at = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_secs() as i64;
+        item.expiration = Some((remaining + 999) / 1000);
+    }
+    if let Some(idle) = idletime {
+        item.access.last_access = now - idle * 1000;
+    }
+    if let Some(freq) = freq {
+        item.access.frequency = freq;
+    }
+
+    if let Some(old) = cache.insert(key, item) {
+        server
+            .lazyfree
+            .release(old, server.lazyfree.config().lazy_server_del);
+    }
+    Some(Value::SimpleString("OK".to_string()))
+}
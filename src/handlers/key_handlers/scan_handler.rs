@@ -0,0 +1,39 @@
+use crate::utilities::lock;
+use crate::{
+    log,
+    models::value::Value,
+    server::Server,
+    utilities::{scan_page, scan_reply, ScanOptions},
+};
+
+// Incrementally iterates the keyspace. Unlike KEYS, each call only returns a
+// page of keys, so large keyspaces can be walked without one long blocking
+// call. Every key present for the whole iteration is returned at least once.
+// SCAN cursor [MATCH pattern] [COUNT count] [TYPE type]
+pub fn scan_handler(server: &mut Server, _: String, args: Vec<Value>) -> Option<Value> {
+    log!("scan_handler called with args: {:?}", args);
+
+    let options = match ScanOptions::parse(&args, "scan", &["TYPE"]) {
+        Ok(options) => options,
+        Err(e) => return Some(e.into()),
+    };
+
+    let cache = lock(&server.cache);
+    let (next_cursor, positions) = scan_page(cache.len(), options.cursor, options.count);
+
+    let keys = positions
+        .rev()
+        .filter_map(|position| cache.get_index(position))
+        .filter(|(key, item)| {
+            options.matches(key)
+                && !item.is_expired()
+                && options
+                    .type_filter
+                    .as_ref()
+                    .is_none_or(|t| *t == item.redis_type.to_string())
+        })
+        .map(|(key, _)| Value::BulkString(key.clone()))
+        .collect();
+
+    Some(scan_reply(next_cursor, keys))
+}
@@ -1,35 +1,138 @@
+use crate::utilities::lock;
 use crate::{
+    command_args::{parse_args, ArgParser, Exclusive, FromArgs},
+    error::{RedisError, RedisResult},
     log,
-    models::{redis_item::RedisItem, redis_type::RedisType, value::Value},
+    models::{redis_item::RedisItem, value::Value},
     server::Server,
-    utilities::unpack_integer,
+    utilities::now_millis,
 };
-use std::time::Instant;
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SetExpiry {
+    In(i64),
+    At(i64),
+    KeepTtl,
+}
+
+struct SetArgs {
+    value: String,
+    condition: Option<String>,
+    get: bool,
+    expiry: Option<SetExpiry>,
+}
+
+impl FromArgs for SetArgs {
+    fn from_args(args: &mut ArgParser) -> RedisResult<Self> {
+        let value = args.arg()?;
+        let mut condition = Exclusive::default();
+        let mut expiry = Exclusive::default();
+        let mut get = false;
+
+        while let Some(option) = args.keyword()? {
+            let (unit_ms, absolute) = match option.as_str() {
+                "NX" | "XX" => {
+                    condition.set(option)?;
+                    continue;
+                }
+                "GET" => {
+                    get = true;
+                    continue;
+                }
+                "KEEPTTL" => {
+                    expiry.set(SetExpiry::KeepTtl)?;
+                    continue;
+                }
+                "EX" => (1000, false),
+                "PX" => (1, false),
+                "EXAT" => (1000, true),
+                "PXAT" => (1, true),
+                _ => return Err(RedisError::Syntax),
+            };
+            let time: i64 = args.value_of()?;
+            let invalid = || RedisError::err("invalid expire time in 'set' command");
+            let ms = time
+                .checked_mul(unit_ms)
+                .filter(|&ms| ms > 0)
+                .ok_or_else(invalid)?;
+            expiry.set(if absolute {
+                SetExpiry::At(ms)
+            } else {
+                SetExpiry::In(ms)
+            })?;
+        }
+
+        Ok(SetArgs {
+            value,
+            condition: condition.into_inner(),
+            get,
+            expiry: expiry.into_inner(),
+        })
+    }
+}
+
+// Sets key to hold a string, replacing any value and TTL it had.
+// SET key value [NX | XX] [GET] [EX seconds | PX milliseconds |
+//   EXAT unix-time-seconds | PXAT unix-time-milliseconds | KEEPTTL]
 pub fn set_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
     log!("args {:?}", args);
-    let value = match args.get(0) {
-        Some(Value::BulkString(v)) => v.clone(),
-        _ => return Some(Value::Error("ERR invalid value".into())),
+    let args: SetArgs = match parse_args("set", args) {
+        Ok(args) => args,
+        Err(e) => return Some(e.into()),
     };
-    let expiration: Option<i64> = match args.get(3) {
-        Some(value) => unpack_integer(value.clone()).ok(),
-        None => None,
+    let mut cache = lock(&server.cache);
+
+    let old = cache.get(&key).filter(|item| !item.is_expired());
+    let old_value = match (args.get, old.map(|item| item.value.unpacked())) {
+        (false, _) | (true, None) => Value::NullBulkString,
+        (true, Some(value)) => match value.into_owned() {
+            value @ (Value::BulkString(_) | Value::SimpleString(_)) => value,
+            Value::Integer(i) => Value::BulkString(i.to_string()),
+            _ => return Some(RedisError::WrongType.into()),
+        },
     };
-    log!("expiration {:?}", expiration);
-    let mut cache = server.cache.lock().unwrap();
-
-    let item = RedisItem {
-        value: Value::BulkString(value),
-        created_at: Instant::now().elapsed().as_secs() as i64,
-        expiration,
-        redis_type: RedisType::String,
+    let allowed = match args.condition.as_deref() {
+        Some("NX") => old.is_none(),
+        Some("XX") => old.is_some(),
+        _ => true,
     };
+    if !allowed {
+        return Some(if args.get {
+            old_value
+        } else {
+            Value::NullBulkString
+        });
+    }
+
+    let mut item = RedisItem::new_string(args.value);
+    // Rounded up to whole seconds so a key never expires early.
+    let now = now_millis();
+    let remaining_ms = match args.expiry {
+        Some(SetExpiry::In(ms)) => Some(ms),
+        Some(SetExpiry::At(at)) => Some(at - now),
+        Some(SetExpiry::KeepTtl) => {
+            if let Some(old) = old {
+                item.created_at = old.created_at;
+                item.expiration = old.expiration;
+            }
+            None
+        }
+        None => None,
+    };
+    if let Some(ms) = remaining_ms {
+        item.expiration = Some((ms.max(0) + 999) / 1000);
+    }
 
     log!("key {:?}", key);
     log!("item {:?}", item);
-
-    cache.insert(key, item);
-    log!("Ok");
-    Some(Value::SimpleString("OK".to_string()))
+    if let Some(old) = cache.insert(key, item) {
+        server
+            .lazyfree
+            .release(old, server.lazyfree.config().lazy_server_del);
+    }
+    if args.get {
+        Some(old_value)
+    } else {
+        Some(Value::SimpleString("OK".to_string()))
+    }
 }
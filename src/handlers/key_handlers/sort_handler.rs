@@ -0,0 +1,286 @@
+use std::cmp::Ordering;
+
+use crate::utilities::lock;
+use crate::{
+    error::RedisError,
+    keyspace::Keyspace,
+    models::{redis_item::RedisItem, value::Value},
+    server::Server,
+    utilities::{now_millis, unpack_integer_arg},
+};
+
+// Returns or stores the elements of the list, set or sorted set at key,
+// sorted numerically unless ALPHA is given.
+// SORT key [BY pattern] [LIMIT offset count] [GET pattern [GET pattern ...]]
+//   [ASC | DESC] [ALPHA] [STORE destination]
+pub fn sort_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    sort_command(server, key, args, false)
+}
+
+#[derive(Default)]
+struct SortOptions {
+    // A pattern without `*` disables sorting altogether.
+    by: Option<String>,
+    dont_sort: bool,
+    limit: Option<(i64, i64)>,
+    get: Vec<String>,
+    desc: bool,
+    alpha: bool,
+    store: Option<String>,
+}
+
+pub fn sort_command(
+    server: &mut Server,
+    key: String,
+    args: Vec<Value>,
+    read_only: bool,
+) -> Option<Value> {
+    let options = match parse_options(&args, read_only) {
+        Ok(options) => options,
+        Err(e) => return Some(e),
+    };
+    if let Err(e) = check_pattern_access(server, &options) {
+        return Some(e.into());
+    }
+
+    let mut cache = lock(&server.cache);
+    let mut elements: Vec<String> = match cache.get(&key).filter(|item| !item.is_expired()) {
+        None => Vec::new(),
+        Some(item) => match item.value.unpacked().as_ref() {
+            Value::List(list) => list.iter().map(element_string).collect(),
+            Value::Set(set) => {
+                // A sorted copy, so replies without sorting are stable.
+                let mut members: Vec<String> = set.iter().cloned().collect();
+                members.sort();
+                members
+            }
+            Value::ZSet(zset) => {
+                let mut members: Vec<(&String, &f64)> = zset.iter().collect();
+                members.sort_by(|a, b| a.1.total_cmp(b.1).then_with(|| a.0.cmp(b.0)));
+                if options.dont_sort && options.desc {
+                    members.reverse();
+                }
+                members.into_iter().map(|(m, _)| m.clone()).collect()
+            }
+            _ => return Some(RedisError::WrongType.into()),
+        },
+    };
+
+    // Without sorting, only sorted sets honour DESC.
+    if !options.dont_sort {
+        match sort_elements(&cache, elements, &options) {
+            Ok(sorted) => elements = sorted,
+            Err(e) => return Some(e),
+        }
+    }
+
+    if let Some((offset, count)) = options.limit {
+        let start = (offset.max(0) as usize).min(elements.len());
+        let end = if count < 0 {
+            elements.len()
+        } else {
+            start.saturating_add(count as usize).min(elements.len())
+        };
+        elements = elements[start..end].to_vec();
+    }
+
+    let reply: Vec<Value> = if options.get.is_empty() {
+        elements.into_iter().map(Value::BulkString).collect()
+    } else {
+        elements
+            .iter()
+            .flat_map(|element| {
+                options
+                    .get
+                    .iter()
+                    .map(
+                        |pattern| match lookup_by_pattern(&cache, pattern, element) {
+                            Some(value) => Value::BulkString(value),
+                            None => Value::NullBulkString,
+                        },
+                    )
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    };
+
+    match options.store {
+        Some(destination) => {
+            let len = reply.len() as i64;
+            if reply.is_empty() {
+                cache.remove(&destination);
+            } else {
+                let list: Vec<Value> = reply
+                    .into_iter()
+                    .map(|value| match value {
+                        Value::NullBulkString => Value::BulkString(String::new()),
+                        value => value,
+                    })
+                    .collect();
+                let mut item = RedisItem::new_list(list);
+                item.pack(&server.pack_limits());
+                cache.insert(destination, item);
+            }
+            Some(Value::Integer(len))
+        }
+        None => Some(Value::Array(reply)),
+    }
+}
+
+// BY and GET patterns read keys the ACL can't check up front, so they need
+// read access to the whole keyspace.
+fn check_pattern_access(server: &Server, options: &SortOptions) -> Result<(), RedisError> {
+    let acl = server.acl();
+    let full_access = acl
+        .user(&server.user)
+        .is_some_and(|user| user.can_read_all_keys());
+    if full_access {
+        return Ok(());
+    }
+    if options.by.is_some() && !options.dont_sort {
+        return Err(RedisError::err(
+            "BY option of SORT denied due to insufficient ACL permissions.",
+        ));
+    }
+    if !options.get.is_empty() {
+        return Err(RedisError::err(
+            "GET option of SORT denied due to insufficient ACL permissions.",
+        ));
+    }
+    Ok(())
+}
+
+fn parse_options(args: &[Value], read_only: bool) -> Result<SortOptions, Value> {
+    let syntax_error = || RedisError::Syntax.into();
+    let mut options = SortOptions::default();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let option = match arg {
+            Value::BulkString(option) => option.to_uppercase(),
+            _ => return Err(syntax_error()),
+        };
+        let mut next_string = || match args.next() {
+            Some(Value::BulkString(value)) => Ok(value.clone()),
+            _ => Err(syntax_error()),
+        };
+        match option.as_str() {
+            "ASC" => options.desc = false,
+            "DESC" => options.desc = true,
+            "ALPHA" => options.alpha = true,
+            "BY" => {
+                let pattern = next_string()?;
+                options.dont_sort = !pattern.contains('*');
+                options.by = Some(pattern);
+            }
+            "GET" => options.get.push(next_string()?),
+            "STORE" if !read_only => options.store = Some(next_string()?),
+            "LIMIT" => {
+                let (offset, count) = match (args.next(), args.next()) {
+                    (Some(offset), Some(count)) => (offset, count),
+                    _ => return Err(syntax_error()),
+                };
+                match (unpack_integer_arg(offset), unpack_integer_arg(count)) {
+                    (Ok(offset), Ok(count)) => options.limit = Some((offset, count)),
+                    (Err(e), _) | (_, Err(e)) => return Err(e.into()),
+                }
+            }
+            _ => return Err(syntax_error()),
+        }
+    }
+    Ok(options)
+}
+
+fn sort_elements(
+    cache: &Keyspace,
+    elements: Vec<String>,
+    options: &SortOptions,
+) -> Result<Vec<String>, Value> {
+    let weights: Vec<(String, Option<String>)> = elements
+        .into_iter()
+        .map(|element| {
+            let weight = match &options.by {
+                Some(pattern) => lookup_by_pattern(cache, pattern, &element),
+                None => Some(element.clone()),
+            };
+            (element, weight)
+        })
+        .collect();
+
+    let mut sorted: Vec<String> = if options.alpha {
+        let mut weights = weights;
+        weights.sort_by(|a, b| {
+            // Missing weights sort first.
+            a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0))
+        });
+        weights.into_iter().map(|(element, _)| element).collect()
+    } else {
+        let mut scores = weights
+            .into_iter()
+            .map(|(element, weight)| {
+                let score = match weight {
+                    Some(weight) => weight.parse::<f64>().ok().filter(|s| !s.is_nan()),
+                    None => Some(0.0),
+                };
+                match score {
+                    Some(score) => Ok((element, score)),
+                    None => Err(RedisError::err(
+                        "One or more scores can't be converted into double",
+                    )
+                    .into()),
+                }
+            })
+            .collect::<Result<Vec<(String, f64)>, Value>>()?;
+        scores.sort_by(|a, b| {
+            a.1.partial_cmp(&b.1)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| a.0.cmp(&b.0))
+        });
+        scores.into_iter().map(|(element, _)| element).collect()
+    };
+
+    if options.desc {
+        sorted.reverse();
+    }
+    Ok(sorted)
+}
+
+fn lookup_by_pattern(cache: &Keyspace, pattern: &str, element: &str) -> Option<String> {
+    if pattern == "#" {
+        return Some(element.to_string());
+    }
+
+    let star = pattern.find('*')?;
+    let (key_pattern, field) = match pattern[star + 1..].find("->") {
+        Some(arrow) if star + 1 + arrow + 2 < pattern.len() => {
+            let arrow = star + 1 + arrow;
+            (&pattern[..arrow], Some(&pattern[arrow + 2..]))
+        }
+        _ => (pattern, None),
+    };
+    let key = key_pattern.replacen('*', element, 1);
+
+    let item = cache.get(&key).filter(|item| !item.is_expired())?;
+    match (item.value.unpacked().as_ref(), field) {
+        (Value::Hash(hash), Some(field)) => {
+            let expired = item
+                .field_expirations
+                .get(field)
+                .is_some_and(|&at| at <= now_millis());
+            if expired {
+                return None;
+            }
+            hash.get(field).map(element_string)
+        }
+        (Value::BulkString(s) | Value::SimpleString(s), None) => Some(s.clone()),
+        (Value::Integer(i), None) => Some(i.to_string()),
+        _ => None,
+    }
+}
+
+fn element_string(value: &Value) -> String {
+    match value {
+        Value::BulkString(s) | Value::SimpleString(s) => s.clone(),
+        Value::Integer(i) => i.to_string(),
+        _ => String::new(),
+    }
+}
@@ -0,0 +1,10 @@
+use super::sort_handler::sort_command;
+use crate::{models::value::Value, server::Server};
+
+// Read-only variant of SORT: same options except STORE, so it is safe to run
+// on replicas.
+// SORT_RO key [BY pattern] [LIMIT offset count] [GET pattern [GET pattern ...]]
+//   [ASC | DESC] [ALPHA]
+pub fn sort_ro_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    sort_command(server, key, args, true)
+}
@@ -0,0 +1,24 @@
+use crate::error::RedisError;
+use crate::utilities::lock;
+use crate::{models::value::Value, server::Server};
+
+// Updates the last access time of the given keys and returns how many exist.
+pub fn touch_handler(server: &mut Server, _: String, args: Vec<Value>) -> Option<Value> {
+    if args.is_empty() {
+        return Some(RedisError::wrong_arity("touch").into());
+    }
+
+    let (log_factor, decay_time) = server.lfu_params();
+    let mut cache = lock(&server.cache);
+    let mut count = 0;
+    for arg in &args {
+        if let Value::BulkString(key) = arg {
+            if let Some(item) = cache.get_mut(key).filter(|item| !item.is_expired()) {
+                item.access.touch(log_factor, decay_time);
+                count += 1;
+            }
+        }
+    }
+
+    Some(Value::Integer(count))
+}
@@ -1,5 +1,8 @@
+use crate::utilities::lock;
 use crate::{log, models::value::Value, server::Server};
 
+// Removes the specified keys like DEL, but frees large values on the lazy-free
+// worker instead of while holding the cache lock.
 pub fn unlink_handler(server: &mut Server, _: String, args: Vec<Value>) -> Option<Value> {
     let keys: Vec<String> = args
         .into_iter()
@@ -11,18 +14,24 @@ pub fn unlink_handler(server: &mut Server, _: String, args: Vec<Value>) -> Optio
 
     log!("keys {:?}", keys);
 
-    let mut removed_count = 0;
+    let mut removed = Vec::new();
     {
-        let mut cache = server.cache.lock().unwrap();
+        let mut cache = lock(&server.cache);
         for key in keys {
-            if cache.remove(&key).is_some() {
-                log!("removed key {}", key);
-                removed_count += 1;
-            } else {
-                log!("key {} not found", key);
+            match cache.remove(&key) {
+                Some(item) => {
+                    log!("removed key {}", key);
+                    removed.push(item);
+                }
+                None => log!("key {} not found", key),
             }
         }
     }
 
+    let removed_count = removed.len() as i64;
+    for item in removed {
+        server.lazyfree.free_item(item);
+    }
+
     Some(Value::Integer(removed_count))
 }
@@ -1,4 +1,5 @@
 use super::list_utils::ListOperation;
+use crate::error::RedisError;
 use crate::{log, models::value::Value, server::Server};
 
 pub fn lindex_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
@@ -8,19 +9,19 @@ pub fn lindex_handler(server: &mut Server, key: String, args: Vec<Value>) -> Opt
         args,
     );
 
-    let index = match args.get(0) {
+    let index = match args.first() {
         Some(Value::Integer(i)) => *i,
-        _ => return Some(Value::Error("ERR value is not an integer".to_string())),
+        _ => return Some(RedisError::NotInteger.into()),
     };
 
-    server.operate_on_list(&key, |list| {
+    server.read_list(&key, |list| {
         let len = list.len() as i64;
         let adjusted_index = if index < 0 { len + index } else { index };
 
         if adjusted_index < 0 || adjusted_index >= len {
             Some(Value::NullBulkString)
         } else {
-            Some(list[adjusted_index as usize].clone())
+            list.get(adjusted_index as usize)
         }
     })
 }
@@ -0,0 +1,29 @@
+use super::list_utils::ListOperation;
+use crate::error::RedisError;
+use crate::{models::value::Value, server::Server};
+
+// Inserts element in the list stored at key either before or after the first
+// occurrence of pivot. Returns -1 when the pivot is not found.
+pub fn linsert_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    let (after, pivot, element) = match (args.first(), args.get(1), args.get(2)) {
+        (Some(Value::BulkString(position)), Some(pivot), Some(element)) => {
+            match position.to_uppercase().as_str() {
+                "BEFORE" => (false, pivot.clone(), element.clone()),
+                "AFTER" => (true, pivot.clone(), element.clone()),
+                _ => return Some(RedisError::Syntax.into()),
+            }
+        }
+        _ => return Some(RedisError::wrong_arity("linsert").into()),
+    };
+
+    server
+        .operate_on_list(&key, |list| match list.iter().position(|v| *v == pivot) {
+            Some(index) => {
+                let index = if after { index + 1 } else { index };
+                list.insert(index, element);
+                Some(Value::Integer(list.len() as i64))
+            }
+            None => Some(Value::Integer(-1)),
+        })
+        .or(Some(Value::Integer(0)))
+}
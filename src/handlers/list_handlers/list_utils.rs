@@ -1,40 +1,374 @@
+use std::collections::VecDeque;
+use std::ops::Range;
+
+use crate::utilities::lock;
 use crate::{
-    models::{redis_type::RedisType, value::Value},
+    error::{RedisError, RedisResult},
+    keyspace::Keyspace,
+    models::{
+        packed::{Listpack, PackLimits, Packed},
+        redis_item::RedisItem,
+        redis_type::RedisType,
+        value::Value,
+    },
     server::Server,
 };
 
 macro_rules! wrong_type_error {
     () => {
-        Some(Value::Error(
-            "ERR operation against a key holding the wrong kind of value".to_string(),
-        ))
+        Some(RedisError::WrongType.into())
     };
 }
 
 pub trait ListOperation {
     fn operate_on_list<F, R>(&mut self, key: &str, f: F) -> Option<Value>
     where
-        F: FnOnce(&mut Vec<Value>) -> R,
+        F: FnOnce(&mut VecDeque<Value>) -> R,
+        R: Into<Option<Value>>;
+
+    fn read_list<F, R>(&self, key: &str, f: F) -> Option<Value>
+    where
+        F: FnOnce(ListRef<'_>) -> R,
         R: Into<Option<Value>>;
 }
 
 impl ListOperation for Server {
     fn operate_on_list<F, R>(&mut self, key: &str, f: F) -> Option<Value>
     where
-        F: FnOnce(&mut Vec<Value>) -> R,
+        F: FnOnce(&mut VecDeque<Value>) -> R,
         R: Into<Option<Value>>,
     {
-        let mut cache = self.cache.lock().unwrap();
-        match cache.get_mut(key) {
-            Some(item) if item.redis_type == RedisType::List => {
-                if let Value::Array(ref mut list) = item.value {
-                    f(list).into()
-                } else {
-                    wrong_type_error!()
-                }
-            }
+        let limits = self.pack_limits();
+        let mut cache = lock(&self.cache);
+        operate_on_list_in(&mut cache, key, &limits, f)
+    }
+
+    fn read_list<F, R>(&self, key: &str, f: F) -> Option<Value>
+    where
+        F: FnOnce(ListRef<'_>) -> R,
+        R: Into<Option<Value>>,
+    {
+        let cache = lock(&self.cache);
+        match cache.get(key).map(|item| &item.value) {
+            Some(Value::List(list)) => f(ListRef::Full(list)).into(),
+            Some(Value::Packed(Packed::List(listpack))) => f(ListRef::Packed(listpack)).into(),
             Some(_) => wrong_type_error!(),
             None => None,
         }
     }
 }
+
+pub enum ListRef<'a> {
+    Full(&'a VecDeque<Value>),
+    Packed(&'a Listpack),
+}
+
+impl ListRef<'_> {
+    pub fn len(&self) -> usize {
+        match self {
+            ListRef::Full(list) => list.len(),
+            ListRef::Packed(listpack) => listpack.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, index: usize) -> Option<Value> {
+        match self {
+            ListRef::Full(list) => list.get(index).cloned(),
+            ListRef::Packed(listpack) => listpack.get(index).map(|entry| entry.to_value()),
+        }
+    }
+
+    pub fn range(&self, range: Range<usize>) -> Vec<Value> {
+        match self {
+            ListRef::Full(list) => list.range(range).cloned().collect(),
+            ListRef::Packed(listpack) => listpack
+                .iter()
+                .skip(range.start)
+                .take(range.len())
+                .map(|entry| entry.to_value())
+                .collect(),
+        }
+    }
+
+    pub fn positions<'b>(
+        &'b self,
+        element: &'b Value,
+        from_tail: bool,
+        limit: usize,
+    ) -> Box<dyn Iterator<Item = usize> + 'b> {
+        let len = self.len();
+        match (self, from_tail) {
+            (ListRef::Full(list), false) => {
+                Box::new((0..len.min(limit)).filter(move |&i| list[i] == *element))
+            }
+            (ListRef::Full(list), true) => Box::new(
+                (len.saturating_sub(limit)..len)
+                    .rev()
+                    .filter(move |&i| list[i] == *element),
+            ),
+            (ListRef::Packed(listpack), false) => Box::new(
+                listpack
+                    .iter()
+                    .take(limit)
+                    .enumerate()
+                    .filter(move |(_, entry)| entry.is_value(element))
+                    .map(|(i, _)| i),
+            ),
+            (ListRef::Packed(listpack), true) => Box::new(
+                listpack
+                    .iter()
+                    .rev()
+                    .take(limit)
+                    .enumerate()
+                    .filter(move |(_, entry)| entry.is_value(element))
+                    .map(move |(i, _)| len - 1 - i),
+            ),
+        }
+    }
+}
+
+// A list left empty by `f` is removed.
+pub fn operate_on_list_in<F, R>(
+    cache: &mut Keyspace,
+    key: &str,
+    limits: &PackLimits,
+    f: F,
+) -> Option<Value>
+where
+    F: FnOnce(&mut VecDeque<Value>) -> R,
+    R: Into<Option<Value>>,
+{
+    let (result, is_empty) = match cache.get_mut(key) {
+        Some(item) if item.redis_type == RedisType::List => {
+            let packed = item.unpack();
+            if let Value::List(ref mut list) = item.value {
+                let result = f(list).into();
+                let is_empty = list.is_empty();
+                if packed {
+                    item.pack(limits);
+                }
+                (result, is_empty)
+            } else {
+                return wrong_type_error!();
+            }
+        }
+        Some(_) => return wrong_type_error!(),
+        None => return None,
+    };
+
+    if is_empty {
+        cache.remove(key);
+    }
+    result
+}
+
+pub fn is_wrong_type(cache: &Keyspace, key: &str) -> bool {
+    cache
+        .get(key)
+        .is_some_and(|item| item.redis_type != RedisType::List)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ListEnd {
+    Left,
+    Right,
+}
+
+impl ListEnd {
+    pub fn parse(value: Option<&Value>) -> Option<ListEnd> {
+        match value {
+            Some(Value::BulkString(s)) => match s.to_uppercase().as_str() {
+                "LEFT" => Some(ListEnd::Left),
+                "RIGHT" => Some(ListEnd::Right),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    pub fn pop(self, list: &mut VecDeque<Value>) -> Option<Value> {
+        match self {
+            ListEnd::Left => list.pop_front(),
+            ListEnd::Right => list.pop_back(),
+        }
+    }
+
+    pub fn push(self, list: &mut VecDeque<Value>, value: Value) {
+        match self {
+            ListEnd::Left => list.push_front(value),
+            ListEnd::Right => list.push_back(value),
+        }
+    }
+
+    fn pop_packed(self, listpack: &mut Listpack) -> Option<Value> {
+        match self {
+            ListEnd::Left => listpack.pop_front(),
+            ListEnd::Right => listpack.pop_back(),
+        }
+    }
+
+    fn push_packed(self, listpack: &mut Listpack, value: &Value) -> Option<()> {
+        match self {
+            ListEnd::Left => listpack.push_front(value),
+            ListEnd::Right => listpack.push_back(value),
+        }
+    }
+}
+
+// A packed list is edited in place and converted once it outgrows `limits`.
+pub fn push_elements_in(
+    cache: &mut Keyspace,
+    key: &str,
+    end: ListEnd,
+    elements: &[Value],
+    limits: &PackLimits,
+) -> Option<Value> {
+    let item = match cache.get_mut(key) {
+        Some(item) if item.redis_type == RedisType::List => item,
+        Some(_) => return wrong_type_error!(),
+        None => return None,
+    };
+
+    let mut pending = elements;
+    if let Value::Packed(Packed::List(listpack)) = &mut item.value {
+        while let Some((element, rest)) = pending.split_first() {
+            if end.push_packed(listpack, element).is_none() {
+                break;
+            }
+            pending = rest;
+        }
+        if !pending.is_empty() || !limits.fits_list(listpack) {
+            item.unpack();
+        }
+    }
+    let len = match &mut item.value {
+        Value::List(list) => {
+            for element in pending {
+                end.push(list, element.clone());
+            }
+            list.len()
+        }
+        Value::Packed(Packed::List(listpack)) => listpack.len(),
+        _ => return wrong_type_error!(),
+    };
+    Some(Value::Integer(len as i64))
+}
+
+pub fn pop_elements_in(
+    cache: &mut Keyspace,
+    key: &str,
+    end: ListEnd,
+    count: usize,
+) -> RedisResult<Option<Vec<Value>>> {
+    let item = match cache.get_mut(key) {
+        Some(item) if item.redis_type == RedisType::List => item,
+        Some(_) => return Err(RedisError::WrongType),
+        None => return Ok(None),
+    };
+
+    let (popped, is_empty) = match &mut item.value {
+        Value::List(list) => {
+            let popped: Vec<Value> = (0..count).map_while(|_| end.pop(list)).collect();
+            (popped, list.is_empty())
+        }
+        Value::Packed(Packed::List(listpack)) => {
+            let popped: Vec<Value> = (0..count).map_while(|_| end.pop_packed(listpack)).collect();
+            (popped, listpack.is_empty())
+        }
+        _ => return Err(RedisError::WrongType),
+    };
+    if is_empty {
+        cache.remove(key);
+    }
+    Ok(Some(popped))
+}
+
+pub fn push_elements(
+    server: &Server,
+    key: &str,
+    end: ListEnd,
+    elements: &[Value],
+    create: bool,
+) -> Option<Value> {
+    let limits = server.pack_limits();
+    let mut cache = lock(&server.cache);
+    if create && !cache.contains_key(key) {
+        let mut item = RedisItem::new_list(VecDeque::new());
+        item.pack(&limits);
+        cache.insert(key.to_string(), item);
+    }
+    push_elements_in(&mut cache, key, end, elements, &limits).or(Some(Value::Integer(0)))
+}
+
+pub fn normalize_range(start: i64, stop: i64, len: usize) -> Option<(usize, usize)> {
+    let len = len as i64;
+    let start = if start < 0 {
+        (len + start).max(0)
+    } else {
+        start
+    };
+    let stop = if stop < 0 {
+        len + stop
+    } else {
+        stop.min(len - 1)
+    };
+
+    if start > stop || start >= len {
+        None
+    } else {
+        Some((start as usize, stop as usize + 1))
+    }
+}
+
+pub fn move_element(
+    server: &mut Server,
+    source: &str,
+    destination: &str,
+    from: ListEnd,
+    to: ListEnd,
+) -> Option<Value> {
+    let limits = server.pack_limits();
+    let mut cache = lock(&server.cache);
+
+    if is_wrong_type(&cache, source) || is_wrong_type(&cache, destination) {
+        return wrong_type_error!();
+    }
+
+    let element = match pop_elements_in(&mut cache, source, from, 1) {
+        Ok(Some(mut popped)) if !popped.is_empty() => popped.remove(0),
+        Ok(_) => return Some(Value::NullBulkString),
+        Err(e) => return Some(e.into()),
+    };
+
+    let elements = [element];
+    if push_elements_in(&mut cache, destination, to, &elements, &limits).is_none() {
+        let mut item = RedisItem::new_list(elements.to_vec());
+        item.pack(&limits);
+        cache.insert(destination.to_string(), item);
+    }
+
+    let [element] = elements;
+    Some(element)
+}
+
+// A `None` count is the single-element form, replying with a bare element.
+pub fn pop_elements(
+    server: &mut Server,
+    key: &str,
+    end: ListEnd,
+    count: Option<usize>,
+) -> Option<Value> {
+    let mut cache = lock(&server.cache);
+    match pop_elements_in(&mut cache, key, end, count.unwrap_or(1)) {
+        Ok(Some(popped)) => match count {
+            None => popped.into_iter().next().or(Some(Value::NullBulkString)),
+            Some(_) => Some(Value::Array(popped)),
+        },
+        Ok(None) => Some(Value::NullBulkString),
+        Err(e) => Some(e.into()),
+    }
+}
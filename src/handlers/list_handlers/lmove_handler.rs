@@ -0,0 +1,18 @@
+use super::list_utils::{move_element, ListEnd};
+use crate::error::RedisError;
+use crate::{models::value::Value, server::Server, utilities::unpack_bulk_str};
+
+// Atomically pops an element from one end of the source list and pushes it to
+// one end of the destination list.
+// LMOVE source destination LEFT|RIGHT LEFT|RIGHT
+pub fn lmove_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    let destination = match args.first().cloned().map(unpack_bulk_str) {
+        Some(Ok(destination)) => destination,
+        _ => return Some(RedisError::wrong_arity("lmove").into()),
+    };
+
+    match (ListEnd::parse(args.get(1)), ListEnd::parse(args.get(2))) {
+        (Some(from), Some(to)) => move_element(server, &key, &destination, from, to),
+        _ => Some(RedisError::Syntax.into()),
+    }
+}
@@ -0,0 +1,64 @@
+use super::list_utils::{pop_elements_in, ListEnd};
+use crate::utilities::lock;
+use crate::{
+    error::RedisError,
+    models::value::Value,
+    server::Server,
+    utilities::{unpack_bulk_str, unpack_integer_arg},
+};
+
+// Pops one or more elements from the first non-empty list among the given keys.
+// LMPOP numkeys key [key ...] LEFT|RIGHT [COUNT count]
+pub fn lmpop_handler(server: &mut Server, _: String, args: Vec<Value>) -> Option<Value> {
+    let numkeys = match args.first().map(unpack_integer_arg) {
+        Some(Ok(n)) if n > 0 => n as usize,
+        Some(_) => return Some(RedisError::err("numkeys should be greater than 0").into()),
+        None => return Some(RedisError::wrong_arity("lmpop").into()),
+    };
+
+    if args.len() < numkeys + 2 {
+        return Some(RedisError::Syntax.into());
+    }
+
+    let keys: Vec<String> = match args[1..=numkeys]
+        .iter()
+        .cloned()
+        .map(unpack_bulk_str)
+        .collect()
+    {
+        Ok(keys) => keys,
+        Err(_) => return Some(RedisError::Syntax.into()),
+    };
+
+    let end = match ListEnd::parse(args.get(numkeys + 1)) {
+        Some(end) => end,
+        None => return Some(RedisError::Syntax.into()),
+    };
+
+    let count = match &args[numkeys + 2..] {
+        [] => 1,
+        [Value::BulkString(option), count] if option.eq_ignore_ascii_case("COUNT") => {
+            match unpack_integer_arg(count) {
+                Ok(count) if count > 0 => count as usize,
+                _ => return Some(RedisError::err("count should be greater than 0").into()),
+            }
+        }
+        _ => return Some(RedisError::Syntax.into()),
+    };
+
+    let mut cache = lock(&server.cache);
+    for key in keys {
+        match pop_elements_in(&mut cache, &key, end, count) {
+            Ok(Some(elements)) => {
+                return Some(Value::Array(vec![
+                    Value::BulkString(key),
+                    Value::Array(elements),
+                ]))
+            }
+            Ok(None) => {}
+            Err(e) => return Some(e.into()),
+        }
+    }
+
+    Some(Value::NullBulkString)
+}
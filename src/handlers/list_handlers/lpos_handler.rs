@@ -0,0 +1,82 @@
+use super::list_utils::ListOperation;
+use crate::error::RedisError;
+use crate::{models::value::Value, server::Server, utilities::unpack_integer_arg};
+
+// Returns the index of matching elements inside the list stored at key.
+// LPOS key element [RANK rank] [COUNT num-matches] [MAXLEN len]
+pub fn lpos_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    let element = match args.first() {
+        Some(element) => element.clone(),
+        None => return Some(RedisError::wrong_arity("lpos").into()),
+    };
+
+    let mut rank: i64 = 1;
+    let mut count: Option<usize> = None;
+    let mut maxlen: usize = 0;
+
+    let mut options = args[1..].iter();
+    while let Some(option) = options.next() {
+        let name = match option {
+            Value::BulkString(s) => s.to_uppercase(),
+            _ => return Some(RedisError::Syntax.into()),
+        };
+        let value = match options.next().map(unpack_integer_arg) {
+            Some(Ok(value)) => value,
+            Some(Err(_)) => return Some(RedisError::NotInteger.into()),
+            None => return Some(RedisError::Syntax.into()),
+        };
+
+        match name.as_str() {
+            "RANK" => {
+                if value == 0 {
+                    return Some(RedisError::err("RANK can't be zero: use 1 to start from the first match, 2 from the second ... or use negative to start from the end of the list").into());
+                }
+                rank = value;
+            }
+            "COUNT" => {
+                if value < 0 {
+                    return Some(RedisError::err("COUNT can't be negative").into());
+                }
+                count = Some(value as usize);
+            }
+            "MAXLEN" => {
+                if value < 0 {
+                    return Some(RedisError::err("MAXLEN can't be negative").into());
+                }
+                maxlen = value as usize;
+            }
+            _ => return Some(RedisError::Syntax.into()),
+        }
+    }
+
+    let no_match = match count {
+        Some(_) => Value::Array(vec![]),
+        None => Value::NullBulkString,
+    };
+
+    server
+        .read_list(&key, |list| {
+            let limit = if maxlen == 0 { usize::MAX } else { maxlen };
+
+            // COUNT 0 means "return every match".
+            let wanted = match count {
+                Some(0) => usize::MAX,
+                Some(n) => n,
+                None => 1,
+            };
+            let skip = (rank.unsigned_abs() - 1) as usize;
+
+            let matches: Vec<Value> = list
+                .positions(&element, rank < 0, limit)
+                .skip(skip)
+                .take(wanted)
+                .map(|i| Value::Integer(i as i64))
+                .collect();
+
+            match count {
+                Some(_) => Some(Value::Array(matches)),
+                None => matches.into_iter().next().or(Some(Value::NullBulkString)),
+            }
+        })
+        .or(Some(no_match))
+}
@@ -0,0 +1,24 @@
+use super::list_utils::{normalize_range, ListOperation};
+use crate::error::RedisError;
+use crate::{models::value::Value, server::Server, utilities::unpack_integer_arg};
+
+// Returns the specified elements of the list stored at key. Start and stop are
+// inclusive and may be negative to count from the tail of the list.
+pub fn lrange_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    let (start, stop) = match (args.first(), args.get(1)) {
+        (Some(start), Some(stop)) => match (unpack_integer_arg(start), unpack_integer_arg(stop)) {
+            (Ok(start), Ok(stop)) => (start, stop),
+            _ => return Some(RedisError::NotInteger.into()),
+        },
+        _ => return Some(RedisError::wrong_arity("lrange").into()),
+    };
+
+    server
+        .read_list(&key, |list| {
+            match normalize_range(start, stop, list.len()) {
+                Some((from, to)) => Some(Value::Array(list.range(from..to))),
+                None => Some(Value::Array(vec![])),
+            }
+        })
+        .or(Some(Value::Array(vec![])))
+}
@@ -1,37 +1,69 @@
-use crate::{handlers::list_utils::ListOperation, log, models::value::Value, server::Server};
+use std::collections::HashSet;
 
+use crate::{
+    command_args::{parse_args, ArgParser, FromArgs},
+    error::{RedisError, RedisResult},
+    handlers::list_utils::ListOperation,
+    log,
+    models::value::Value,
+    server::Server,
+};
+
+struct LremArgs {
+    count: i64,
+    element: String,
+}
+
+impl FromArgs for LremArgs {
+    fn from_args(args: &mut ArgParser) -> RedisResult<Self> {
+        let count = args.arg()?;
+        let element = match args.arg()? {
+            Value::BulkString(element) => element,
+            _ => return Err(RedisError::err("value is not a bulk string")),
+        };
+        Ok(LremArgs { count, element })
+    }
+}
+
+// Removes the first count occurrences of element from the list, walking from
+// the head for a positive count and from the tail for a negative one. A count
+// of 0 removes them all.
+// LREM key count element
 pub fn lrem_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
     log!("lrem_handler called with key: {} and args: {:?}", key, args);
-    let count = match args.get(0) {
-        Some(Value::Integer(i)) => *i,
-        _ => return Some(Value::Error("ERR value is not an integer".to_string())),
+    let LremArgs { count, element } = match parse_args("lrem", args) {
+        Ok(args) => args,
+        Err(e) => return Some(e.into()),
     };
-
-    let value = match args.get(1) {
-        Some(Value::BulkString(v)) => v.clone(),
-        _ => return Some(Value::Error("ERR value is not a bulk string".to_string())),
+    let limit = match count {
+        0 => usize::MAX,
+        count => count.unsigned_abs() as usize,
     };
 
     server
         .operate_on_list(&key, |list| {
-            let mut removed = 0;
-            log!("list before lrem: {:?}", list);
-            list.retain(|list_item| {
-                if removed == count {
-                    return true;
-                }
-                if list_item == &Value::BulkString(value.clone()) {
-                    removed += 1;
-                    return false;
-                }
-                true
+            let matches = |item: &Value| matches!(item, Value::BulkString(s) if *s == element);
+            let positions: HashSet<usize> = if count < 0 {
+                (0..list.len())
+                    .rev()
+                    .filter(|&i| matches(&list[i]))
+                    .take(limit)
+                    .collect()
+            } else {
+                (0..list.len())
+                    .filter(|&i| matches(&list[i]))
+                    .take(limit)
+                    .collect()
+            };
+
+            let mut index = 0;
+            list.retain(|_| {
+                let keep = !positions.contains(&index);
+                index += 1;
+                keep
             });
             log!("list after lrem: {:?}", list);
-            log!("count after lrem: {:?}", count);
-            log!("removed after lrem: {:?}", removed);
-            Some(Value::Integer(removed))
+            Some(Value::Integer(positions.len() as i64))
         })
-        .or(Some(Value::Error(
-            "ERR operation against a key holding the wrong kind of value".to_string(),
-        )))
+        .or(Some(Value::Integer(0)))
 }
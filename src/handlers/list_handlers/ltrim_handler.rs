@@ -0,0 +1,28 @@
+use super::list_utils::{normalize_range, ListOperation};
+use crate::error::RedisError;
+use crate::{models::value::Value, server::Server, utilities::unpack_integer_arg};
+
+// Trims the list stored at key so that it only contains the specified range of
+// elements. Trimming everything away deletes the key.
+pub fn ltrim_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    let (start, stop) = match (args.first(), args.get(1)) {
+        (Some(start), Some(stop)) => match (unpack_integer_arg(start), unpack_integer_arg(stop)) {
+            (Ok(start), Ok(stop)) => (start, stop),
+            _ => return Some(RedisError::NotInteger.into()),
+        },
+        _ => return Some(RedisError::wrong_arity("ltrim").into()),
+    };
+
+    server
+        .operate_on_list(&key, |list| {
+            match normalize_range(start, stop, list.len()) {
+                Some((from, to)) => {
+                    list.truncate(to);
+                    list.drain(..from);
+                }
+                None => list.clear(),
+            }
+            Some(Value::SimpleString("OK".to_string()))
+        })
+        .or(Some(Value::SimpleString("OK".to_string())))
+}
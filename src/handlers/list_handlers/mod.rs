@@ -1,18 +1,36 @@
 pub mod lindex_handler;
+pub mod linsert_handler;
 pub mod list_utils;
 pub mod llen_handler;
+pub mod lmove_handler;
+pub mod lmpop_handler;
 pub mod lpop_handler;
+pub mod lpos_handler;
 pub mod lpush_handler;
+pub mod lpushx_handler;
+pub mod lrange_handler;
 pub mod lrem_handler;
 pub mod lset_handler;
+pub mod ltrim_handler;
 pub mod rpop_handler;
+pub mod rpoplpush_handler;
 pub mod rpush_handler;
+pub mod rpushx_handler;
 
 pub use lindex_handler::lindex_handler;
+pub use linsert_handler::linsert_handler;
 pub use llen_handler::llen_handler;
+pub use lmove_handler::lmove_handler;
+pub use lmpop_handler::lmpop_handler;
 pub use lpop_handler::lpop_handler;
+pub use lpos_handler::lpos_handler;
 pub use lpush_handler::lpush_handler;
+pub use lpushx_handler::lpushx_handler;
+pub use lrange_handler::lrange_handler;
 pub use lrem_handler::lrem_handler;
 pub use lset_handler::lset_handler;
+pub use ltrim_handler::ltrim_handler;
 pub use rpop_handler::rpop_handler;
+pub use rpoplpush_handler::rpoplpush_handler;
 pub use rpush_handler::rpush_handler;
+pub use rpushx_handler::rpushx_handler;
@@ -1,14 +1,15 @@
-use super::list_utils::ListOperation;
-use crate::{models::value::Value, server::Server};
+use super::list_utils::{pop_elements, ListEnd};
+use crate::error::RedisError;
+use crate::{models::value::Value, server::Server, utilities::unpack_integer_arg};
 
-pub fn rpop_handler(server: &mut Server, key: String, _args: Vec<Value>) -> Option<Value> {
-    server
-        .operate_on_list(&key, |list| {
-            if list.is_empty() {
-                Some(Value::NullBulkString)
-            } else {
-                Some(list.remove(list.len() - 1))
-            }
-        })
-        .or(Some(Value::Error("ERR no such key".to_string())))
+pub fn rpop_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    let count = match args.first() {
+        Some(value) => match unpack_integer_arg(value) {
+            Ok(count) if count >= 0 => Some(count as usize),
+            _ => return Some(RedisError::err("value is out of range, must be positive").into()),
+        },
+        None => None,
+    };
+
+    pop_elements(server, &key, ListEnd::Right, count)
 }
@@ -0,0 +1,13 @@
+use super::list_utils::{move_element, ListEnd};
+use crate::error::RedisError;
+use crate::{models::value::Value, server::Server, utilities::unpack_bulk_str};
+
+// Equivalent to LMOVE source destination RIGHT LEFT.
+pub fn rpoplpush_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    match args.first().cloned().map(unpack_bulk_str) {
+        Some(Ok(destination)) => {
+            move_element(server, &key, &destination, ListEnd::Right, ListEnd::Left)
+        }
+        _ => Some(RedisError::wrong_arity("rpoplpush").into()),
+    }
+}
@@ -0,0 +1,13 @@
+use super::list_utils::{push_elements, ListEnd};
+use crate::error::RedisError;
+use crate::{models::value::Value, server::Server};
+
+// Appends the values at the tail of the list stored at key, only if key already
+// exists and holds a list. Returns 0 and does nothing otherwise.
+pub fn rpushx_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    if args.is_empty() {
+        return Some(RedisError::wrong_arity("rpushx").into());
+    }
+
+    push_elements(server, &key, ListEnd::Right, &args, false)
+}
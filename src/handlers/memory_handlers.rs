@@ -0,0 +1,242 @@
+use std::cmp::Reverse;
+use std::mem::size_of;
+
+use crate::utilities::lock;
+use crate::{
+    command_args::ArgParser,
+    error::{RedisError, RedisResult},
+    memory::{bytes_to_human, peak_memory, used_memory},
+    models::{
+        redis_item::RedisItem,
+        value::{table_size, Value},
+    },
+    server::Server,
+};
+
+const DEFAULT_SAMPLES: usize = 5;
+
+const DOCTOR_MIN_MEMORY: usize = 5 * 1024 * 1024;
+
+// Reports how memory is used and which keys use it.
+// MEMORY USAGE key [SAMPLES count] | STATS | DOCTOR | MALLOC-STATS | PURGE
+//   | HELP
+pub fn memory_handler(server: &mut Server, _key: String, args: Vec<Value>) -> Option<Value> {
+    let mut args = ArgParser::new("memory", args);
+    let subcommand = match args.arg::<String>() {
+        Ok(subcommand) => subcommand,
+        Err(e) => return Some(e.into()),
+    };
+    let result = match subcommand.to_uppercase().as_str() {
+        "USAGE" if matches!(args.len(), 1 | 3) => usage(server, args),
+        "STATS" if args.is_empty() => Ok(stats(server)),
+        "DOCTOR" if args.is_empty() => Ok(Value::BulkString(doctor(server))),
+        "MALLOC-STATS" if args.is_empty() => Ok(Value::BulkString(
+            "Stats not supported for the current allocator".to_string(),
+        )),
+        "PURGE" if args.is_empty() => Ok(Value::SimpleString("OK".to_string())),
+        "HELP" if args.is_empty() => Ok(help()),
+        _ => Err(RedisError::err(format!(
+            "unknown subcommand or wrong number of arguments for '{}'. Try MEMORY HELP.",
+            subcommand
+        ))),
+    };
+    Some(result.unwrap_or_else(Value::from))
+}
+
+fn help() -> Value {
+    Value::Array(
+        [
+            "MEMORY <subcommand> [<arg> [value] [opt] ...]. Subcommands are:",
+            "DOCTOR",
+            "    Return memory problems reports.",
+            "MALLOC-STATS",
+            "    Return internal statistics report from the memory allocator.",
+            "PURGE",
+            "    Attempt to purge dirty pages for reclamation by the allocator.",
+            "STATS",
+            "    Return information about the memory usage of the server.",
+            "USAGE <key> [SAMPLES <count>]",
+            "    Return memory in bytes used by <key> and its value. Nested values are",
+            "    sampled up to <count> times (default: 5, 0 means sample all).",
+            "HELP",
+            "    Print this help.",
+        ]
+        .iter()
+        .map(|line| Value::SimpleString(line.to_string()))
+        .collect(),
+    )
+}
+
+fn usage(server: &Server, mut args: ArgParser) -> RedisResult<Value> {
+    let key: String = args.arg()?;
+    let mut samples = DEFAULT_SAMPLES;
+    if !args.is_empty() {
+        if !args.flag("SAMPLES") {
+            return Err(RedisError::Syntax);
+        }
+        let count: i64 = args.value_of()?;
+        samples = usize::try_from(count).map_err(|_| RedisError::Syntax)?;
+    }
+
+    let cache = lock(&server.cache);
+    Ok(match cache.get(&key).filter(|item| !item.is_expired()) {
+        Some(item) => Value::Integer(key_usage(&key, item, samples) as i64),
+        None => Value::NullBulkString,
+    })
+}
+
+fn key_usage(key: &str, item: &RedisItem, samples: usize) -> usize {
+    key.len() + item.sampled_memory_usage(samples)
+}
+
+struct DbUsage {
+    keys: usize,
+    dataset: usize,
+    overhead: usize,
+}
+
+fn db_usage(server: &Server, db: usize) -> DbUsage {
+    let keyspace = lock(&server.databases[db]);
+    let dataset = keyspace
+        .iter()
+        .map(|(key, item)| key_usage(key, item, 0))
+        .sum();
+    // Only key headers, control bytes and free slots count as overhead.
+    let overhead = table_size::<(String, RedisItem)>(keyspace.capacity())
+        - keyspace.len() * size_of::<RedisItem>();
+    DbUsage {
+        keys: keyspace.len(),
+        dataset,
+        overhead,
+    }
+}
+
+fn stats(server: &Server) -> Value {
+    let used = used_memory();
+    let peak = peak_memory();
+    let mut fields = vec![
+        ("peak.allocated".to_string(), Value::Integer(peak as i64)),
+        ("total.allocated".to_string(), Value::Integer(used as i64)),
+    ];
+
+    let (mut keys, mut dataset) = (0, 0);
+    for db in 0..server.databases.len() {
+        let usage = db_usage(server, db);
+        if usage.keys == 0 {
+            continue;
+        }
+        keys += usage.keys;
+        dataset += usage.dataset;
+        fields.push((
+            format!("db.{}", db),
+            Value::Array(vec![
+                Value::BulkString("overhead.hashtable.main".to_string()),
+                Value::Integer(usage.overhead as i64),
+                Value::BulkString("dataset.bytes".to_string()),
+                Value::Integer(usage.dataset as i64),
+                Value::BulkString("keys.count".to_string()),
+                Value::Integer(usage.keys as i64),
+            ]),
+        ));
+    }
+
+    let dataset = dataset.min(used);
+    let percentage = |part: usize, whole: usize| {
+        let percentage = if whole == 0 {
+            0.0
+        } else {
+            part as f64 * 100.0 / whole as f64
+        };
+        Value::BulkString(percentage.to_string())
+    };
+    fields.extend([
+        (
+            "overhead.total".to_string(),
+            Value::Integer((used - dataset) as i64),
+        ),
+        ("keys.count".to_string(), Value::Integer(keys as i64)),
+        (
+            "keys.bytes-per-key".to_string(),
+            Value::Integer(used.checked_div(keys).unwrap_or(0) as i64),
+        ),
+        ("dataset.bytes".to_string(), Value::Integer(dataset as i64)),
+        ("dataset.percentage".to_string(), percentage(dataset, used)),
+        ("peak.percentage".to_string(), percentage(used, peak)),
+    ]);
+
+    Value::Array(
+        fields
+            .into_iter()
+            .flat_map(|(name, value)| [Value::BulkString(name), value])
+            .collect(),
+    )
+}
+
+fn doctor(server: &Server) -> String {
+    let used = used_memory();
+    if used < DOCTOR_MIN_MEMORY {
+        return "Hi Sam, this instance is empty or is using very little memory, my issues \
+            detector can't be used in these conditions. Please, leave for your mission on Earth \
+            and fill it with some data. The new Sam and I will be back to our programming as \
+            soon as I finished rebooting."
+            .to_string();
+    }
+
+    let mut reports = Vec::new();
+    let peak = peak_memory();
+    if peak / 3 * 2 > used {
+        reports.push(format!(
+            " * Peak memory: In the past this instance used more than 150% the memory that is \
+            currently using ({} at peak, {} now). The allocator is normally not able to release \
+            memory after a peak, so the process may hold on to more memory than used_memory \
+            reports.",
+            bytes_to_human(peak),
+            bytes_to_human(used)
+        ));
+    }
+
+    let maxmemory = server.config().maxmemory as usize;
+    if maxmemory != 0 && used > maxmemory / 10 * 9 {
+        reports.push(format!(
+            " * Memory limit: {} of the {} allowed by maxmemory are in use. Once the limit is \
+            reached keys are evicted according to maxmemory-policy ({}), or writes fail with \
+            -OOM under noeviction.",
+            bytes_to_human(used),
+            bytes_to_human(maxmemory),
+            server.config().maxmemory_policy
+        ));
+    }
+
+    let mut big_keys = Vec::new();
+    for (db, keyspace) in server.databases.iter().enumerate() {
+        for (key, item) in lock(keyspace).iter() {
+            let size = key_usage(key, item, DEFAULT_SAMPLES);
+            if size > used / 10 {
+                big_keys.push((size, db, key.clone()));
+            }
+        }
+    }
+    if !big_keys.is_empty() {
+        big_keys.sort_by_key(|(size, _, _)| Reverse(*size));
+        let keys: Vec<String> = big_keys
+            .iter()
+            .map(|(size, db, key)| format!("'{}' in db{} ({})", key, db, bytes_to_human(*size)))
+            .collect();
+        reports.push(format!(
+            " * Big keys: each of these keys uses more than 10% of the memory in use: {}. Check \
+            them with MEMORY USAGE and consider splitting them or giving them a TTL.",
+            keys.join(", ")
+        ));
+    }
+
+    if reports.is_empty() {
+        return "Hi Sam, I can't find any memory issue in your instance. I can only account for \
+            what occurs on this base."
+            .to_string();
+    }
+    format!(
+        "Sam, I detected a few issues in this Redis instance memory implants:\n\n{}\n\nI'm here \
+        to keep you safe, Sam. I want to help you.\n",
+        reports.join("\n\n")
+    )
+}
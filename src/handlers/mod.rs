@@ -1,11 +1,27 @@
+pub mod acl_handlers;
 pub mod basic_handlers;
+pub mod command_handlers;
+pub mod config_handlers;
+pub mod connection_handlers;
 pub mod hash_handlers;
+pub mod info_handlers;
 pub mod key_handlers;
 pub mod list_handlers;
+pub mod memory_handlers;
 pub mod replication_handlers;
+pub mod set_handlers;
+pub mod zset_handlers;
 
+pub use acl_handlers::*;
 pub use basic_handlers::*;
+pub use command_handlers::*;
+pub use config_handlers::*;
+pub use connection_handlers::*;
 pub use hash_handlers::*;
+pub use info_handlers::*;
 pub use key_handlers::*;
 pub use list_handlers::*;
+pub use memory_handlers::*;
 pub use replication_handlers::*;
+pub use set_handlers::*;
+pub use zset_handlers::*;
@@ -24,6 +24,13 @@ pub fn psync_handler(server: &mut Server) -> Option<Value> {
     }
 }
 
+// Switches the connection to streaming the snapshot once the reply is sent.
+// Registered like any other command so it needs authentication and the ACL.
+pub fn fullresync_handler(server: &mut Server) -> Option<Value> {
+    server.sync = true;
+    Some(Value::SimpleString("OK".to_string()))
+}
+
 fn generate_repl_id() -> String {
     Uuid::new_v4().to_string().replace("-", "")
 }
@@ -0,0 +1,6 @@
+pub mod sadd_handler;
+pub mod set_utils;
+pub mod sscan_handler;
+
+pub use sadd_handler::sadd_handler;
+pub use sscan_handler::sscan_handler;
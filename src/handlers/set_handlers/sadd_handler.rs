@@ -0,0 +1,27 @@
+use super::set_utils::SetOperation;
+use crate::error::RedisError;
+use crate::{models::value::Value, server::Server};
+
+// Adds the specified members to the set stored at key and returns the number
+// of members that were not already present.
+pub fn sadd_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    if args.is_empty() {
+        return Some(RedisError::wrong_arity("sadd").into());
+    }
+
+    let mut members = Vec::with_capacity(args.len());
+    for arg in args {
+        match arg {
+            Value::BulkString(member) => members.push(member),
+            _ => return Some(RedisError::Syntax.into()),
+        }
+    }
+
+    server.upsert_set(&key, |set| {
+        let added = members
+            .into_iter()
+            .filter(|member| set.insert(member.clone()))
+            .count();
+        Some(Value::Integer(added as i64))
+    })
+}
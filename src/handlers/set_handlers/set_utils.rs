@@ -0,0 +1,88 @@
+use crate::utilities::lock;
+use crate::{
+    error::RedisError,
+    keyspace::Keyspace,
+    models::{packed::PackLimits, redis_item::RedisItem, redis_type::RedisType, value::Value},
+    server::Server,
+};
+use indexmap::IndexSet;
+
+macro_rules! wrong_type_error {
+    () => {
+        Some(RedisError::WrongType.into())
+    };
+}
+
+pub trait SetOperation {
+    fn operate_on_set<F, R>(&mut self, key: &str, f: F) -> Option<Value>
+    where
+        F: FnOnce(&mut IndexSet<String>) -> R,
+        R: Into<Option<Value>>;
+
+    fn upsert_set<F, R>(&mut self, key: &str, f: F) -> Option<Value>
+    where
+        F: FnOnce(&mut IndexSet<String>) -> R,
+        R: Into<Option<Value>>;
+}
+
+impl SetOperation for Server {
+    fn operate_on_set<F, R>(&mut self, key: &str, f: F) -> Option<Value>
+    where
+        F: FnOnce(&mut IndexSet<String>) -> R,
+        R: Into<Option<Value>>,
+    {
+        let limits = self.pack_limits();
+        let mut cache = lock(&self.cache);
+        operate_on_set_in(&mut cache, key, &limits, f)
+    }
+
+    fn upsert_set<F, R>(&mut self, key: &str, f: F) -> Option<Value>
+    where
+        F: FnOnce(&mut IndexSet<String>) -> R,
+        R: Into<Option<Value>>,
+    {
+        let limits = self.pack_limits();
+        let mut cache = lock(&self.cache);
+        if !cache.contains_key(key) {
+            let mut item = RedisItem::new_set(IndexSet::new());
+            item.pack(&limits);
+            cache.insert(key.to_string(), item);
+        }
+        operate_on_set_in(&mut cache, key, &limits, f)
+    }
+}
+
+// A set left without members by `f` is removed.
+pub fn operate_on_set_in<F, R>(
+    cache: &mut Keyspace,
+    key: &str,
+    limits: &PackLimits,
+    f: F,
+) -> Option<Value>
+where
+    F: FnOnce(&mut IndexSet<String>) -> R,
+    R: Into<Option<Value>>,
+{
+    let (result, is_empty) = match cache.get_mut(key) {
+        Some(item) if item.redis_type == RedisType::Set => {
+            let packed = item.unpack();
+            if let Value::Set(ref mut set) = item.value {
+                let result = f(set).into();
+                let is_empty = set.is_empty();
+                if packed {
+                    item.pack(limits);
+                }
+                (result, is_empty)
+            } else {
+                return wrong_type_error!();
+            }
+        }
+        Some(_) => return wrong_type_error!(),
+        None => return None,
+    };
+
+    if is_empty {
+        cache.remove(key);
+    }
+    result
+}
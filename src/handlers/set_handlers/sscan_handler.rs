@@ -0,0 +1,29 @@
+use super::set_utils::SetOperation;
+use crate::{
+    models::value::Value,
+    server::Server,
+    utilities::{scan_page, scan_reply, ScanOptions},
+};
+
+// Incrementally iterates the members of the set stored at key.
+// SSCAN key cursor [MATCH pattern] [COUNT count]
+pub fn sscan_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    let options = match ScanOptions::parse(&args, "sscan", &[]) {
+        Ok(options) => options,
+        Err(e) => return Some(e.into()),
+    };
+
+    let count = options.count_for(server, &key);
+    server
+        .operate_on_set(&key, |set| {
+            let (next_cursor, positions) = scan_page(set.len(), options.cursor, count);
+            let members = positions
+                .rev()
+                .filter_map(|position| set.get_index(position))
+                .filter(|member| options.matches(member))
+                .map(|member| Value::BulkString(member.clone()))
+                .collect();
+            Some(scan_reply(next_cursor, members))
+        })
+        .or(Some(scan_reply(0, vec![])))
+}
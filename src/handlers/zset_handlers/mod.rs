@@ -0,0 +1,6 @@
+pub mod zadd_handler;
+pub mod zscan_handler;
+pub mod zset_utils;
+
+pub use zadd_handler::zadd_handler;
+pub use zscan_handler::zscan_handler;
@@ -0,0 +1,84 @@
+use super::zset_utils::{parse_score, ZSetOperation};
+use crate::error::RedisError;
+use crate::{models::value::Value, server::Server};
+
+// Adds members with the given scores to the sorted set stored at key, updating
+// the score of members that already exist.
+// ZADD key [NX | XX] [CH] score member [score member ...]
+// Replies with the number of new members, or of changed members with CH.
+pub fn zadd_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    let mut nx = false;
+    let mut xx = false;
+    let mut ch = false;
+
+    let mut rest = &args[..];
+    while let Some(Value::BulkString(flag)) = rest.first() {
+        match flag.to_uppercase().as_str() {
+            "NX" => nx = true,
+            "XX" => xx = true,
+            "CH" => ch = true,
+            _ => break,
+        }
+        rest = &rest[1..];
+    }
+
+    if nx && xx {
+        return Some(
+            RedisError::err("XX and NX options at the same time are not compatible").into(),
+        );
+    }
+    if rest.is_empty() || !rest.len().is_multiple_of(2) {
+        return Some(RedisError::Syntax.into());
+    }
+
+    let mut pairs = Vec::with_capacity(rest.len() / 2);
+    for chunk in rest.chunks(2) {
+        let score = match parse_score(&chunk[0]) {
+            Ok(score) => score,
+            Err(e) => return Some(e.into()),
+        };
+        match &chunk[1] {
+            Value::BulkString(member) => pairs.push((member.clone(), score)),
+            _ => return Some(RedisError::Syntax.into()),
+        }
+    }
+
+    if xx {
+        // XX never creates the key.
+        return server
+            .operate_on_zset(&key, |zset| {
+                let mut changed = 0;
+                for (member, score) in pairs {
+                    if let Some(current) = zset.get_mut(&member) {
+                        if *current != score {
+                            *current = score;
+                            changed += 1;
+                        }
+                    }
+                }
+                Some(Value::Integer(if ch { changed } else { 0 }))
+            })
+            .or(Some(Value::Integer(0)));
+    }
+
+    server.upsert_zset(&key, |zset| {
+        let mut added = 0;
+        let mut changed = 0;
+        for (member, score) in pairs {
+            match zset.get_mut(&member) {
+                Some(_) if nx => {}
+                Some(current) => {
+                    if *current != score {
+                        *current = score;
+                        changed += 1;
+                    }
+                }
+                None => {
+                    zset.insert(member, score);
+                    added += 1;
+                }
+            }
+        }
+        Some(Value::Integer(if ch { added + changed } else { added }))
+    })
+}
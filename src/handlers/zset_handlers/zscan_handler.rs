@@ -0,0 +1,39 @@
+use super::zset_utils::ZSetOperation;
+use crate::{
+    models::value::Value,
+    server::Server,
+    utilities::{scan_page, scan_reply, ScanOptions},
+};
+
+// Incrementally iterates the members and scores of the sorted set stored at
+// key.
+// ZSCAN key cursor [MATCH pattern] [COUNT count] [NOSCORES]
+pub fn zscan_handler(server: &mut Server, key: String, args: Vec<Value>) -> Option<Value> {
+    let options = match ScanOptions::parse(&args, "zscan", &["NOSCORES"]) {
+        Ok(options) => options,
+        Err(e) => return Some(e.into()),
+    };
+
+    let count = options.count_for(server, &key);
+    server
+        .operate_on_zset(&key, |zset| {
+            let (next_cursor, positions) = scan_page(zset.len(), options.cursor, count);
+
+            let mut entries = Vec::new();
+            for (member, score) in positions
+                .rev()
+                .filter_map(|position| zset.get_index(position))
+            {
+                if !options.matches(member) {
+                    continue;
+                }
+                entries.push(Value::BulkString(member.clone()));
+                if !options.no_values {
+                    entries.push(Value::BulkString(score.to_string()));
+                }
+            }
+
+            Some(scan_reply(next_cursor, entries))
+        })
+        .or(Some(scan_reply(0, vec![])))
+}
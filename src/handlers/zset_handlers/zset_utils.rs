@@ -0,0 +1,100 @@
+use crate::utilities::lock;
+use crate::{
+    error::RedisError,
+    keyspace::Keyspace,
+    models::{packed::PackLimits, redis_item::RedisItem, redis_type::RedisType, value::Value},
+    server::Server,
+};
+use indexmap::IndexMap;
+
+macro_rules! wrong_type_error {
+    () => {
+        Some(RedisError::WrongType.into())
+    };
+}
+
+pub trait ZSetOperation {
+    fn operate_on_zset<F, R>(&mut self, key: &str, f: F) -> Option<Value>
+    where
+        F: FnOnce(&mut IndexMap<String, f64>) -> R,
+        R: Into<Option<Value>>;
+
+    fn upsert_zset<F, R>(&mut self, key: &str, f: F) -> Option<Value>
+    where
+        F: FnOnce(&mut IndexMap<String, f64>) -> R,
+        R: Into<Option<Value>>;
+}
+
+impl ZSetOperation for Server {
+    fn operate_on_zset<F, R>(&mut self, key: &str, f: F) -> Option<Value>
+    where
+        F: FnOnce(&mut IndexMap<String, f64>) -> R,
+        R: Into<Option<Value>>,
+    {
+        let limits = self.pack_limits();
+        let mut cache = lock(&self.cache);
+        operate_on_zset_in(&mut cache, key, &limits, f)
+    }
+
+    fn upsert_zset<F, R>(&mut self, key: &str, f: F) -> Option<Value>
+    where
+        F: FnOnce(&mut IndexMap<String, f64>) -> R,
+        R: Into<Option<Value>>,
+    {
+        let limits = self.pack_limits();
+        let mut cache = lock(&self.cache);
+        if !cache.contains_key(key) {
+            let mut item = RedisItem::new_zset(IndexMap::new());
+            item.pack(&limits);
+            cache.insert(key.to_string(), item);
+        }
+        operate_on_zset_in(&mut cache, key, &limits, f)
+    }
+}
+
+// A sorted set left without members by `f` is removed.
+pub fn operate_on_zset_in<F, R>(
+    cache: &mut Keyspace,
+    key: &str,
+    limits: &PackLimits,
+    f: F,
+) -> Option<Value>
+where
+    F: FnOnce(&mut IndexMap<String, f64>) -> R,
+    R: Into<Option<Value>>,
+{
+    let (result, is_empty) = match cache.get_mut(key) {
+        Some(item) if item.redis_type == RedisType::ZSet => {
+            let packed = item.unpack();
+            if let Value::ZSet(ref mut zset) = item.value {
+                let result = f(zset).into();
+                let is_empty = zset.is_empty();
+                if packed {
+                    item.pack(limits);
+                }
+                (result, is_empty)
+            } else {
+                return wrong_type_error!();
+            }
+        }
+        Some(_) => return wrong_type_error!(),
+        None => return None,
+    };
+
+    if is_empty {
+        cache.remove(key);
+    }
+    result
+}
+
+pub fn parse_score(value: &Value) -> Result<f64, RedisError> {
+    let invalid = || RedisError::NotFloat;
+    match value {
+        Value::BulkString(s) => match s.parse::<f64>() {
+            Ok(score) if !score.is_nan() => Ok(score),
+            _ => Err(invalid()),
+        },
+        Value::Integer(i) => Ok(*i as f64),
+        _ => Err(invalid()),
+    }
+}
@@ -0,0 +1,194 @@
+use std::ops::Index;
+
+use indexmap::{IndexMap, IndexSet};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::models::redis_item::RedisItem;
+
+#[derive(Clone, Debug, Default)]
+pub struct Keyspace {
+    items: IndexMap<String, RedisItem>,
+    volatile: IndexSet<String>,
+    // The keys of `items` holding hashes with field TTLs. Fields expiring
+    // don't update it, so it may hold hashes that have none left.
+    volatile_fields: IndexSet<String>,
+}
+
+impl Keyspace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.items.capacity()
+    }
+
+    pub fn expires(&self) -> usize {
+        self.volatile.len()
+    }
+
+    pub fn field_expires(&self) -> usize {
+        self.volatile_fields.len()
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.items.contains_key(key)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&RedisItem> {
+        self.items.get(key)
+    }
+
+    // Change the item's TTL with `set_expiration`, and call
+    // `track_field_expirations` after giving its fields a TTL.
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut RedisItem> {
+        self.items.get_mut(key)
+    }
+
+    pub fn insert(&mut self, key: String, item: RedisItem) -> Option<RedisItem> {
+        if item.expiration.is_some() {
+            self.volatile.insert(key.clone());
+        } else {
+            self.volatile.swap_remove(&key);
+        }
+        if item.field_expirations.is_empty() {
+            self.volatile_fields.swap_remove(&key);
+        } else {
+            self.volatile_fields.insert(key.clone());
+        }
+        self.items.insert(key, item)
+    }
+
+    // Removing swaps the last key into the hole, which SCAN relies on: keys
+    // only ever move to a lower position.
+    pub fn remove(&mut self, key: &str) -> Option<RedisItem> {
+        let item = self.items.swap_remove(key)?;
+        if item.expiration.is_some() {
+            self.volatile.swap_remove(key);
+        }
+        self.volatile_fields.swap_remove(key);
+        Some(item)
+    }
+
+    // `expiration` is in seconds from the item's creation.
+    pub fn set_expiration(&mut self, key: &str, expiration: Option<i64>) -> bool {
+        let Some(item) = self.items.get_mut(key) else {
+            return false;
+        };
+        item.expiration = expiration;
+        if expiration.is_some() {
+            self.volatile.insert(key.to_string());
+        } else {
+            self.volatile.swap_remove(key);
+        }
+        true
+    }
+
+    pub fn track_field_expirations(&mut self, key: &str) {
+        match self.items.get(key) {
+            Some(item) if !item.field_expirations.is_empty() => {
+                self.volatile_fields.insert(key.to_string());
+            }
+            _ => {
+                self.volatile_fields.swap_remove(key);
+            }
+        }
+    }
+
+    pub fn get_index(&self, index: usize) -> Option<(&String, &RedisItem)> {
+        self.items.get_index(index)
+    }
+
+    pub fn get_volatile_index(&self, index: usize) -> Option<(&String, &RedisItem)> {
+        let key = self.volatile.get_index(index)?;
+        self.items.get_key_value(key.as_str())
+    }
+
+    pub fn get_volatile_fields_index(&self, index: usize) -> Option<(&String, &RedisItem)> {
+        let key = self.volatile_fields.get_index(index)?;
+        self.items.get_key_value(key.as_str())
+    }
+
+    pub fn iter(&self) -> indexmap::map::Iter<'_, String, RedisItem> {
+        self.items.iter()
+    }
+
+    pub fn keys(&self) -> indexmap::map::Keys<'_, String, RedisItem> {
+        self.items.keys()
+    }
+
+    pub fn values(&self) -> indexmap::map::Values<'_, String, RedisItem> {
+        self.items.values()
+    }
+
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&String, &mut RedisItem) -> bool,
+    {
+        let volatile = &mut self.volatile;
+        let volatile_fields = &mut self.volatile_fields;
+        self.items.retain(|key, item| {
+            let kept = keep(key, item);
+            if !kept {
+                volatile.swap_remove(key);
+                volatile_fields.swap_remove(key);
+            }
+            kept
+        });
+    }
+
+    pub fn clear(&mut self) {
+        self.items.clear();
+        self.volatile.clear();
+        self.volatile_fields.clear();
+    }
+}
+
+impl FromIterator<(String, RedisItem)> for Keyspace {
+    fn from_iter<I: IntoIterator<Item = (String, RedisItem)>>(iter: I) -> Self {
+        let mut keyspace = Keyspace::new();
+        for (key, item) in iter {
+            keyspace.insert(key, item);
+        }
+        keyspace
+    }
+}
+
+impl Index<&str> for Keyspace {
+    type Output = RedisItem;
+
+    fn index(&self, key: &str) -> &RedisItem {
+        &self.items[key]
+    }
+}
+
+impl<'a> IntoIterator for &'a Keyspace {
+    type Item = (&'a String, &'a RedisItem);
+    type IntoIter = indexmap::map::Iter<'a, String, RedisItem>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
+
+// Dumped as a plain map of the items; the volatile indexes are rebuilt on load.
+impl Serialize for Keyspace {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.items.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Keyspace {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let items = IndexMap::<String, RedisItem>::deserialize(deserializer)?;
+        Ok(items.into_iter().collect())
+    }
+}
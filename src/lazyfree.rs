@@ -0,0 +1,139 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, RwLock};
+use std::thread;
+
+use crate::keyspace::Keyspace;
+use crate::log;
+use crate::models::args::Args;
+use crate::models::redis_item::RedisItem;
+use crate::models::value::Value;
+use crate::utilities::{read_lock, write_lock};
+
+// Smaller values are cheaper to drop inline than to hand to the worker.
+pub const LAZYFREE_THRESHOLD: usize = 64;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LazyFreeConfig {
+    pub lazy_user_del: bool,
+    pub lazy_user_flush: bool,
+    pub lazy_expire: bool,
+    // Old values replaced by SET, RENAME or COPY REPLACE.
+    pub lazy_server_del: bool,
+}
+
+impl From<&Args> for LazyFreeConfig {
+    fn from(args: &Args) -> Self {
+        Self {
+            lazy_user_del: args.lazyfree_lazy_user_del,
+            lazy_user_flush: args.lazyfree_lazy_user_flush,
+            lazy_expire: args.lazyfree_lazy_expire,
+            lazy_server_del: args.lazyfree_lazy_server_del,
+        }
+    }
+}
+
+enum Job {
+    Item(RedisItem),
+    Keyspace(Keyspace),
+}
+
+impl Job {
+    fn objects(&self) -> usize {
+        match self {
+            Job::Item(_) => 1,
+            Job::Keyspace(keyspace) => keyspace.len(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct LazyFree {
+    config: RwLock<LazyFreeConfig>,
+    sender: Sender<Job>,
+    pending: Arc<AtomicUsize>,
+}
+
+impl LazyFree {
+    pub fn new(config: LazyFreeConfig) -> Self {
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let pending = Arc::new(AtomicUsize::new(0));
+        let worker_pending = pending.clone();
+        thread::Builder::new()
+            .name("lazyfree".to_string())
+            .spawn(move || {
+                for job in receiver {
+                    let objects = job.objects();
+                    match job {
+                        Job::Item(item) => drop(item),
+                        Job::Keyspace(keyspace) => drop(keyspace),
+                    }
+                    worker_pending.fetch_sub(objects, Ordering::SeqCst);
+                }
+            })
+            .expect("failed to spawn the lazy-free thread");
+
+        Self {
+            config: RwLock::new(config),
+            sender,
+            pending,
+        }
+    }
+
+    pub fn config(&self) -> LazyFreeConfig {
+        *read_lock(&self.config)
+    }
+
+    pub fn set_config(&self, config: LazyFreeConfig) {
+        *write_lock(&self.config) = config;
+    }
+
+    pub fn free_item(&self, item: RedisItem) {
+        if free_effort(&item) > LAZYFREE_THRESHOLD {
+            self.submit(Job::Item(item));
+        }
+    }
+
+    pub fn free_keyspace(&self, keyspace: Keyspace) {
+        if !keyspace.is_empty() {
+            self.submit(Job::Keyspace(keyspace));
+        }
+    }
+
+    pub fn release(&self, item: RedisItem, lazy: bool) {
+        if lazy {
+            self.free_item(item);
+        }
+    }
+
+    pub fn pending_objects(&self) -> usize {
+        self.pending.load(Ordering::SeqCst)
+    }
+
+    fn submit(&self, job: Job) {
+        let objects = job.objects();
+        self.pending.fetch_add(objects, Ordering::SeqCst);
+        if self.sender.send(job).is_err() {
+            // The worker is gone; the value was dropped with the failed send.
+            log!("Lazy-free worker is not running, freed inline");
+            self.pending.fetch_sub(objects, Ordering::SeqCst);
+        }
+    }
+}
+
+impl Default for LazyFree {
+    fn default() -> Self {
+        Self::new(LazyFreeConfig::default())
+    }
+}
+
+fn free_effort(item: &RedisItem) -> usize {
+    match &item.value {
+        Value::Array(values) => values.len(),
+        Value::Hash(hash) => hash.len(),
+        Value::List(list) => list.len(),
+        Value::Set(set) => set.len(),
+        Value::ZSet(zset) => zset.len(),
+        _ => 1,
+    }
+}
@@ -1,10 +1,24 @@
+pub mod acl;
+pub mod command_args;
 pub mod commands;
+pub mod config;
 pub mod database;
+pub mod error;
+pub mod eviction;
+pub mod expiration;
+pub mod glob;
 pub mod handlers;
+pub mod keyspace;
+pub mod lazyfree;
+pub mod memory;
 pub mod models;
+pub mod network;
+pub mod rdb;
 pub mod replica;
 pub mod resp;
 pub mod server;
+pub mod stats;
+pub mod tls;
 pub mod utilities;
 
 pub mod my_redis_server {
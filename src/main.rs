@@ -1,11 +1,14 @@
-use redis_starter_rust::{models::args::Args, server::Server};
-use structopt::StructOpt;
+use redis_starter_rust::{config::Config, memory::CountingAllocator, server::Server};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
 
 #[tokio::main]
 async fn main() {
-    let args = Args::from_args();
+    let config = Config::load(std::env::args()).unwrap_or_else(|e| e.exit());
+    let args = config.args.clone();
 
-    let mut server = Server::new(args.clone());
+    let mut server = Server::new(config);
 
     server.match_replica(args.clone()).await;
 
@@ -1,14 +1,276 @@
 use structopt::StructOpt;
 
+use crate::config::{parse_dbfilename, parse_memory, SaveRules};
+use crate::eviction::MaxmemoryPolicy;
+use crate::network::{parse_permissions, BindAddrs};
+use crate::tls::AuthClients;
+
 #[derive(StructOpt, Debug, Clone)]
 #[structopt(
     name = "my_redis_server",
-    about = "A Redis-compatible server implementation"
+    about = "A Redis-compatible server implementation",
+    usage = "my_redis_server [/path/to/redis.conf] [--directive value ...]"
 )]
 pub struct Args {
     #[structopt(short, long, default_value = "6379", help = "Port to listen on")]
     pub port: u16,
 
+    #[structopt(
+        long,
+        default_value = "127.0.0.1 -::1",
+        help = "Addresses to listen on, separated by spaces; a leading - makes one optional"
+    )]
+    pub bind: BindAddrs,
+
+    #[structopt(
+        long,
+        parse(from_os_str),
+        help = "Also listen on this Unix domain socket"
+    )]
+    pub unixsocket: Option<std::path::PathBuf>,
+
+    #[structopt(
+        long,
+        default_value = "0",
+        parse(try_from_str = parse_permissions),
+        help = "Octal permissions of the Unix socket file, e.g. 700"
+    )]
+    pub unixsocketperm: u32,
+
+    #[structopt(
+        long,
+        default_value = "yes",
+        parse(try_from_str = parse_yes_no),
+        help = "Refuse non-loopback clients while the default user has no password (yes/no)"
+    )]
+    pub protected_mode: bool,
+
     #[structopt(long = "replicaof", number_of_values = 2, help = "Set up replication")]
     pub replicaof: Option<Vec<String>>,
+
+    #[structopt(long, default_value = "16", help = "Number of databases")]
+    pub databases: usize,
+
+    #[structopt(long, help = "Require clients to AUTH with this password")]
+    pub requirepass: Option<String>,
+
+    #[structopt(long, help = "Password a replica authenticates to its master with")]
+    pub masterauth: Option<String>,
+
+    #[structopt(
+        long,
+        parse(from_os_str),
+        help = "File ACL users are loaded from and saved to"
+    )]
+    pub aclfile: Option<std::path::PathBuf>,
+
+    #[structopt(
+        long,
+        default_value = "0",
+        help = "Port to accept TLS connections on (0 disables)"
+    )]
+    pub tls_port: u16,
+
+    #[structopt(long, parse(from_os_str), help = "Server certificate, in PEM")]
+    pub tls_cert_file: Option<std::path::PathBuf>,
+
+    #[structopt(
+        long,
+        parse(from_os_str),
+        help = "Private key of the server certificate, in PEM"
+    )]
+    pub tls_key_file: Option<std::path::PathBuf>,
+
+    #[structopt(
+        long,
+        parse(from_os_str),
+        help = "CA bundle verifying client certificates and the master's certificate"
+    )]
+    pub tls_ca_cert_file: Option<std::path::PathBuf>,
+
+    #[structopt(
+        long,
+        default_value = "yes",
+        help = "Require TLS clients to present a certificate (yes/optional/no)"
+    )]
+    pub tls_auth_clients: AuthClients,
+
+    #[structopt(
+        long,
+        default_value = "no",
+        parse(try_from_str = parse_yes_no),
+        help = "Connect to the master over TLS (yes/no)"
+    )]
+    pub tls_replication: bool,
+
+    #[structopt(
+        long,
+        default_value = "no",
+        parse(try_from_str = parse_yes_no),
+        help = "Make DEL free values in the background like UNLINK (yes/no)"
+    )]
+    pub lazyfree_lazy_user_del: bool,
+
+    #[structopt(
+        long,
+        default_value = "no",
+        parse(try_from_str = parse_yes_no),
+        help = "Make FLUSHALL and FLUSHDB default to ASYNC (yes/no)"
+    )]
+    pub lazyfree_lazy_user_flush: bool,
+
+    #[structopt(
+        long,
+        default_value = "no",
+        parse(try_from_str = parse_yes_no),
+        help = "Free expired keys in the background (yes/no)"
+    )]
+    pub lazyfree_lazy_expire: bool,
+
+    #[structopt(
+        long,
+        default_value = "no",
+        parse(try_from_str = parse_yes_no),
+        help = "Free implicitly deleted values in the background (yes/no)"
+    )]
+    pub lazyfree_lazy_server_del: bool,
+
+    #[structopt(
+        long,
+        default_value = ".",
+        parse(from_os_str),
+        help = "Directory the dump file is written to"
+    )]
+    pub dir: std::path::PathBuf,
+
+    #[structopt(
+        long,
+        default_value = "dump.rdb",
+        parse(try_from_str = parse_dbfilename),
+        help = "Name of the dump file"
+    )]
+    pub dbfilename: String,
+
+    #[structopt(
+        long,
+        default_value = "300 1",
+        help = "Pairs of SECONDS CHANGES: dump when both have passed; empty disables dumps"
+    )]
+    pub save: SaveRules,
+
+    #[structopt(
+        long,
+        default_value = "10",
+        help = "How many times per second background tasks like active expiry run"
+    )]
+    pub hz: u32,
+
+    #[structopt(
+        long,
+        default_value = "1gb",
+        parse(try_from_str = parse_memory),
+        help = "Disconnect clients whose unparsed input grows beyond this size"
+    )]
+    pub client_query_buffer_limit: u64,
+
+    #[structopt(
+        long,
+        default_value = "0",
+        parse(try_from_str = parse_memory),
+        help = "Evict keys or refuse writes past this much memory, e.g. 1gb (0 is unlimited)"
+    )]
+    pub maxmemory: u64,
+
+    #[structopt(
+        long,
+        default_value = "noeviction",
+        help = "Keys evicted past maxmemory: noeviction, allkeys-lru, volatile-lru, allkeys-lfu, volatile-lfu, allkeys-random, volatile-random or volatile-ttl"
+    )]
+    pub maxmemory_policy: MaxmemoryPolicy,
+
+    #[structopt(
+        long,
+        default_value = "5",
+        help = "Keys sampled per database when looking for one to evict"
+    )]
+    pub maxmemory_samples: usize,
+
+    #[structopt(
+        long,
+        default_value = "10",
+        help = "How many hits the LFU counter needs to grow; higher is slower"
+    )]
+    pub lfu_log_factor: u32,
+
+    #[structopt(
+        long,
+        default_value = "1",
+        help = "Minutes without access after which the LFU counter drops by one (0 disables decay)"
+    )]
+    pub lfu_decay_time: u64,
+
+    #[structopt(
+        long,
+        default_value = "128",
+        help = "Most fields a hash keeps in its compact listpack encoding"
+    )]
+    pub hash_max_listpack_entries: usize,
+
+    #[structopt(
+        long,
+        default_value = "64",
+        help = "Longest field or value, in bytes, a listpack-encoded hash holds"
+    )]
+    pub hash_max_listpack_value: usize,
+
+    #[structopt(
+        long,
+        default_value = "-2",
+        allow_hyphen_values = true,
+        help = "Largest listpack-encoded list: a number of elements, or -1 to -5 for 4kb to 64kb"
+    )]
+    pub list_max_listpack_size: i64,
+
+    #[structopt(
+        long,
+        default_value = "512",
+        help = "Most members a set of integers keeps in its compact intset encoding"
+    )]
+    pub set_max_intset_entries: usize,
+
+    #[structopt(
+        long,
+        default_value = "128",
+        help = "Most members a set keeps in its compact listpack encoding"
+    )]
+    pub set_max_listpack_entries: usize,
+
+    #[structopt(
+        long,
+        default_value = "64",
+        help = "Longest member, in bytes, a listpack-encoded set holds"
+    )]
+    pub set_max_listpack_value: usize,
+
+    #[structopt(
+        long,
+        default_value = "128",
+        help = "Most members a sorted set keeps in its compact listpack encoding"
+    )]
+    pub zset_max_listpack_entries: usize,
+
+    #[structopt(
+        long,
+        default_value = "64",
+        help = "Longest member, in bytes, a listpack-encoded sorted set holds"
+    )]
+    pub zset_max_listpack_value: usize,
+}
+
+fn parse_yes_no(value: &str) -> Result<bool, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "yes" => Ok(true),
+        "no" => Ok(false),
+        _ => Err(format!("expected yes or no, got '{}'", value)),
+    }
 }
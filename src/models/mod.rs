@@ -1,4 +1,6 @@
 pub mod args;
+pub mod packed;
 pub mod redis_item;
+pub mod redis_string;
 pub mod redis_type;
 pub mod value;
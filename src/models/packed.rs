@@ -0,0 +1,503 @@
+use std::borrow::Cow;
+use std::collections::VecDeque;
+
+use indexmap::{IndexMap, IndexSet};
+use serde::{Deserialize, Serialize};
+
+use crate::models::redis_string::as_integer;
+use crate::models::value::Value;
+
+// Integer-looking strings are stored as integers, tagged apart from
+// `Value::Integer` so they unpack to the same value.
+const TAG_STR: u8 = 0;
+const TAG_INT: u8 = 1;
+const TAG_INT_STR: u8 = 2;
+const TAG_FLOAT: u8 = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PackLimits {
+    pub hash_entries: usize,
+    pub hash_value: usize,
+    // Positive: the most elements. -1 to -5: the most bytes, 4kb to 64kb.
+    pub list_size: i64,
+    pub set_intset_entries: usize,
+    pub set_entries: usize,
+    pub set_value: usize,
+    pub zset_entries: usize,
+    pub zset_value: usize,
+}
+
+impl PackLimits {
+    pub fn fits_list(&self, list: &Listpack) -> bool {
+        if self.list_size >= 0 {
+            list.len() <= self.list_size as usize
+        } else {
+            let level = self.list_size.unsigned_abs().min(5) - 1;
+            list.bytes.len() <= 4096 << level
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Packed {
+    Hash(Listpack),
+    List(Listpack),
+    Set(Listpack),
+    IntSet(IntSet),
+    // Members and their scores, alternating, in score order.
+    ZSet(Listpack),
+}
+
+impl Packed {
+    pub fn pack(value: &Value, limits: &PackLimits) -> Option<Packed> {
+        match value {
+            Value::Hash(hash) => pack_hash(hash, limits),
+            Value::List(list) => pack_list(list, limits),
+            Value::Set(set) => pack_set(set, limits),
+            Value::ZSet(zset) => pack_zset(zset, limits),
+            _ => None,
+        }
+    }
+
+    pub fn unpack(&self) -> Value {
+        match self {
+            Packed::Hash(listpack) => {
+                let mut entries = listpack.iter();
+                let mut hash = IndexMap::with_capacity(listpack.len() / 2);
+                while let (Some(field), Some(value)) = (entries.next(), entries.next()) {
+                    hash.insert(field.to_string(), value.to_value());
+                }
+                Value::Hash(hash)
+            }
+            Packed::List(listpack) => Value::List(listpack.iter().map(Entry::to_value).collect()),
+            Packed::Set(listpack) => {
+                Value::Set(listpack.iter().map(|entry| entry.to_string()).collect())
+            }
+            Packed::IntSet(intset) => Value::Set(intset.iter().map(|i| i.to_string()).collect()),
+            Packed::ZSet(listpack) => {
+                let mut entries = listpack.iter();
+                let mut zset = IndexMap::with_capacity(listpack.len() / 2);
+                while let (Some(member), Some(Entry::Float(score))) =
+                    (entries.next(), entries.next())
+                {
+                    zset.insert(member.to_string(), score);
+                }
+                Value::ZSet(zset)
+            }
+        }
+    }
+
+    pub fn encoding(&self) -> &'static str {
+        match self {
+            Packed::IntSet(_) => "intset",
+            _ => "listpack",
+        }
+    }
+
+    pub fn heap_size(&self) -> usize {
+        match self {
+            Packed::Hash(listpack)
+            | Packed::List(listpack)
+            | Packed::Set(listpack)
+            | Packed::ZSet(listpack) => listpack.bytes.capacity(),
+            Packed::IntSet(intset) => intset.bytes.capacity(),
+        }
+    }
+}
+
+fn pack_hash(hash: &IndexMap<String, Value>, limits: &PackLimits) -> Option<Packed> {
+    if hash.len() > limits.hash_entries {
+        return None;
+    }
+    let mut listpack = Listpack::default();
+    for (field, value) in hash {
+        if field.len() > limits.hash_value || scalar_len(value)? > limits.hash_value {
+            return None;
+        }
+        listpack.push_str(field);
+        listpack.push_back(value)?;
+    }
+    Some(Packed::Hash(listpack.shrunk()))
+}
+
+fn pack_list(list: &VecDeque<Value>, limits: &PackLimits) -> Option<Packed> {
+    if limits.list_size >= 0 && list.len() > limits.list_size as usize {
+        return None;
+    }
+    let mut listpack = Listpack::default();
+    for element in list {
+        listpack.push_back(element)?;
+    }
+    limits
+        .fits_list(&listpack)
+        .then(|| Packed::List(listpack.shrunk()))
+}
+
+fn pack_set(set: &IndexSet<String>, limits: &PackLimits) -> Option<Packed> {
+    if set.len() <= limits.set_intset_entries {
+        if let Some(intset) = IntSet::from_members(set) {
+            return Some(Packed::IntSet(intset));
+        }
+    }
+    if set.len() > limits.set_entries || set.iter().any(|m| m.len() > limits.set_value) {
+        return None;
+    }
+    let mut listpack = Listpack::default();
+    for member in set {
+        listpack.push_str(member);
+    }
+    Some(Packed::Set(listpack.shrunk()))
+}
+
+fn pack_zset(zset: &IndexMap<String, f64>, limits: &PackLimits) -> Option<Packed> {
+    if zset.len() > limits.zset_entries || zset.keys().any(|m| m.len() > limits.zset_value) {
+        return None;
+    }
+    let mut members: Vec<(&String, &f64)> = zset.iter().collect();
+    members.sort_by(|a, b| a.1.total_cmp(b.1).then_with(|| a.0.cmp(b.0)));
+    let mut listpack = Listpack::default();
+    for (member, score) in members {
+        listpack.push_str(member);
+        listpack.push_float(*score);
+    }
+    Some(Packed::ZSet(listpack.shrunk()))
+}
+
+fn scalar_len(value: &Value) -> Option<usize> {
+    match value {
+        Value::BulkString(s) => Some(s.len()),
+        Value::Integer(_) => Some(0),
+        _ => None,
+    }
+}
+
+// Each entry ends with its own length in reversed varint bytes, so the list
+// can be walked from either end.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Listpack {
+    bytes: Vec<u8>,
+    len: usize,
+}
+
+impl Listpack {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn iter(&self) -> Entries<'_> {
+        Entries {
+            bytes: &self.bytes,
+            front: 0,
+            back: self.bytes.len(),
+        }
+    }
+
+    pub fn get(&self, index: usize) -> Option<Entry<'_>> {
+        if index >= self.len {
+            None
+        } else if index < self.len / 2 {
+            self.iter().nth(index)
+        } else {
+            self.iter().nth_back(self.len - 1 - index)
+        }
+    }
+
+    pub fn push_back(&mut self, value: &Value) -> Option<()> {
+        let entry = encode_value(value)?;
+        self.bytes.extend_from_slice(&entry);
+        self.len += 1;
+        Some(())
+    }
+
+    pub fn push_front(&mut self, value: &Value) -> Option<()> {
+        let entry = encode_value(value)?;
+        self.bytes.splice(0..0, entry);
+        self.len += 1;
+        Some(())
+    }
+
+    pub fn pop_front(&mut self) -> Option<Value> {
+        let mut entries = self.iter();
+        let value = entries.next()?.to_value();
+        let end = entries.front;
+        self.bytes.drain(..end);
+        self.len -= 1;
+        Some(value)
+    }
+
+    pub fn pop_back(&mut self) -> Option<Value> {
+        let mut entries = self.iter();
+        let value = entries.next_back()?.to_value();
+        let start = entries.back;
+        self.bytes.truncate(start);
+        self.len -= 1;
+        Some(value)
+    }
+
+    fn push_str(&mut self, s: &str) {
+        write_entry(&mut self.bytes, |out| encode_str(out, s));
+        self.len += 1;
+    }
+
+    fn push_float(&mut self, f: f64) {
+        write_entry(&mut self.bytes, |out| {
+            out.push(TAG_FLOAT);
+            out.extend_from_slice(&f.to_le_bytes());
+        });
+        self.len += 1;
+    }
+
+    fn shrunk(mut self) -> Self {
+        self.bytes.shrink_to_fit();
+        self
+    }
+}
+
+fn encode_str(out: &mut Vec<u8>, s: &str) {
+    match as_integer(s) {
+        Some(i) => {
+            out.push(TAG_INT_STR);
+            write_varint(out, zigzag(i));
+        }
+        None => {
+            out.push(TAG_STR);
+            write_varint(out, s.len() as u64);
+            out.extend_from_slice(s.as_bytes());
+        }
+    }
+}
+
+fn encode_value(value: &Value) -> Option<Vec<u8>> {
+    let mut entry = Vec::new();
+    match value {
+        Value::BulkString(s) => write_entry(&mut entry, |out| encode_str(out, s)),
+        Value::Integer(i) => write_entry(&mut entry, |out| {
+            out.push(TAG_INT);
+            write_varint(out, zigzag(*i));
+        }),
+        _ => return None,
+    }
+    Some(entry)
+}
+
+fn write_entry(out: &mut Vec<u8>, encode: impl FnOnce(&mut Vec<u8>)) {
+    let start = out.len();
+    encode(out);
+    let len = (out.len() - start) as u64;
+    let at = out.len();
+    write_varint(out, len);
+    out[at..].reverse();
+}
+
+fn varint_len(mut value: u64) -> usize {
+    let mut len = 1;
+    while value >= 0x80 {
+        value >>= 7;
+        len += 1;
+    }
+    len
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Entry<'a> {
+    Str(&'a str),
+    Int(i64),
+    IntStr(i64),
+    Float(f64),
+}
+
+impl<'a> Entry<'a> {
+    pub fn to_value(self) -> Value {
+        match self {
+            Entry::Int(i) => Value::Integer(i),
+            entry => Value::BulkString(entry.to_string()),
+        }
+    }
+
+    pub fn as_str(self) -> Cow<'a, str> {
+        match self {
+            Entry::Str(s) => Cow::Borrowed(s),
+            entry => Cow::Owned(entry.to_string()),
+        }
+    }
+
+    pub fn is_str(self, s: &str) -> bool {
+        match self {
+            Entry::Str(entry) => entry == s,
+            Entry::IntStr(i) => as_integer(s) == Some(i),
+            _ => false,
+        }
+    }
+
+    pub fn is_value(self, value: &Value) -> bool {
+        match (self, value) {
+            (Entry::Int(i), Value::Integer(j)) => i == *j,
+            (entry, Value::BulkString(s)) => entry.is_str(s),
+            _ => false,
+        }
+    }
+}
+
+impl std::fmt::Display for Entry<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Entry::Str(s) => f.write_str(s),
+            Entry::Int(i) | Entry::IntStr(i) => write!(f, "{}", i),
+            Entry::Float(score) => write!(f, "{}", score),
+        }
+    }
+}
+
+pub struct Entries<'a> {
+    bytes: &'a [u8],
+    front: usize,
+    back: usize,
+}
+
+impl<'a> Iterator for Entries<'a> {
+    type Item = Entry<'a>;
+
+    fn next(&mut self) -> Option<Entry<'a>> {
+        if self.front >= self.back {
+            return None;
+        }
+        let start = self.front;
+        let (entry, end) = decode(self.bytes, start);
+        self.front = end + varint_len((end - start) as u64);
+        Some(entry)
+    }
+}
+
+impl<'a> DoubleEndedIterator for Entries<'a> {
+    fn next_back(&mut self) -> Option<Entry<'a>> {
+        if self.back <= self.front {
+            return None;
+        }
+        let mut len = 0;
+        let mut shift = 0;
+        let mut pos = self.back;
+        loop {
+            pos -= 1;
+            let byte = self.bytes[pos];
+            len |= ((byte & 0x7f) as usize) << shift;
+            if byte & 0x80 == 0 {
+                break;
+            }
+            shift += 7;
+        }
+        self.back = pos - len;
+        Some(decode(self.bytes, self.back).0)
+    }
+}
+
+// Returns the entry with the position its trailing length starts at.
+fn decode(bytes: &[u8], mut pos: usize) -> (Entry<'_>, usize) {
+    let tag = bytes[pos];
+    pos += 1;
+    let entry = match tag {
+        TAG_STR => {
+            let len = read_varint(bytes, &mut pos) as usize;
+            let s = &bytes[pos..pos + len];
+            pos += len;
+            // Only ever written from a `&str`.
+            Entry::Str(std::str::from_utf8(s).expect("listpack string is UTF-8"))
+        }
+        TAG_INT => Entry::Int(unzigzag(read_varint(bytes, &mut pos))),
+        TAG_INT_STR => Entry::IntStr(unzigzag(read_varint(bytes, &mut pos))),
+        _ => {
+            let float = bytes[pos..pos + 8].try_into().unwrap();
+            pos += 8;
+            Entry::Float(f64::from_le_bytes(float))
+        }
+    };
+    (entry, pos)
+}
+
+fn read_varint(bytes: &[u8], pos: &mut usize) -> u64 {
+    let mut value = 0;
+    let mut shift = 0;
+    loop {
+        let byte = bytes[*pos];
+        *pos += 1;
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return value;
+        }
+        shift += 7;
+    }
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn zigzag(i: i64) -> u64 {
+    ((i << 1) ^ (i >> 63)) as u64
+}
+
+fn unzigzag(u: u64) -> i64 {
+    (u >> 1) as i64 ^ -((u & 1) as i64)
+}
+
+// Sorted members, each stored in the width of the widest one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IntSet {
+    width: u8,
+    bytes: Vec<u8>,
+}
+
+impl IntSet {
+    fn from_members(set: &IndexSet<String>) -> Option<IntSet> {
+        let mut members = set
+            .iter()
+            .map(|member| as_integer(member))
+            .collect::<Option<Vec<i64>>>()?;
+        members.sort_unstable();
+        let width = members.iter().map(|&i| width_of(i)).max().unwrap_or(2);
+        let mut bytes = Vec::with_capacity(members.len() * width as usize);
+        for i in members {
+            bytes.extend_from_slice(&i.to_le_bytes()[..width as usize]);
+        }
+        Some(IntSet { width, bytes })
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len() / self.width as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    fn get(&self, index: usize) -> i64 {
+        let width = self.width as usize;
+        let bytes = &self.bytes[index * width..(index + 1) * width];
+        match width {
+            2 => i16::from_le_bytes(bytes.try_into().unwrap()) as i64,
+            4 => i32::from_le_bytes(bytes.try_into().unwrap()) as i64,
+            _ => i64::from_le_bytes(bytes.try_into().unwrap()),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = i64> + '_ {
+        (0..self.len()).map(|index| self.get(index))
+    }
+}
+
+fn width_of(i: i64) -> u8 {
+    if i16::try_from(i).is_ok() {
+        2
+    } else if i32::try_from(i).is_ok() {
+        4
+    } else {
+        8
+    }
+}
@@ -1,19 +1,78 @@
+use indexmap::{IndexMap, IndexSet};
+
+use crate::models::packed::{PackLimits, Packed};
+use crate::models::redis_string::RedisString;
 use crate::models::redis_type::RedisType;
-use crate::models::value::Value;
+use crate::models::value::{table_size, Value};
+use crate::utilities::now_millis;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::time::SystemTime;
 
+// So new keys are not the first to go under an LFU policy.
+pub const LFU_INIT_VAL: u8 = 5;
+
+// Not persisted: a key loaded from disk starts out as freshly created.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccessStats {
+    // Unix time in milliseconds.
+    pub last_access: i64,
+    pub frequency: u8,
+}
+
+impl Default for AccessStats {
+    fn default() -> Self {
+        AccessStats {
+            last_access: now_millis(),
+            frequency: LFU_INIT_VAL,
+        }
+    }
+}
+
+impl AccessStats {
+    pub fn touch(&mut self, log_factor: u32, decay_time: u64) {
+        self.frequency = self.decayed_frequency(decay_time);
+        self.last_access = now_millis();
+        if self.frequency == u8::MAX {
+            return;
+        }
+        let base = self.frequency.saturating_sub(LFU_INIT_VAL) as f64;
+        let probability = 1.0 / (base * log_factor as f64 + 1.0);
+        if rand::thread_rng().gen::<f64>() < probability {
+            self.frequency += 1;
+        }
+    }
+
+    // Drops by one for every `decay_time` minutes without access.
+    pub fn decayed_frequency(&self, decay_time: u64) -> u8 {
+        if decay_time == 0 {
+            return self.frequency;
+        }
+        let idle_minutes = (now_millis() - self.last_access).max(0) as u64 / 60_000;
+        let periods = (idle_minutes / decay_time).min(u8::MAX as u64) as u8;
+        self.frequency.saturating_sub(periods)
+    }
+
+    pub fn idle_seconds(&self) -> i64 {
+        (now_millis() - self.last_access).max(0) / 1000
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RedisItem {
     pub value: Value,
     pub created_at: i64,
     pub expiration: Option<i64>,
     pub redis_type: RedisType,
+    // Per-field expirations of a hash, as unix timestamps in milliseconds.
+    pub field_expirations: HashMap<String, i64>,
+    #[serde(skip)]
+    pub access: AccessStats,
 }
 
 impl RedisItem {
-    pub fn new_hash(hash: HashMap<String, Value>) -> Self {
+    pub fn new_hash(hash: IndexMap<String, Value>) -> Self {
         RedisItem {
             value: Value::Hash(hash),
             created_at: SystemTime::now()
@@ -22,33 +81,47 @@ impl RedisItem {
                 .as_secs() as i64,
             expiration: None,
             redis_type: RedisType::Hash,
+            field_expirations: HashMap::new(),
+            access: AccessStats::default(),
         }
     }
 
     pub fn new_string(s: String) -> Self {
         RedisItem {
-            value: Value::BulkString(s),
+            value: Value::Str(RedisString::new(s)),
             created_at: SystemTime::now()
                 .duration_since(SystemTime::UNIX_EPOCH)
                 .expect("Time went backwards")
                 .as_secs() as i64,
             expiration: None,
             redis_type: RedisType::String,
+            field_expirations: HashMap::new(),
+            access: AccessStats::default(),
         }
     }
 
-    pub fn new_list(list: Vec<Value>) -> Self {
+    pub fn new_list(list: impl Into<VecDeque<Value>>) -> Self {
         RedisItem {
-            value: Value::Array(list),
+            value: Value::List(list.into()),
             created_at: SystemTime::now()
                 .duration_since(SystemTime::UNIX_EPOCH)
                 .expect("Time went backwards")
                 .as_secs() as i64,
             expiration: None,
             redis_type: RedisType::List,
+            field_expirations: HashMap::new(),
+            access: AccessStats::default(),
         }
     }
 
+    pub fn new_set(set: IndexSet<String>) -> Self {
+        RedisItem::new(Value::Set(set), RedisType::Set)
+    }
+
+    pub fn new_zset(zset: IndexMap<String, f64>) -> Self {
+        RedisItem::new(Value::ZSet(zset), RedisType::ZSet)
+    }
+
     // A general constructor that can be used for any type
     pub fn new(value: Value, redis_type: RedisType) -> Self {
         RedisItem {
@@ -59,6 +132,89 @@ impl RedisItem {
                 .as_secs() as i64,
             expiration: None,
             redis_type,
+            field_expirations: HashMap::new(),
+            access: AccessStats::default(),
+        }
+    }
+
+    // Lists used to be stored as `Value::Array` in older dumps.
+    pub fn upgrade_legacy_list(&mut self) {
+        if self.redis_type == RedisType::List {
+            if let Value::Array(list) = &mut self.value {
+                self.value = Value::List(std::mem::take(list).into());
+            }
+        }
+    }
+
+    // The caller decides what to do with a hash left empty.
+    pub fn purge_expired_fields(&mut self, now_ms: i64) -> usize {
+        if self.field_expirations.is_empty() {
+            return 0;
+        }
+
+        let expired: Vec<String> = self
+            .field_expirations
+            .iter()
+            .filter(|(_, &at)| at <= now_ms)
+            .map(|(field, _)| field.clone())
+            .collect();
+
+        for field in &expired {
+            self.field_expirations.remove(field);
+            if let Value::Hash(hash) = &mut self.value {
+                hash.swap_remove(field);
+            }
+        }
+        expired.len()
+    }
+
+    pub fn memory_usage(&self) -> usize {
+        self.sampled_memory_usage(0)
+    }
+
+    pub fn sampled_memory_usage(&self, samples: usize) -> usize {
+        size_of::<RedisItem>()
+            + self.value.sampled_heap_size(samples)
+            + table_size::<(String, i64)>(self.field_expirations.capacity())
+            + self
+                .field_expirations
+                .keys()
+                .map(String::capacity)
+                .sum::<usize>()
+    }
+
+    pub fn expires_at(&self) -> Option<i64> {
+        self.expiration.map(|ttl| self.created_at + ttl)
+    }
+
+    // Hashes with field TTLs are kept in full.
+    pub fn pack(&mut self, limits: &PackLimits) {
+        if !self.field_expirations.is_empty() {
+            return;
+        }
+        if let Some(packed) = Packed::pack(&self.value, limits) {
+            self.value = Value::Packed(packed);
+        }
+    }
+
+    pub fn unpack(&mut self) -> bool {
+        match &self.value {
+            Value::Packed(packed) => {
+                self.value = packed.unpack();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn encoding(&self) -> &'static str {
+        match &self.value {
+            Value::Packed(packed) => packed.encoding(),
+            Value::Str(s) => s.encoding(),
+            Value::List(_) => "quicklist",
+            Value::Hash(_) | Value::Set(_) => "hashtable",
+            Value::ZSet(_) => "skiplist",
+            _ => "raw",
         }
     }
 
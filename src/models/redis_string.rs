@@ -0,0 +1,102 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+pub const EMBSTR_SIZE_LIMIT: usize = 44;
+
+// Nothing is actually shared, but OBJECT REFCOUNT reports these like Redis.
+pub const SHARED_INTEGERS: i64 = 10_000;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum RedisString {
+    Int(i64),
+    Embedded(EmbStr),
+    Raw(String),
+}
+
+impl RedisString {
+    pub fn new(s: String) -> Self {
+        if let Some(i) = as_integer(&s) {
+            RedisString::Int(i)
+        } else if s.len() <= EMBSTR_SIZE_LIMIT {
+            RedisString::Embedded(EmbStr::new(&s))
+        } else {
+            RedisString::Raw(s)
+        }
+    }
+
+    pub fn as_str(&self) -> Cow<'_, str> {
+        match self {
+            RedisString::Int(i) => Cow::Owned(i.to_string()),
+            RedisString::Embedded(s) => Cow::Borrowed(s.as_str()),
+            RedisString::Raw(s) => Cow::Borrowed(s),
+        }
+    }
+
+    pub fn encoding(&self) -> &'static str {
+        match self {
+            RedisString::Int(_) => "int",
+            RedisString::Embedded(_) => "embstr",
+            RedisString::Raw(_) => "raw",
+        }
+    }
+
+    pub fn is_shared(&self) -> bool {
+        matches!(self, RedisString::Int(i) if (0..SHARED_INTEGERS).contains(i))
+    }
+
+    pub fn heap_size(&self) -> usize {
+        match self {
+            RedisString::Raw(s) => s.capacity(),
+            _ => 0,
+        }
+    }
+}
+
+// Dumps store the text, so the encoding is chosen again on load.
+impl Serialize for RedisString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for RedisString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(RedisString::new)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct EmbStr {
+    len: u8,
+    bytes: [u8; EMBSTR_SIZE_LIMIT],
+}
+
+impl EmbStr {
+    fn new(s: &str) -> Self {
+        let mut bytes = [0; EMBSTR_SIZE_LIMIT];
+        bytes[..s.len()].copy_from_slice(s.as_bytes());
+        EmbStr {
+            len: s.len() as u8,
+            bytes,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        // Only ever copied from a `&str`.
+        std::str::from_utf8(&self.bytes[..self.len as usize]).expect("embstr is UTF-8")
+    }
+}
+
+impl fmt::Debug for EmbStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+// Only when `s` is written the way it prints, so it converts back unchanged.
+pub fn as_integer(s: &str) -> Option<i64> {
+    let i: i64 = s.parse().ok()?;
+    (i.to_string() == s).then_some(i)
+}
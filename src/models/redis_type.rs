@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum RedisType {
@@ -10,15 +11,16 @@ pub enum RedisType {
     None,
 }
 
-impl ToString for RedisType {
-    fn to_string(&self) -> String {
-        match self {
-            RedisType::String => "string".to_string(),
-            RedisType::List => "list".to_string(),
-            RedisType::Set => "set".to_string(),
-            RedisType::ZSet => "zset".to_string(),
-            RedisType::Hash => "hash".to_string(),
-            RedisType::None => "none".to_string(),
-        }
+impl fmt::Display for RedisType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            RedisType::String => "string",
+            RedisType::List => "list",
+            RedisType::Set => "set",
+            RedisType::ZSet => "zset",
+            RedisType::Hash => "hash",
+            RedisType::None => "none",
+        };
+        write!(f, "{}", name)
     }
 }
@@ -1,41 +1,144 @@
+use indexmap::{IndexMap, IndexSet};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::VecDeque;
+
+use crate::models::packed::Packed;
+use crate::models::redis_string::RedisString;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Value {
     SimpleString(String),
     BulkString(String),
     Array(Vec<Value>),
-    Hash(HashMap<String, Value>),
+    Hash(IndexMap<String, Value>),
     Integer(i64),
     Error(String),
     NullBulkString,
+    // Variants are only ever appended, so older dumps still deserialize.
+    List(VecDeque<Value>),
+    Set(IndexSet<String>),
+    ZSet(IndexMap<String, f64>),
+    Packed(Packed),
+    Str(RedisString),
+    // A bulk string that isn't valid UTF-8, such as a DUMP payload.
+    Bytes(Vec<u8>),
 }
 
 impl Value {
-    pub fn serialize(self) -> String {
+    pub fn serialize(self) -> Vec<u8> {
+        let mut serialized = Vec::new();
+        self.serialize_into(&mut serialized);
+        serialized
+    }
+
+    fn serialize_into(self, out: &mut Vec<u8>) {
         match self {
             Value::Array(values) => {
-                let mut serialized = format!("*{}\r\n", values.len());
+                out.extend_from_slice(format!("*{}\r\n", values.len()).as_bytes());
                 for value in values {
-                    serialized.push_str(&value.serialize());
+                    value.serialize_into(out);
+                }
+            }
+            Value::List(values) => {
+                out.extend_from_slice(format!("*{}\r\n", values.len()).as_bytes());
+                for value in values {
+                    value.serialize_into(out);
+                }
+            }
+            Value::Set(set) => {
+                out.extend_from_slice(format!("*{}\r\n", set.len()).as_bytes());
+                for member in set {
+                    Value::BulkString(member).serialize_into(out);
+                }
+            }
+            Value::ZSet(zset) => {
+                out.extend_from_slice(format!("*{}\r\n", zset.len() * 2).as_bytes());
+                for (member, score) in zset {
+                    Value::BulkString(member).serialize_into(out);
+                    Value::BulkString(score.to_string()).serialize_into(out);
                 }
-
-                serialized
             }
             Value::Hash(hash) => {
-                let mut serialized = String::new();
-                for (key, value) in hash.clone() {
-                    serialized.push_str(&Value::BulkString(key).serialize());
-                    serialized.push_str(&value.serialize());
+                out.extend_from_slice(format!("*{}\r\n", hash.len() * 2).as_bytes());
+                for (key, value) in hash {
+                    Value::BulkString(key).serialize_into(out);
+                    value.serialize_into(out);
                 }
-                format!("*{}\r\n{}", hash.len() * 2, serialized)
             }
-            Value::SimpleString(s) => format!("+{}\r\n", s),
-            Value::BulkString(s) => format!("${}\r\n{}\r\n", s.chars().count(), s),
-            Value::NullBulkString => format!("$-1\r\n"),
-            Value::Integer(i) => format!(":{}\r\n", i),
-            Value::Error(e) => format!("-{}\r\n", e),
+            Value::SimpleString(s) => out.extend_from_slice(format!("+{}\r\n", s).as_bytes()),
+            Value::BulkString(s) => Value::Bytes(s.into_bytes()).serialize_into(out),
+            Value::Bytes(bytes) => {
+                out.extend_from_slice(format!("${}\r\n", bytes.len()).as_bytes());
+                out.extend_from_slice(&bytes);
+                out.extend_from_slice(b"\r\n");
+            }
+            Value::NullBulkString => out.extend_from_slice(b"$-1\r\n"),
+            Value::Integer(i) => out.extend_from_slice(format!(":{}\r\n", i).as_bytes()),
+            Value::Error(e) => out.extend_from_slice(format!("-{}\r\n", e).as_bytes()),
+            Value::Packed(packed) => packed.unpack().serialize_into(out),
+            Value::Str(s) => Value::BulkString(s.as_str().into_owned()).serialize_into(out),
+        }
+    }
+
+    pub fn unpacked(&self) -> Cow<'_, Value> {
+        match self {
+            Value::Packed(packed) => Cow::Owned(packed.unpack()),
+            Value::Str(s) => Cow::Owned(Value::BulkString(s.as_str().into_owned())),
+            value => Cow::Borrowed(value),
+        }
+    }
+
+    pub fn heap_size(&self) -> usize {
+        self.sampled_heap_size(0)
+    }
+
+    // 0 samples measures every element.
+    pub fn sampled_heap_size(&self, samples: usize) -> usize {
+        let nested = |value: &Value| value.sampled_heap_size(samples);
+        match self {
+            Value::SimpleString(s) | Value::BulkString(s) | Value::Error(s) => s.capacity(),
+            Value::Array(values) => {
+                values.capacity() * size_of::<Value>() + extrapolate(values.iter(), samples, nested)
+            }
+            Value::List(list) => {
+                list.capacity() * size_of::<Value>() + extrapolate(list.iter(), samples, nested)
+            }
+            Value::Hash(hash) => {
+                table_size::<(String, Value)>(hash.capacity())
+                    + extrapolate(hash.iter(), samples, |(field, value)| {
+                        field.capacity() + nested(value)
+                    })
+            }
+            Value::Set(set) => {
+                table_size::<String>(set.capacity())
+                    + extrapolate(set.iter(), samples, String::capacity)
+            }
+            Value::ZSet(zset) => {
+                table_size::<(String, f64)>(zset.capacity())
+                    + extrapolate(zset.keys(), samples, String::capacity)
+            }
+            Value::Packed(packed) => packed.heap_size(),
+            Value::Str(s) => s.heap_size(),
+            Value::Bytes(bytes) => bytes.capacity(),
+            Value::Integer(_) | Value::NullBulkString => 0,
         }
     }
 }
+
+fn extrapolate<I, F>(elements: I, samples: usize, size: F) -> usize
+where
+    I: ExactSizeIterator,
+    F: FnMut(I::Item) -> usize,
+{
+    let len = elements.len();
+    if samples == 0 || len <= samples {
+        return elements.map(size).sum();
+    }
+    elements.take(samples).map(size).sum::<usize>() * len / samples
+}
+
+// The entries plus one control byte each.
+pub fn table_size<T>(capacity: usize) -> usize {
+    capacity * (size_of::<T>() + 1)
+}
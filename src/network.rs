@@ -0,0 +1,191 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
+use tokio::net::{TcpListener, UnixListener};
+
+use crate::log;
+use crate::resp::RespHandler;
+use crate::server::Server;
+use crate::stats::Stats;
+use crate::tls::TlsContext;
+
+pub const PROTECTED_MODE_ERROR: &str = "-DENIED Redis is running in protected mode because \
+    protected mode is enabled and no password is set for the default user. In this mode \
+    connections are only accepted from the loopback interface. If you want to connect from \
+    external computers to Redis you may adopt one of the following solutions: 1) Just disable \
+    protected mode sending the command 'CONFIG SET protected-mode no' from the loopback \
+    interface by connecting to Redis from the same host the server is running, however MAKE \
+    SURE Redis is not publicly accessible from internet if you do so. Use CONFIG REWRITE to make \
+    this change permanent. 2) Alternatively you can just disable the protected mode by editing \
+    the Redis configuration file, and setting the protected mode option to 'no', and then \
+    restarting the server. 3) If you started the server manually just for testing, restart it \
+    with the '--protected-mode no' option. 4) Set up an authentication password for the default \
+    user. NOTE: You only need to do one of the above things in order for the server to start \
+    accepting connections from the outside.\r\n";
+
+// A leading `-` makes the address optional, e.g. `-::1` on hosts without IPv6.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BindAddr {
+    pub ip: IpAddr,
+    pub optional: bool,
+}
+
+impl FromStr for BindAddr {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (optional, addr) = match value.strip_prefix('-') {
+            Some(addr) => (true, addr),
+            None => (false, value),
+        };
+        let ip = match addr {
+            "*" => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+            "::*" => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+            _ => addr
+                .parse()
+                .map_err(|_| format!("invalid bind address '{}'", value))?,
+        };
+        Ok(BindAddr { ip, optional })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BindAddrs(pub Vec<BindAddr>);
+
+impl FromStr for BindAddrs {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let addrs = value
+            .split_whitespace()
+            .map(BindAddr::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+        if addrs.is_empty() {
+            return Err("bind needs at least one address".to_string());
+        }
+        Ok(BindAddrs(addrs))
+    }
+}
+
+impl fmt::Display for BindAddrs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let addrs: Vec<String> = self
+            .0
+            .iter()
+            .map(|addr| format!("{}{}", if addr.optional { "-" } else { "" }, addr.ip))
+            .collect();
+        write!(f, "{}", addrs.join(" "))
+    }
+}
+
+pub fn parse_permissions(value: &str) -> Result<u32, String> {
+    u32::from_str_radix(value, 8).map_err(|_| format!("invalid octal permissions '{}'", value))
+}
+
+pub async fn bind_tcp(addrs: &[BindAddr], port: u16) -> io::Result<Vec<TcpListener>> {
+    let mut listeners = Vec::new();
+    for addr in addrs {
+        match TcpListener::bind((addr.ip, port)).await {
+            Ok(listener) => listeners.push(listener),
+            Err(e) if addr.optional => {
+                log!("Skipping optional bind address {}: {}", addr.ip, e);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(listeners)
+}
+
+pub fn bind_unix(path: &Path, permissions: u32) -> io::Result<UnixListener> {
+    if path.exists() {
+        fs::remove_file(path)?;
+    }
+    let listener = UnixListener::bind(path)?;
+    if permissions != 0 {
+        fs::set_permissions(path, fs::Permissions::from_mode(permissions))?;
+    }
+    Ok(listener)
+}
+
+pub fn spawn_tcp_listener(server: Server, listener: TcpListener, tls: Option<Arc<TlsContext>>) {
+    tokio::spawn(async move {
+        loop {
+            let (stream, peer) = match listener.accept().await {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    log!("Error accepting connection: {}", e);
+                    continue;
+                }
+            };
+            let server = server.clone();
+            let protected = server.denies_protected(peer.ip());
+            let tls = tls.clone();
+            tokio::spawn(async move {
+                match tls {
+                    Some(tls) => {
+                        let acceptor = tls.acceptor().expect("TLS listener without a certificate");
+                        match acceptor.accept(stream).await {
+                            Ok(stream) => serve(server, stream, protected).await,
+                            Err(e) => log!("TLS handshake failed: {}", e),
+                        }
+                    }
+                    None => serve(server, stream, protected).await,
+                }
+            });
+        }
+    });
+}
+
+pub fn spawn_unix_listener(server: Server, listener: UnixListener) {
+    tokio::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, _)) => {
+                    tokio::spawn(serve(server.clone(), stream, false));
+                }
+                Err(e) => log!("Error accepting connection: {}", e),
+            }
+        }
+    });
+}
+
+async fn serve<S>(server: Server, mut stream: S, protected: bool)
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let _connection = ConnectionGuard::new(server.stats.clone());
+    if protected {
+        log!("Refusing a non-loopback client in protected mode");
+        let _ = stream.write_all(PROTECTED_MODE_ERROR.as_bytes()).await;
+    } else {
+        let mut handler = RespHandler::new(stream);
+        log!("Handling client");
+        match handler.handle_client(server).await {
+            Ok(_) => log!("Client disconnected gracefully"),
+            Err(e) => log!("Client disconnected with error: {}", e),
+        }
+    }
+}
+
+// A guard, so the count drops even when the connection's task panics.
+struct ConnectionGuard(Arc<Stats>);
+
+impl ConnectionGuard {
+    fn new(stats: Arc<Stats>) -> Self {
+        stats.record_connection();
+        ConnectionGuard(stats)
+    }
+}
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.0.record_disconnection();
+    }
+}
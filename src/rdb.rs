@@ -0,0 +1,303 @@
+use std::collections::{HashMap, VecDeque};
+
+use indexmap::{IndexMap, IndexSet};
+use thiserror::Error;
+
+use crate::error::RedisError;
+use crate::models::redis_item::RedisItem;
+use crate::models::redis_type::RedisType;
+use crate::models::value::Value;
+
+pub const RDB_VERSION: u16 = 12;
+
+const RDB_TYPE_STRING: u8 = 0;
+const RDB_TYPE_LIST: u8 = 1;
+const RDB_TYPE_SET: u8 = 2;
+const RDB_TYPE_HASH: u8 = 4;
+const RDB_TYPE_ZSET_2: u8 = 5;
+const RDB_TYPE_HASH_METADATA: u8 = 24;
+
+const RDB_6BITLEN: u8 = 0;
+const RDB_14BITLEN: u8 = 1;
+const RDB_32BITLEN: u8 = 0x80;
+const RDB_64BITLEN: u8 = 0x81;
+const RDB_ENCVAL: u8 = 3;
+
+const RDB_ENC_INT8: u8 = 0;
+const RDB_ENC_INT16: u8 = 1;
+const RDB_ENC_INT32: u8 = 2;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum RdbError {
+    #[error("DUMP payload version or checksum are wrong")]
+    VersionOrChecksum,
+
+    #[error("Bad data format")]
+    BadFormat,
+}
+
+impl From<RdbError> for RedisError {
+    fn from(error: RdbError) -> Self {
+        RedisError::err(error.to_string())
+    }
+}
+
+// Expired hash fields must already be purged.
+pub fn dump_payload(item: &RedisItem) -> Vec<u8> {
+    let mut payload = Vec::new();
+    write_object(&mut payload, item);
+    payload.extend_from_slice(&RDB_VERSION.to_le_bytes());
+    let crc = crc64::crc64(0, &payload);
+    payload.extend_from_slice(&crc.to_le_bytes());
+    payload
+}
+
+pub fn restore_payload(payload: &[u8]) -> Result<RedisItem, RdbError> {
+    if payload.len() < 10 {
+        return Err(RdbError::VersionOrChecksum);
+    }
+    let (body, crc) = payload.split_at(payload.len() - 8);
+    let version = u16::from_le_bytes([body[body.len() - 2], body[body.len() - 1]]);
+    let crc = u64::from_le_bytes(crc.try_into().expect("split at 8 bytes"));
+    if version > RDB_VERSION || crc64::crc64(0, body) != crc {
+        return Err(RdbError::VersionOrChecksum);
+    }
+
+    let mut reader = Reader {
+        data: &body[..body.len() - 2],
+        pos: 0,
+    };
+    let item = reader.read_object()?;
+    if reader.pos != reader.data.len() {
+        return Err(RdbError::BadFormat);
+    }
+    Ok(item)
+}
+
+fn write_object(out: &mut Vec<u8>, item: &RedisItem) {
+    match item.value.unpacked().as_ref() {
+        Value::List(list) => {
+            out.push(RDB_TYPE_LIST);
+            write_len(out, list.len() as u64);
+            for element in list {
+                write_string(out, &value_to_string(element));
+            }
+        }
+        Value::Array(list) => {
+            out.push(RDB_TYPE_LIST);
+            write_len(out, list.len() as u64);
+            for element in list {
+                write_string(out, &value_to_string(element));
+            }
+        }
+        Value::Set(set) => {
+            out.push(RDB_TYPE_SET);
+            write_len(out, set.len() as u64);
+            for member in set {
+                write_string(out, member);
+            }
+        }
+        Value::ZSet(zset) => {
+            out.push(RDB_TYPE_ZSET_2);
+            write_len(out, zset.len() as u64);
+            for (member, score) in zset {
+                write_string(out, member);
+                out.extend_from_slice(&score.to_le_bytes());
+            }
+        }
+        Value::Hash(hash) if item.field_expirations.is_empty() => {
+            out.push(RDB_TYPE_HASH);
+            write_len(out, hash.len() as u64);
+            for (field, value) in hash {
+                write_string(out, field);
+                write_string(out, &value_to_string(value));
+            }
+        }
+        Value::Hash(hash) => {
+            // Field TTLs are stored relative to the earliest one, plus one so
+            // that zero can mean "no TTL".
+            let min_expire = item.field_expirations.values().min().copied().unwrap_or(0);
+            out.push(RDB_TYPE_HASH_METADATA);
+            out.extend_from_slice(&min_expire.to_le_bytes());
+            write_len(out, hash.len() as u64);
+            for (field, value) in hash {
+                let ttl = item
+                    .field_expirations
+                    .get(field)
+                    .map_or(0, |at| (at - min_expire) as u64 + 1);
+                write_len(out, ttl);
+                write_string(out, field);
+                write_string(out, &value_to_string(value));
+            }
+        }
+        value => {
+            out.push(RDB_TYPE_STRING);
+            write_string(out, &value_to_string(value));
+        }
+    }
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::SimpleString(s) | Value::BulkString(s) => s.clone(),
+        Value::Integer(i) => i.to_string(),
+        _ => String::new(),
+    }
+}
+
+fn write_len(out: &mut Vec<u8>, len: u64) {
+    if len < 1 << 6 {
+        out.push((RDB_6BITLEN << 6) | len as u8);
+    } else if len < 1 << 14 {
+        out.push((RDB_14BITLEN << 6) | (len >> 8) as u8);
+        out.push(len as u8);
+    } else if len <= u32::MAX as u64 {
+        out.push(RDB_32BITLEN);
+        out.extend_from_slice(&(len as u32).to_be_bytes());
+    } else {
+        out.push(RDB_64BITLEN);
+        out.extend_from_slice(&len.to_be_bytes());
+    }
+}
+
+fn write_string(out: &mut Vec<u8>, s: &str) {
+    write_len(out, s.len() as u64);
+    out.extend_from_slice(s.as_bytes());
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn read_object(&mut self) -> Result<RedisItem, RdbError> {
+        let item = match self.read_u8()? {
+            RDB_TYPE_STRING => RedisItem::new_string(self.read_string()?),
+            RDB_TYPE_LIST => {
+                let len = self.read_len()?;
+                let mut list = VecDeque::new();
+                for _ in 0..len {
+                    list.push_back(Value::BulkString(self.read_string()?));
+                }
+                RedisItem::new_list(list)
+            }
+            RDB_TYPE_SET => {
+                let len = self.read_len()?;
+                let mut set = IndexSet::new();
+                for _ in 0..len {
+                    set.insert(self.read_string()?);
+                }
+                RedisItem::new_set(set)
+            }
+            RDB_TYPE_ZSET_2 => {
+                let len = self.read_len()?;
+                let mut zset = IndexMap::new();
+                for _ in 0..len {
+                    let member = self.read_string()?;
+                    let score = f64::from_le_bytes(self.read_array()?);
+                    if score.is_nan() {
+                        return Err(RdbError::BadFormat);
+                    }
+                    zset.insert(member, score);
+                }
+                RedisItem::new_zset(zset)
+            }
+            RDB_TYPE_HASH => {
+                let len = self.read_len()?;
+                let mut hash = IndexMap::new();
+                for _ in 0..len {
+                    let field = self.read_string()?;
+                    hash.insert(field, Value::BulkString(self.read_string()?));
+                }
+                RedisItem::new_hash(hash)
+            }
+            RDB_TYPE_HASH_METADATA => {
+                let min_expire = i64::from_le_bytes(self.read_array()?);
+                let len = self.read_len()?;
+                let mut hash = IndexMap::new();
+                let mut field_expirations = HashMap::new();
+                for _ in 0..len {
+                    let ttl = self.read_len()?;
+                    let field = self.read_string()?;
+                    if ttl > 0 {
+                        let expire_at = i64::try_from(ttl - 1)
+                            .ok()
+                            .and_then(|offset| min_expire.checked_add(offset))
+                            .ok_or(RdbError::BadFormat)?;
+                        field_expirations.insert(field.clone(), expire_at);
+                    }
+                    hash.insert(field, Value::BulkString(self.read_string()?));
+                }
+                let mut item = RedisItem::new_hash(hash);
+                item.field_expirations = field_expirations;
+                item
+            }
+            _ => return Err(RdbError::BadFormat),
+        };
+        if item.redis_type != RedisType::String && is_empty(&item.value) {
+            return Err(RdbError::BadFormat);
+        }
+        Ok(item)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, RdbError> {
+        let byte = *self.data.get(self.pos).ok_or(RdbError::BadFormat)?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Result<&[u8], RdbError> {
+        let end = self.pos.checked_add(len).ok_or(RdbError::BadFormat)?;
+        let bytes = self.data.get(self.pos..end).ok_or(RdbError::BadFormat)?;
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], RdbError> {
+        Ok(self
+            .read_bytes(N)?
+            .try_into()
+            .expect("read exactly N bytes"))
+    }
+
+    fn read_len_or_encoding(&mut self) -> Result<Result<u64, u8>, RdbError> {
+        let first = self.read_u8()?;
+        Ok(match first >> 6 {
+            RDB_6BITLEN => Ok((first & 0x3f) as u64),
+            RDB_14BITLEN => Ok((((first & 0x3f) as u64) << 8) | self.read_u8()? as u64),
+            RDB_ENCVAL => Err(first & 0x3f),
+            _ if first == RDB_32BITLEN => Ok(u32::from_be_bytes(self.read_array()?) as u64),
+            _ if first == RDB_64BITLEN => Ok(u64::from_be_bytes(self.read_array()?)),
+            _ => return Err(RdbError::BadFormat),
+        })
+    }
+
+    fn read_len(&mut self) -> Result<u64, RdbError> {
+        self.read_len_or_encoding()?
+            .map_err(|_| RdbError::BadFormat)
+    }
+
+    fn read_string(&mut self) -> Result<String, RdbError> {
+        match self.read_len_or_encoding()? {
+            Ok(len) => {
+                let len = usize::try_from(len).map_err(|_| RdbError::BadFormat)?;
+                String::from_utf8(self.read_bytes(len)?.to_vec()).map_err(|_| RdbError::BadFormat)
+            }
+            Err(RDB_ENC_INT8) => Ok((self.read_u8()? as i8).to_string()),
+            Err(RDB_ENC_INT16) => Ok(i16::from_le_bytes(self.read_array()?).to_string()),
+            Err(RDB_ENC_INT32) => Ok(i32::from_le_bytes(self.read_array()?).to_string()),
+            Err(_) => Err(RdbError::BadFormat),
+        }
+    }
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::List(list) => list.is_empty(),
+        Value::Set(set) => set.is_empty(),
+        Value::ZSet(zset) => zset.is_empty(),
+        Value::Hash(hash) => hash.is_empty(),
+        _ => false,
+    }
+}
@@ -1,36 +1,62 @@
+use std::sync::Arc;
+
 use anyhow::Result;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::TcpStream;
 
 use crate::log;
+use crate::models::value::Value;
 use crate::server::Server;
+use crate::tls::{AsyncStream, TlsContext};
 use crate::utilities::ServerState;
 
 pub struct ReplicaClient {
     pub port: u16,
-    pub stream: TcpStream,
+    pub stream: Box<dyn AsyncStream>,
     pub handshakes: u8,
     pub sync: bool,
+    // The master's requirepass, sent with AUTH right after PING.
+    masterauth: Option<String>,
+    awaiting_auth: bool,
 }
 
 impl ReplicaClient {
-    pub async fn new(vec: Vec<String>) -> Result<Self> {
+    pub async fn new(
+        vec: Vec<String>,
+        masterauth: Option<String>,
+        tls: Option<Arc<TlsContext>>,
+    ) -> Result<Self> {
         let mut iter = vec.into_iter();
         let addr = iter.next().unwrap();
         let port = iter.next().unwrap();
         let stream = TcpStream::connect(format!("{addr}:{port}")).await.unwrap();
+        let stream: Box<dyn AsyncStream> = match tls {
+            Some(tls) => Box::new(tls.connect(&addr, stream).await?),
+            None => Box::new(stream),
+        };
 
         Ok(Self {
             port: port.parse::<u16>().unwrap(),
             stream,
             handshakes: 0,
             sync: false,
+            masterauth,
+            awaiting_auth: false,
         })
     }
 
     pub async fn send_ping(&mut self, server: &Server) -> Result<()> {
         let msg = server.send_ping().unwrap();
-        self.stream.write_all(msg.serialize().as_bytes()).await?;
+        self.stream.write_all(&msg.serialize()).await?;
+        Ok(())
+    }
+
+    pub async fn send_auth(&mut self, password: &str) -> Result<()> {
+        let msg = Value::Array(vec![
+            Value::BulkString("AUTH".to_string()),
+            Value::BulkString(password.to_string()),
+        ]);
+        self.stream.write_all(&msg.serialize()).await?;
         Ok(())
     }
 
@@ -42,15 +68,13 @@ impl ReplicaClient {
             _ => vec![],
         };
         let replconf = server.generate_replconf(command, params).unwrap();
-        self.stream
-            .write_all(replconf.serialize().as_bytes())
-            .await?;
+        self.stream.write_all(&replconf.serialize()).await?;
         Ok(())
     }
 
     pub async fn send_psync(&mut self, server: &Server) -> Result<()> {
         let msg = server.send_psync().unwrap();
-        self.stream.write_all(msg.serialize().as_bytes()).await?;
+        self.stream.write_all(&msg.serialize()).await?;
         Ok(())
     }
 
@@ -72,9 +96,27 @@ impl ReplicaClient {
         server: &mut Server,
     ) -> Result<(), Box<dyn std::error::Error>> {
         match response.trim() {
-            "+PONG" => {
+            // A master with requirepass refuses the PING until we AUTH.
+            "+PONG" | "-NOAUTH Authentication required." if self.handshakes == 1 => {
+                match self.masterauth.clone() {
+                    Some(password) => {
+                        self.send_auth(&password).await?;
+                        self.awaiting_auth = true;
+                    }
+                    None => self.send_replconf(server).await?,
+                }
+            }
+            "+OK" if self.awaiting_auth => {
+                // The AUTH reply isn't a handshake step of its own.
+                self.awaiting_auth = false;
+                self.handshakes -= 1;
                 self.send_replconf(server).await?;
             }
+            _ if self.awaiting_auth => {
+                return Err(
+                    format!("Failed to authenticate with master: {}", response.trim()).into(),
+                );
+            }
             "+OK" => {
                 if self.handshakes == 3 {
                     self.send_psync(server).await?;
@@ -1,37 +1,52 @@
-use anyhow::Result;
-use bytes::BytesMut;
+use anyhow::{anyhow, Result};
+use bytes::{Buf, BytesMut};
+use std::panic::{self, AssertUnwindSafe};
 use tokio::fs::File;
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 use tokio::net::TcpStream;
 
-use crate::commands::{COMMAND_HANDLERS, DEPRECATED_COMMANDS};
+use crate::commands::{dispatch, COMMAND_TABLE, DEPRECATED_COMMANDS};
+use crate::error::RedisError;
+use crate::handlers::hello_authenticates;
 use crate::log;
 use crate::models::value::Value;
 use crate::server::Server;
 use crate::utilities::{extract_command, parse_message};
 
-pub struct RespHandler {
-    stream: TcpStream,
+pub struct RespHandler<S = TcpStream> {
+    stream: S,
     buffer: BytesMut,
+    // Only checked while the default user has a password.
+    authenticated: bool,
+    closing: bool,
+    query_buffer_limit: usize,
 }
 
-impl RespHandler {
-    pub fn new(stream: TcpStream) -> Self {
+impl<S: AsyncRead + AsyncWrite + Unpin> RespHandler<S> {
+    pub fn new(stream: S) -> Self {
         RespHandler {
             stream,
             buffer: BytesMut::with_capacity(512),
+            authenticated: false,
+            closing: false,
+            query_buffer_limit: usize::MAX,
         }
     }
 
     pub async fn handle_client(&mut self, mut server: Server) -> Result<()> {
         loop {
-            if let Some(value) = self.read_value().await? {
-                log!("value: {:?}", value);
-                let response = self.process_command(value, &mut server)?;
-                if let Some(response) = response {
-                    log!("response: {:?}", response);
-                    self.write_value(response).await?;
-                }
+            self.query_buffer_limit = server.config().client_query_buffer_limit as usize;
+            let Some(value) = self.read_value().await? else {
+                return Ok(());
+            };
+            log!("value: {:?}", value);
+            let response = self.process_command(value, &mut server)?;
+            if let Some(response) = response {
+                log!("response: {:?}", response);
+                self.write_value(response).await?;
+            }
+            if self.closing {
+                return Ok(());
             }
 
             if server.sync {
@@ -40,32 +55,60 @@ impl RespHandler {
         }
     }
 
-    fn process_command(&self, value: Value, server: &mut Server) -> Result<Option<Value>> {
+    fn process_command(&mut self, value: Value, server: &mut Server) -> Result<Option<Value>> {
         match value {
-            Value::Error(err) => Ok(Some(Value::Error(err))),
+            Value::Error(err) => {
+                server.stats.record_error_reply(&err);
+                Ok(Some(Value::Error(err)))
+            }
             _ => self.execute_command(value, server),
         }
     }
 
-    fn execute_command(&self, value: Value, server: &mut Server) -> Result<Option<Value>> {
+    fn execute_command(&mut self, value: Value, server: &mut Server) -> Result<Option<Value>> {
         match extract_command(value) {
-            Ok((command, key, args)) => {
-                if let Some(new_command) = DEPRECATED_COMMANDS.get(command.as_str()) {
-                    Ok(Some(Value::Error(format!(
+            Ok((command, args)) => {
+                let name = command.to_uppercase();
+                if let Some(new_command) = DEPRECATED_COMMANDS.get(name.as_str()) {
+                    log!(
                         "Warning: Command '{}' is deprecated. Use '{}' instead.",
-                        command, new_command
-                    ))))
-                } else if command == "FULLRESYNC" {
-                    server.sync = true;
-                    Ok(Some(Value::SimpleString("OK".to_string())))
-                } else if let Some(command_function) = COMMAND_HANDLERS.get(command.as_str()) {
-                    log!("command: {}", command);
-                    Ok(command_function(server, key, args))
+                        name,
+                        new_command
+                    );
+                }
+
+                if self.requires_auth(server, &name, &args) {
+                    Ok(Some(reject(server, RedisError::NoAuth)))
+                } else if let Err(e) = check_acl(server, &name, &args) {
+                    Ok(Some(reject(server, e)))
                 } else {
-                    Ok(Some(Value::Error("Unknown command".to_string())))
+                    log!("command: {}", command);
+                    let authenticates =
+                        name == "AUTH" || (name == "HELLO" && hello_authenticates(&args));
+                    let response = dispatch(server, &command, args);
+                    let succeeded = !matches!(response, Some(Value::Error(_)));
+                    if authenticates && succeeded {
+                        self.authenticated = true;
+                    }
+                    self.closing = name == "QUIT";
+                    Ok(response)
                 }
             }
-            Err(e) => Ok(Some(Value::Error(e.to_string()))),
+            Err(e) => Ok(Some(reject(server, protocol_error(e)))),
+        }
+    }
+
+    // HELLO only runs unauthenticated when it authenticates itself.
+    fn requires_auth(&self, server: &Server, name: &str, args: &[Value]) -> bool {
+        if self.authenticated || !server.acl().requires_auth() {
+            return false;
+        }
+        match COMMAND_TABLE.get(name) {
+            Some(command) if command.has_flag("no-auth") => {
+                name == "HELLO" && !hello_authenticates(args)
+            }
+            Some(_) => true,
+            None => false,
         }
     }
 
@@ -83,30 +126,64 @@ impl RespHandler {
         let replconf = server
             .generate_replconf("REPLCONF", vec![("GETACK", "1".to_string())])
             .unwrap();
-        self.stream
-            .write_all(replconf.serialize().as_bytes())
-            .await?;
+        self.stream.write_all(&replconf.serialize()).await?;
         server.sync = false;
 
         Ok(())
     }
 
     pub async fn read_value(&mut self) -> Result<Option<Value>> {
-        let bytes_read = self.stream.read_buf(&mut self.buffer).await?;
-
-        if bytes_read == 0 {
-            return Ok(None);
-        }
+        loop {
+            // A parser panic is answered like any other malformed frame.
+            let parsed = panic::catch_unwind(AssertUnwindSafe(|| parse_message(&self.buffer)))
+                .unwrap_or_else(|_| Err(anyhow!("invalid frame")));
+            match parsed {
+                Ok(Some((value, len))) => {
+                    self.buffer.advance(len);
+                    return Ok(Some(value));
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    self.buffer.clear();
+                    return Ok(Some(protocol_error(e).into()));
+                }
+            }
 
-        match parse_message(self.buffer.split()) {
-            Ok((v, _)) => Ok(Some(v)),
-            Err(e) => Ok(Some(Value::Error(e.to_string()))),
+            if self.stream.read_buf(&mut self.buffer).await? == 0 {
+                return Ok(None);
+            }
+            log!("Buffered {} bytes", self.buffer.len());
+            if self.buffer.len() > self.query_buffer_limit {
+                anyhow::bail!("query buffer exceeds client-query-buffer-limit");
+            }
         }
     }
 
     pub async fn write_value(&mut self, value: Value) -> Result<()> {
-        self.stream.write(value.serialize().as_bytes()).await?;
+        self.stream.write_all(&value.serialize()).await?;
 
         Ok(())
     }
 }
+
+// Unknown commands and wrong arities pass, so dispatch replies its usual error.
+fn check_acl(server: &Server, name: &str, args: &[Value]) -> Result<(), RedisError> {
+    match COMMAND_TABLE.get(name) {
+        Some(command) if command.arity_matches(args.len() + 1) => {
+            server.acl().check(&server.user, command, args)
+        }
+        _ => Ok(()),
+    }
+}
+
+fn protocol_error(error: anyhow::Error) -> RedisError {
+    RedisError::err(format!("Protocol error: {}", error))
+}
+
+fn reject(server: &Server, error: RedisError) -> Value {
+    let reply: Value = error.into();
+    if let Value::Error(message) = &reply {
+        server.stats.record_error_reply(message);
+    }
+    reply
+}
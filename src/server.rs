@@ -1,16 +1,30 @@
+use crate::acl::{Acl, DEFAULT_USER};
+use crate::config::Config;
 use crate::database::Database;
+use crate::eviction::EvictionPool;
+use crate::expiration::{active_expire_cycle, cycle_budget};
+use crate::keyspace::Keyspace;
+use crate::lazyfree::{LazyFree, LazyFreeConfig};
 use crate::log;
 use crate::models::args::Args;
-use crate::models::redis_item::RedisItem;
+use crate::models::packed::PackLimits;
 use crate::models::value::Value;
+use crate::network::{bind_tcp, bind_unix, spawn_tcp_listener, spawn_unix_listener};
 use crate::replica::ReplicaClient;
-use crate::resp::RespHandler;
-use crate::utilities::ServerState;
-use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
-use tokio::net::TcpListener;
+use crate::stats::Stats;
+use crate::tls::{TlsConfig, TlsContext};
+use crate::utilities::{lock, now_secs, read_lock, write_lock, ServerState};
+use std::fmt;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
 use tokio::time::{interval, sleep, Duration};
 
+pub const REDIS_VERSION: &str = "7.2.4";
+
+const TLS_RELOAD_INTERVAL: Duration = Duration::from_secs(5);
+
+pub type Db = Arc<Mutex<Keyspace>>;
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum Role {
     Main,
@@ -19,123 +33,235 @@ pub enum Role {
 
 #[derive(Clone, Debug)]
 pub struct Server {
-    pub cache: Arc<Mutex<HashMap<String, RedisItem>>>,
+    // One of `databases`.
+    pub cache: Db,
+    pub databases: Arc<Vec<Db>>,
+    pub db_index: usize,
+    pub lazyfree: Arc<LazyFree>,
+    pub stats: Arc<Stats>,
     pub role: Role,
     pub port: u16,
     pub sync: bool,
     pub server_state: ServerState,
+    pub acl: Arc<Mutex<Acl>>,
+    pub user: String,
+    pub tls: Option<Arc<TlsContext>>,
+    pub config: Arc<RwLock<Config>>,
+    pub eviction_pool: Arc<Mutex<EvictionPool>>,
 }
 
 impl Server {
-    pub fn new(args: Args) -> Self {
+    pub fn new(config: Config) -> Self {
+        let args = config.args.clone();
+        let lazyfree = Arc::new(LazyFree::new(LazyFreeConfig::from(&args)));
+        let tls_config = TlsConfig::from(&args);
+        let tls = tls_config.enabled().then(|| {
+            let context = TlsContext::new(tls_config).expect("Failed to set up TLS");
+            Arc::new(context)
+        });
         let role = match args.replicaof {
             Some(vec) => {
                 let mut iter = vec.into_iter();
                 let addr = iter.next().unwrap();
-                let _ = iter.next().unwrap();
+                let port = iter.next().unwrap();
                 Role::Slave {
                     host: addr,
-                    port: args.port,
+                    port: port.parse().expect("Invalid replicaof port"),
                 }
             }
             None => Role::Main,
         };
-        let server = Self {
-            cache: Arc::new(Mutex::new(HashMap::new())),
+        let databases = Self::create_databases(args.databases.max(1));
+        Self {
+            cache: databases[0].clone(),
+            databases,
+            db_index: 0,
+            lazyfree,
+            stats: Arc::new(Stats::default()),
             role,
             port: args.port,
             sync: false,
             server_state: ServerState::Initialising,
-        };
+            acl: Arc::new(Mutex::new(Acl::new(
+                args.requirepass.as_deref(),
+                args.aclfile,
+            ))),
+            user: DEFAULT_USER.to_string(),
+            tls,
+            config: Arc::new(RwLock::new(config)),
+            eviction_pool: Default::default(),
+        }
+    }
 
-        server
+    pub fn create_databases(count: usize) -> Arc<Vec<Db>> {
+        Arc::new(
+            (0..count)
+                .map(|_| Arc::new(Mutex::new(Keyspace::new())))
+                .collect(),
+        )
+    }
+
+    pub fn select_db(&mut self, index: usize) -> bool {
+        match self.databases.get(index) {
+            Some(db) => {
+                self.cache = db.clone();
+                self.db_index = index;
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Takes the lower index first so two commands can't deadlock. The guards
+    // are returned in argument order.
+    pub fn lock_db_pair(
+        &self,
+        a: usize,
+        b: usize,
+    ) -> (MutexGuard<'_, Keyspace>, MutexGuard<'_, Keyspace>) {
+        if a < b {
+            let first = lock(&self.databases[a]);
+            let second = lock(&self.databases[b]);
+            (first, second)
+        } else {
+            let second = lock(&self.databases[b]);
+            let first = lock(&self.databases[a]);
+            (first, second)
+        }
     }
 
     pub async fn match_replica(&mut self, args: Args) {
-        match args.replicaof {
-            Some(vec) => {
-                let mut replica = ReplicaClient::new(vec).await.unwrap();
-                replica.send_ping(&self).await.unwrap();
-
-                while replica.sync == false {
-                    match replica.read_response().await {
-                        Ok(response) => {
-                            log!("response in match: {}", response);
-                            replica.handshakes += 1;
-                            replica.handle_response(&response, self).await.unwrap();
-                        }
-                        Err(e) => {
-                            log!("Failed to read from stream: {}", e);
-                        }
+        if let Some(vec) = args.replicaof {
+            let tls = self.tls.clone().filter(|tls| tls.config.replication);
+            let mut replica = ReplicaClient::new(vec, args.masterauth, tls).await.unwrap();
+            replica.send_ping(self).await.unwrap();
+
+            while !replica.sync {
+                match replica.read_response().await {
+                    Ok(response) => {
+                        log!("response in match: {}", response);
+                        replica.handshakes += 1;
+                        replica.handle_response(&response, self).await.unwrap();
+                    }
+                    Err(e) => {
+                        log!("Failed to read from stream: {}", e);
                     }
                 }
             }
-            None => {}
         }
     }
 
     pub async fn listen(&mut self, port: u16) {
-        let listener = TcpListener::bind(("127.0.0.1", port)).await.unwrap();
-        log!("Listening on Port {}", port);
+        let config = self.config().clone();
+        let mut listeners = Vec::new();
+        if port != 0 {
+            let bound = bind_tcp(&config.bind.0, port).await.unwrap();
+            log!("Listening on Port {}", port);
+            listeners.extend(bound.into_iter().map(|listener| (listener, None)));
+        }
+        if let Some(tls) = self.tls.clone().filter(|tls| tls.config.port != 0) {
+            let bound = bind_tcp(&config.bind.0, tls.config.port).await.unwrap();
+            log!("Listening for TLS connections on Port {}", tls.config.port);
+            listeners.extend(
+                bound
+                    .into_iter()
+                    .map(|listener| (listener, Some(tls.clone()))),
+            );
+            spawn_tls_reloader(tls);
+        }
+        let unix_listener = config.unixsocket.as_ref().map(|path| {
+            let listener = bind_unix(path, config.unixsocketperm).unwrap();
+            log!("Listening on Unix socket {}", path.display());
+            listener
+        });
 
-        let db = Database::new(self.cache.clone(), "dump.rdb");
+        let db = Database::with_databases(self.databases.clone(), &config.dump_path());
 
         if let Err(e) = db.read_backup() {
             log!("Failed to load backup: {}", e);
         }
+        self.stats.record_save(0, now_secs());
 
-        let db_clone = db.clone();
+        let server = self.clone();
         tokio::spawn(async move {
-            sleep(Duration::from_secs(300)).await;
-
-            let mut interval_timer = interval(Duration::from_secs(300));
+            let mut interval_timer = interval(Duration::from_secs(1));
             loop {
                 interval_timer.tick().await;
-                if let Err(e) = db_clone.dump_backup() {
-                    log!("Failed to dump backup: {}", e);
-                } else {
-                    log!("Backup dumped successfully.");
+                let (path, due) = {
+                    let config = server.config();
+                    let elapsed = now_secs().saturating_sub(server.stats.last_save());
+                    let due = config.save.due(elapsed as u64, server.stats.dirty());
+                    (config.dump_path(), due)
+                };
+                if due {
+                    server.dump(&path);
                 }
             }
         });
 
-        loop {
-            tokio::select! {
-                accept_result = listener.accept() => {
-                    match accept_result {
-                        Ok((stream, _)) => {
-                            let server_clone = self.clone();
-                            tokio::spawn(async move {
-                                let mut handler = RespHandler::new(stream);
-                                log!("Handling client");
-                                match handler.handle_client(server_clone).await {
-                                    Ok(_) => log!("Client disconnected gracefully"),
-                                    Err(e) => log!("Client disconnected with error: {}", e),
-                                }
-                            });
-                        },
-                        Err(e) => {
-                            log!("Error accepting connection: {}", e);
-                        }
-                    }
-                }
+        let server = self.clone();
+        tokio::spawn(async move {
+            let mut next_db = 0;
+            loop {
+                let hz = server.config().hz.clamp(1, 500);
+                sleep(Duration::from_millis(1000 / hz as u64)).await;
+                active_expire_cycle(&server, &mut next_db, cycle_budget(hz));
+            }
+        });
 
-                _ = tokio::signal::ctrl_c() => {
-                    log!("Received Ctrl+C, initiating graceful shutdown...");
+        for (listener, tls) in listeners {
+            spawn_tcp_listener(self.clone(), listener, tls);
+        }
+        if let Some(listener) = unix_listener {
+            spawn_unix_listener(self.clone(), listener);
+        }
 
-                    if let Err(e) = db.dump_backup() {
-                        log!("Failed to dump backup on shutdown: {}", e);
-                    } else {
-                        log!("Backup dumped successfully on shutdown.");
-                    }
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            log!("Failed to listen for Ctrl+C: {}", e);
+        }
+        log!("Received Ctrl+C, initiating graceful shutdown...");
 
-                    log!("Server is shutting down gracefully.");
-                    break;
-                }
+        let path = self.config().dump_path();
+        self.dump(&path);
+        if let Some(path) = &config.unixsocket {
+            let _ = std::fs::remove_file(path);
+        }
+
+        log!("Server is shutting down gracefully.");
+    }
+
+    fn dump(&self, path: &str) {
+        let dirty = self.stats.dirty();
+        match Database::with_databases(self.databases.clone(), path).dump_backup() {
+            Ok(()) => {
+                self.stats.record_save(dirty, now_secs());
+                log!("Backup dumped successfully.");
+            }
+            Err(e) => {
+                self.stats.record_save_failure();
+                log!("Failed to dump backup: {}", e);
             }
         }
     }
 
+    pub fn config(&self) -> RwLockReadGuard<'_, Config> {
+        read_lock(&self.config)
+    }
+
+    pub fn config_mut(&self) -> RwLockWriteGuard<'_, Config> {
+        write_lock(&self.config)
+    }
+
+    pub fn acl(&self) -> MutexGuard<'_, Acl> {
+        lock(&self.acl)
+    }
+
+    pub fn denies_protected(&self, ip: IpAddr) -> bool {
+        self.config().protected_mode
+            && !ip.to_canonical().is_loopback()
+            && !self.acl().requires_auth()
+    }
+
     pub fn send_ping(&self) -> Option<Value> {
         match &self.role {
             Role::Main => None,
@@ -174,13 +300,53 @@ impl Server {
         let payload = Value::Array(msg);
         Some(payload)
     }
+
+    pub fn touch_key(&self, key: &str) {
+        let (log_factor, decay_time) = self.lfu_params();
+        if let Some(item) = lock(&self.cache).get_mut(key) {
+            item.access.touch(log_factor, decay_time);
+        }
+    }
+
+    pub fn lfu_params(&self) -> (u32, u64) {
+        let config = self.config();
+        (config.lfu_log_factor, config.lfu_decay_time)
+    }
+
+    pub fn pack_limits(&self) -> PackLimits {
+        let config = self.config();
+        PackLimits {
+            hash_entries: config.hash_max_listpack_entries,
+            hash_value: config.hash_max_listpack_value,
+            list_size: config.list_max_listpack_size,
+            set_intset_entries: config.set_max_intset_entries,
+            set_entries: config.set_max_listpack_entries,
+            set_value: config.set_max_listpack_value,
+            zset_entries: config.zset_max_listpack_entries,
+            zset_value: config.zset_max_listpack_value,
+        }
+    }
+}
+
+fn spawn_tls_reloader(tls: Arc<TlsContext>) {
+    tokio::spawn(async move {
+        let mut interval_timer = interval(TLS_RELOAD_INTERVAL);
+        loop {
+            interval_timer.tick().await;
+            match tls.reload_if_changed() {
+                Ok(true) => log!("Reloaded TLS certificates"),
+                Ok(false) => {}
+                Err(e) => log!("Failed to reload TLS certificates: {}", e),
+            }
+        }
+    });
 }
 
-impl ToString for Role {
-    fn to_string(&self) -> String {
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::Main => String::from("master"),
-            Self::Slave { host: _, port: _ } => String::from("slave"),
+            Self::Main => write!(f, "master"),
+            Self::Slave { host: _, port: _ } => write!(f, "slave"),
         }
     }
 }
@@ -0,0 +1,200 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::utilities::lock;
+
+// Bucket i counts the calls that took less than 2^i microseconds.
+const LATENCY_BUCKETS: usize = 32;
+
+#[derive(Debug)]
+pub struct Stats {
+    started: Instant,
+    internal_errors: AtomicU64,
+    evicted_keys: AtomicU64,
+    expired_keys: AtomicU64,
+    dirty: AtomicU64,
+    last_save: AtomicI64,
+    last_save_failed: AtomicBool,
+    connected_clients: AtomicU64,
+    total_connections: AtomicU64,
+    commands: Mutex<BTreeMap<&'static str, CommandStats>>,
+    errors: Mutex<BTreeMap<String, u64>>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct CommandStats {
+    pub calls: u64,
+    pub usec: u64,
+    pub rejected_calls: u64,
+    pub failed_calls: u64,
+    latencies: [u64; LATENCY_BUCKETS],
+}
+
+impl CommandStats {
+    // Rounded up to the power of two bounding the histogram bucket.
+    pub fn latency_percentile(&self, percentile: f64) -> u64 {
+        let rank = (self.calls as f64 * percentile / 100.0).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        for (bucket, count) in self.latencies.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return 1 << bucket;
+            }
+        }
+        1 << (LATENCY_BUCKETS - 1)
+    }
+}
+
+impl Default for Stats {
+    fn default() -> Self {
+        Stats {
+            started: Instant::now(),
+            internal_errors: Default::default(),
+            evicted_keys: Default::default(),
+            expired_keys: Default::default(),
+            dirty: Default::default(),
+            last_save: Default::default(),
+            last_save_failed: Default::default(),
+            connected_clients: Default::default(),
+            total_connections: Default::default(),
+            commands: Default::default(),
+            errors: Default::default(),
+        }
+    }
+}
+
+impl Stats {
+    pub fn uptime(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    pub fn record_internal_error(&self) {
+        self.internal_errors.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn internal_errors(&self) -> u64 {
+        self.internal_errors.load(Ordering::Relaxed)
+    }
+
+    pub fn record_eviction(&self) {
+        self.evicted_keys.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn evicted_keys(&self) -> u64 {
+        self.evicted_keys.load(Ordering::Relaxed)
+    }
+
+    pub fn record_expired(&self, keys: usize) {
+        self.expired_keys.fetch_add(keys as u64, Ordering::Relaxed);
+    }
+
+    pub fn expired_keys(&self) -> u64 {
+        self.expired_keys.load(Ordering::Relaxed)
+    }
+
+    pub fn record_write(&self) {
+        self.dirty.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn dirty(&self) -> u64 {
+        self.dirty.load(Ordering::Relaxed)
+    }
+
+    // Writes made while the dump ran still count towards the next one.
+    pub fn record_save(&self, dirty: u64, at: i64) {
+        self.dirty.fetch_sub(dirty, Ordering::Relaxed);
+        self.last_save.store(at, Ordering::Relaxed);
+        self.last_save_failed.store(false, Ordering::Relaxed);
+    }
+
+    pub fn record_save_failure(&self) {
+        self.last_save_failed.store(true, Ordering::Relaxed);
+    }
+
+    pub fn last_save(&self) -> i64 {
+        self.last_save.load(Ordering::Relaxed)
+    }
+
+    pub fn last_save_failed(&self) -> bool {
+        self.last_save_failed.load(Ordering::Relaxed)
+    }
+
+    pub fn record_connection(&self) {
+        self.connected_clients.fetch_add(1, Ordering::Relaxed);
+        self.total_connections.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_disconnection(&self) {
+        self.connected_clients.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    pub fn connected_clients(&self) -> u64 {
+        self.connected_clients.load(Ordering::Relaxed)
+    }
+
+    pub fn total_connections(&self) -> u64 {
+        self.total_connections.load(Ordering::Relaxed)
+    }
+
+    pub fn record_call(&self, command: &'static str, elapsed: Duration, failed: bool) {
+        let usec = elapsed.as_micros() as u64;
+        let bucket = (u64::BITS - usec.leading_zeros()) as usize;
+        let mut commands = self.commands();
+        let stats = commands.entry(command).or_default();
+        stats.calls += 1;
+        stats.usec += usec;
+        stats.failed_calls += failed as u64;
+        stats.latencies[bucket.min(LATENCY_BUCKETS - 1)] += 1;
+    }
+
+    pub fn record_rejected_call(&self, command: &'static str) {
+        self.commands().entry(command).or_default().rejected_calls += 1;
+    }
+
+    pub fn command_stats(&self) -> Vec<(&'static str, CommandStats)> {
+        self.commands()
+            .iter()
+            .map(|(name, stats)| (*name, stats.clone()))
+            .collect()
+    }
+
+    pub fn total_commands(&self) -> u64 {
+        self.commands().values().map(|stats| stats.calls).sum()
+    }
+
+    pub fn record_error_reply(&self, message: &str) {
+        let prefix = message.split(' ').next().unwrap_or_default();
+        *self.errors().entry(prefix.to_string()).or_default() += 1;
+    }
+
+    pub fn error_stats(&self) -> Vec<(String, u64)> {
+        self.errors()
+            .iter()
+            .map(|(prefix, count)| (prefix.clone(), *count))
+            .collect()
+    }
+
+    pub fn total_error_replies(&self) -> u64 {
+        self.errors().values().sum()
+    }
+
+    // The pending writes and the gauges such as connected clients are kept.
+    pub fn reset(&self) {
+        self.internal_errors.store(0, Ordering::Relaxed);
+        self.evicted_keys.store(0, Ordering::Relaxed);
+        self.expired_keys.store(0, Ordering::Relaxed);
+        self.total_connections.store(0, Ordering::Relaxed);
+        self.commands().clear();
+        self.errors().clear();
+    }
+
+    fn commands(&self) -> MutexGuard<'_, BTreeMap<&'static str, CommandStats>> {
+        lock(&self.commands)
+    }
+
+    fn errors(&self) -> MutexGuard<'_, BTreeMap<String, u64>> {
+        lock(&self.errors)
+    }
+}
@@ -0,0 +1,282 @@
+use std::fs::{self, File};
+use std::io::{self, BufReader};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::SystemTime;
+
+use rustls::crypto::{ring, CryptoProvider};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName};
+use rustls::server::WebPkiClientVerifier;
+use rustls::{ClientConfig, RootCertStore, ServerConfig};
+use thiserror::Error;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpStream;
+use tokio_rustls::{client, TlsAcceptor, TlsConnector};
+
+use crate::models::args::Args;
+use crate::utilities::{lock, read_lock, write_lock};
+
+pub trait AsyncStream: AsyncRead + AsyncWrite + Unpin + Send {}
+
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> AsyncStream for T {}
+
+#[derive(Error, Debug)]
+pub enum TlsError {
+    #[error("Failed to read {0}: {1}")]
+    Io(PathBuf, io::Error),
+
+    #[error("No certificate found in {0}")]
+    NoCertificate(PathBuf),
+
+    #[error("No private key found in {0}")]
+    NoPrivateKey(PathBuf),
+
+    #[error("{0} must be set")]
+    Missing(&'static str),
+
+    #[error("Invalid TLS configuration: {0}")]
+    Config(String),
+}
+
+impl From<rustls::Error> for TlsError {
+    fn from(error: rustls::Error) -> Self {
+        TlsError::Config(error.to_string())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum AuthClients {
+    #[default]
+    Yes,
+    Optional,
+    No,
+}
+
+impl std::str::FromStr for AuthClients {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "yes" => Ok(AuthClients::Yes),
+            "optional" => Ok(AuthClients::Optional),
+            "no" => Ok(AuthClients::No),
+            _ => Err(format!("expected yes, optional or no, got '{}'", value)),
+        }
+    }
+}
+
+impl std::fmt::Display for AuthClients {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            AuthClients::Yes => "yes",
+            AuthClients::Optional => "optional",
+            AuthClients::No => "no",
+        })
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TlsConfig {
+    pub port: u16,
+    pub cert_file: Option<PathBuf>,
+    pub key_file: Option<PathBuf>,
+    pub ca_cert_file: Option<PathBuf>,
+    pub auth_clients: AuthClients,
+    pub replication: bool,
+}
+
+impl From<&Args> for TlsConfig {
+    fn from(args: &Args) -> Self {
+        Self {
+            port: args.tls_port,
+            cert_file: args.tls_cert_file.clone(),
+            key_file: args.tls_key_file.clone(),
+            ca_cert_file: args.tls_ca_cert_file.clone(),
+            auth_clients: args.tls_auth_clients,
+            replication: args.tls_replication,
+        }
+    }
+}
+
+impl TlsConfig {
+    pub fn enabled(&self) -> bool {
+        self.port != 0 || self.replication
+    }
+
+    fn files(&self) -> impl Iterator<Item = &PathBuf> {
+        [&self.cert_file, &self.key_file, &self.ca_cert_file]
+            .into_iter()
+            .flatten()
+    }
+}
+
+struct Configs {
+    server: Option<Arc<ServerConfig>>,
+    client: Option<Arc<ClientConfig>>,
+}
+
+pub struct TlsContext {
+    pub config: TlsConfig,
+    configs: RwLock<Configs>,
+    loaded: Mutex<Vec<Option<SystemTime>>>,
+}
+
+impl std::fmt::Debug for TlsContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TlsContext")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TlsContext {
+    pub fn new(config: TlsConfig) -> Result<Self, TlsError> {
+        let context = TlsContext {
+            loaded: Mutex::new(modification_times(&config)),
+            configs: RwLock::new(build_configs(&config)?),
+            config,
+        };
+        if context.config.port != 0 && context.acceptor().is_none() {
+            return Err(TlsError::Missing("tls-cert-file and tls-key-file"));
+        }
+        if context.config.replication && context.connector().is_none() {
+            return Err(TlsError::Missing("tls-ca-cert-file"));
+        }
+        Ok(context)
+    }
+
+    // On error the previous certificates stay in use.
+    pub fn reload(&self) -> Result<(), TlsError> {
+        let loaded = modification_times(&self.config);
+        let configs = build_configs(&self.config)?;
+        *write_lock(&self.configs) = configs;
+        *lock(&self.loaded) = loaded;
+        Ok(())
+    }
+
+    pub fn reload_if_changed(&self) -> Result<bool, TlsError> {
+        if *lock(&self.loaded) == modification_times(&self.config) {
+            return Ok(false);
+        }
+        self.reload().map(|_| true)
+    }
+
+    pub fn acceptor(&self) -> Option<TlsAcceptor> {
+        let configs = read_lock(&self.configs);
+        configs.server.clone().map(TlsAcceptor::from)
+    }
+
+    pub fn connector(&self) -> Option<TlsConnector> {
+        let configs = read_lock(&self.configs);
+        configs.client.clone().map(TlsConnector::from)
+    }
+
+    pub async fn connect(
+        &self,
+        host: &str,
+        stream: TcpStream,
+    ) -> io::Result<client::TlsStream<TcpStream>> {
+        let connector = self
+            .connector()
+            .ok_or_else(|| io::Error::other("tls-ca-cert-file must be set"))?;
+        let name = ServerName::try_from(host.to_string())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        connector.connect(name, stream).await
+    }
+}
+
+fn modification_times(config: &TlsConfig) -> Vec<Option<SystemTime>> {
+    config
+        .files()
+        .map(|file| fs::metadata(file).and_then(|m| m.modified()).ok())
+        .collect()
+}
+
+fn provider() -> Arc<CryptoProvider> {
+    Arc::new(ring::default_provider())
+}
+
+fn build_configs(config: &TlsConfig) -> Result<Configs, TlsError> {
+    let identity = match (&config.cert_file, &config.key_file) {
+        (Some(cert), Some(key)) => Some((load_certs(cert)?, load_key(key)?)),
+        (None, None) => None,
+        _ => return Err(TlsError::Missing("tls-cert-file and tls-key-file")),
+    };
+    let roots = match &config.ca_cert_file {
+        Some(ca) => Some(Arc::new(load_roots(ca)?)),
+        None => None,
+    };
+
+    let server = match &identity {
+        Some((certs, key)) => {
+            let builder = ServerConfig::builder_with_provider(provider())
+                .with_safe_default_protocol_versions()?;
+            let builder = match (config.auth_clients, &roots) {
+                (AuthClients::No, _) => builder.with_no_client_auth(),
+                (_, None) => return Err(TlsError::Missing("tls-ca-cert-file")),
+                (auth_clients, Some(roots)) => {
+                    let verifier =
+                        WebPkiClientVerifier::builder_with_provider(roots.clone(), provider());
+                    let verifier = if auth_clients == AuthClients::Optional {
+                        verifier.allow_unauthenticated()
+                    } else {
+                        verifier
+                    };
+                    let verifier = verifier
+                        .build()
+                        .map_err(|e| TlsError::Config(e.to_string()))?;
+                    builder.with_client_cert_verifier(verifier)
+                }
+            };
+            Some(Arc::new(
+                builder.with_single_cert(certs.clone(), key.clone_key())?,
+            ))
+        }
+        None => None,
+    };
+
+    let client = match &roots {
+        Some(roots) => {
+            let builder = ClientConfig::builder_with_provider(provider())
+                .with_safe_default_protocol_versions()?
+                .with_root_certificates(roots.clone());
+            Some(Arc::new(match identity {
+                Some((certs, key)) => builder.with_client_auth_cert(certs, key)?,
+                None => builder.with_no_client_auth(),
+            }))
+        }
+        None => None,
+    };
+
+    Ok(Configs { server, client })
+}
+
+fn open(path: &Path) -> Result<BufReader<File>, TlsError> {
+    File::open(path)
+        .map(BufReader::new)
+        .map_err(|e| TlsError::Io(path.to_path_buf(), e))
+}
+
+fn load_certs(path: &Path) -> Result<Vec<CertificateDer<'static>>, TlsError> {
+    let certs = rustls_pemfile::certs(&mut open(path)?)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| TlsError::Io(path.to_path_buf(), e))?;
+    if certs.is_empty() {
+        return Err(TlsError::NoCertificate(path.to_path_buf()));
+    }
+    Ok(certs)
+}
+
+fn load_key(path: &Path) -> Result<PrivateKeyDer<'static>, TlsError> {
+    rustls_pemfile::private_key(&mut open(path)?)
+        .map_err(|e| TlsError::Io(path.to_path_buf(), e))?
+        .ok_or_else(|| TlsError::NoPrivateKey(path.to_path_buf()))
+}
+
+fn load_roots(path: &Path) -> Result<RootCertStore, TlsError> {
+    let mut roots = RootCertStore::empty();
+    for cert in load_certs(path)? {
+        roots.add(cert)?;
+    }
+    Ok(roots)
+}
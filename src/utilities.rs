@@ -1,14 +1,19 @@
 use lazy_static::lazy_static;
 
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 use std::fmt::Arguments;
-use std::sync::{Arc, Mutex};
+use std::ops::Range;
+use std::sync::{Mutex, MutexGuard, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::time::SystemTime;
 
 use anyhow::Result;
-use bytes::BytesMut;
+
+use crate::error::{RedisError, RedisResult};
+use crate::glob::string_match;
 
 use crate::models::redis_item::RedisItem;
 use crate::models::value::Value;
+use crate::server::{Db, Server};
 #[derive(Debug, Clone, PartialEq)]
 pub enum ServerState {
     Initialising,
@@ -17,6 +22,20 @@ pub enum ServerState {
     AwaitingGetAck,
     StreamingCommands,
 }
+// Poison is ignored: a panicking command must not wedge the lock for every
+// other client.
+pub fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+pub fn read_lock<T>(lock: &RwLock<T>) -> RwLockReadGuard<'_, T> {
+    lock.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+pub fn write_lock<T>(lock: &RwLock<T>) -> RwLockWriteGuard<'_, T> {
+    lock.write().unwrap_or_else(PoisonError::into_inner)
+}
+
 pub fn log_message(file: &str, line: u32, args: Arguments) {
     println!("{}:{}: {:?}", file, line, args);
 }
@@ -29,42 +48,28 @@ macro_rules! log {
 }
 
 lazy_static! {
-    static ref NO_ARG_COMMANDS: HashSet<&'static str> = {
+    static ref NO_TOUCH_COMMANDS: HashSet<&'static str> = {
         let mut m = HashSet::new();
-        m.insert("PING");
-        m.insert("INFO");
-        m.insert("PSYNC");
-        m.insert("FLUSHALL");
+        m.insert("TYPE");
+        // Keeps the IDLETIME and FREQ given by the client.
+        m.insert("RESTORE");
         m
     };
 }
 
-lazy_static! {
-    static ref NO_KEY_COMMANDS: HashSet<&'static str> = {
-        let mut m = HashSet::new();
-        m.insert("UNLINK");
-        m.insert("DEL");
-        m.insert("KEYS");
-        m
-    };
+pub fn touches_key(command: &str) -> bool {
+    !NO_TOUCH_COMMANDS.contains(command)
 }
 
-pub fn extract_command(value: Value) -> Result<(String, String, Vec<Value>)> {
+pub fn extract_command(value: Value) -> Result<(String, Vec<Value>)> {
     match value {
         Value::Array(a) => {
-            let command = unpack_bulk_str(a.first().unwrap().clone()).unwrap();
             let mut iter = a.into_iter();
-            if NO_ARG_COMMANDS.contains(command.as_str()) {
-                return Ok((command, "".to_string(), vec![]));
-            }
-            if NO_KEY_COMMANDS.contains(command.as_str()) {
-                iter.next();
-                return Ok((command, "".to_string(), iter.collect()));
-            }
-            iter.next();
-            let key = unpack_bulk_str(iter.next().ok_or_else(|| anyhow::anyhow!("Missing key"))?)?;
-
-            Ok((command, key, iter.collect()))
+            let command = unpack_bulk_str(
+                iter.next()
+                    .ok_or_else(|| anyhow::anyhow!("Missing command"))?,
+            )?;
+            Ok((command, iter.collect()))
         }
         _ => Err(anyhow::anyhow!("Unexpected command format")),
     }
@@ -84,66 +89,95 @@ pub fn unpack_integer(value: Value) -> Result<i64> {
     }
 }
 
-pub fn parse_message(buffer: BytesMut) -> Result<(Value, usize)> {
-    log!("Parsing message: {:?}", buffer);
-    match buffer[0] as char {
-        '+' => parse_simple_string(buffer),
-        '*' => parse_array(buffer),
-        '$' => parse_bulk_string(buffer),
-        ':' => parse_integer(buffer),
-        _ => Err(anyhow::anyhow!("Unknown value type {:?}", buffer)),
+// Clients send every argument as a bulk string, so both forms are accepted.
+pub fn unpack_integer_arg(value: &Value) -> RedisResult<i64> {
+    match value {
+        Value::Integer(i) => Ok(*i),
+        Value::BulkString(s) => s.parse::<i64>().map_err(|_| RedisError::NotInteger),
+        _ => Err(RedisError::NotInteger),
     }
 }
 
-fn parse_simple_string(buffer: BytesMut) -> Result<(Value, usize)> {
-    if let Some((line, len)) = read_until_crlf(&buffer[1..]) {
-        let string = String::from_utf8(line.to_vec()).unwrap();
+// Arrays nested deeper than MAX_NESTING would overflow the parser's stack.
+const MAX_NESTING: usize = 32;
+const MAX_MULTIBULK_LEN: i64 = 1024 * 1024;
+const PROTO_MAX_BULK_LEN: i64 = 512 * 1024 * 1024;
 
-        return Ok((Value::SimpleString(string), len + 1));
-    }
+// `Ok(None)` means the buffer ends before the value does.
+pub fn parse_message(buffer: &[u8]) -> Result<Option<(Value, usize)>> {
+    parse_value(buffer, 0)
+}
 
-    return Err(anyhow::anyhow!("Invalid string {:?}", buffer));
+fn parse_value(buffer: &[u8], depth: usize) -> Result<Option<(Value, usize)>> {
+    let Some(&kind) = buffer.first() else {
+        return Ok(None);
+    };
+    match kind as char {
+        '+' => parse_simple_string(buffer),
+        '*' => parse_array(buffer, depth),
+        '$' => parse_bulk_string(buffer),
+        ':' => parse_integer(buffer),
+        _ => Err(anyhow::anyhow!("Unknown value type {:?}", kind as char)),
+    }
 }
 
-fn parse_array(buffer: BytesMut) -> Result<(Value, usize)> {
-    let (array_length, mut bytes_consumed) =
-        if let Some((line, len)) = read_until_crlf(&buffer[1..]) {
-            let array_length = parse_int(line)?;
+fn parse_simple_string(buffer: &[u8]) -> Result<Option<(Value, usize)>> {
+    let Some((line, len)) = read_until_crlf(&buffer[1..]) else {
+        return Ok(None);
+    };
+    let string = String::from_utf8(line.to_vec())?;
+    Ok(Some((Value::SimpleString(string), len + 1)))
+}
 
-            (array_length, len + 1)
-        } else {
-            return Err(anyhow::anyhow!("Invalid array format {:?}", buffer));
-        };
+fn parse_array(buffer: &[u8], depth: usize) -> Result<Option<(Value, usize)>> {
+    if depth >= MAX_NESTING {
+        return Err(anyhow::anyhow!("too many nested arrays"));
+    }
+    let Some((line, len)) = read_until_crlf(&buffer[1..]) else {
+        return Ok(None);
+    };
+    let array_length = parse_int(line)?;
+    if array_length > MAX_MULTIBULK_LEN {
+        return Err(anyhow::anyhow!("invalid multibulk length"));
+    }
+    let mut bytes_consumed = len + 1;
 
     let mut items = vec![];
     for _ in 0..array_length {
-        let (array_item, len) = parse_message(BytesMut::from(&buffer[bytes_consumed..]))?;
-
+        let Some((array_item, len)) = parse_value(&buffer[bytes_consumed..], depth + 1)? else {
+            return Ok(None);
+        };
         items.push(array_item);
         bytes_consumed += len
     }
 
-    return Ok((Value::Array(items), bytes_consumed));
+    Ok(Some((Value::Array(items), bytes_consumed)))
 }
 
-fn parse_bulk_string(buffer: BytesMut) -> Result<(Value, usize)> {
-    let (bulk_str_len, bytes_consumed) = if let Some((line, len)) = read_until_crlf(&buffer[1..]) {
-        let bulk_str_len = parse_int(line)?;
-
-        (bulk_str_len, len + 1)
-    } else {
-        return Err(anyhow::anyhow!("Invalid array format {:?}", buffer));
+fn parse_bulk_string(buffer: &[u8]) -> Result<Option<(Value, usize)>> {
+    let Some((line, len)) = read_until_crlf(&buffer[1..]) else {
+        return Ok(None);
+    };
+    let bytes_consumed = len + 1;
+    let bulk_str_len = match parse_int(line)? {
+        -1 => return Ok(Some((Value::NullBulkString, bytes_consumed))),
+        len if !(0..=PROTO_MAX_BULK_LEN).contains(&len) => {
+            return Err(anyhow::anyhow!("invalid bulk length"))
+        }
+        len => len as usize,
     };
 
-    let end_of_bulk_str = bytes_consumed + bulk_str_len as usize;
+    let end_of_bulk_str = bytes_consumed + bulk_str_len;
     let total_parsed = end_of_bulk_str + 2;
+    if buffer.len() < total_parsed {
+        return Ok(None);
+    }
 
-    Ok((
-        Value::BulkString(String::from_utf8(
-            buffer[bytes_consumed..end_of_bulk_str].to_vec(),
-        )?),
-        total_parsed,
-    ))
+    let value = match String::from_utf8(buffer[bytes_consumed..end_of_bulk_str].to_vec()) {
+        Ok(string) => Value::BulkString(string),
+        Err(e) => Value::Bytes(e.into_bytes()),
+    };
+    Ok(Some((value, total_parsed)))
 }
 
 fn read_until_crlf(buffer: &[u8]) -> Option<(&[u8], usize)> {
@@ -153,35 +187,43 @@ fn read_until_crlf(buffer: &[u8]) -> Option<(&[u8], usize)> {
         }
     }
 
-    return None;
+    None
 }
 
 fn parse_int(buffer: &[u8]) -> Result<i64> {
     Ok(String::from_utf8(buffer.to_vec())?.parse::<i64>()?)
 }
 
-fn parse_integer(buffer: BytesMut) -> Result<(Value, usize)> {
-    if let Some((line, len)) = read_until_crlf(&buffer[1..]) {
-        let integer = parse_int(line)?;
-        Ok((Value::Integer(integer), len + 1))
-    } else {
-        Err(anyhow::anyhow!("Invalid integer format {:?}", buffer))
-    }
+fn parse_integer(buffer: &[u8]) -> Result<Option<(Value, usize)>> {
+    let Some((line, len)) = read_until_crlf(&buffer[1..]) else {
+        return Ok(None);
+    };
+    let integer = parse_int(line)?;
+    Ok(Some((Value::Integer(integer), len + 1)))
+}
+
+pub fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_millis() as i64
 }
 
-pub fn should_set_expiry(item: &RedisItem, expiration: i64, option: String) -> bool {
+pub fn now_secs() -> i64 {
+    now_millis() / 1000
+}
+
+// A key without a TTL counts as never expiring for GT and LT.
+pub fn should_set_expiry(item: &RedisItem, at: i64, condition: Option<&str>) -> bool {
     log!("item {:?}", item);
-    log!("expiration {:?}", expiration);
-    log!("option {:?}", option);
-
-    match option.to_uppercase().as_str() {
-        "NX" => item.expiration.is_none(),
-        "XX" => {
-            log!("item.expiration {:?}", item.expiration);
-            item.expiration.is_some()
-        }
-        "GT" => item.expiration.map_or(true, |exp| expiration > exp),
-        "LT" => item.expiration.map_or(true, |exp| expiration < exp),
+    let current = item
+        .expiration
+        .map(|expiration| item.created_at + expiration);
+    match condition {
+        Some("NX") => current.is_none(),
+        Some("XX") => current.is_some(),
+        Some("GT") => current.is_some_and(|current| at > current),
+        Some("LT") => current.is_none_or(|current| at < current),
         _ => true,
     }
 }
@@ -209,17 +251,104 @@ pub fn extract_args(args: Vec<Value>) -> (String, Option<String>, Option<String>
     (key, arg1, arg2, additional_args)
 }
 
-pub fn lock_and_get_item<'a, F, R>(
-    cache: &Arc<Mutex<HashMap<String, RedisItem>>>,
-    key: &str,
-    callback: F,
-) -> Result<R, Value>
+pub fn lock_and_get_item<F, R>(cache: &Db, key: &str, callback: F) -> Result<R, Value>
 where
     F: FnOnce(&mut RedisItem) -> R,
 {
-    let mut cache = cache.lock().unwrap();
+    let mut cache = lock(cache);
     match cache.get_mut(key) {
         Some(item) => Ok(callback(item)),
-        None => Err(Value::Error("ERR no such key".to_string())),
+        None => Err(RedisError::NoSuchKey.into()),
     }
 }
+
+// The cursor is the number of positions left to visit, walking down from the
+// last. Removals only move elements to lower positions, so every element
+// present throughout the iteration is returned at least once.
+pub fn scan_page(len: usize, cursor: u64, count: usize) -> (u64, Range<usize>) {
+    let end = match cursor {
+        0 => len,
+        cursor => usize::try_from(cursor).unwrap_or(usize::MAX).min(len),
+    };
+    let start = end.saturating_sub(count.max(1));
+    (start as u64, start..end)
+}
+
+#[derive(Debug)]
+pub struct ScanOptions {
+    pub cursor: u64,
+    pub pattern: Option<String>,
+    pub count: usize,
+    pub type_filter: Option<String>,
+    pub no_values: bool,
+}
+
+impl ScanOptions {
+    pub fn parse(args: &[Value], command: &str, extra: &[&str]) -> RedisResult<ScanOptions> {
+        let cursor = match args.first() {
+            Some(Value::BulkString(cursor)) => cursor
+                .parse::<u64>()
+                .map_err(|_| RedisError::err("invalid cursor"))?,
+            _ => return Err(RedisError::wrong_arity(command)),
+        };
+
+        let mut options = ScanOptions {
+            cursor,
+            pattern: None,
+            count: 10,
+            type_filter: None,
+            no_values: false,
+        };
+
+        let mut iter = args[1..].iter();
+        while let Some(option) = iter.next() {
+            let name = match option {
+                Value::BulkString(s) => s.to_uppercase(),
+                _ => return Err(RedisError::Syntax),
+            };
+            match name.as_str() {
+                "MATCH" => match iter.next() {
+                    Some(Value::BulkString(glob)) => options.pattern = Some(glob.clone()),
+                    _ => return Err(RedisError::Syntax),
+                },
+                "COUNT" => match iter.next().map(unpack_integer_arg) {
+                    Some(Ok(n)) if n >= 1 => options.count = n as usize,
+                    Some(Ok(_)) | None => return Err(RedisError::Syntax),
+                    Some(Err(e)) => return Err(e),
+                },
+                "TYPE" if extra.contains(&"TYPE") => match iter.next() {
+                    Some(Value::BulkString(t)) => options.type_filter = Some(t.to_lowercase()),
+                    _ => return Err(RedisError::Syntax),
+                },
+                "NOVALUES" | "NOSCORES" if extra.contains(&name.as_str()) => {
+                    options.no_values = true
+                }
+                _ => return Err(RedisError::Syntax),
+            }
+        }
+        Ok(options)
+    }
+
+    // A packed collection is returned whole, as its elements don't keep their
+    // positions as it changes.
+    pub fn count_for(&self, server: &Server, key: &str) -> usize {
+        let cache = lock(&server.cache);
+        match cache.get(key).map(|item| &item.value) {
+            Some(Value::Packed(_)) => usize::MAX,
+            _ => self.count,
+        }
+    }
+
+    pub fn matches(&self, element: &str) -> bool {
+        self.pattern
+            .as_ref()
+            .is_none_or(|pattern| string_match(pattern, element, false))
+    }
+}
+
+pub fn scan_reply(cursor: u64, elements: Vec<Value>) -> Value {
+    Value::Array(vec![
+        Value::BulkString(cursor.to_string()),
+        Value::Array(elements),
+    ])
+}
@@ -0,0 +1,211 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{acl_handler, auth_handler};
+    use redis_starter_rust::models::value::Value;
+    use redis_starter_rust::server::Server;
+
+    use crate::setup::setup_server;
+
+    fn args(args: &[&str]) -> Vec<Value> {
+        args.iter()
+            .map(|arg| Value::BulkString(arg.to_string()))
+            .collect()
+    }
+
+    fn acl(server: &mut Server, command_args: &[&str]) -> Option<Value> {
+        acl_handler(server, String::new(), args(command_args))
+    }
+
+    fn ok() -> Option<Value> {
+        Some(Value::SimpleString("OK".to_string()))
+    }
+
+    fn bulk(s: &str) -> Value {
+        Value::BulkString(s.to_string())
+    }
+
+    #[test]
+    fn test_acl_setuser_getuser_deluser() {
+        let mut server = setup_server();
+        assert_eq!(
+            acl(
+                &mut server,
+                &[
+                    "SETUSER",
+                    "analytics",
+                    "on",
+                    "nopass",
+                    "%R~report:*",
+                    "+@read"
+                ]
+            ),
+            ok()
+        );
+        assert_eq!(
+            acl(&mut server, &["GETUSER", "analytics"]),
+            Some(Value::Array(vec![
+                bulk("flags"),
+                Value::Array(vec![bulk("on"), bulk("nopass")]),
+                bulk("passwords"),
+                Value::Array(vec![]),
+                bulk("commands"),
+                bulk("-@all +@read"),
+                bulk("keys"),
+                bulk("%R~report:*"),
+                bulk("channels"),
+                bulk(""),
+                bulk("selectors"),
+                Value::Array(vec![]),
+            ]))
+        );
+        assert_eq!(
+            acl(&mut server, &["USERS"]),
+            Some(Value::Array(vec![bulk("analytics"), bulk("default")]))
+        );
+        assert_eq!(
+            acl(&mut server, &["LIST"]),
+            Some(Value::Array(vec![
+                bulk("user analytics on nopass %R~report:* resetchannels -@all +@read"),
+                bulk("user default on nopass ~* &* +@all"),
+            ]))
+        );
+
+        assert_eq!(
+            acl(&mut server, &["DELUSER", "analytics", "ghost"]),
+            Some(Value::Integer(1))
+        );
+        assert_eq!(
+            acl(&mut server, &["GETUSER", "analytics"]),
+            Some(Value::NullBulkString)
+        );
+        assert_eq!(
+            acl(&mut server, &["DELUSER", "default"]),
+            Some(Value::Error(
+                "ERR The 'default' user cannot be removed".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_acl_whoami() {
+        let mut server = setup_server();
+        assert_eq!(acl(&mut server, &["WHOAMI"]), Some(bulk("default")));
+
+        acl(&mut server, &["SETUSER", "alice", "on", ">pw"]);
+        auth_handler(&mut server, String::new(), args(&["alice", "pw"]));
+        assert_eq!(acl(&mut server, &["WHOAMI"]), Some(bulk("alice")));
+    }
+
+    #[test]
+    fn test_acl_cat() {
+        let mut server = setup_server();
+        let categories = match acl(&mut server, &["CAT"]) {
+            Some(Value::Array(categories)) => categories,
+            other => panic!("unexpected reply: {:?}", other),
+        };
+        assert!(categories.contains(&bulk("hash")));
+
+        let commands = match acl(&mut server, &["CAT", "Hash"]) {
+            Some(Value::Array(commands)) => commands,
+            other => panic!("unexpected reply: {:?}", other),
+        };
+        assert!(commands.contains(&bulk("hget")));
+        assert!(!commands.contains(&bulk("get")));
+
+        assert_eq!(
+            acl(&mut server, &["CAT", "nope"]),
+            Some(Value::Error("ERR Unknown category 'nope'".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_acl_dryrun() {
+        let mut server = setup_server();
+        acl(
+            &mut server,
+            &["SETUSER", "analytics", "on", "~report:*", "+get"],
+        );
+
+        assert_eq!(
+            acl(&mut server, &["DRYRUN", "analytics", "get", "report:1"]),
+            ok()
+        );
+        assert_eq!(
+            acl(
+                &mut server,
+                &["DRYRUN", "analytics", "set", "report:1", "v"]
+            ),
+            Some(bulk(
+                "This user has no permissions to run the 'set' command"
+            ))
+        );
+        assert_eq!(
+            acl(&mut server, &["DRYRUN", "analytics", "get", "other"]),
+            Some(bulk(
+                "This user has no permissions to access the 'other' key"
+            ))
+        );
+        assert_eq!(
+            acl(&mut server, &["DRYRUN", "ghost", "get", "k"]),
+            Some(Value::Error("ERR User 'ghost' not found".to_string()))
+        );
+        assert_eq!(
+            acl(&mut server, &["DRYRUN", "analytics", "get"]),
+            Some(Value::Error(
+                "ERR wrong number of arguments for 'get' command".to_string()
+            ))
+        );
+
+        // DRYRUN doesn't log.
+        assert_eq!(acl(&mut server, &["LOG"]), Some(Value::Array(vec![])));
+    }
+
+    #[test]
+    fn test_acl_log() {
+        let mut server = setup_server();
+        for _ in 0..2 {
+            auth_handler(&mut server, String::new(), args(&["ghost", "pw"]));
+        }
+
+        let entries = match acl(&mut server, &["LOG", "1"]) {
+            Some(Value::Array(entries)) => entries,
+            other => panic!("unexpected reply: {:?}", other),
+        };
+        assert_eq!(entries.len(), 1);
+        let entry = match &entries[0] {
+            Value::Array(entry) => entry,
+            other => panic!("unexpected entry: {:?}", other),
+        };
+        assert_eq!(
+            &entry[..4],
+            &[
+                bulk("count"),
+                Value::Integer(2),
+                bulk("reason"),
+                bulk("auth")
+            ]
+        );
+        assert_eq!(&entry[8..10], &[bulk("username"), bulk("ghost")]);
+
+        assert_eq!(acl(&mut server, &["LOG", "RESET"]), ok());
+        assert_eq!(acl(&mut server, &["LOG"]), Some(Value::Array(vec![])));
+        assert_eq!(
+            acl(&mut server, &["LOG", "-1"]),
+            Some(Value::Error(
+                "ERR value is not an integer or out of range".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_acl_unknown_subcommand() {
+        let mut server = setup_server();
+        assert_eq!(
+            acl(&mut server, &["WHOAMI", "extra"]),
+            Some(Value::Error(
+                "ERR unknown subcommand or wrong number of arguments for 'WHOAMI'. Try ACL HELP."
+                    .to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,331 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::acl::{hash_password, Acl, Denial, DEFAULT_USER};
+    use redis_starter_rust::commands::COMMAND_TABLE;
+    use redis_starter_rust::error::RedisError;
+    use redis_starter_rust::models::value::Value;
+
+    fn rules(rules: &[&str]) -> Vec<String> {
+        rules.iter().map(|rule| rule.to_string()).collect()
+    }
+
+    fn args(args: &[&str]) -> Vec<Value> {
+        args.iter()
+            .map(|arg| Value::BulkString(arg.to_string()))
+            .collect()
+    }
+
+    fn check(acl: &Acl, user: &str, command: &str, command_args: &[&str]) -> Result<(), Denial> {
+        let command = COMMAND_TABLE.get(command).unwrap();
+        acl.user(user).unwrap().check(command, &args(command_args))
+    }
+
+    #[test]
+    fn test_default_user() {
+        let acl = Acl::default();
+        assert!(!acl.requires_auth());
+        assert_eq!(
+            acl.user(DEFAULT_USER).unwrap().describe(),
+            "user default on nopass ~* &* +@all"
+        );
+
+        let acl = Acl::new(Some("secret"), None);
+        assert!(acl.requires_auth());
+        assert_eq!(
+            acl.user(DEFAULT_USER).unwrap().describe(),
+            format!("user default on #{} ~* &* +@all", hash_password("secret"))
+        );
+    }
+
+    #[test]
+    fn test_read_only_key_pattern() {
+        let mut acl = Acl::default();
+        acl.set_user("analytics", &rules(&["on", ">pw", "%R~report:*", "+@read"]))
+            .unwrap();
+
+        assert_eq!(check(&acl, "analytics", "GET", &["report:1"]), Ok(()));
+        assert_eq!(
+            check(&acl, "analytics", "GET", &["users:1"]),
+            Err(Denial::Key("users:1".to_string()))
+        );
+        assert_eq!(
+            check(&acl, "analytics", "SET", &["report:1", "v"]),
+            Err(Denial::Command("set"))
+        );
+        assert_eq!(
+            check(&acl, "analytics", "EXISTS", &["report:1", "secret"]),
+            Err(Denial::Key("secret".to_string()))
+        );
+        // AUTH, HELLO and QUIT are always allowed.
+        assert_eq!(check(&acl, "analytics", "AUTH", &["pw"]), Ok(()));
+    }
+
+    #[test]
+    fn test_write_only_key_pattern() {
+        let mut acl = Acl::default();
+        acl.set_user(
+            "writer",
+            &rules(&["on", "%W~queue:*", "+@all", "-@dangerous"]),
+        )
+        .unwrap();
+
+        assert_eq!(check(&acl, "writer", "SET", &["queue:1", "v"]), Ok(()));
+        assert_eq!(
+            check(&acl, "writer", "GET", &["queue:1"]),
+            Err(Denial::Key("queue:1".to_string()))
+        );
+        assert_eq!(
+            check(&acl, "writer", "FLUSHALL", &[]),
+            Err(Denial::Command("flushall"))
+        );
+        assert_eq!(
+            acl.user("writer").unwrap().describe(),
+            "user writer on %W~queue:* resetchannels +@all -@dangerous"
+        );
+    }
+
+    #[test]
+    fn test_commands_returning_values_need_read_access() {
+        let mut acl = Acl::default();
+        acl.set_user(
+            "writer",
+            &rules(&["on", "%W~queue:*", "%R~source:*", "~both:*", "+@all"]),
+        )
+        .unwrap();
+
+        assert_eq!(check(&acl, "writer", "LPUSH", &["queue:1", "v"]), Ok(()));
+        assert_eq!(check(&acl, "writer", "DEL", &["queue:1"]), Ok(()));
+        for (command, command_args) in [
+            ("LPOP", &["queue:1"][..]),
+            ("INCR", &["queue:1"]),
+            ("HINCRBY", &["queue:1", "f", "1"]),
+            ("HGETEX", &["queue:1", "FIELDS", "1", "f"]),
+            ("LMOVE", &["queue:1", "both:1", "LEFT", "LEFT"]),
+            ("RENAME", &["queue:1", "both:1"]),
+        ] {
+            assert_eq!(
+                check(&acl, "writer", command, command_args),
+                Err(Denial::Key("queue:1".to_string())),
+                "{}",
+                command
+            );
+        }
+        assert_eq!(
+            check(&acl, "writer", "COPY", &["queue:1", "both:1"]),
+            Err(Denial::Key("queue:1".to_string()))
+        );
+
+        // Sources are only read and destinations only written.
+        assert_eq!(
+            check(&acl, "writer", "COPY", &["source:1", "queue:1"]),
+            Ok(())
+        );
+        assert_eq!(
+            check(&acl, "writer", "SORT", &["source:1", "STORE", "queue:1"]),
+            Ok(())
+        );
+        assert_eq!(
+            check(&acl, "writer", "SORT", &["queue:1", "STORE", "queue:2"]),
+            Err(Denial::Key("queue:1".to_string()))
+        );
+        assert_eq!(
+            check(
+                &acl,
+                "writer",
+                "LMOVE",
+                &["both:1", "queue:1", "LEFT", "LEFT"]
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_disabled_user_is_denied() {
+        let mut acl = Acl::default();
+        acl.set_user("worker", &rules(&["on", "nopass", "~*", "+@all"]))
+            .unwrap();
+        assert_eq!(check(&acl, "worker", "GET", &["k"]), Ok(()));
+
+        acl.set_user("worker", &rules(&["off"])).unwrap();
+        assert_eq!(
+            check(&acl, "worker", "GET", &["k"]),
+            Err(Denial::Command("get"))
+        );
+        assert_eq!(check(&acl, "worker", "AUTH", &["other", "pw"]), Ok(()));
+    }
+
+    #[test]
+    fn test_command_rules() {
+        let mut acl = Acl::default();
+        acl.set_user(
+            "user",
+            &rules(&["on", "allkeys", "+@hash", "-hdel", "+get"]),
+        )
+        .unwrap();
+
+        assert_eq!(check(&acl, "user", "HGET", &["h", "f"]), Ok(()));
+        assert_eq!(check(&acl, "user", "GET", &["k"]), Ok(()));
+        assert_eq!(
+            check(&acl, "user", "HDEL", &["h", "f"]),
+            Err(Denial::Command("hdel"))
+        );
+        assert_eq!(
+            acl.user("user").unwrap().command_rules(),
+            "-@all +@hash -hdel +get"
+        );
+
+        acl.set_user("user", &rules(&["nocommands"])).unwrap();
+        assert_eq!(acl.user("user").unwrap().command_rules(), "-@all");
+        assert_eq!(
+            check(&acl, "user", "GET", &["k"]),
+            Err(Denial::Command("get"))
+        );
+    }
+
+    #[test]
+    fn test_set_user_errors_are_atomic() {
+        let mut acl = Acl::default();
+        let cases = [
+            ("+nosuchcommand", "Unknown command or category name in ACL"),
+            (
+                "+@nosuchcategory",
+                "Unknown command or category name in ACL",
+            ),
+            ("%X~key", "Syntax error"),
+            ("bogus", "Syntax error"),
+            (
+                "#abc",
+                "The password hash must be exactly 64 characters and contain only lowercase \
+                 hexadecimal characters",
+            ),
+            (
+                "<never-set",
+                "The password you are trying to remove from the user does not exist",
+            ),
+        ];
+        for (rule, reason) in cases {
+            assert_eq!(
+                acl.set_user("user", &rules(&["on", rule])),
+                Err(RedisError::err(format!(
+                    "Error in ACL SETUSER modifier '{}': {}",
+                    rule, reason
+                )))
+            );
+        }
+        assert!(acl.user("user").is_none());
+    }
+
+    #[test]
+    fn test_passwords() {
+        let mut acl = Acl::default();
+        let hash = hash_password("second");
+        acl.set_user("user", &rules(&["on", ">first", &format!("#{}", hash)]))
+            .unwrap();
+
+        assert_eq!(acl.authenticate("user", "first"), Ok(()));
+        assert_eq!(acl.authenticate("user", "second"), Ok(()));
+        assert_eq!(
+            acl.authenticate("user", "third"),
+            Err(RedisError::WrongPass)
+        );
+        assert_eq!(
+            acl.authenticate("nobody", "first"),
+            Err(RedisError::WrongPass)
+        );
+
+        acl.set_user("user", &rules(&["<first"])).unwrap();
+        assert_eq!(
+            acl.authenticate("user", "first"),
+            Err(RedisError::WrongPass)
+        );
+
+        acl.set_user("user", &rules(&["off"])).unwrap();
+        assert_eq!(
+            acl.authenticate("user", "second"),
+            Err(RedisError::WrongPass)
+        );
+
+        acl.set_user("user", &rules(&["on", "reset"])).unwrap();
+        assert_eq!(
+            acl.user("user").unwrap().describe(),
+            "user user off resetchannels -@all"
+        );
+    }
+
+    #[test]
+    fn test_log_groups_similar_denials() {
+        let mut acl = Acl::default();
+        acl.set_user("user", &rules(&["on", "nopass", "+get"]))
+            .unwrap();
+        let get = COMMAND_TABLE.get("GET").unwrap();
+        let set = COMMAND_TABLE.get("SET").unwrap();
+
+        for _ in 0..2 {
+            assert_eq!(
+                acl.check("user", set, &args(&["k", "v"])),
+                Err(RedisError::NoPerm(
+                    "User user has no permissions to run the 'set' command".to_string()
+                ))
+            );
+        }
+        assert_eq!(
+            acl.check("user", get, &args(&["k"])),
+            Err(RedisError::NoPerm(
+                "No permissions to access a key".to_string()
+            ))
+        );
+        let _ = acl.authenticate("user", "anything");
+        let _ = acl.authenticate("ghost", "anything");
+
+        let log: Vec<_> = acl.log().collect();
+        assert_eq!(log.len(), 3);
+        assert_eq!((log[0].reason, log[0].object.as_str()), ("auth", "AUTH"));
+        assert_eq!(log[0].username, "ghost");
+        assert_eq!((log[1].reason, log[1].object.as_str()), ("key", "k"));
+        assert_eq!((log[2].reason, log[2].count), ("command", 2));
+
+        acl.reset_log();
+        assert_eq!(acl.log().count(), 0);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path = std::env::temp_dir().join(format!("acl-test-{}.acl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut acl = Acl::new(None, Some(path.clone()));
+        acl.set_user("analytics", &rules(&["on", ">pw", "%R~report:*", "+@read"]))
+            .unwrap();
+        acl.save().unwrap();
+
+        let mut loaded = Acl::new(None, Some(path.clone()));
+        assert_eq!(
+            loaded.user("analytics").unwrap().describe(),
+            acl.user("analytics").unwrap().describe()
+        );
+        assert_eq!(loaded.authenticate("analytics", "pw"), Ok(()));
+
+        // A bad line leaves the loaded users untouched.
+        std::fs::write(&path, "user ok on\nuser bad +nosuchcommand\n").unwrap();
+        let error = loaded.load().unwrap_err().to_string();
+        assert!(error.contains(":2: Unknown command or category name in ACL"));
+        assert!(loaded.user("analytics").is_some());
+
+        std::fs::write(&path, "user ok on nopass\n").unwrap();
+        loaded.load().unwrap();
+        assert!(loaded.user("analytics").is_none());
+        assert!(loaded.user("ok").is_some());
+        assert!(loaded.user(DEFAULT_USER).is_some());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_save_without_file() {
+        let acl = Acl::default();
+        assert!(acl
+            .save()
+            .unwrap_err()
+            .to_string()
+            .starts_with("ERR This Redis instance is not configured to use an ACL file."));
+    }
+}
@@ -0,0 +1,2 @@
+pub mod acl_handler_test;
+pub mod acl_test;
@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{
+        flushall_handler, flushdb_handler, select_handler, set_handler,
+    };
+    use redis_starter_rust::models::value::Value;
+
+    use crate::setup::setup_server;
+
+    fn set_in(server: &mut redis_starter_rust::server::Server, db: &str) {
+        select_handler(
+            server,
+            String::new(),
+            vec![Value::BulkString(db.to_string())],
+        );
+        set_handler(
+            server,
+            "key".to_string(),
+            vec![Value::BulkString("v".to_string())],
+        );
+    }
+
+    #[test]
+    fn test_flushdb_handler_only_clears_selected_db() {
+        let mut server = setup_server();
+        set_in(&mut server, "0");
+        set_in(&mut server, "1");
+
+        let result = flushdb_handler(&mut server, "".to_string(), vec![]);
+        assert_eq!(result, Some(Value::SimpleString("OK".to_string())));
+        assert!(server.databases[1].lock().unwrap().is_empty());
+        assert!(server.databases[0].lock().unwrap().contains_key("key"));
+    }
+
+    #[test]
+    fn test_flushall_handler_clears_every_db() {
+        let mut server = setup_server();
+        set_in(&mut server, "0");
+        set_in(&mut server, "5");
+
+        flushall_handler(&mut server, "".to_string(), vec![]);
+        assert!(server
+            .databases
+            .iter()
+            .all(|db| db.lock().unwrap().is_empty()));
+    }
+
+    #[test]
+    fn test_flushdb_handler_async() {
+        let mut server = setup_server();
+        set_in(&mut server, "0");
+
+        let result = flushdb_handler(
+            &mut server,
+            "".to_string(),
+            vec![Value::BulkString("async".to_string())],
+        );
+        assert_eq!(result, Some(Value::SimpleString("OK".to_string())));
+        assert!(server.cache.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_flush_handlers_reject_unknown_modes() {
+        let mut server = setup_server();
+        let args = vec![Value::BulkString("LATER".to_string())];
+
+        assert_eq!(
+            flushdb_handler(&mut server, "".to_string(), args.clone()),
+            Some(Value::Error("ERR syntax error".to_string()))
+        );
+        assert_eq!(
+            flushall_handler(&mut server, "".to_string(), args),
+            Some(Value::Error("ERR syntax error".to_string()))
+        );
+    }
+}
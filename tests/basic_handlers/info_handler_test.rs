@@ -0,0 +1,144 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::commands::dispatch;
+    use redis_starter_rust::handlers::{info_handler, select_handler, set_handler};
+    use redis_starter_rust::models::value::Value;
+    use redis_starter_rust::server::Server;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    fn info(server: &mut Server, sections: &[&str]) -> String {
+        match info_handler(server, String::new(), bulk(sections)) {
+            Some(Value::BulkString(info)) => info,
+            other => panic!("unexpected reply: {:?}", other),
+        }
+    }
+
+    fn headers(info: &str) -> Vec<&str> {
+        info.split("\r\n")
+            .filter(|line| line.starts_with("# "))
+            .collect()
+    }
+
+    #[test]
+    fn test_info_handler_keyspace() {
+        let mut server = setup_server();
+        for key in ["a", "b"] {
+            set_handler(&mut server, key.to_string(), bulk(&["v"]));
+        }
+        select_handler(&mut server, String::new(), bulk(&["2"]));
+        set_handler(&mut server, "c".to_string(), bulk(&["v"]));
+
+        let info = info(&mut server, &[]);
+        assert!(info.contains("# Keyspace\r\n"));
+        assert!(info.contains("db0:keys=2,expires=0,avg_ttl=0\r\n"));
+        assert!(info.contains("db2:keys=1,expires=0,avg_ttl=0\r\n"));
+        assert!(!info.contains("db1:"));
+    }
+
+    #[test]
+    fn test_info_handler_memory() {
+        let mut server = setup_server();
+        let info = info(&mut server, &[]);
+        assert!(info.contains("# Memory\r\nused_memory:"));
+        assert!(info.contains("\r\nused_memory_peak:"));
+        assert!(info.contains("\r\nmaxmemory:0\r\nmaxmemory_human:0B\r\n"));
+        assert!(info.contains("\r\nmaxmemory_policy:noeviction\r\n"));
+        assert!(info.contains("\r\nevicted_keys:0\r\n"));
+    }
+
+    #[test]
+    fn test_info_handler_sections() {
+        let mut server = setup_server();
+        let default = info(&mut server, &[]);
+        assert_eq!(
+            headers(&default),
+            [
+                "# Server",
+                "# Clients",
+                "# Memory",
+                "# Persistence",
+                "# Stats",
+                "# Replication",
+                "# CPU",
+                "# Modules",
+                "# Errorstats",
+                "# Keyspace"
+            ]
+        );
+        // Sections are separated by a blank line, and every line is a
+        // header or a field.
+        assert!(default.contains("\r\n\r\n# Clients\r\n"));
+        assert!(default.ends_with("\r\n"));
+        for line in default.split("\r\n").filter(|line| !line.is_empty()) {
+            assert!(line.starts_with("# ") || line.contains(':'), "{}", line);
+        }
+
+        let all = info(&mut server, &["everything"]);
+        assert_eq!(headers(&all).len(), 12);
+        assert_eq!(headers(&info(&mut server, &["ALL"])), headers(&all));
+        assert_eq!(headers(&info(&mut server, &["default"])), headers(&default));
+
+        let some = info(&mut server, &["keyspace", "CPU", "nosuchsection", "cpu"]);
+        assert_eq!(headers(&some), ["# CPU", "# Keyspace"]);
+        assert!(some.contains("\r\nused_cpu_user:"));
+        assert_eq!(info(&mut server, &["nosuchsection"]), "");
+    }
+
+    #[test]
+    fn test_info_handler_server_and_replication() {
+        let mut server = setup_server();
+        let info = info(&mut server, &["server", "replication"]);
+        assert!(info.starts_with("# Server\r\nredis_version:"));
+        assert!(info.contains("\r\ntcp_port:6379\r\n"));
+        assert!(info.contains("\r\nuptime_in_seconds:"));
+        assert!(info.contains("# Replication\r\nrole:master\r\nmaster_replid:"));
+        assert!(info.contains("\r\nmaster_repl_offset:0\r\n"));
+        assert!(!info.contains("nmaster"));
+    }
+
+    #[test]
+    fn test_info_handler_command_and_error_stats() {
+        let mut server = setup_server();
+        dispatch(&mut server, "SET", bulk(&["key", "value"]));
+        dispatch(&mut server, "get", bulk(&["key"]));
+        dispatch(&mut server, "GET", bulk(&["key"]));
+        dispatch(&mut server, "GET", bulk(&[]));
+        dispatch(&mut server, "HGET", bulk(&["key", "field"]));
+        dispatch(&mut server, "NOSUCHCOMMAND", bulk(&[]));
+
+        let info = info(
+            &mut server,
+            &["commandstats", "errorstats", "stats", "latencystats"],
+        );
+        assert!(info.contains("\r\ncmdstat_set:calls=1,usec="));
+        assert!(info.contains(",rejected_calls=0,failed_calls=0\r\n"));
+        let get = info
+            .split("\r\n")
+            .find(|line| line.starts_with("cmdstat_get:"))
+            .unwrap();
+        assert!(get.starts_with("cmdstat_get:calls=2,"));
+        assert!(get.ends_with(",rejected_calls=1,failed_calls=0"));
+        assert!(info.contains(",rejected_calls=0,failed_calls=1\r\n"));
+        assert!(info
+            .contains("# Errorstats\r\nerrorstat_ERR:count=2\r\nerrorstat_WRONGTYPE:count=1\r\n"));
+        assert!(info.contains("\r\ntotal_commands_processed:4\r\n"));
+        assert!(info.contains("\r\ntotal_error_replies:3\r\n"));
+        assert!(info.contains("\r\nlatency_percentiles_usec_get:p50="));
+        assert!(info.contains(",p99=") && info.contains(",p99.9="));
+
+        dispatch(&mut server, "CONFIG", bulk(&["RESETSTAT"]));
+        let info = self::info(&mut server, &["commandstats", "errorstats"]);
+        // Only the RESETSTAT call itself is left.
+        assert!(info.starts_with("# Commandstats\r\ncmdstat_config:calls=1,"));
+        assert!(!info.contains("cmdstat_get"));
+        assert!(info.ends_with("\r\n\r\n# Errorstats\r\n"));
+    }
+}
@@ -0,0 +1,4 @@
+pub mod flushdb_handler_test;
+pub mod info_handler_test;
+pub mod select_handler_test;
+pub mod swapdb_handler_test;
@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{get_handler, select_handler, set_handler};
+    use redis_starter_rust::models::value::Value;
+
+    use crate::setup::setup_server;
+
+    #[test]
+    fn test_select_handler_isolates_databases() {
+        let mut server = setup_server();
+        set_handler(
+            &mut server,
+            "key".to_string(),
+            vec![Value::BulkString("db0".to_string())],
+        );
+
+        let result = select_handler(
+            &mut server,
+            String::new(),
+            vec![Value::BulkString("3".to_string())],
+        );
+        assert_eq!(result, Some(Value::SimpleString("OK".to_string())));
+        assert_eq!(server.db_index, 3);
+        assert_eq!(
+            get_handler(&mut server, "key".to_string(), vec![]),
+            Some(Value::NullBulkString)
+        );
+
+        select_handler(
+            &mut server,
+            String::new(),
+            vec![Value::BulkString("0".to_string())],
+        );
+        assert_eq!(
+            get_handler(&mut server, "key".to_string(), vec![]),
+            Some(Value::BulkString("db0".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_select_handler_out_of_range() {
+        let mut server = setup_server();
+
+        for index in ["16", "-1"] {
+            let result = select_handler(
+                &mut server,
+                String::new(),
+                vec![Value::BulkString(index.to_string())],
+            );
+            assert_eq!(
+                result,
+                Some(Value::Error("ERR DB index is out of range".to_string()))
+            );
+        }
+        assert_eq!(server.db_index, 0);
+    }
+
+    #[test]
+    fn test_select_handler_not_an_integer() {
+        let mut server = setup_server();
+
+        let result = select_handler(
+            &mut server,
+            String::new(),
+            vec![Value::BulkString("one".to_string())],
+        );
+        assert_eq!(
+            result,
+            Some(Value::Error(
+                "ERR value is not an integer or out of range".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{get_handler, select_handler, set_handler, swapdb_handler};
+    use redis_starter_rust::models::value::Value;
+
+    use crate::setup::setup_server;
+
+    fn indexes(first: &str, second: &str) -> Vec<Value> {
+        vec![
+            Value::BulkString(first.to_string()),
+            Value::BulkString(second.to_string()),
+        ]
+    }
+
+    #[test]
+    fn test_swapdb_handler() {
+        let mut server = setup_server();
+        set_handler(
+            &mut server,
+            "key".to_string(),
+            vec![Value::BulkString("db0".to_string())],
+        );
+
+        let result = swapdb_handler(&mut server, String::new(), indexes("0", "1"));
+        assert_eq!(result, Some(Value::SimpleString("OK".to_string())));
+
+        // The connection stays on db 0, which now holds db 1's (empty) data.
+        assert_eq!(
+            get_handler(&mut server, "key".to_string(), vec![]),
+            Some(Value::NullBulkString)
+        );
+        select_handler(
+            &mut server,
+            String::new(),
+            vec![Value::BulkString("1".to_string())],
+        );
+        assert_eq!(
+            get_handler(&mut server, "key".to_string(), vec![]),
+            Some(Value::BulkString("db0".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_swapdb_handler_same_index() {
+        let mut server = setup_server();
+        set_handler(
+            &mut server,
+            "key".to_string(),
+            vec![Value::BulkString("v".to_string())],
+        );
+
+        let result = swapdb_handler(&mut server, String::new(), indexes("0", "0"));
+        assert_eq!(result, Some(Value::SimpleString("OK".to_string())));
+        assert!(server.cache.lock().unwrap().contains_key("key"));
+    }
+
+    #[test]
+    fn test_swapdb_handler_invalid_indexes() {
+        let mut server = setup_server();
+
+        assert_eq!(
+            swapdb_handler(&mut server, String::new(), indexes("a", "1")),
+            Some(Value::Error("ERR invalid first DB index".to_string()))
+        );
+        assert_eq!(
+            swapdb_handler(&mut server, String::new(), indexes("0", "b")),
+            Some(Value::Error("ERR invalid second DB index".to_string()))
+        );
+        assert_eq!(
+            swapdb_handler(&mut server, String::new(), indexes("0", "16")),
+            Some(Value::Error("ERR DB index is out of range".to_string()))
+        );
+    }
+}
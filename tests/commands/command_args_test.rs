@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::command_args::{parse_args, ArgParser, Exclusive, FromArgs};
+    use redis_starter_rust::error::{RedisError, RedisResult};
+    use redis_starter_rust::models::value::Value;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|value| Value::BulkString(value.to_string()))
+            .collect()
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct ZaddLike {
+        condition: Option<String>,
+        ch: bool,
+        members: Vec<(f64, String)>,
+    }
+
+    impl FromArgs for ZaddLike {
+        fn from_args(args: &mut ArgParser) -> RedisResult<Self> {
+            let mut condition = Exclusive::default();
+            for keyword in ["NX", "XX"] {
+                if args.flag(keyword) {
+                    condition.set(keyword.to_string())?;
+                }
+            }
+            let ch = args.flag("CH");
+            let members = args.repeated(|args| Ok((args.arg()?, args.arg()?)))?;
+            Ok(ZaddLike {
+                condition: condition.into_inner(),
+                ch,
+                members,
+            })
+        }
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let parsed: ZaddLike =
+            parse_args("zadd", bulk(&["nx", "CH", "1.5", "a", "-inf", "b"])).unwrap();
+        assert_eq!(
+            parsed,
+            ZaddLike {
+                condition: Some("NX".to_string()),
+                ch: true,
+                members: vec![(1.5, "a".to_string()), (f64::NEG_INFINITY, "b".to_string())],
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_args_errors() {
+        let cases = [
+            (bulk(&[]), RedisError::wrong_arity("zadd")),
+            (bulk(&["1", "a", "2"]), RedisError::wrong_arity("zadd")),
+            (bulk(&["one", "a"]), RedisError::NotFloat),
+            (bulk(&["nan", "a"]), RedisError::NotFloat),
+            (bulk(&["NX", "XX", "1", "a"]), RedisError::Syntax),
+        ];
+        for (args, error) in cases {
+            assert_eq!(parse_args::<ZaddLike>("zadd", args), Err(error));
+        }
+    }
+
+    #[test]
+    fn test_arg_parser() {
+        let mut args = ArgParser::new("cmd", bulk(&["42", "LIMIT", "x"]));
+        assert_eq!(args.arg::<i64>(), Ok(42));
+        assert_eq!(args.keyword(), Ok(Some("LIMIT".to_string())));
+        assert_eq!(args.len(), 1);
+        assert_eq!(args.finish(), Err(RedisError::Syntax));
+        assert_eq!(args.value_of::<i64>(), Err(RedisError::NotInteger));
+        assert_eq!(args.value_of::<String>(), Err(RedisError::Syntax));
+        assert_eq!(args.keyword(), Ok(None));
+        assert_eq!(args.finish(), Ok(()));
+    }
+}
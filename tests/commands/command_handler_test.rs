@@ -0,0 +1,160 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::commands::COMMAND_TABLE;
+    use redis_starter_rust::handlers::command_handler;
+    use redis_starter_rust::models::value::Value;
+
+    use crate::setup::setup_server;
+
+    fn args(args: &[&str]) -> Vec<Value> {
+        args.iter()
+            .map(|arg| Value::BulkString(arg.to_string()))
+            .collect()
+    }
+
+    fn statuses(values: &[&str]) -> Value {
+        Value::Array(
+            values
+                .iter()
+                .map(|value| Value::SimpleString(value.to_string()))
+                .collect(),
+        )
+    }
+
+    fn bulk_strings(values: &[&str]) -> Value {
+        Value::Array(
+            values
+                .iter()
+                .map(|value| Value::BulkString(value.to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_command_count() {
+        let mut server = setup_server();
+        let result = command_handler(&mut server, String::new(), args(&["COUNT"]));
+        assert_eq!(result, Some(Value::Integer(COMMAND_TABLE.len() as i64)));
+
+        let all = match command_handler(&mut server, String::new(), vec![]) {
+            Some(Value::Array(all)) => all,
+            other => panic!("unexpected reply: {:?}", other),
+        };
+        assert_eq!(all.len(), COMMAND_TABLE.len());
+    }
+
+    #[test]
+    fn test_command_info() {
+        let mut server = setup_server();
+        let result = command_handler(&mut server, String::new(), args(&["INFO", "get", "nope"]));
+        assert_eq!(
+            result,
+            Some(Value::Array(vec![
+                Value::Array(vec![
+                    Value::BulkString("get".to_string()),
+                    Value::Integer(2),
+                    statuses(&["readonly", "fast"]),
+                    Value::Integer(1),
+                    Value::Integer(1),
+                    Value::Integer(1),
+                    statuses(&["@read", "@string", "@fast"]),
+                    Value::Array(vec![]),
+                    Value::Array(vec![]),
+                    Value::Array(vec![]),
+                ]),
+                Value::NullBulkString,
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_command_info_categories() {
+        let command = COMMAND_TABLE.get("FLUSHALL").unwrap();
+        assert_eq!(command.arity, -1);
+        assert_eq!(command.flags, vec!["write"]);
+        assert_eq!(
+            command.acl_categories,
+            vec!["keyspace", "write", "slow", "dangerous"]
+        );
+
+        let command = COMMAND_TABLE.get("PSYNC").unwrap();
+        assert!(command.has_flag("admin"));
+        assert_eq!(command.acl_categories, vec!["admin", "slow", "dangerous"]);
+    }
+
+    #[test]
+    fn test_command_docs() {
+        let mut server = setup_server();
+        let result = command_handler(&mut server, String::new(), args(&["DOCS", "hmset", "nope"]));
+        assert_eq!(
+            result,
+            Some(Value::Array(vec![
+                Value::BulkString("hmset".to_string()),
+                Value::Array(vec![
+                    Value::BulkString("summary".to_string()),
+                    Value::BulkString("Sets the values of multiple fields.".to_string()),
+                    Value::BulkString("group".to_string()),
+                    Value::BulkString("hash".to_string()),
+                    Value::BulkString("doc_flags".to_string()),
+                    statuses(&["deprecated"]),
+                    Value::BulkString("replaced_by".to_string()),
+                    Value::BulkString("`HSET`".to_string()),
+                ]),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_command_getkeys() {
+        let mut server = setup_server();
+        let cases = [
+            (args(&["GETKEYS", "set", "k", "v"]), bulk_strings(&["k"])),
+            (
+                args(&["GETKEYS", "del", "a", "b", "c"]),
+                bulk_strings(&["a", "b", "c"]),
+            ),
+            (
+                args(&["GETKEYS", "lmove", "src", "dst", "LEFT", "RIGHT"]),
+                bulk_strings(&["src", "dst"]),
+            ),
+            (
+                args(&["GETKEYS", "lmpop", "2", "a", "b", "LEFT"]),
+                bulk_strings(&["a", "b"]),
+            ),
+            (
+                args(&["GETKEYS", "sort", "list", "LIMIT", "0", "1", "STORE", "out"]),
+                bulk_strings(&["list", "out"]),
+            ),
+            (
+                args(&[
+                    "GETKEYS", "migrate", "host", "6379", "", "0", "5000", "KEYS", "a", "b",
+                ]),
+                bulk_strings(&["a", "b"]),
+            ),
+        ];
+        for (arguments, expected) in cases {
+            let result = command_handler(&mut server, String::new(), arguments);
+            assert_eq!(result, Some(expected));
+        }
+    }
+
+    #[test]
+    fn test_command_getkeys_errors() {
+        let mut server = setup_server();
+        let cases = [
+            (args(&["GETKEYS", "nope"]), "ERR Invalid command specified"),
+            (
+                args(&["GETKEYS", "get"]),
+                "ERR Invalid number of arguments specified for command",
+            ),
+            (
+                args(&["GETKEYS", "ping"]),
+                "ERR The command has no key arguments",
+            ),
+        ];
+        for (arguments, error) in cases {
+            let result = command_handler(&mut server, String::new(), arguments);
+            assert_eq!(result, Some(Value::Error(error.to_string())));
+        }
+    }
+}
@@ -0,0 +1,152 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::commands::{dispatch, run_isolated};
+    use redis_starter_rust::models::value::Value;
+
+    use crate::setup::setup_server;
+
+    fn args(args: &[&str]) -> Vec<Value> {
+        args.iter()
+            .map(|arg| Value::BulkString(arg.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_dispatch_splits_key() {
+        let mut server = setup_server();
+        let result = dispatch(&mut server, "SET", args(&["key", "value"]));
+        assert_eq!(result, Some(Value::SimpleString("OK".to_string())));
+
+        let result = dispatch(&mut server, "GET", args(&["key"]));
+        assert_eq!(result, Some(Value::BulkString("value".to_string())));
+    }
+
+    #[test]
+    fn test_dispatch_variadic_keys() {
+        let mut server = setup_server();
+        dispatch(&mut server, "SET", args(&["a", "1"]));
+        dispatch(&mut server, "SET", args(&["b", "2"]));
+
+        let result = dispatch(&mut server, "EXISTS", args(&["a", "b", "c"]));
+        assert_eq!(result, Some(Value::Integer(2)));
+        let result = dispatch(&mut server, "DEL", args(&["a", "b"]));
+        assert_eq!(result, Some(Value::Integer(2)));
+    }
+
+    #[test]
+    fn test_dispatch_wrong_number_of_arguments() {
+        let mut server = setup_server();
+        for (command, arguments) in [
+            ("GET", args(&[])),
+            ("GET", args(&["a", "b"])),
+            ("SET", args(&["a"])),
+            ("DBSIZE", args(&["a"])),
+            ("LINSERT", args(&["list", "BEFORE", "pivot"])),
+        ] {
+            let result = dispatch(&mut server, command, arguments);
+            assert_eq!(
+                result,
+                Some(Value::Error(format!(
+                    "ERR wrong number of arguments for '{}' command",
+                    command.to_lowercase()
+                )))
+            );
+        }
+        assert!(server.cache.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_dispatch_ignores_case() {
+        let mut server = setup_server();
+        let result = dispatch(&mut server, "set", args(&["foo", "bar"]));
+        assert_eq!(result, Some(Value::SimpleString("OK".to_string())));
+
+        let result = dispatch(&mut server, "gEt", args(&["foo"]));
+        assert_eq!(result, Some(Value::BulkString("bar".to_string())));
+    }
+
+    #[test]
+    fn test_dispatch_unknown_command() {
+        let mut server = setup_server();
+        let result = dispatch(&mut server, "foo", args(&[]));
+        assert_eq!(
+            result,
+            Some(Value::Error(
+                "ERR unknown command 'foo', with args beginning with: ".to_string()
+            ))
+        );
+
+        let result = dispatch(&mut server, "foo", args(&["a", "b c"]));
+        assert_eq!(
+            result,
+            Some(Value::Error(
+                "ERR unknown command 'foo', with args beginning with: 'a' 'b c' ".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_dispatch_wrong_type() {
+        let mut server = setup_server();
+        dispatch(&mut server, "LPUSH", args(&["list", "a"]));
+
+        let result = dispatch(&mut server, "GET", args(&["list"]));
+        assert_eq!(
+            result,
+            Some(Value::Error(
+                "WRONGTYPE Operation against a key holding the wrong kind of value".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_run_isolated_recovers_from_panic() {
+        let mut server = setup_server();
+        dispatch(&mut server, "SET", args(&["key", "value"]));
+
+        // Panic while holding the cache lock, poisoning it.
+        let result = run_isolated(&mut server, |server| {
+            let _cache = server.cache.lock().unwrap();
+            panic!("handler bug");
+        });
+        assert_eq!(result, Some(Value::Error("ERR internal error".to_string())));
+        assert_eq!(server.stats.internal_errors(), 1);
+        assert!(server.cache.is_poisoned());
+
+        let result = dispatch(&mut server, "GET", args(&["key"]));
+        assert_eq!(result, Some(Value::BulkString("value".to_string())));
+
+        let info = match dispatch(&mut server, "INFO", vec![]) {
+            Some(Value::BulkString(info)) => info,
+            other => panic!("unexpected reply: {:?}", other),
+        };
+        assert!(info.contains("internal_errors:1\r\n"));
+    }
+
+    #[test]
+    fn test_run_isolated_shared_locks_survive_panic() {
+        let mut server = setup_server();
+
+        // Panic while holding the ACL and eviction pool locks.
+        let result = run_isolated(&mut server, |server| {
+            let _acl = server.acl.lock().unwrap();
+            let _pool = server.eviction_pool.lock().unwrap();
+            panic!("handler bug");
+        });
+        assert_eq!(result, Some(Value::Error("ERR internal error".to_string())));
+
+        let result = dispatch(&mut server, "ACL", args(&["USERS"]));
+        assert_eq!(
+            result,
+            Some(Value::Array(vec![Value::BulkString("default".to_string())]))
+        );
+        dispatch(
+            &mut server,
+            "CONFIG",
+            args(&["SET", "maxmemory-policy", "allkeys-lru"]),
+        );
+        dispatch(&mut server, "CONFIG", args(&["SET", "maxmemory", "1"]));
+        let result = dispatch(&mut server, "GET", args(&["key"]));
+        assert_eq!(result, Some(Value::NullBulkString));
+    }
+}
@@ -0,0 +1,3 @@
+pub mod command_args_test;
+pub mod command_handler_test;
+pub mod dispatch_test;
@@ -0,0 +1,248 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use redis_starter_rust::commands::dispatch;
+    use redis_starter_rust::config::{parse_memory, split_args, Config, ConfigError, SaveRules};
+    use redis_starter_rust::handlers::config_handler;
+    use redis_starter_rust::models::value::Value;
+    use redis_starter_rust::server::Server;
+
+    use crate::setup::setup_server;
+
+    fn args(args: &[&str]) -> Vec<Value> {
+        args.iter()
+            .map(|arg| Value::BulkString(arg.to_string()))
+            .collect()
+    }
+
+    fn config(server: &mut Server, command_args: &[&str]) -> Option<Value> {
+        config_handler(server, String::new(), args(command_args))
+    }
+
+    fn ok() -> Option<Value> {
+        Some(Value::SimpleString("OK".to_string()))
+    }
+
+    fn error(message: &str) -> Option<Value> {
+        Some(Value::Error(message.to_string()))
+    }
+
+    fn load(argv: &[&str]) -> Result<Config, ConfigError> {
+        Config::load(
+            ["my_redis_server"]
+                .iter()
+                .chain(argv)
+                .map(|arg| arg.to_string()),
+        )
+    }
+
+    fn write_file(name: &str, contents: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("config-test-{}-{}.conf", std::process::id(), name));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_load_file_and_overrides() {
+        let file = write_file(
+            "load",
+            "# A comment\n\nport 7000\nbind 127.0.0.1 -::1\nsave \"\"\nrequirepass \"pa ss\"\nhz 5\n",
+        );
+        let config = load(&[file.to_str().unwrap(), "--hz", "20", "--databases", "4"]).unwrap();
+
+        assert_eq!(config.file, Some(file));
+        assert_eq!(config.port, 7000);
+        assert_eq!(config.hz, 20);
+        assert_eq!(config.databases, 4);
+        assert_eq!(config.save, SaveRules(vec![]));
+        assert_eq!(config.requirepass.as_deref(), Some("pa ss"));
+        assert_eq!(config.bind.to_string(), "127.0.0.1 -::1");
+    }
+
+    #[test]
+    fn test_load_errors() {
+        let file = write_file("bad-directive", "port 7000\nno-such-directive yes\n");
+        match load(&[file.to_str().unwrap()]) {
+            Err(ConfigError::File { line, .. }) => assert_eq!(line, 2),
+            other => panic!("expected a file error, got {:?}", other),
+        }
+
+        let file = write_file("bad-quotes", "requirepass \"secret\n");
+        assert!(matches!(
+            load(&[file.to_str().unwrap()]),
+            Err(ConfigError::File { line: 1, .. })
+        ));
+
+        assert!(matches!(
+            load(&["--no-such-directive", "1"]),
+            Err(ConfigError::UnknownDirective(_))
+        ));
+        assert!(matches!(
+            load(&["--port", "many"]),
+            Err(ConfigError::Args(_))
+        ));
+    }
+
+    #[test]
+    fn test_split_args() {
+        assert_eq!(
+            split_args(r#"bind 127.0.0.1 "a b" 'c d' "\x41\n""#).unwrap(),
+            vec!["bind", "127.0.0.1", "a b", "c d", "A\n"]
+        );
+        assert_eq!(split_args("save \"\"").unwrap(), vec!["save", ""]);
+        assert!(split_args("requirepass \"abc\"def").is_err());
+    }
+
+    #[test]
+    fn test_parse_memory() {
+        assert_eq!(parse_memory("100"), Ok(100));
+        assert_eq!(parse_memory("1k"), Ok(1000));
+        assert_eq!(parse_memory("1KB"), Ok(1024));
+        assert_eq!(parse_memory("2gb"), Ok(2 * 1024 * 1024 * 1024));
+        assert!(parse_memory("1tb").is_err());
+        assert!(parse_memory("gb").is_err());
+    }
+
+    #[test]
+    fn test_config_get() {
+        let mut server = setup_server();
+        assert_eq!(
+            config(&mut server, &["GET", "hz", "port"]),
+            Some(Value::Array(args(&["port", "6379", "hz", "10"])))
+        );
+        let lazyfree = match config(&mut server, &["GET", "LAZYFREE-*"]) {
+            Some(Value::Array(values)) => values,
+            other => panic!("expected an array, got {:?}", other),
+        };
+        assert_eq!(lazyfree.len(), 8);
+        assert_eq!(
+            config(&mut server, &["GET", "no-such-*"]),
+            Some(Value::Array(vec![]))
+        );
+    }
+
+    #[test]
+    fn test_config_set() {
+        let mut server = setup_server();
+        assert_eq!(
+            config(
+                &mut server,
+                &[
+                    "SET",
+                    "hz",
+                    "50",
+                    "lazyfree-lazy-user-del",
+                    "yes",
+                    "save",
+                    ""
+                ]
+            ),
+            ok()
+        );
+        assert_eq!(server.config().hz, 50);
+        assert_eq!(server.config().save, SaveRules(vec![]));
+        assert!(server.lazyfree.config().lazy_user_del);
+
+        assert_eq!(config(&mut server, &["SET", "requirepass", "secret"]), ok());
+        assert!(server.acl.lock().unwrap().requires_auth());
+        assert_eq!(config(&mut server, &["SET", "requirepass", ""]), ok());
+        assert!(!server.acl.lock().unwrap().requires_auth());
+    }
+
+    #[test]
+    fn test_config_set_errors() {
+        let mut server = setup_server();
+        assert_eq!(
+            config(&mut server, &["SET", "no-such-param", "1"]),
+            error("ERR Unknown option or number of arguments for CONFIG SET - 'no-such-param'")
+        );
+        assert_eq!(
+            config(&mut server, &["SET", "databases", "4"]),
+            error(
+                "ERR CONFIG SET failed (possibly related to argument 'databases') - can't set immutable config"
+            )
+        );
+        assert_eq!(
+            config(&mut server, &["SET", "hz", "5", "hz", "6"]),
+            error(
+                "ERR CONFIG SET failed (possibly related to argument 'hz') - duplicate parameter"
+            )
+        );
+
+        // An invalid value leaves every parameter unchanged.
+        let reply = config(&mut server, &["SET", "hz", "50", "dbfilename", "a/b"]);
+        match reply {
+            Some(Value::Error(message)) => assert!(
+                message.starts_with(
+                    "ERR CONFIG SET failed (possibly related to argument 'dbfilename')"
+                ),
+                "{}",
+                message
+            ),
+            other => panic!("expected an error, got {:?}", other),
+        }
+        assert_eq!(server.config().hz, 10);
+    }
+
+    #[test]
+    fn test_config_set_rolls_back() {
+        let mut server = setup_server();
+        let reply = config(
+            &mut server,
+            &["SET", "lazyfree-lazy-expire", "yes", "dir", "/no/such/dir"],
+        );
+        assert!(matches!(reply, Some(Value::Error(_))));
+        assert!(!server.config().lazyfree_lazy_expire);
+        assert!(!server.lazyfree.config().lazy_expire);
+        assert_eq!(server.config().dir, PathBuf::from("."));
+    }
+
+    #[test]
+    fn test_config_rewrite() {
+        let mut server = setup_server();
+        assert_eq!(
+            config(&mut server, &["REWRITE"]),
+            error("ERR The server is running without a config file")
+        );
+
+        let file = write_file("rewrite", "# Keep this comment\nhz 5\n\nport 7000\nhz 6\n");
+        server.config_mut().file = Some(file.clone());
+        assert_eq!(
+            config(&mut server, &["SET", "hz", "25", "requirepass", "a b"]),
+            ok()
+        );
+        assert_eq!(config(&mut server, &["REWRITE"]), ok());
+
+        let contents = std::fs::read_to_string(&file).unwrap();
+        assert_eq!(
+            contents,
+            "# Keep this comment\nhz 25\n\nport 6379\n# Generated by CONFIG REWRITE\nrequirepass \"a b\"\n"
+        );
+        let reloaded = load(&[file.to_str().unwrap()]).unwrap();
+        assert_eq!(reloaded.hz, 25);
+        assert_eq!(reloaded.requirepass.as_deref(), Some("a b"));
+    }
+
+    #[test]
+    fn test_config_resetstat() {
+        let mut server = setup_server();
+        server.stats.record_internal_error();
+        assert_eq!(config(&mut server, &["RESETSTAT"]), ok());
+        assert_eq!(server.stats.internal_errors(), 0);
+    }
+
+    #[test]
+    fn test_writes_count_towards_save() {
+        let mut server = setup_server();
+        dispatch(&mut server, "SET", args(&["key", "value"]));
+        dispatch(&mut server, "GET", args(&["key"]));
+        assert_eq!(server.stats.dirty(), 1);
+
+        assert!(SaveRules(vec![(300, 1)]).due(300, 1));
+        assert!(!SaveRules(vec![(300, 1)]).due(299, 1));
+        assert!(!SaveRules(vec![(300, 1)]).due(300, 0));
+        assert!(!SaveRules(vec![]).due(1000, 1000));
+    }
+}
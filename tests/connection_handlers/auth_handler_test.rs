@@ -0,0 +1,158 @@
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+
+    use redis_starter_rust::handlers::auth_handler;
+    use redis_starter_rust::models::value::Value;
+    use redis_starter_rust::resp::RespHandler;
+    use redis_starter_rust::server::Server;
+
+    use crate::setup::setup_server;
+
+    fn args(args: &[&str]) -> Vec<Value> {
+        args.iter()
+            .map(|arg| Value::BulkString(arg.to_string()))
+            .collect()
+    }
+
+    fn protected_server() -> Server {
+        let server = setup_server();
+        server.acl.lock().unwrap().set_requirepass("secret");
+        server
+    }
+
+    #[test]
+    fn test_auth_handler() {
+        let mut server = protected_server();
+        let ok = Some(Value::SimpleString("OK".to_string()));
+        let wrongpass = Some(Value::Error(
+            "WRONGPASS invalid username-password pair or user is disabled.".to_string(),
+        ));
+
+        assert_eq!(
+            auth_handler(&mut server, String::new(), args(&["secret"])),
+            ok
+        );
+        assert_eq!(
+            auth_handler(&mut server, String::new(), args(&["default", "secret"])),
+            ok
+        );
+        assert_eq!(
+            auth_handler(&mut server, String::new(), args(&["guess"])),
+            wrongpass
+        );
+        assert_eq!(
+            auth_handler(&mut server, String::new(), args(&["admin", "secret"])),
+            wrongpass
+        );
+    }
+
+    #[test]
+    fn test_auth_handler_without_requirepass() {
+        let mut server = setup_server();
+        let result = auth_handler(&mut server, String::new(), args(&["anything"]));
+        assert!(
+            matches!(result, Some(Value::Error(e)) if e.starts_with("ERR AUTH <password> called without"))
+        );
+
+        let result = auth_handler(&mut server, String::new(), args(&["default", "anything"]));
+        assert_eq!(result, Some(Value::SimpleString("OK".to_string())));
+    }
+
+    // Serves `server` on an ephemeral port and connects a client to it.
+    fn connect(server: Server) -> TcpStream {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        listener.set_nonblocking(true).unwrap();
+        std::thread::spawn(move || {
+            let runtime = tokio::runtime::Runtime::new().unwrap();
+            runtime.block_on(async move {
+                let listener = tokio::net::TcpListener::from_std(listener).unwrap();
+                let (stream, _) = listener.accept().await.unwrap();
+                let _ = RespHandler::new(stream).handle_client(server).await;
+            });
+        });
+        TcpStream::connect(("127.0.0.1", port)).unwrap()
+    }
+
+    fn send(stream: &mut TcpStream, command: &[&str]) -> String {
+        stream
+            .write_all(&Value::Array(args(command)).serialize())
+            .unwrap();
+        let mut buffer = [0; 512];
+        let n = stream.read(&mut buffer).unwrap();
+        String::from_utf8_lossy(&buffer[..n]).to_string()
+    }
+
+    #[test]
+    fn test_connection_requires_auth() {
+        let mut stream = connect(protected_server());
+
+        assert_eq!(
+            send(&mut stream, &["SET", "key", "value"]),
+            "-NOAUTH Authentication required.\r\n"
+        );
+        assert_eq!(
+            send(&mut stream, &["HELLO", "2"]),
+            "-NOAUTH Authentication required.\r\n"
+        );
+        assert!(send(&mut stream, &["AUTH", "wrong"]).starts_with("-WRONGPASS"));
+        assert!(send(&mut stream, &["NOSUCHCOMMAND"]).starts_with("-ERR unknown command"));
+
+        assert_eq!(send(&mut stream, &["AUTH", "secret"]), "+OK\r\n");
+        assert_eq!(send(&mut stream, &["SET", "key", "value"]), "+OK\r\n");
+
+        assert_eq!(send(&mut stream, &["QUIT"]), "+OK\r\n");
+        let mut buffer = [0; 16];
+        assert_eq!(stream.read(&mut buffer).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_connection_fullresync_requires_auth() {
+        let mut stream = connect(protected_server());
+        assert_eq!(
+            send(&mut stream, &["FULLRESYNC"]),
+            "-NOAUTH Authentication required.\r\n"
+        );
+        // Still serving commands, not the snapshot.
+        assert_eq!(send(&mut stream, &["AUTH", "secret"]), "+OK\r\n");
+        assert_eq!(send(&mut stream, &["PING"]), "+PONG\r\n");
+    }
+
+    #[test]
+    fn test_connection_hello_auth() {
+        let mut stream = connect(protected_server());
+
+        let reply = send(&mut stream, &["HELLO", "2", "AUTH", "default", "secret"]);
+        assert!(reply.starts_with("*12\r\n"));
+        assert_eq!(send(&mut stream, &["PING"]), "+PONG\r\n");
+    }
+
+    #[test]
+    fn test_connection_enforces_acl() {
+        let server = setup_server();
+        server
+            .acl
+            .lock()
+            .unwrap()
+            .set_user(
+                "analytics",
+                &["on", ">pw", "%R~report:*", "+@read"].map(String::from),
+            )
+            .unwrap();
+        let mut stream = connect(server);
+
+        assert_eq!(send(&mut stream, &["SET", "report:1", "v"]), "+OK\r\n");
+        assert_eq!(send(&mut stream, &["AUTH", "analytics", "pw"]), "+OK\r\n");
+        assert_eq!(send(&mut stream, &["GET", "report:1"]), "$1\r\nv\r\n");
+        assert_eq!(
+            send(&mut stream, &["GET", "users:1"]),
+            "-NOPERM No permissions to access a key\r\n"
+        );
+        assert_eq!(
+            send(&mut stream, &["SET", "report:1", "w"]),
+            "-NOPERM User analytics has no permissions to run the 'set' command\r\n"
+        );
+    }
+}
@@ -0,0 +1,51 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::hello_handler;
+    use redis_starter_rust::models::value::Value;
+
+    use crate::setup::setup_server;
+
+    fn args(args: &[&str]) -> Vec<Value> {
+        args.iter()
+            .map(|arg| Value::BulkString(arg.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_hello_handler() {
+        let mut server = setup_server();
+        let reply = match hello_handler(&mut server, String::new(), args(&["2"])) {
+            Some(Value::Array(reply)) => reply,
+            other => panic!("unexpected reply: {:?}", other),
+        };
+        assert_eq!(reply[4], Value::BulkString("proto".to_string()));
+        assert_eq!(reply[5], Value::Integer(2));
+        assert_eq!(reply[9], Value::BulkString("master".to_string()));
+    }
+
+    #[test]
+    fn test_hello_handler_errors() {
+        let mut server = setup_server();
+        server.acl.lock().unwrap().set_requirepass("secret");
+
+        let cases = [
+            (
+                args(&["3"]),
+                "NOPROTO sorry, this protocol version is not supported.",
+            ),
+            (
+                args(&["two"]),
+                "ERR Protocol version is not an integer or out of range",
+            ),
+            (
+                args(&["2", "AUTH", "default", "wrong"]),
+                "WRONGPASS invalid username-password pair or user is disabled.",
+            ),
+            (args(&["2", "AUTH", "default"]), "ERR syntax error"),
+        ];
+        for (args, error) in cases {
+            let result = hello_handler(&mut server, String::new(), args);
+            assert_eq!(result, Some(Value::Error(error.to_string())));
+        }
+    }
+}
@@ -0,0 +1,2 @@
+pub mod auth_handler_test;
+pub mod hello_handler_test;
@@ -0,0 +1,156 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, VecDeque};
+    use std::sync::{Arc, Mutex};
+
+    use indexmap::IndexMap;
+    use redis_starter_rust::database::Database;
+use redis_starter_rust::keyspace::Keyspace;
+    use redis_starter_rust::models::redis_item::RedisItem;
+    use redis_starter_rust::models::redis_type::RedisType;
+    use redis_starter_rust::models::value::Value;
+    use redis_starter_rust::server::Server;
+    use serde::Serialize;
+
+    fn temp_path(name: &str) -> String {
+        std::env::temp_dir()
+            .join(format!("{}-{}.rdb", name, std::process::id()))
+            .to_string_lossy()
+            .to_string()
+    }
+
+    // Item layout written by dumps that predate the versioned format.
+    #[derive(Serialize)]
+    struct LegacyRedisItem {
+        value: Value,
+        created_at: i64,
+        expiration: Option<i64>,
+        redis_type: RedisType,
+    }
+
+    #[test]
+    fn test_read_backup_upgrades_legacy_lists() {
+        let path = temp_path("legacy-list");
+        let mut legacy = HashMap::new();
+        legacy.insert(
+            "list".to_string(),
+            LegacyRedisItem {
+                value: Value::Array(vec![
+                    Value::BulkString("a".to_string()),
+                    Value::BulkString("b".to_string()),
+                ]),
+                created_at: 0,
+                expiration: None,
+                redis_type: RedisType::List,
+            },
+        );
+        std::fs::write(&path, bincode::serialize(&legacy).unwrap()).unwrap();
+
+        let cache = Arc::new(Mutex::new(Keyspace::new()));
+        let db = Database::new(cache.clone(), &path);
+        db.read_backup().unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let cache = cache.lock().unwrap();
+        assert_eq!(
+            cache.get("list").map(|item| &item.value),
+            Some(&Value::List(VecDeque::from(vec![
+                Value::BulkString("a".to_string()),
+                Value::BulkString("b".to_string()),
+            ])))
+        );
+    }
+
+    #[test]
+    fn test_dump_and_read_backup_round_trip() {
+        let path = temp_path("round-trip");
+        let cache = Arc::new(Mutex::new(Keyspace::new()));
+        cache.lock().unwrap().insert(
+            "list".to_string(),
+            RedisItem::new_list(vec![Value::BulkString("a".to_string())]),
+        );
+        Database::new(cache, &path).dump_backup().unwrap();
+
+        let restored = Arc::new(Mutex::new(Keyspace::new()));
+        Database::new(restored.clone(), &path)
+            .read_backup()
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let restored = restored.lock().unwrap();
+        assert_eq!(
+            restored.get("list").map(|item| &item.value),
+            Some(&Value::List(VecDeque::from(vec![Value::BulkString(
+                "a".to_string()
+            )])))
+        );
+    }
+
+    #[test]
+    fn test_round_trip_keeps_database_indexes() {
+        let path = temp_path("multi-db");
+        let databases = Server::create_databases(16);
+        databases[3].lock().unwrap().insert(
+            "list".to_string(),
+            RedisItem::new_list(vec![Value::BulkString("a".to_string())]),
+        );
+        Database::with_databases(databases, &path)
+            .dump_backup()
+            .unwrap();
+
+        let restored = Server::create_databases(16);
+        Database::with_databases(restored.clone(), &path)
+            .read_backup()
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(restored[0].lock().unwrap().is_empty());
+        assert!(restored[3].lock().unwrap().contains_key("list"));
+    }
+
+    #[test]
+    fn test_read_backup_loads_single_db_dumps_into_db0() {
+        let path = temp_path("single-db");
+        let mut cache = HashMap::new();
+        cache.insert(
+            "list".to_string(),
+            RedisItem::new_list(vec![Value::BulkString("a".to_string())]),
+        );
+        let mut buffer = b"MYREDIS\0".to_vec();
+        buffer.extend_from_slice(&2u16.to_le_bytes());
+        buffer.extend_from_slice(&bincode::serialize(&cache).unwrap());
+        std::fs::write(&path, buffer).unwrap();
+
+        let restored = Server::create_databases(16);
+        Database::with_databases(restored.clone(), &path)
+            .read_backup()
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(restored[0].lock().unwrap().contains_key("list"));
+    }
+
+    #[test]
+    fn test_round_trip_keeps_field_expirations() {
+        let path = temp_path("field-ttl");
+        let cache = Arc::new(Mutex::new(Keyspace::new()));
+        let mut hash = IndexMap::new();
+        hash.insert("field".to_string(), Value::BulkString("v".to_string()));
+        let mut item = RedisItem::new_hash(hash);
+        item.field_expirations.insert("field".to_string(), i64::MAX);
+        cache.lock().unwrap().insert("hash".to_string(), item);
+        Database::new(cache, &path).dump_backup().unwrap();
+
+        let restored = Arc::new(Mutex::new(Keyspace::new()));
+        Database::new(restored.clone(), &path)
+            .read_backup()
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let restored = restored.lock().unwrap();
+        assert_eq!(
+            restored.get("hash").unwrap().field_expirations.get("field"),
+            Some(&i64::MAX)
+        );
+    }
+}
@@ -0,0 +1,191 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::commands::dispatch;
+    use redis_starter_rust::config;
+    use redis_starter_rust::eviction::{evict_key, MaxmemoryPolicy};
+    use redis_starter_rust::models::redis_item::{AccessStats, RedisItem};
+    use redis_starter_rust::models::value::Value;
+    use redis_starter_rust::server::Server;
+    use redis_starter_rust::utilities::now_millis;
+
+    use crate::setup::setup_server;
+
+    fn args(args: &[&str]) -> Vec<Value> {
+        args.iter()
+            .map(|arg| Value::BulkString(arg.to_string()))
+            .collect()
+    }
+
+    fn set_config(server: &Server, changes: &[(&str, &str)]) {
+        let changes: Vec<(String, String)> = changes
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        config::set(server, &changes).unwrap();
+    }
+
+    // Stores `key` with the given idle time in seconds, frequency and TTL.
+    fn insert(server: &Server, key: &str, idle: i64, frequency: u8, ttl: Option<i64>) {
+        let mut item = RedisItem::new_string("value".to_string());
+        item.access = AccessStats {
+            last_access: now_millis() - idle * 1000,
+            frequency,
+        };
+        item.expiration = ttl;
+        server.cache.lock().unwrap().insert(key.to_string(), item);
+    }
+
+    fn keys(server: &Server) -> Vec<String> {
+        let mut keys: Vec<String> = server.cache.lock().unwrap().keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    #[test]
+    fn test_parse_policy() {
+        for name in [
+            "noeviction",
+            "allkeys-lru",
+            "volatile-lru",
+            "allkeys-lfu",
+            "volatile-lfu",
+            "allkeys-random",
+            "volatile-random",
+            "volatile-ttl",
+        ] {
+            let policy: MaxmemoryPolicy = name.parse().unwrap();
+            assert_eq!(policy.to_string(), name);
+        }
+        assert_eq!(
+            "ALLKEYS-LRU".parse::<MaxmemoryPolicy>(),
+            Ok(MaxmemoryPolicy::AllKeysLru)
+        );
+        assert!("lru".parse::<MaxmemoryPolicy>().is_err());
+    }
+
+    #[test]
+    fn test_noeviction_refuses_denyoom_commands() {
+        let mut server = setup_server();
+        insert(&server, "key", 0, 5, None);
+        set_config(&server, &[("maxmemory", "1")]);
+
+        assert_eq!(
+            dispatch(&mut server, "SET", args(&["other", "value"])),
+            Some(Value::Error(
+                "OOM command not allowed when used memory > 'maxmemory'.".to_string()
+            ))
+        );
+        // Reads and deletions still work.
+        assert_eq!(
+            dispatch(&mut server, "GET", args(&["key"])),
+            Some(Value::BulkString("value".to_string()))
+        );
+        assert_eq!(
+            dispatch(&mut server, "DEL", args(&["key"])),
+            Some(Value::Integer(1))
+        );
+    }
+
+    #[test]
+    fn test_allkeys_lru_evicts_the_idlest_key() {
+        let server = setup_server();
+        set_config(
+            &server,
+            &[
+                ("maxmemory-policy", "allkeys-lru"),
+                ("maxmemory-samples", "10"),
+            ],
+        );
+        insert(&server, "recent", 1, 5, None);
+        insert(&server, "old", 600, 5, None);
+        insert(&server, "older", 60, 5, None);
+
+        assert!(evict_key(&server).is_some());
+        assert_eq!(keys(&server), vec!["older", "recent"]);
+        assert!(evict_key(&server).is_some());
+        assert_eq!(keys(&server), vec!["recent"]);
+        assert_eq!(server.stats.evicted_keys(), 2);
+    }
+
+    #[test]
+    fn test_allkeys_lfu_evicts_the_least_frequent_key() {
+        let server = setup_server();
+        set_config(
+            &server,
+            &[
+                ("maxmemory-policy", "allkeys-lfu"),
+                ("maxmemory-samples", "10"),
+            ],
+        );
+        insert(&server, "hot", 0, 200, None);
+        insert(&server, "cold", 0, 1, None);
+
+        assert!(evict_key(&server).is_some());
+        assert_eq!(keys(&server), vec!["hot"]);
+    }
+
+    #[test]
+    fn test_volatile_policies_only_evict_keys_with_a_ttl() {
+        let server = setup_server();
+        set_config(
+            &server,
+            &[
+                ("maxmemory-policy", "volatile-ttl"),
+                ("maxmemory-samples", "10"),
+            ],
+        );
+        insert(&server, "persistent", 600, 5, None);
+        insert(&server, "soon", 0, 5, Some(10));
+        insert(&server, "later", 0, 5, Some(1000));
+
+        assert!(evict_key(&server).is_some());
+        assert_eq!(keys(&server), vec!["later", "persistent"]);
+
+        set_config(&server, &[("maxmemory-policy", "volatile-random")]);
+        assert!(evict_key(&server).is_some());
+        assert_eq!(evict_key(&server), None);
+        assert_eq!(keys(&server), vec!["persistent"]);
+    }
+
+    #[test]
+    fn test_evicts_until_nothing_is_left() {
+        let mut server = setup_server();
+        for key in ["a", "b", "c"] {
+            insert(&server, key, 0, 5, None);
+        }
+        set_config(&server, &[("maxmemory-policy", "allkeys-random")]);
+        // Used memory never drops under one byte, so every key is evicted and
+        // the command is still refused.
+        set_config(&server, &[("maxmemory", "1")]);
+        assert_eq!(
+            dispatch(&mut server, "SET", args(&["d", "value"])),
+            Some(Value::Error(
+                "OOM command not allowed when used memory > 'maxmemory'.".to_string()
+            ))
+        );
+        assert!(keys(&server).is_empty());
+        assert_eq!(server.stats.evicted_keys(), 3);
+    }
+
+    #[test]
+    fn test_lfu_counter_decays() {
+        let access = AccessStats {
+            last_access: now_millis() - 3 * 60 * 1000,
+            frequency: 20,
+        };
+        assert_eq!(access.decayed_frequency(1), 17);
+        assert_eq!(access.decayed_frequency(2), 19);
+        assert_eq!(access.decayed_frequency(0), 20);
+
+        let mut access = access;
+        access.touch(10, 1);
+        assert!(access.frequency == 17 || access.frequency == 18);
+    }
+
+    #[test]
+    fn test_memory_usage_grows_with_contents() {
+        let small = RedisItem::new_string("x".to_string());
+        let large = RedisItem::new_string("x".repeat(10_000));
+        assert!(large.memory_usage() >= small.memory_usage() + 9_000);
+    }
+}
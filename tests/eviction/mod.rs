@@ -0,0 +1 @@
+pub mod eviction_test;
@@ -0,0 +1,131 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use redis_starter_rust::expiration::{
+        active_expire_cycle, expire_fields_sample, expire_keys_sample,
+    };
+    use redis_starter_rust::keyspace::Keyspace;
+    use redis_starter_rust::models::redis_item::RedisItem;
+    use redis_starter_rust::models::value::Value;
+
+    use crate::setup::setup_server;
+
+    fn hash_item(fields: &[(&str, Option<i64>)]) -> RedisItem {
+        let hash = fields
+            .iter()
+            .map(|(field, _)| (field.to_string(), Value::BulkString("v".to_string())))
+            .collect();
+        let mut item = RedisItem::new_hash(hash);
+        for (field, at) in fields {
+            if let Some(at) = at {
+                item.field_expirations.insert(field.to_string(), *at);
+            }
+        }
+        item
+    }
+
+    fn expired_item() -> RedisItem {
+        let mut item = RedisItem::new_string("old".to_string());
+        item.created_at = 0;
+        item.expiration = Some(1);
+        item
+    }
+
+    #[test]
+    fn test_expire_fields_sample_reclaims_fields() {
+        let mut cache = Keyspace::new();
+        cache.insert(
+            "partial".to_string(),
+            hash_item(&[("old", Some(100)), ("new", Some(10_000)), ("plain", None)]),
+        );
+        cache.insert("gone".to_string(), hash_item(&[("old", Some(100))]));
+        cache.insert(
+            "string".to_string(),
+            RedisItem::new_string("value".to_string()),
+        );
+
+        let sample = expire_fields_sample(&mut cache, 1_000);
+
+        assert_eq!(sample.sampled, 2);
+        assert_eq!(sample.fields, 2);
+        assert_eq!(sample.removed.len(), 1);
+        assert!(!cache.contains_key("gone"));
+        assert!(cache.contains_key("string"));
+        let partial = cache.get("partial").unwrap();
+        match &partial.value {
+            Value::Hash(hash) => {
+                let mut fields: Vec<_> = hash.keys().cloned().collect();
+                fields.sort();
+                assert_eq!(fields, vec!["new".to_string(), "plain".to_string()]);
+            }
+            other => panic!("unexpected value {:?}", other),
+        }
+        assert!(!partial.field_expirations.contains_key("old"));
+    }
+
+    #[test]
+    fn test_expire_keys_sample_removes_expired_keys() {
+        let mut cache = Keyspace::new();
+        cache.insert("expired".to_string(), expired_item());
+        let mut live = RedisItem::new_string("new".to_string());
+        live.expiration = Some(10_000);
+        cache.insert("live".to_string(), live);
+        cache.insert(
+            "persistent".to_string(),
+            RedisItem::new_string("value".to_string()),
+        );
+
+        let sample = expire_keys_sample(&mut cache);
+        assert_eq!(sample.sampled, 2);
+        assert_eq!(sample.expired, 1);
+        assert_eq!(
+            *sample.removed[0].value.unpacked(),
+            Value::BulkString("old".to_string())
+        );
+        assert!(!cache.contains_key("expired"));
+        assert!(cache.contains_key("live"));
+        assert_eq!(cache.expires(), 1);
+    }
+
+    #[test]
+    fn test_active_expire_cycle_reclaims_expired_keys() {
+        let server = setup_server();
+        {
+            let mut cache = server.databases[3].lock().unwrap();
+            for i in 0..1000 {
+                cache.insert(
+                    format!("persistent:{}", i),
+                    RedisItem::new_string("value".to_string()),
+                );
+            }
+            for i in 0..100 {
+                cache.insert(format!("expired:{}", i), expired_item());
+            }
+        }
+
+        let mut next_db = 0;
+        active_expire_cycle(&server, &mut next_db, Duration::from_secs(10));
+
+        let cache = server.databases[3].lock().unwrap();
+        assert_eq!(cache.len(), 1000);
+        assert_eq!(cache.expires(), 0);
+        assert_eq!(server.stats.expired_keys(), 100);
+    }
+
+    #[test]
+    fn test_active_expire_cycle_stops_when_out_of_time() {
+        let server = setup_server();
+        server.databases[5]
+            .lock()
+            .unwrap()
+            .insert("expired".to_string(), expired_item());
+
+        let mut next_db = 5;
+        active_expire_cycle(&server, &mut next_db, Duration::ZERO);
+
+        assert_eq!(next_db, 5);
+        assert!(server.databases[5].lock().unwrap().contains_key("expired"));
+        assert_eq!(server.stats.expired_keys(), 0);
+    }
+}
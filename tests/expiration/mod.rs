@@ -0,0 +1 @@
+pub mod expiration_test;
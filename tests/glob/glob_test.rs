@@ -0,0 +1,64 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::glob::string_match;
+
+    #[test]
+    fn test_wildcards() {
+        // Like stringmatchlen, an empty string never matches; KEYS special
+        // cases a lone `*` instead.
+        assert!(!string_match("*", "", false));
+        assert!(string_match("*", "anything", false));
+        assert!(string_match("h?llo", "hello", false));
+        assert!(!string_match("h?llo", "hllo", false));
+        assert!(string_match("h*llo", "heeeello", false));
+        assert!(string_match("h*llo", "hllo", false));
+        assert!(string_match("a**b", "ab", false));
+        assert!(!string_match("a*b", "abc", false));
+    }
+
+    #[test]
+    fn test_character_classes() {
+        assert!(string_match("h[ae]llo", "hello", false));
+        assert!(string_match("h[ae]llo", "hallo", false));
+        assert!(!string_match("h[ae]llo", "hillo", false));
+        assert!(string_match("h[^e]llo", "hallo", false));
+        assert!(!string_match("h[^e]llo", "hello", false));
+        assert!(string_match("h[a-b]llo", "hbllo", false));
+        assert!(string_match("h[b-a]llo", "hallo", false));
+        assert!(!string_match("h[a-b]llo", "hcllo", false));
+    }
+
+    #[test]
+    fn test_escapes() {
+        assert!(string_match("user:\\*", "user:*", false));
+        assert!(!string_match("user:\\*", "user:1", false));
+        assert!(string_match("\\[1\\]", "[1]", false));
+        assert!(string_match("[\\]]", "]", false));
+        assert!(string_match("[\\-]", "-", false));
+        assert!(string_match("a\\", "a\\", false));
+    }
+
+    #[test]
+    fn test_brackets_and_braces_in_keys() {
+        assert!(string_match("{user}:*", "{user}:1", false));
+        assert!(string_match("tag:{*}", "tag:{42}", false));
+        assert!(string_match("list:[[]0]", "list:[0]", false));
+        // An unterminated class runs to the end of the pattern.
+        assert!(string_match("key[ab", "keya", false));
+        assert!(!string_match("key[ab", "keyc", false));
+    }
+
+    #[test]
+    fn test_nocase() {
+        assert!(string_match("HELLO*", "hello world", true));
+        assert!(string_match("[A-C]x", "bx", true));
+        assert!(!string_match("HELLO*", "hello world", false));
+    }
+
+    #[test]
+    fn test_pathological_pattern() {
+        let pattern = "a*".repeat(30) + "b";
+        let string = "a".repeat(60);
+        assert!(!string_match(&pattern, &string, false));
+    }
+}
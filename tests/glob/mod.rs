@@ -0,0 +1 @@
+pub mod glob_test;
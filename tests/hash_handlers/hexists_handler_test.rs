@@ -65,6 +65,8 @@ mod tests {
                     created_at: Instant::now().elapsed().as_secs() as i64,
                     expiration: None,
                     redis_type: redis_starter_rust::models::redis_type::RedisType::String,
+                    field_expirations: Default::default(),
+                    access: Default::default(),
                 },
             );
         }
@@ -74,7 +76,7 @@ mod tests {
         assert_eq!(
             result,
             Some(Value::Error(
-                "ERR operation against a key holding the wrong kind of value".to_string()
+                "WRONGTYPE Operation against a key holding the wrong kind of value".to_string()
             ))
         );
     }
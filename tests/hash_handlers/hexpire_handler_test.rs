@@ -0,0 +1,188 @@
+#[cfg(test)]
+mod tests {
+    use std::thread::sleep;
+    use std::time::Duration;
+
+    use redis_starter_rust::handlers::{
+        hexpire_handler, hget_handler, hgetall_handler, hlen_handler, hpexpire_handler,
+        hset_handler,
+    };
+    use redis_starter_rust::models::value::Value;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    fn integers(values: &[i64]) -> Option<Value> {
+        Some(Value::Array(
+            values.iter().map(|v| Value::Integer(*v)).collect(),
+        ))
+    }
+
+    #[test]
+    fn test_hexpire_handler() {
+        let mut server = setup_server();
+        hset_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["a", "1", "b", "2"]),
+        );
+
+        let result = hexpire_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["100", "FIELDS", "3", "a", "b", "missing"]),
+        );
+        assert_eq!(result, integers(&[1, 1, -2]));
+    }
+
+    #[test]
+    fn test_hexpire_handler_conditions() {
+        let mut server = setup_server();
+        hset_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["a", "1", "b", "2"]),
+        );
+        hexpire_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["100", "FIELDS", "1", "a"]),
+        );
+
+        let nx = hexpire_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["200", "NX", "FIELDS", "2", "a", "b"]),
+        );
+        assert_eq!(nx, integers(&[0, 1]));
+
+        let gt = hexpire_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["150", "GT", "FIELDS", "2", "a", "b"]),
+        );
+        assert_eq!(gt, integers(&[1, 0]));
+
+        let lt = hexpire_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["50", "lt", "FIELDS", "2", "a", "b"]),
+        );
+        assert_eq!(lt, integers(&[1, 1]));
+    }
+
+    #[test]
+    fn test_hexpire_handler_zero_deletes_field() {
+        let mut server = setup_server();
+        hset_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["a", "1", "b", "2"]),
+        );
+
+        let result = hexpire_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["0", "FIELDS", "1", "a"]),
+        );
+        assert_eq!(result, integers(&[2]));
+        assert_eq!(
+            hget_handler(&mut server, "myhash".to_string(), bulk(&["a"])),
+            Some(Value::NullBulkString)
+        );
+    }
+
+    #[test]
+    fn test_expired_fields_are_invisible() {
+        let mut server = setup_server();
+        hset_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["a", "1", "b", "2"]),
+        );
+        hpexpire_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["10", "FIELDS", "1", "a"]),
+        );
+        sleep(Duration::from_millis(20));
+
+        assert_eq!(
+            hget_handler(&mut server, "myhash".to_string(), bulk(&["a"])),
+            Some(Value::NullBulkString)
+        );
+        assert_eq!(
+            hlen_handler(&mut server, "myhash".to_string(), vec![]),
+            Some(Value::Integer(1))
+        );
+        assert_eq!(
+            hgetall_handler(&mut server, "myhash".to_string(), vec![]),
+            Some(Value::Array(bulk(&["b", "2"])))
+        );
+    }
+
+    #[test]
+    fn test_key_deleted_when_last_field_expires() {
+        let mut server = setup_server();
+        hset_handler(&mut server, "myhash".to_string(), bulk(&["a", "1"]));
+        hpexpire_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["10", "FIELDS", "1", "a"]),
+        );
+        sleep(Duration::from_millis(20));
+
+        assert_eq!(
+            hlen_handler(&mut server, "myhash".to_string(), vec![]),
+            Some(Value::Integer(0))
+        );
+        assert!(!server.cache.lock().unwrap().contains_key("myhash"));
+    }
+
+    #[test]
+    fn test_hexpire_handler_missing_key() {
+        let mut server = setup_server();
+        let result = hexpire_handler(
+            &mut server,
+            "missing".to_string(),
+            bulk(&["100", "FIELDS", "2", "a", "b"]),
+        );
+        assert_eq!(result, integers(&[-2, -2]));
+    }
+
+    #[test]
+    fn test_hexpire_handler_numfields_mismatch() {
+        let mut server = setup_server();
+        hset_handler(&mut server, "myhash".to_string(), bulk(&["a", "1"]));
+
+        let result = hexpire_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["100", "FIELDS", "2", "a"]),
+        );
+        assert_eq!(
+            result,
+            Some(Value::Error(
+                "ERR The `numfields` parameter must match the number of arguments".to_string()
+            ))
+        );
+
+        let result = hexpire_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["100", "FIELDS", "0"]),
+        );
+        assert_eq!(
+            result,
+            Some(Value::Error(
+                "ERR Parameter `numFields` should be greater than 0".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,55 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{hexpireat_handler, hset_handler, httl_handler};
+    use redis_starter_rust::models::value::Value;
+    use redis_starter_rust::utilities::now_millis;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_hexpireat_handler() {
+        let mut server = setup_server();
+        hset_handler(&mut server, "myhash".to_string(), bulk(&["a", "1"]));
+        let at = (now_millis() / 1000 + 100).to_string();
+
+        let result = hexpireat_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&[&at, "FIELDS", "1", "a"]),
+        );
+        assert_eq!(result, Some(Value::Array(vec![Value::Integer(1)])));
+
+        match httl_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["FIELDS", "1", "a"]),
+        ) {
+            Some(Value::Array(ttls)) => match ttls[0] {
+                Value::Integer(ttl) => assert!((99..=100).contains(&ttl)),
+                ref other => panic!("unexpected ttl {:?}", other),
+            },
+            other => panic!("unexpected reply {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_hexpireat_handler_past_time_deletes_field() {
+        let mut server = setup_server();
+        hset_handler(&mut server, "myhash".to_string(), bulk(&["a", "1"]));
+
+        let result = hexpireat_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["1", "FIELDS", "1", "a"]),
+        );
+        assert_eq!(result, Some(Value::Array(vec![Value::Integer(2)])));
+        assert!(!server.cache.lock().unwrap().contains_key("myhash"));
+    }
+}
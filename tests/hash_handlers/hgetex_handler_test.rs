@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{hgetex_handler, hset_handler, httl_handler};
+    use redis_starter_rust::models::value::Value;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_hgetex_handler_sets_ttl() {
+        let mut server = setup_server();
+        hset_handler(&mut server, "myhash".to_string(), bulk(&["a", "1"]));
+
+        let result = hgetex_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["EX", "100", "FIELDS", "2", "a", "b"]),
+        );
+        assert_eq!(
+            result,
+            Some(Value::Array(vec![
+                Value::BulkString("1".to_string()),
+                Value::NullBulkString,
+            ]))
+        );
+        assert_eq!(
+            httl_handler(
+                &mut server,
+                "myhash".to_string(),
+                bulk(&["FIELDS", "1", "a"])
+            ),
+            Some(Value::Array(vec![Value::Integer(100)]))
+        );
+    }
+
+    #[test]
+    fn test_hgetex_handler_persist() {
+        let mut server = setup_server();
+        hset_handler(&mut server, "myhash".to_string(), bulk(&["a", "1"]));
+        hgetex_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["PX", "100000", "FIELDS", "1", "a"]),
+        );
+
+        hgetex_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["PERSIST", "FIELDS", "1", "a"]),
+        );
+        assert_eq!(
+            httl_handler(
+                &mut server,
+                "myhash".to_string(),
+                bulk(&["FIELDS", "1", "a"])
+            ),
+            Some(Value::Array(vec![Value::Integer(-1)]))
+        );
+    }
+
+    #[test]
+    fn test_hgetex_handler_syntax_error() {
+        let mut server = setup_server();
+        let result = hgetex_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["EX", "10", "PERSIST", "FIELDS", "1", "a"]),
+        );
+        assert_eq!(result, Some(Value::Error("ERR syntax error".to_string())));
+    }
+}
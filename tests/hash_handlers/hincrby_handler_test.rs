@@ -0,0 +1,84 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{hget_handler, hincrby_handler, hset_handler};
+    use redis_starter_rust::models::value::Value;
+    use redis_starter_rust::server::Server;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    fn setup() -> Server {
+        let mut server = setup_server();
+        hset_handler(
+            &mut server,
+            "profile".to_string(),
+            bulk(&["visits", "5", "name", "alice"]),
+        );
+        server
+    }
+
+    #[test]
+    fn test_hincrby_handler_existing_field() {
+        let mut server = setup();
+        let result = hincrby_handler(&mut server, "profile".to_string(), bulk(&["visits", "3"]));
+        assert_eq!(result, Some(Value::Integer(8)));
+
+        let result = hget_handler(&mut server, "profile".to_string(), bulk(&["visits"]));
+        assert_eq!(result, Some(Value::BulkString("8".to_string())));
+    }
+
+    #[test]
+    fn test_hincrby_handler_new_key_and_field() {
+        let mut server = setup();
+        let result = hincrby_handler(&mut server, "counters".to_string(), bulk(&["hits", "-2"]));
+        assert_eq!(result, Some(Value::Integer(-2)));
+    }
+
+    #[test]
+    fn test_hincrby_handler_not_an_integer() {
+        let mut server = setup();
+        let result = hincrby_handler(&mut server, "profile".to_string(), bulk(&["name", "1"]));
+        assert_eq!(
+            result,
+            Some(Value::Error("ERR hash value is not an integer".to_string()))
+        );
+
+        let result = hincrby_handler(&mut server, "profile".to_string(), bulk(&["visits", "x"]));
+        assert_eq!(
+            result,
+            Some(Value::Error(
+                "ERR value is not an integer or out of range".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_hincrby_handler_overflow() {
+        let mut server = setup();
+        hset_handler(
+            &mut server,
+            "profile".to_string(),
+            bulk(&["big", &i64::MAX.to_string()]),
+        );
+        let result = hincrby_handler(&mut server, "profile".to_string(), bulk(&["big", "1"]));
+        assert_eq!(
+            result,
+            Some(Value::Error(
+                "ERR increment or decrement would overflow".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_hincrby_handler_error_does_not_create_key() {
+        let mut server = setup();
+        hincrby_handler(&mut server, "missing".to_string(), bulk(&["f", "nope"]));
+        assert!(!server.cache.lock().unwrap().contains_key("missing"));
+    }
+}
@@ -0,0 +1,61 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{hincrbyfloat_handler, hset_handler};
+    use redis_starter_rust::models::value::Value;
+    use redis_starter_rust::server::Server;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    fn setup() -> Server {
+        let mut server = setup_server();
+        hset_handler(
+            &mut server,
+            "mykey".to_string(),
+            bulk(&["field", "10.50", "name", "alice"]),
+        );
+        server
+    }
+
+    #[test]
+    fn test_hincrbyfloat_handler() {
+        let mut server = setup();
+        let result =
+            hincrbyfloat_handler(&mut server, "mykey".to_string(), bulk(&["field", "0.1"]));
+        assert_eq!(result, Some(Value::BulkString("10.6".to_string())));
+
+        let result = hincrbyfloat_handler(&mut server, "mykey".to_string(), bulk(&["field", "-5"]));
+        assert_eq!(result, Some(Value::BulkString("5.6".to_string())));
+    }
+
+    #[test]
+    fn test_hincrbyfloat_handler_integer_result() {
+        let mut server = setup();
+        hset_handler(&mut server, "mykey".to_string(), bulk(&["total", "5.0e3"]));
+        let result =
+            hincrbyfloat_handler(&mut server, "mykey".to_string(), bulk(&["total", "2.0e2"]));
+        assert_eq!(result, Some(Value::BulkString("5200".to_string())));
+    }
+
+    #[test]
+    fn test_hincrbyfloat_handler_not_a_float() {
+        let mut server = setup();
+        let result = hincrbyfloat_handler(&mut server, "mykey".to_string(), bulk(&["name", "1"]));
+        assert_eq!(
+            result,
+            Some(Value::Error("ERR hash value is not a float".to_string()))
+        );
+
+        let result = hincrbyfloat_handler(&mut server, "mykey".to_string(), bulk(&["field", "x"]));
+        assert_eq!(
+            result,
+            Some(Value::Error("ERR value is not a valid float".to_string()))
+        );
+    }
+}
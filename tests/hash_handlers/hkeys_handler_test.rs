@@ -55,11 +55,13 @@ mod tests {
             let mut cache = server.cache.lock().unwrap();
             cache.insert(
                 "key".to_string(),
-                redis_starter_rust::my_redis_server::redis_item::RedisItem  {
+                redis_starter_rust::my_redis_server::redis_item::RedisItem {
                     value: Value::BulkString("some string".to_string()),
                     created_at: Instant::now().elapsed().as_secs() as i64,
                     expiration: None,
                     redis_type: RedisType::String,
+                    field_expirations: Default::default(),
+                    access: Default::default(),
                 },
             );
         }
@@ -67,7 +69,7 @@ mod tests {
         assert_eq!(
             result,
             Some(Value::Error(
-                "ERR operation against a key holding the wrong kind of value".to_string()
+                "WRONGTYPE Operation against a key holding the wrong kind of value".to_string()
             ))
         );
     }
@@ -0,0 +1,51 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{hmget_handler, hset_handler};
+    use redis_starter_rust::models::value::Value;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_hmget_handler() {
+        let mut server = setup_server();
+        hset_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["field1", "Hello", "field2", "World"]),
+        );
+
+        let result = hmget_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["field1", "field2", "nofield"]),
+        );
+        assert_eq!(
+            result,
+            Some(Value::Array(vec![
+                Value::BulkString("Hello".to_string()),
+                Value::BulkString("World".to_string()),
+                Value::NullBulkString,
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_hmget_handler_missing_key() {
+        let mut server = setup_server();
+        let result = hmget_handler(&mut server, "missing".to_string(), bulk(&["a", "b"]));
+        assert_eq!(
+            result,
+            Some(Value::Array(vec![
+                Value::NullBulkString,
+                Value::NullBulkString
+            ]))
+        );
+    }
+}
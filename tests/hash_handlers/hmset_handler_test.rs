@@ -0,0 +1,40 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{hget_handler, hmset_handler};
+    use redis_starter_rust::models::value::Value;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_hmset_handler() {
+        let mut server = setup_server();
+        let result = hmset_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["field1", "Hello", "field2", "World"]),
+        );
+        assert_eq!(result, Some(Value::SimpleString("OK".to_string())));
+
+        let result = hget_handler(&mut server, "myhash".to_string(), bulk(&["field2"]));
+        assert_eq!(result, Some(Value::BulkString("World".to_string())));
+    }
+
+    #[test]
+    fn test_hmset_handler_missing_value() {
+        let mut server = setup_server();
+        let result = hmset_handler(&mut server, "myhash".to_string(), bulk(&["field1"]));
+        assert_eq!(
+            result,
+            Some(Value::Error(
+                "ERR wrong number of arguments for 'hmset' command".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,53 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{
+        hexpire_handler, hpersist_handler, hset_handler, httl_handler,
+    };
+    use redis_starter_rust::models::value::Value;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_hpersist_handler() {
+        let mut server = setup_server();
+        hset_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["a", "1", "b", "2"]),
+        );
+        hexpire_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["100", "FIELDS", "1", "a"]),
+        );
+
+        let result = hpersist_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["FIELDS", "3", "a", "b", "c"]),
+        );
+        assert_eq!(
+            result,
+            Some(Value::Array(vec![
+                Value::Integer(1),
+                Value::Integer(-1),
+                Value::Integer(-2),
+            ]))
+        );
+        assert_eq!(
+            httl_handler(
+                &mut server,
+                "myhash".to_string(),
+                bulk(&["FIELDS", "1", "a"])
+            ),
+            Some(Value::Array(vec![Value::Integer(-1)]))
+        );
+    }
+}
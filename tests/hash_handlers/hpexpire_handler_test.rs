@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{hpexpire_handler, hpttl_handler, hset_handler};
+    use redis_starter_rust::models::value::Value;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_hpexpire_handler() {
+        let mut server = setup_server();
+        hset_handler(&mut server, "myhash".to_string(), bulk(&["a", "1"]));
+
+        let result = hpexpire_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["5000", "FIELDS", "1", "a"]),
+        );
+        assert_eq!(result, Some(Value::Array(vec![Value::Integer(1)])));
+
+        match hpttl_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["FIELDS", "1", "a"]),
+        ) {
+            Some(Value::Array(ttls)) => match ttls[0] {
+                Value::Integer(ttl) => assert!(ttl > 4000 && ttl <= 5000),
+                ref other => panic!("unexpected ttl {:?}", other),
+            },
+            other => panic!("unexpected reply {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_hpexpire_handler_invalid_time() {
+        let mut server = setup_server();
+        hset_handler(&mut server, "myhash".to_string(), bulk(&["a", "1"]));
+
+        let result = hpexpire_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["-1", "FIELDS", "1", "a"]),
+        );
+        assert_eq!(
+            result,
+            Some(Value::Error(
+                "ERR invalid expire time in 'hpexpire' command".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,48 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{hpexpireat_handler, hpttl_handler, hset_handler};
+    use redis_starter_rust::models::value::Value;
+    use redis_starter_rust::utilities::now_millis;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_hpexpireat_handler() {
+        let mut server = setup_server();
+        hset_handler(&mut server, "myhash".to_string(), bulk(&["a", "1"]));
+        let at = (now_millis() + 60_000).to_string();
+
+        let result = hpexpireat_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&[&at, "XX", "FIELDS", "1", "a"]),
+        );
+        assert_eq!(result, Some(Value::Array(vec![Value::Integer(0)])));
+
+        let result = hpexpireat_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&[&at, "FIELDS", "1", "a"]),
+        );
+        assert_eq!(result, Some(Value::Array(vec![Value::Integer(1)])));
+
+        match hpttl_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["FIELDS", "1", "a"]),
+        ) {
+            Some(Value::Array(ttls)) => match ttls[0] {
+                Value::Integer(ttl) => assert!(ttl > 59_000 && ttl <= 60_000),
+                ref other => panic!("unexpected ttl {:?}", other),
+            },
+            other => panic!("unexpected reply {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,22 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::hpttl_handler;
+    use redis_starter_rust::models::value::Value;
+
+    use crate::setup::setup_server;
+
+    #[test]
+    fn test_hpttl_handler_missing_key() {
+        let mut server = setup_server();
+        let result = hpttl_handler(
+            &mut server,
+            "missing".to_string(),
+            vec![
+                Value::BulkString("FIELDS".to_string()),
+                Value::BulkString("1".to_string()),
+                Value::BulkString("a".to_string()),
+            ],
+        );
+        assert_eq!(result, Some(Value::Array(vec![Value::Integer(-2)])));
+    }
+}
@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use redis_starter_rust::handlers::{hrandfield_handler, hset_handler};
+    use redis_starter_rust::models::value::Value;
+    use redis_starter_rust::server::Server;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    fn setup() -> Server {
+        let mut server = setup_server();
+        hset_handler(
+            &mut server,
+            "coin".to_string(),
+            bulk(&["heads", "obverse", "tails", "reverse", "edge", "null"]),
+        );
+        server
+    }
+
+    fn array(result: Option<Value>) -> Vec<Value> {
+        match result {
+            Some(Value::Array(values)) => values,
+            other => panic!("Expected array, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_hrandfield_handler_single() {
+        let mut server = setup();
+        let result = hrandfield_handler(&mut server, "coin".to_string(), vec![]);
+        assert!(matches!(
+            result,
+            Some(Value::BulkString(ref f)) if ["heads", "tails", "edge"].contains(&f.as_str())
+        ));
+
+        let result = hrandfield_handler(&mut server, "missing".to_string(), vec![]);
+        assert_eq!(result, Some(Value::NullBulkString));
+    }
+
+    #[test]
+    fn test_hrandfield_handler_positive_count_is_distinct() {
+        let mut server = setup();
+        let fields = array(hrandfield_handler(
+            &mut server,
+            "coin".to_string(),
+            bulk(&["5"]),
+        ));
+        assert_eq!(fields.len(), 3);
+        let unique: HashSet<_> = fields.iter().map(|f| format!("{:?}", f)).collect();
+        assert_eq!(unique.len(), 3);
+    }
+
+    #[test]
+    fn test_hrandfield_handler_negative_count_repeats() {
+        let mut server = setup();
+        let fields = array(hrandfield_handler(
+            &mut server,
+            "coin".to_string(),
+            bulk(&["-7"]),
+        ));
+        assert_eq!(fields.len(), 7);
+    }
+
+    #[test]
+    fn test_hrandfield_handler_with_values() {
+        let mut server = setup();
+        let entries = array(hrandfield_handler(
+            &mut server,
+            "coin".to_string(),
+            bulk(&["2", "WITHVALUES"]),
+        ));
+        assert_eq!(entries.len(), 4);
+        for pair in entries.chunks(2) {
+            let expected = match &pair[0] {
+                Value::BulkString(f) if f == "heads" => "obverse",
+                Value::BulkString(f) if f == "tails" => "reverse",
+                _ => "null",
+            };
+            assert_eq!(pair[1], Value::BulkString(expected.to_string()));
+        }
+    }
+
+    #[test]
+    fn test_hrandfield_handler_huge_count() {
+        let mut server = setup();
+        let fields = array(hrandfield_handler(
+            &mut server,
+            "coin".to_string(),
+            bulk(&["100000000000000"]),
+        ));
+        assert_eq!(fields.len(), 3);
+
+        let result = hrandfield_handler(
+            &mut server,
+            "coin".to_string(),
+            bulk(&["-100000000000000", "WITHVALUES"]),
+        );
+        assert_eq!(
+            result,
+            Some(Value::Error("ERR value is out of range".to_string()))
+        );
+    }
+}
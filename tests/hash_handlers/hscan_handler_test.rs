@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use redis_starter_rust::handlers::{hscan_handler, hset_handler};
+    use redis_starter_rust::models::value::Value;
+    use redis_starter_rust::server::Server;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    fn setup(fields: usize) -> Server {
+        let mut server = setup_server();
+        let mut args = vec![];
+        for i in 0..fields {
+            args.push(Value::BulkString(format!("field:{}", i)));
+            args.push(Value::BulkString(format!("value:{}", i)));
+        }
+        hset_handler(&mut server, "myhash".to_string(), args);
+        server
+    }
+
+    fn scan(server: &mut Server, args: Vec<Value>) -> (String, Vec<Value>) {
+        match hscan_handler(server, "myhash".to_string(), args) {
+            Some(Value::Array(mut reply)) => {
+                let entries = match reply.pop() {
+                    Some(Value::Array(entries)) => entries,
+                    other => panic!("Expected entries, got {:?}", other),
+                };
+                match reply.pop() {
+                    Some(Value::BulkString(cursor)) => (cursor, entries),
+                    other => panic!("Expected cursor, got {:?}", other),
+                }
+            }
+            other => panic!("Expected array, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_hscan_handler_visits_every_field() {
+        // Large enough not to be packed.
+        let mut server = setup(200);
+        let mut cursor = "0".to_string();
+        let mut seen = HashSet::new();
+        loop {
+            let (next, entries) = scan(&mut server, bulk(&[&cursor, "COUNT", "7"]));
+            assert!(entries.len() <= 14);
+            for pair in entries.chunks(2) {
+                if let Value::BulkString(field) = &pair[0] {
+                    seen.insert(field.clone());
+                }
+            }
+            if next == "0" {
+                break;
+            }
+            cursor = next;
+        }
+        assert_eq!(seen.len(), 200);
+    }
+
+    #[test]
+    fn test_hscan_handler_match_and_novalues() {
+        let mut server = setup(20);
+        let (cursor, entries) = scan(
+            &mut server,
+            bulk(&["0", "MATCH", "field:1*", "COUNT", "100", "NOVALUES"]),
+        );
+        assert_eq!(cursor, "0");
+        let mut fields: Vec<String> = entries
+            .into_iter()
+            .map(|f| match f {
+                Value::BulkString(f) => f,
+                other => panic!("Expected field, got {:?}", other),
+            })
+            .collect();
+        fields.sort();
+        let mut expected: Vec<String> = (10..20).map(|i| format!("field:{}", i)).collect();
+        expected.insert(0, "field:1".to_string());
+        assert_eq!(fields, expected);
+    }
+
+    #[test]
+    fn test_hscan_handler_missing_key() {
+        let mut server = setup(0);
+        let (cursor, entries) = scan(&mut server, bulk(&["0"]));
+        assert_eq!(cursor, "0");
+        assert!(entries.is_empty());
+    }
+
+    #[test]
+    fn test_hscan_handler_invalid_cursor() {
+        let mut server = setup(1);
+        let result = hscan_handler(&mut server, "myhash".to_string(), bulk(&["abc"]));
+        assert_eq!(result, Some(Value::Error("ERR invalid cursor".to_string())));
+    }
+}
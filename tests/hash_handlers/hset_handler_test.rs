@@ -3,7 +3,7 @@ mod tests {
 
     use std::time::Instant;
 
-    use redis_starter_rust::handlers::hset_handler;
+    use redis_starter_rust::handlers::{hexpire_handler, hset_handler};
     use redis_starter_rust::models::redis_type::RedisType;
     use redis_starter_rust::models::value::Value;
     use redis_starter_rust::{models::redis_item::RedisItem, server::Server};
@@ -36,7 +36,7 @@ mod tests {
 
         let cache = server.cache.lock().unwrap();
         if let Some(item) = cache.get("myhash") {
-            if let Value::Hash(hash) = &item.value {
+            if let Value::Hash(hash) = item.value.unpacked().as_ref() {
                 assert_eq!(
                     hash.get("field1"),
                     Some(&Value::BulkString("value1".to_string()))
@@ -71,7 +71,7 @@ mod tests {
 
         let cache = server.cache.lock().unwrap();
         if let Some(item) = cache.get("myhash") {
-            if let Value::Hash(hash) = &item.value {
+            if let Value::Hash(hash) = item.value.unpacked().as_ref() {
                 assert_eq!(
                     hash.get("field1"),
                     Some(&Value::BulkString("new_value1".to_string()))
@@ -120,6 +120,8 @@ mod tests {
                     created_at: Instant::now().elapsed().as_secs() as i64,
                     expiration: None,
                     redis_type: RedisType::String,
+                    field_expirations: Default::default(),
+                    access: Default::default(),
                 },
             );
         }
@@ -132,8 +134,28 @@ mod tests {
         assert_eq!(
             result,
             Some(Value::Error(
-                "ERR operation against a key holding the wrong kind of value".to_string()
+                "WRONGTYPE Operation against a key holding the wrong kind of value".to_string()
             ))
         );
     }
+
+    #[test]
+    fn test_hset_overwriting_the_last_field_ttl_untracks_the_key() {
+        let mut server = setup();
+        let bulk = |values: &[&str]| -> Vec<Value> {
+            values
+                .iter()
+                .map(|v| Value::BulkString(v.to_string()))
+                .collect()
+        };
+        hexpire_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["100", "FIELDS", "1", "field1"]),
+        );
+        assert_eq!(server.cache.lock().unwrap().field_expires(), 1);
+
+        hset_handler(&mut server, "myhash".to_string(), bulk(&["field1", "v"]));
+        assert_eq!(server.cache.lock().unwrap().field_expires(), 0);
+    }
 }
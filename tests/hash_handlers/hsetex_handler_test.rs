@@ -0,0 +1,119 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{hget_handler, hsetex_handler, httl_handler};
+    use redis_starter_rust::models::value::Value;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_hsetex_handler() {
+        let mut server = setup_server();
+        let result = hsetex_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["EX", "100", "FIELDS", "2", "a", "1", "b", "2"]),
+        );
+        assert_eq!(result, Some(Value::Integer(1)));
+        assert_eq!(
+            httl_handler(
+                &mut server,
+                "myhash".to_string(),
+                bulk(&["FIELDS", "2", "a", "b"])
+            ),
+            Some(Value::Array(vec![Value::Integer(100), Value::Integer(100)]))
+        );
+    }
+
+    #[test]
+    fn test_hsetex_handler_fnx_fxx() {
+        let mut server = setup_server();
+        hsetex_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["FIELDS", "1", "a", "1"]),
+        );
+
+        let fnx = hsetex_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["FNX", "FIELDS", "2", "a", "x", "b", "y"]),
+        );
+        assert_eq!(fnx, Some(Value::Integer(0)));
+        assert_eq!(
+            hget_handler(&mut server, "myhash".to_string(), bulk(&["b"])),
+            Some(Value::NullBulkString)
+        );
+
+        let fxx = hsetex_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["FXX", "FIELDS", "1", "a", "x"]),
+        );
+        assert_eq!(fxx, Some(Value::Integer(1)));
+        assert_eq!(
+            hget_handler(&mut server, "myhash".to_string(), bulk(&["a"])),
+            Some(Value::BulkString("x".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_hsetex_handler_keepttl() {
+        let mut server = setup_server();
+        hsetex_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["EX", "100", "FIELDS", "1", "a", "1"]),
+        );
+
+        hsetex_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["KEEPTTL", "FIELDS", "1", "a", "2"]),
+        );
+        assert_eq!(
+            httl_handler(
+                &mut server,
+                "myhash".to_string(),
+                bulk(&["FIELDS", "1", "a"])
+            ),
+            Some(Value::Array(vec![Value::Integer(100)]))
+        );
+
+        hsetex_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["FIELDS", "1", "a", "3"]),
+        );
+        assert_eq!(
+            httl_handler(
+                &mut server,
+                "myhash".to_string(),
+                bulk(&["FIELDS", "1", "a"])
+            ),
+            Some(Value::Array(vec![Value::Integer(-1)]))
+        );
+    }
+
+    #[test]
+    fn test_hsetex_handler_huge_numfields() {
+        let mut server = setup_server();
+        let result = hsetex_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["FIELDS", "9223372036854775807", "a", "1"]),
+        );
+        assert_eq!(
+            result,
+            Some(Value::Error(
+                "ERR The `numfields` parameter must match the number of arguments".to_string()
+            ))
+        );
+    }
+}
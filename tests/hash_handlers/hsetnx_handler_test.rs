@@ -0,0 +1,39 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{hget_handler, hsetnx_handler};
+    use redis_starter_rust::models::value::Value;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_hsetnx_handler() {
+        let mut server = setup_server();
+        let result = hsetnx_handler(&mut server, "myhash".to_string(), bulk(&["field", "Hello"]));
+        assert_eq!(result, Some(Value::Integer(1)));
+
+        let result = hsetnx_handler(&mut server, "myhash".to_string(), bulk(&["field", "World"]));
+        assert_eq!(result, Some(Value::Integer(0)));
+
+        let result = hget_handler(&mut server, "myhash".to_string(), bulk(&["field"]));
+        assert_eq!(result, Some(Value::BulkString("Hello".to_string())));
+    }
+
+    #[test]
+    fn test_hsetnx_handler_wrong_arguments() {
+        let mut server = setup_server();
+        let result = hsetnx_handler(&mut server, "myhash".to_string(), bulk(&["field"]));
+        assert_eq!(
+            result,
+            Some(Value::Error(
+                "ERR wrong number of arguments for 'hsetnx' command".to_string()
+            ))
+        );
+    }
+}
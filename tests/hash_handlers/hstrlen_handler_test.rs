@@ -0,0 +1,33 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{hset_handler, hstrlen_handler};
+    use redis_starter_rust::models::value::Value;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_hstrlen_handler() {
+        let mut server = setup_server();
+        hset_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["f1", "HelloWorld", "f2", "99"]),
+        );
+
+        let result = hstrlen_handler(&mut server, "myhash".to_string(), bulk(&["f1"]));
+        assert_eq!(result, Some(Value::Integer(10)));
+        let result = hstrlen_handler(&mut server, "myhash".to_string(), bulk(&["f2"]));
+        assert_eq!(result, Some(Value::Integer(2)));
+        let result = hstrlen_handler(&mut server, "myhash".to_string(), bulk(&["f3"]));
+        assert_eq!(result, Some(Value::Integer(0)));
+        let result = hstrlen_handler(&mut server, "missing".to_string(), bulk(&["f1"]));
+        assert_eq!(result, Some(Value::Integer(0)));
+    }
+}
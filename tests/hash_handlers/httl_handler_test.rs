@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{hexpire_handler, hset_handler, httl_handler};
+    use redis_starter_rust::models::value::Value;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_httl_handler() {
+        let mut server = setup_server();
+        hset_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["a", "1", "b", "2"]),
+        );
+        hexpire_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["100", "FIELDS", "1", "a"]),
+        );
+
+        let result = httl_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["FIELDS", "3", "a", "b", "c"]),
+        );
+        assert_eq!(
+            result,
+            Some(Value::Array(vec![
+                Value::Integer(100),
+                Value::Integer(-1),
+                Value::Integer(-2),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_httl_handler_missing_fields_keyword() {
+        let mut server = setup_server();
+        let result = httl_handler(&mut server, "myhash".to_string(), bulk(&["1", "a"]));
+        assert_eq!(
+            result,
+            Some(Value::Error(
+                "ERR Mandatory argument FIELDS is missing or not at the right position".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_httl_handler_after_hset() {
+        let mut server = setup_server();
+        hset_handler(&mut server, "myhash".to_string(), bulk(&["a", "1"]));
+        hexpire_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["100", "FIELDS", "1", "a"]),
+        );
+        hset_handler(&mut server, "myhash".to_string(), bulk(&["a", "2"]));
+
+        let result = httl_handler(
+            &mut server,
+            "myhash".to_string(),
+            bulk(&["FIELDS", "1", "a"]),
+        );
+        assert_eq!(result, Some(Value::Array(vec![Value::Integer(-1)])));
+    }
+}
@@ -1,8 +1,25 @@
 pub mod hdel_handler_test;
 pub mod hexists_handler_test;
+pub mod hexpire_handler_test;
+pub mod hexpireat_handler_test;
 pub mod hget_handler_test;
 pub mod hgetall_handler_test;
+pub mod hgetex_handler_test;
+pub mod hincrby_handler_test;
+pub mod hincrbyfloat_handler_test;
 pub mod hkeys_handler_test;
 pub mod hlen_handler_test;
+pub mod hmget_handler_test;
+pub mod hmset_handler_test;
+pub mod hpersist_handler_test;
+pub mod hpexpire_handler_test;
+pub mod hpexpireat_handler_test;
+pub mod hpttl_handler_test;
+pub mod hrandfield_handler_test;
+pub mod hscan_handler_test;
 pub mod hset_handler_test;
+pub mod hsetex_handler_test;
+pub mod hsetnx_handler_test;
+pub mod hstrlen_handler_test;
+pub mod httl_handler_test;
 pub mod hvals_handler_test;
@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{copy_handler, get_handler, hset_handler, set_handler};
+    use redis_starter_rust::models::value::Value;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_copy_handler() {
+        let mut server = setup_server();
+        hset_handler(&mut server, "src".to_string(), bulk(&["f", "v"]));
+
+        let result = copy_handler(&mut server, "src".to_string(), bulk(&["dst"]));
+        assert_eq!(result, Some(Value::Integer(1)));
+
+        let cache = server.cache.lock().unwrap();
+        assert_eq!(cache["src"].value, cache["dst"].value);
+    }
+
+    #[test]
+    fn test_copy_handler_keeps_ttl() {
+        let mut server = setup_server();
+        set_handler(&mut server, "src".to_string(), bulk(&["v"]));
+        server
+            .cache
+            .lock()
+            .unwrap()
+            .get_mut("src")
+            .unwrap()
+            .expiration = Some(i64::MAX / 2);
+
+        copy_handler(&mut server, "src".to_string(), bulk(&["dst"]));
+        assert_eq!(
+            server.cache.lock().unwrap()["dst"].expiration,
+            Some(i64::MAX / 2)
+        );
+    }
+
+    #[test]
+    fn test_copy_handler_replace() {
+        let mut server = setup_server();
+        set_handler(&mut server, "src".to_string(), bulk(&["new"]));
+        set_handler(&mut server, "dst".to_string(), bulk(&["old"]));
+
+        let result = copy_handler(&mut server, "src".to_string(), bulk(&["dst"]));
+        assert_eq!(result, Some(Value::Integer(0)));
+        assert_eq!(
+            get_handler(&mut server, "dst".to_string(), vec![]),
+            Some(Value::BulkString("old".to_string()))
+        );
+
+        let result = copy_handler(&mut server, "src".to_string(), bulk(&["dst", "REPLACE"]));
+        assert_eq!(result, Some(Value::Integer(1)));
+        assert_eq!(
+            get_handler(&mut server, "dst".to_string(), vec![]),
+            Some(Value::BulkString("new".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_copy_handler_errors() {
+        let mut server = setup_server();
+        set_handler(&mut server, "src".to_string(), bulk(&["v"]));
+
+        assert_eq!(
+            copy_handler(&mut server, "src".to_string(), bulk(&["src"])),
+            Some(Value::Error(
+                "ERR source and destination objects are the same".to_string()
+            ))
+        );
+        assert_eq!(
+            copy_handler(&mut server, "src".to_string(), bulk(&["dst", "DB", "99"])),
+            Some(Value::Error("ERR DB index is out of range".to_string()))
+        );
+        assert_eq!(
+            copy_handler(&mut server, "missing".to_string(), bulk(&["dst"])),
+            Some(Value::Integer(0))
+        );
+    }
+
+    #[test]
+    fn test_copy_handler_to_other_db() {
+        let mut server = setup_server();
+        set_handler(&mut server, "src".to_string(), bulk(&["v"]));
+
+        let result = copy_handler(&mut server, "src".to_string(), bulk(&["src", "DB", "2"]));
+        assert_eq!(result, Some(Value::Integer(1)));
+        assert!(server.cache.lock().unwrap().contains_key("src"));
+        assert_eq!(
+            *server.databases[2].lock().unwrap()["src"].value.unpacked(),
+            Value::BulkString("v".to_string())
+        );
+    }
+}
@@ -0,0 +1,28 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{dbsize_handler, hset_handler, set_handler};
+    use redis_starter_rust::models::value::Value;
+
+    use crate::setup::setup_server;
+
+    #[test]
+    fn test_dbsize_handler() {
+        let mut server = setup_server();
+        assert_eq!(dbsize_handler(&server), Some(Value::Integer(0)));
+
+        set_handler(
+            &mut server,
+            "a".to_string(),
+            vec![Value::BulkString("v".to_string())],
+        );
+        hset_handler(
+            &mut server,
+            "h".to_string(),
+            vec![
+                Value::BulkString("f".to_string()),
+                Value::BulkString("v".to_string()),
+            ],
+        );
+        assert_eq!(dbsize_handler(&server), Some(Value::Integer(2)));
+    }
+}
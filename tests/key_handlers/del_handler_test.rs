@@ -1,8 +1,11 @@
 #[cfg(test)]
 mod tests {
 
+    use std::sync::Arc;
 
     use redis_starter_rust::handlers::{del_handler, set_handler};
+    use redis_starter_rust::lazyfree::{LazyFree, LazyFreeConfig};
+    use redis_starter_rust::models::redis_item::RedisItem;
     use redis_starter_rust::models::value::Value;
     use redis_starter_rust::server::Server;
 
@@ -14,15 +17,9 @@ mod tests {
     #[test]
     fn test_del_handler() {
         let mut server = setup();
-        let args = vec![
-            Value::BulkString("key1".to_string()),
-            Value::BulkString("value1".to_string()),
-        ];
+        let args = vec![Value::BulkString("value1".to_string())];
         set_handler(&mut server, "key1".to_string(), args);
-        let args = vec![
-            Value::BulkString("key2".to_string()),
-            Value::BulkString("value2".to_string()),
-        ];
+        let args = vec![Value::BulkString("value2".to_string())];
         set_handler(&mut server, "key2".to_string(), args);
 
         let args = vec![
@@ -37,4 +34,26 @@ mod tests {
         let result = del_handler(&mut server, "nonexistent_key".to_string(), args);
         assert_eq!(result, Some(Value::Integer(0)));
     }
+
+    #[test]
+    fn test_del_handler_with_lazy_user_del() {
+        let mut server = setup();
+        server.lazyfree = Arc::new(LazyFree::new(LazyFreeConfig {
+            lazy_user_del: true,
+            ..Default::default()
+        }));
+        let values: Vec<Value> = (0..1000)
+            .map(|i| Value::BulkString(i.to_string()))
+            .collect();
+        server
+            .cache
+            .lock()
+            .unwrap()
+            .insert("list".to_string(), RedisItem::new_list(values));
+
+        let args = vec![Value::BulkString("list".to_string())];
+        let result = del_handler(&mut server, "".to_string(), args);
+        assert_eq!(result, Some(Value::Integer(1)));
+        assert!(server.cache.lock().unwrap().is_empty());
+    }
 }
@@ -0,0 +1,117 @@
+#[cfg(test)]
+mod tests {
+    use indexmap::{IndexMap, IndexSet};
+    use redis_starter_rust::handlers::{dump_handler, set_handler};
+    use redis_starter_rust::models::redis_item::RedisItem;
+    use redis_starter_rust::models::value::Value;
+    use redis_starter_rust::rdb::{restore_payload, RdbError, RDB_VERSION};
+
+    use crate::setup::setup_server;
+
+    fn dump(server: &mut redis_starter_rust::server::Server, key: &str) -> Vec<u8> {
+        match dump_handler(server, key.to_string(), vec![]) {
+            Some(Value::Bytes(payload)) => payload,
+            other => panic!("unexpected reply: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_dump_handler_string() {
+        let mut server = setup_server();
+        set_handler(
+            &mut server,
+            "key".to_string(),
+            vec![Value::BulkString("hello".to_string())],
+        );
+
+        let payload = dump(&mut server, "key");
+        // Type, length-prefixed string, then the version and CRC64 footer.
+        assert_eq!(&payload[..7], b"\x00\x05hello");
+        assert_eq!(&payload[7..9], &RDB_VERSION.to_le_bytes());
+        assert_eq!(payload.len(), 17);
+        assert_eq!(
+            *restore_payload(&payload).unwrap().value.unpacked(),
+            Value::BulkString("hello".to_string())
+        );
+    }
+
+    #[test]
+    fn test_dump_handler_round_trips_every_type() {
+        let mut server = setup_server();
+        let mut hash = IndexMap::new();
+        hash.insert("field".to_string(), Value::BulkString("value".to_string()));
+        let mut hash_item = RedisItem::new_hash(hash);
+        hash_item
+            .field_expirations
+            .insert("field".to_string(), i64::MAX / 2);
+        let long: Vec<Value> = (0..300).map(|i| Value::BulkString(i.to_string())).collect();
+        let items = vec![
+            ("list", RedisItem::new_list(long)),
+            (
+                "set",
+                RedisItem::new_set(IndexSet::from(["a".to_string(), "b".to_string()])),
+            ),
+            (
+                "zset",
+                RedisItem::new_zset(IndexMap::from([("m".to_string(), -1.5)])),
+            ),
+            ("hash", hash_item),
+        ];
+        for (key, item) in items {
+            server
+                .cache
+                .lock()
+                .unwrap()
+                .insert(key.to_string(), item.clone());
+
+            let restored = restore_payload(&dump(&mut server, key)).unwrap();
+            assert_eq!(restored.value, item.value);
+            assert_eq!(restored.redis_type, item.redis_type);
+            assert_eq!(restored.field_expirations, item.field_expirations);
+        }
+    }
+
+    #[test]
+    fn test_dump_handler_missing_key() {
+        let mut server = setup_server();
+        assert_eq!(
+            dump_handler(&mut server, "missing".to_string(), vec![]),
+            Some(Value::NullBulkString)
+        );
+    }
+
+    #[test]
+    fn test_restore_payload_rejects_corruption() {
+        let mut server = setup_server();
+        set_handler(
+            &mut server,
+            "key".to_string(),
+            vec![Value::BulkString("hello".to_string())],
+        );
+        let mut payload = dump(&mut server, "key");
+        payload[3] ^= 0xff;
+
+        assert_eq!(
+            restore_payload(&payload).err(),
+            Some(RdbError::VersionOrChecksum)
+        );
+        assert_eq!(
+            restore_payload(b"short").err(),
+            Some(RdbError::VersionOrChecksum)
+        );
+    }
+
+    #[test]
+    fn test_restore_payload_rejects_overflowing_field_ttl() {
+        // A hash with field TTLs: type 24, the minimum expiry, then one field
+        // whose TTL offset would overflow it.
+        let mut payload = vec![24];
+        payload.extend_from_slice(&i64::MAX.to_le_bytes());
+        payload.extend_from_slice(b"\x01\x03\x01f\x01v");
+        payload.extend_from_slice(&RDB_VERSION.to_le_bytes());
+        let crc = crc64::crc64(0, &payload);
+        payload.extend_from_slice(&crc.to_le_bytes());
+
+        assert_eq!(restore_payload(&payload).err(), Some(RdbError::BadFormat));
+    }
+}
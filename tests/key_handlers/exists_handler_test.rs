@@ -0,0 +1,36 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{exists_handler, set_handler};
+    use redis_starter_rust::models::value::Value;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_exists_handler() {
+        let mut server = setup_server();
+        set_handler(&mut server, "a".to_string(), bulk(&["1"]));
+        set_handler(&mut server, "b".to_string(), bulk(&["2"]));
+
+        let result = exists_handler(&mut server, "".to_string(), bulk(&["a", "b", "a", "nope"]));
+        assert_eq!(result, Some(Value::Integer(3)));
+    }
+
+    #[test]
+    fn test_exists_handler_no_keys() {
+        let mut server = setup_server();
+        let result = exists_handler(&mut server, "".to_string(), vec![]);
+        assert_eq!(
+            result,
+            Some(Value::Error(
+                "ERR wrong number of arguments for 'exists' command".to_string()
+            ))
+        );
+    }
+}
@@ -1,7 +1,6 @@
 #[cfg(test)]
 mod tests {
 
-
     use redis_starter_rust::handlers::{expire_handler, set_handler};
     use redis_starter_rust::log;
     use redis_starter_rust::models::value::Value;
@@ -15,12 +14,9 @@ mod tests {
     #[test]
     fn test_expire_handler() {
         let mut server = setup();
-        let args = vec![
-            Value::BulkString("key".to_string()),
-            Value::BulkString("value".to_string()),
-        ];
+        let args = vec![Value::BulkString("value".to_string())];
         set_handler(&mut server, "key".to_string(), args);
-        let args = vec![Value::BulkString("key".to_string()), Value::Integer(10)];
+        let args = vec![Value::BulkString("10".to_string())];
         log!("args {:?}", args);
         let result = expire_handler(&mut server, "key".to_string(), args.clone());
         assert_eq!(result, Some(Value::Integer(1)));
@@ -29,14 +25,10 @@ mod tests {
     #[test]
     fn test_expire_handler_with_nx() {
         let mut server = setup();
-        let args = vec![
-            Value::BulkString("key".to_string()),
-            Value::BulkString("value".to_string()),
-        ];
+        let args = vec![Value::BulkString("value".to_string())];
         set_handler(&mut server, "key".to_string(), args);
         let args = vec![
-            Value::BulkString("key".to_string()),
-            Value::Integer(10),
+            Value::BulkString("10".to_string()),
             Value::BulkString("NX".to_string()),
         ];
         log!("args {:?}", args);
@@ -48,15 +40,13 @@ mod tests {
     fn test_expire_handler_with_xx() {
         let mut server = setup();
         let args = vec![
-            Value::BulkString("key".to_string()),
             Value::BulkString("value".to_string()),
             Value::BulkString("PX".to_string()),
             Value::Integer(10),
         ];
         set_handler(&mut server, "key".to_string(), args);
         let args = vec![
-            Value::BulkString("key".to_string()),
-            Value::Integer(10),
+            Value::BulkString("10".to_string()),
             Value::BulkString("XX".to_string()),
         ];
         log!("args {:?}", args);
@@ -67,17 +57,13 @@ mod tests {
     #[test]
     fn test_expire_handler_with_gt() {
         let mut server = setup();
-        let args = vec![
-            Value::BulkString("key".to_string()),
-            Value::BulkString("value".to_string()),
-        ];
+        let args = vec![Value::BulkString("value".to_string())];
         set_handler(&mut server, "key".to_string(), args);
-        let args = vec![Value::BulkString("key".to_string()), Value::Integer(5)];
+        let args = vec![Value::BulkString("5".to_string())];
         expire_handler(&mut server, "key".to_string(), args.clone());
 
         let args = vec![
-            Value::BulkString("key".to_string()),
-            Value::Integer(10),
+            Value::BulkString("10".to_string()),
             Value::BulkString("GT".to_string()),
         ];
         log!("args {:?}", args);
@@ -88,17 +74,13 @@ mod tests {
     #[test]
     fn test_expire_handler_with_lt() {
         let mut server = setup();
-        let args = vec![
-            Value::BulkString("key".to_string()),
-            Value::BulkString("value".to_string()),
-        ];
+        let args = vec![Value::BulkString("value".to_string())];
         set_handler(&mut server, "key".to_string(), args);
-        let args = vec![Value::BulkString("key".to_string()), Value::Integer(15)];
+        let args = vec![Value::BulkString("15".to_string())];
         expire_handler(&mut server, "key".to_string(), args.clone());
 
         let args = vec![
-            Value::BulkString("key".to_string()),
-            Value::Integer(10),
+            Value::BulkString("10".to_string()),
             Value::BulkString("LT".to_string()),
         ];
         log!("args {:?}", args);
@@ -106,12 +88,67 @@ mod tests {
         assert_eq!(result, Some(Value::Integer(1)));
 
         let args = vec![
-            Value::BulkString("key".to_string()),
-            Value::Integer(10),
+            Value::BulkString("10".to_string()),
             Value::BulkString("LT".to_string()),
         ];
 
         let result = expire_handler(&mut server, "key".to_string(), args.clone());
         assert_eq!(result, Some(Value::Integer(0)));
     }
+
+    #[test]
+    fn test_expire_handler_invalid_arguments() {
+        let mut server = setup();
+        set_handler(
+            &mut server,
+            "key".to_string(),
+            vec![Value::BulkString("value".to_string())],
+        );
+
+        let cases = [
+            (vec![], "ERR wrong number of arguments for 'expire' command"),
+            (
+                vec![Value::BulkString("soon".to_string())],
+                "ERR value is not an integer or out of range",
+            ),
+            (
+                vec![
+                    Value::BulkString("10".to_string()),
+                    Value::BulkString("NX".to_string()),
+                    Value::BulkString("XX".to_string()),
+                ],
+                "ERR NX and XX, GT or LT options at the same time are not compatible",
+            ),
+            (
+                vec![
+                    Value::BulkString("10".to_string()),
+                    Value::BulkString("YY".to_string()),
+                ],
+                "ERR Unsupported option YY",
+            ),
+        ];
+        for (args, error) in cases {
+            let result = expire_handler(&mut server, "key".to_string(), args);
+            assert_eq!(result, Some(Value::Error(error.to_string())));
+        }
+    }
+
+    #[test]
+    fn test_expire_handler_non_positive_deletes() {
+        let mut server = setup();
+        set_handler(
+            &mut server,
+            "key".to_string(),
+            vec![Value::BulkString("value".to_string())],
+        );
+
+        let args = vec![Value::BulkString("0".to_string())];
+        let result = expire_handler(&mut server, "key".to_string(), args);
+        assert_eq!(result, Some(Value::Integer(1)));
+        assert!(!server.cache.lock().unwrap().contains_key("key"));
+
+        let args = vec![Value::BulkString("10".to_string())];
+        let result = expire_handler(&mut server, "key".to_string(), args);
+        assert_eq!(result, Some(Value::Integer(0)));
+    }
 }
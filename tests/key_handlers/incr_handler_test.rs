@@ -0,0 +1,174 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{
+        decr_handler, decrby_handler, get_handler, hset_handler, incr_handler, incrby_handler,
+        object_handler, set_handler,
+    };
+    use redis_starter_rust::models::value::Value;
+    use redis_starter_rust::server::Server;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    fn object(server: &mut Server, args: &[&str]) -> Option<Value> {
+        object_handler(server, "".to_string(), bulk(args))
+    }
+
+    fn encoding(server: &mut Server, key: &str) -> Option<Value> {
+        object(server, &["ENCODING", key])
+    }
+
+    fn error(message: &str) -> Option<Value> {
+        Some(Value::Error(message.to_string()))
+    }
+
+    #[test]
+    fn test_string_encodings() {
+        let mut server = setup_server();
+        set_handler(&mut server, "int".to_string(), bulk(&["-12345"]));
+        set_handler(&mut server, "padded".to_string(), bulk(&["007"]));
+        set_handler(&mut server, "short".to_string(), bulk(&[&"a".repeat(44)]));
+        set_handler(&mut server, "long".to_string(), bulk(&[&"a".repeat(45)]));
+
+        let str = |s: &str| Some(Value::BulkString(s.to_string()));
+        assert_eq!(encoding(&mut server, "int"), str("int"));
+        assert_eq!(encoding(&mut server, "padded"), str("embstr"));
+        assert_eq!(encoding(&mut server, "short"), str("embstr"));
+        assert_eq!(encoding(&mut server, "long"), str("raw"));
+
+        assert_eq!(
+            get_handler(&mut server, "int".to_string(), vec![]),
+            str("-12345")
+        );
+        assert_eq!(
+            get_handler(&mut server, "padded".to_string(), vec![]),
+            str("007")
+        );
+        assert_eq!(
+            get_handler(&mut server, "long".to_string(), vec![]),
+            str(&"a".repeat(45))
+        );
+    }
+
+    #[test]
+    fn test_shared_integers() {
+        let mut server = setup_server();
+        set_handler(&mut server, "small".to_string(), bulk(&["9999"]));
+        set_handler(&mut server, "big".to_string(), bulk(&["10000"]));
+        set_handler(&mut server, "negative".to_string(), bulk(&["-1"]));
+
+        assert_eq!(
+            object(&mut server, &["REFCOUNT", "small"]),
+            Some(Value::Integer(i32::MAX as i64))
+        );
+        assert_eq!(
+            object(&mut server, &["REFCOUNT", "big"]),
+            Some(Value::Integer(1))
+        );
+        assert_eq!(
+            object(&mut server, &["REFCOUNT", "negative"]),
+            Some(Value::Integer(1))
+        );
+    }
+
+    #[test]
+    fn test_incr_and_decr() {
+        let mut server = setup_server();
+        assert_eq!(
+            incr_handler(&mut server, "counter".to_string(), vec![]),
+            Some(Value::Integer(1))
+        );
+        assert_eq!(
+            incrby_handler(&mut server, "counter".to_string(), bulk(&["41"])),
+            Some(Value::Integer(42))
+        );
+        assert_eq!(
+            decr_handler(&mut server, "counter".to_string(), vec![]),
+            Some(Value::Integer(41))
+        );
+        assert_eq!(
+            decrby_handler(&mut server, "counter".to_string(), bulk(&["50"])),
+            Some(Value::Integer(-9))
+        );
+        assert_eq!(
+            encoding(&mut server, "counter"),
+            Some(Value::BulkString("int".to_string()))
+        );
+        assert_eq!(
+            get_handler(&mut server, "counter".to_string(), vec![]),
+            Some(Value::BulkString("-9".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_incr_keeps_ttl() {
+        let mut server = setup_server();
+        set_handler(
+            &mut server,
+            "counter".to_string(),
+            bulk(&["1", "EX", "100"]),
+        );
+        incr_handler(&mut server, "counter".to_string(), vec![]);
+
+        let cache = server.cache.lock().unwrap();
+        assert!(cache["counter"].expiration.is_some());
+    }
+
+    #[test]
+    fn test_incr_non_integer() {
+        let mut server = setup_server();
+        set_handler(&mut server, "text".to_string(), bulk(&["abc"]));
+        set_handler(&mut server, "padded".to_string(), bulk(&["007"]));
+        hset_handler(&mut server, "hash".to_string(), bulk(&["f", "v"]));
+
+        let not_integer = error("ERR value is not an integer or out of range");
+        assert_eq!(
+            incr_handler(&mut server, "text".to_string(), vec![]),
+            not_integer
+        );
+        assert_eq!(
+            incr_handler(&mut server, "padded".to_string(), vec![]),
+            not_integer
+        );
+        assert_eq!(
+            incrby_handler(&mut server, "counter".to_string(), bulk(&["1.5"])),
+            not_integer
+        );
+        assert_eq!(
+            incr_handler(&mut server, "hash".to_string(), vec![]),
+            error("WRONGTYPE Operation against a key holding the wrong kind of value")
+        );
+    }
+
+    #[test]
+    fn test_incr_overflow() {
+        let mut server = setup_server();
+        set_handler(
+            &mut server,
+            "max".to_string(),
+            bulk(&[&i64::MAX.to_string()]),
+        );
+        assert_eq!(
+            incr_handler(&mut server, "max".to_string(), vec![]),
+            error("ERR increment or decrement would overflow")
+        );
+        assert_eq!(
+            get_handler(&mut server, "max".to_string(), vec![]),
+            Some(Value::BulkString(i64::MAX.to_string()))
+        );
+        assert_eq!(
+            decrby_handler(
+                &mut server,
+                "other".to_string(),
+                bulk(&[&i64::MIN.to_string()])
+            ),
+            error("ERR decrement would overflow")
+        );
+    }
+}
@@ -1,21 +1,32 @@
 use redis_starter_rust::handlers::keys_handler;
+use redis_starter_rust::lazyfree::LazyFree;
 use redis_starter_rust::models::redis_type::RedisType;
 use redis_starter_rust::models::{redis_item::RedisItem, value::Value};
 use redis_starter_rust::server::{Role, Server};
+use redis_starter_rust::stats::Stats;
 use redis_starter_rust::utilities::ServerState;
 
-use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 use std::time::Instant;
 
 #[test]
 fn test_keys_handler() {
+    let databases = Server::create_databases(16);
     let mut server = Server {
-        cache: Arc::new(Mutex::new(HashMap::new())),
+        cache: databases[0].clone(),
+        databases,
+        db_index: 0,
+        lazyfree: Arc::new(LazyFree::default()),
+        stats: Arc::new(Stats::default()),
         role: Role::Main,
         port: 6379,
         sync: false,
         server_state: ServerState::StreamingCommands,
+        acl: Default::default(),
+        user: "default".to_string(),
+        tls: None,
+        config: Default::default(),
+        eviction_pool: Default::default(),
     };
 
     // Populate the cache with some test data
@@ -27,6 +38,8 @@ fn test_keys_handler() {
             created_at: Instant::now().elapsed().as_secs() as i64,
             expiration: None,
             redis_type: RedisType::String,
+            field_expirations: Default::default(),
+            access: Default::default(),
         },
     );
     cache.insert(
@@ -36,6 +49,8 @@ fn test_keys_handler() {
             created_at: Instant::now().elapsed().as_secs() as i64,
             expiration: None,
             redis_type: RedisType::String,
+            field_expirations: Default::default(),
+            access: Default::default(),
         },
     );
     cache.insert(
@@ -45,6 +60,8 @@ fn test_keys_handler() {
             created_at: Instant::now().elapsed().as_secs() as i64,
             expiration: None,
             redis_type: RedisType::String,
+            field_expirations: Default::default(),
+            access: Default::default(),
         },
     );
     drop(cache);
@@ -116,3 +133,58 @@ fn test_keys_handler() {
         ))
     );
 }
+
+#[test]
+fn test_keys_handler_special_characters() {
+    let databases = Server::create_databases(16);
+    let mut server = Server {
+        cache: databases[0].clone(),
+        databases,
+        db_index: 0,
+        lazyfree: Arc::new(LazyFree::default()),
+        stats: Arc::new(Stats::default()),
+        role: Role::Main,
+        port: 6379,
+        sync: false,
+        server_state: ServerState::StreamingCommands,
+        acl: Default::default(),
+        user: "default".to_string(),
+        tls: None,
+        config: Default::default(),
+        eviction_pool: Default::default(),
+    };
+
+    let mut cache = server.cache.lock().unwrap();
+    for key in ["user:{42}", "user:[1]", "user:1", "user.1"] {
+        cache.insert(key.to_string(), RedisItem::new_string("value".to_string()));
+    }
+    drop(cache);
+
+    let keys = |server: &mut Server, pattern: &str| -> Option<Value> {
+        keys_handler(
+            server,
+            "".to_string(),
+            vec![Value::BulkString(pattern.to_string())],
+        )
+    };
+
+    assert_eq!(
+        keys(&mut server, "user:{*}"),
+        Some(Value::Array(vec![Value::BulkString(
+            "user:{42}".to_string()
+        )]))
+    );
+    assert_eq!(
+        keys(&mut server, "user:\\[1\\]"),
+        Some(Value::Array(vec![Value::BulkString(
+            "user:[1]".to_string()
+        )]))
+    );
+    assert_eq!(
+        keys(&mut server, "user[.:]1"),
+        Some(Value::Array(vec![
+            Value::BulkString("user.1".to_string()),
+            Value::BulkString("user:1".to_string()),
+        ]))
+    );
+}
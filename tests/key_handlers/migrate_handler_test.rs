@@ -0,0 +1,285 @@
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Write};
+    use std::sync::{Arc, Mutex};
+
+    use redis_starter_rust::handlers::{get_handler, migrate_handler, set_handler};
+    use redis_starter_rust::models::value::Value;
+    use redis_starter_rust::resp::RespHandler;
+    use redis_starter_rust::server::Server;
+
+    use crate::setup::setup_server;
+
+    // Serves `target` on an ephemeral port from a background runtime.
+    fn spawn_target(target: Server) -> u16 {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        listener.set_nonblocking(true).unwrap();
+        std::thread::spawn(move || {
+            let runtime = tokio::runtime::Runtime::new().unwrap();
+            runtime.block_on(async move {
+                let listener = tokio::net::TcpListener::from_std(listener).unwrap();
+                loop {
+                    let (stream, _) = listener.accept().await.unwrap();
+                    let server = target.clone();
+                    tokio::spawn(async move {
+                        let _ = RespHandler::new(stream).handle_client(server).await;
+                    });
+                }
+            });
+        });
+        port
+    }
+
+    // Serves a scripted target on an ephemeral port. `reply` gets the index of
+    // the connection and each command received on it, and returns the reply
+    // line to send, or `None` to close the connection.
+    fn spawn_fake_target<F>(mut reply: F) -> u16
+    where
+        F: FnMut(usize, Vec<String>) -> Option<String> + Send + 'static,
+    {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        std::thread::spawn(move || {
+            for (index, stream) in listener.incoming().enumerate() {
+                let mut stream = BufReader::new(stream.unwrap());
+                while let Some(command) = read_command(&mut stream) {
+                    match reply(index, command) {
+                        Some(line) => stream
+                            .get_mut()
+                            .write_all(format!("{}\r\n", line).as_bytes())
+                            .unwrap(),
+                        None => break,
+                    }
+                }
+            }
+        });
+        port
+    }
+
+    fn read_command(stream: &mut impl BufRead) -> Option<Vec<String>> {
+        let mut line = String::new();
+        stream.read_line(&mut line).ok()?;
+        let count: usize = line.trim_end().strip_prefix('*')?.parse().ok()?;
+        let mut command = Vec::new();
+        for _ in 0..count {
+            let mut line = String::new();
+            stream.read_line(&mut line).ok()?;
+            let len: usize = line.trim_end().strip_prefix('$')?.parse().ok()?;
+            let mut arg = vec![0; len + 2];
+            stream.read_exact(&mut arg).ok()?;
+            arg.truncate(len);
+            command.push(String::from_utf8_lossy(&arg).into_owned());
+        }
+        Some(command)
+    }
+
+    fn migrate_args(port: u16, key: &str, db: i64, options: &[&str]) -> Vec<Value> {
+        let mut args: Vec<Value> = ["127.0.0.1", &port.to_string(), key, &db.to_string(), "1000"]
+            .iter()
+            .map(|arg| Value::BulkString(arg.to_string()))
+            .collect();
+        args.extend(options.iter().map(|o| Value::BulkString(o.to_string())));
+        args
+    }
+
+    fn set(server: &mut Server, key: &str, value: &str) {
+        set_handler(
+            server,
+            key.to_string(),
+            vec![Value::BulkString(value.to_string())],
+        );
+    }
+
+    #[test]
+    fn test_migrate_handler_moves_key() {
+        let mut target = setup_server();
+        let port = spawn_target(target.clone());
+        let mut server = setup_server();
+        set(&mut server, "key", "hello");
+
+        let result = migrate_handler(
+            &mut server,
+            "".to_string(),
+            migrate_args(port, "key", 0, &[]),
+        );
+        assert_eq!(result, Some(Value::SimpleString("OK".to_string())));
+        assert!(server.cache.lock().unwrap().is_empty());
+        assert_eq!(
+            get_handler(&mut target, "key".to_string(), vec![]),
+            Some(Value::BulkString("hello".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_migrate_handler_large_values() {
+        let mut target = setup_server();
+        let port = spawn_target(target.clone());
+        let mut server = setup_server();
+        let big = "v".repeat(8 * 1024);
+        set(&mut server, "big", &big);
+        set(&mut server, "other", &"w".repeat(450));
+
+        // The connection is pooled, so the second MIGRATE reuses it.
+        for key in ["big", "other"] {
+            let result =
+                migrate_handler(&mut server, "".to_string(), migrate_args(port, key, 0, &[]));
+            assert_eq!(result, Some(Value::SimpleString("OK".to_string())));
+        }
+        assert_eq!(
+            get_handler(&mut target, "big".to_string(), vec![]),
+            Some(Value::BulkString(big))
+        );
+        assert!(target.cache.lock().unwrap().contains_key("other"));
+    }
+
+    #[test]
+    fn test_migrate_handler_keys_copy_and_db() {
+        let target = setup_server();
+        let port = spawn_target(target.clone());
+        let mut server = setup_server();
+        set(&mut server, "a", "1");
+        set(&mut server, "b", "2");
+
+        let result = migrate_handler(
+            &mut server,
+            "".to_string(),
+            migrate_args(port, "", 3, &["COPY", "KEYS", "a", "b", "missing"]),
+        );
+        assert_eq!(result, Some(Value::SimpleString("OK".to_string())));
+        assert_eq!(server.cache.lock().unwrap().len(), 2);
+        let db = target.databases[3].lock().unwrap();
+        assert!(db.contains_key("a") && db.contains_key("b"));
+    }
+
+    #[test]
+    fn test_migrate_handler_busy_key_and_nokey() {
+        let mut target = setup_server();
+        set(&mut target, "key", "old");
+        let port = spawn_target(target.clone());
+        let mut server = setup_server();
+        set(&mut server, "key", "new");
+
+        let result = migrate_handler(
+            &mut server,
+            "".to_string(),
+            migrate_args(port, "key", 0, &[]),
+        );
+        assert_eq!(
+            result,
+            Some(Value::Error(
+                "ERR Target instance replied with error: BUSYKEY Target key name already exists."
+                    .to_string()
+            ))
+        );
+        assert!(server.cache.lock().unwrap().contains_key("key"));
+
+        let result = migrate_handler(
+            &mut server,
+            "".to_string(),
+            migrate_args(port, "key", 0, &["REPLACE"]),
+        );
+        assert_eq!(result, Some(Value::SimpleString("OK".to_string())));
+        assert_eq!(
+            get_handler(&mut target, "key".to_string(), vec![]),
+            Some(Value::BulkString("new".to_string()))
+        );
+
+        let result = migrate_handler(
+            &mut server,
+            "".to_string(),
+            migrate_args(port, "key", 0, &[]),
+        );
+        assert_eq!(result, Some(Value::SimpleString("NOKEY".to_string())));
+    }
+
+    #[test]
+    fn test_migrate_handler_unreachable_target() {
+        let mut server = setup_server();
+        set(&mut server, "key", "v");
+        // Bind and drop a listener to find a port nobody listens on.
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+
+        let result = migrate_handler(
+            &mut server,
+            "".to_string(),
+            migrate_args(port, "key", 0, &[]),
+        );
+        assert_eq!(
+            result,
+            Some(Value::Error(
+                "IOERR error or timeout connecting to the client".to_string()
+            ))
+        );
+        assert!(server.cache.lock().unwrap().contains_key("key"));
+    }
+
+    #[test]
+    fn test_migrate_handler_retry_resends_only_unanswered() {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let log = received.clone();
+        let port = spawn_fake_target(move |connection, command| {
+            log.lock()
+                .unwrap()
+                .push((connection, command[..2].join(" ")));
+            // The cached connection drops after acknowledging "b".
+            if connection == 0 && command[1] == "c" {
+                return None;
+            }
+            Some("+OK".to_string())
+        });
+        let mut server = setup_server();
+        for key in ["a", "b", "c"] {
+            set(&mut server, key, "v");
+        }
+
+        let result = migrate_handler(&mut server, "".to_string(), migrate_args(port, "a", 0, &[]));
+        assert_eq!(result, Some(Value::SimpleString("OK".to_string())));
+        let result = migrate_handler(
+            &mut server,
+            "".to_string(),
+            migrate_args(port, "", 0, &["KEYS", "b", "c"]),
+        );
+        assert_eq!(result, Some(Value::SimpleString("OK".to_string())));
+        assert!(server.cache.lock().unwrap().is_empty());
+
+        let received = received.lock().unwrap();
+        let second: Vec<&str> = received
+            .iter()
+            .filter(|(connection, _)| *connection == 1)
+            .map(|(_, command)| command.as_str())
+            .collect();
+        assert_eq!(second, vec!["SELECT 0", "RESTORE c"]);
+    }
+
+    #[test]
+    fn test_migrate_handler_keeps_keys_changed_during_transfer() {
+        let mut server = setup_server();
+        set(&mut server, "a", "old");
+        set(&mut server, "b", "old");
+        let mut source = server.clone();
+        let port = spawn_fake_target(move |_, command| {
+            // Written while MIGRATE waits for the target, so the lock is free.
+            if command[0] == "RESTORE" && command[1] == "a" {
+                set(&mut source, "a", "new");
+            }
+            Some("+OK".to_string())
+        });
+
+        let result = migrate_handler(
+            &mut server,
+            "".to_string(),
+            migrate_args(port, "", 0, &["KEYS", "a", "b"]),
+        );
+        assert_eq!(result, Some(Value::SimpleString("OK".to_string())));
+        assert_eq!(
+            get_handler(&mut server, "a".to_string(), vec![]),
+            Some(Value::BulkString("new".to_string()))
+        );
+        assert!(!server.cache.lock().unwrap().contains_key("b"));
+    }
+}
@@ -1,8 +1,23 @@
+pub mod copy_handler_test;
+pub mod dbsize_handler_test;
 pub mod del_handler_test;
+pub mod dump_handler_test;
+pub mod exists_handler_test;
 pub mod expire_handler_test;
 pub mod get_handler_test;
+pub mod incr_handler_test;
 pub mod keys_handler_test;
+pub mod migrate_handler_test;
+pub mod move_handler_test;
+pub mod object_handler_test;
+pub mod randomkey_handler_test;
 pub mod rename_handler_test;
+pub mod renamenx_handler_test;
+pub mod restore_handler_test;
+pub mod scan_handler_test;
 pub mod set_handler_test;
+pub mod sort_handler_test;
+pub mod sort_ro_handler_test;
+pub mod touch_handler_test;
 pub mod type_handler_test;
 pub mod unlink_handler_test;
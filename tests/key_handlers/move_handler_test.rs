@@ -0,0 +1,59 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{move_handler, set_handler};
+    use redis_starter_rust::models::value::Value;
+
+    use crate::setup::setup_server;
+
+    fn set(server: &mut redis_starter_rust::server::Server, key: &str) {
+        set_handler(
+            server,
+            key.to_string(),
+            vec![Value::BulkString("v".to_string())],
+        );
+    }
+
+    #[test]
+    fn test_move_handler() {
+        let mut server = setup_server();
+        set(&mut server, "key");
+
+        let result = move_handler(&mut server, "key".to_string(), vec![Value::Integer(1)]);
+        assert_eq!(result, Some(Value::Integer(1)));
+        assert!(!server.cache.lock().unwrap().contains_key("key"));
+        assert!(server.databases[1].lock().unwrap().contains_key("key"));
+    }
+
+    #[test]
+    fn test_move_handler_missing_or_existing_target() {
+        let mut server = setup_server();
+        let result = move_handler(&mut server, "key".to_string(), vec![Value::Integer(1)]);
+        assert_eq!(result, Some(Value::Integer(0)));
+
+        set(&mut server, "key");
+        server.select_db(1);
+        set(&mut server, "key");
+        server.select_db(0);
+
+        let result = move_handler(&mut server, "key".to_string(), vec![Value::Integer(1)]);
+        assert_eq!(result, Some(Value::Integer(0)));
+        assert!(server.cache.lock().unwrap().contains_key("key"));
+    }
+
+    #[test]
+    fn test_move_handler_same_or_invalid_db() {
+        let mut server = setup_server();
+        set(&mut server, "key");
+
+        assert_eq!(
+            move_handler(&mut server, "key".to_string(), vec![Value::Integer(0)]),
+            Some(Value::Error(
+                "ERR source and destination objects are the same".to_string()
+            ))
+        );
+        assert_eq!(
+            move_handler(&mut server, "key".to_string(), vec![Value::Integer(16)]),
+            Some(Value::Error("ERR DB index is out of range".to_string()))
+        );
+    }
+}
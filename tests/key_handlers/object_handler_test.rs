@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{hset_handler, lpush_handler, object_handler, set_handler};
+    use redis_starter_rust::models::redis_item::LFU_INIT_VAL;
+    use redis_starter_rust::models::value::Value;
+    use redis_starter_rust::server::Server;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    fn object(server: &mut Server, args: &[&str]) -> Option<Value> {
+        object_handler(server, "".to_string(), bulk(args))
+    }
+
+    #[test]
+    fn test_object_encoding() {
+        let mut server = setup_server();
+        set_handler(&mut server, "s".to_string(), bulk(&["v"]));
+        hset_handler(&mut server, "h".to_string(), bulk(&["f", "v"]));
+        lpush_handler(&mut server, "l".to_string(), bulk(&["v"]));
+
+        assert_eq!(
+            object(&mut server, &["ENCODING", "s"]),
+            Some(Value::BulkString("embstr".to_string()))
+        );
+        assert_eq!(
+            object(&mut server, &["encoding", "h"]),
+            Some(Value::BulkString("listpack".to_string()))
+        );
+        assert_eq!(
+            object(&mut server, &["ENCODING", "l"]),
+            Some(Value::BulkString("listpack".to_string()))
+        );
+        assert_eq!(
+            object(&mut server, &["ENCODING", "missing"]),
+            Some(Value::NullBulkString)
+        );
+    }
+
+    #[test]
+    fn test_object_idletime_freq_refcount() {
+        let mut server = setup_server();
+        set_handler(&mut server, "s".to_string(), bulk(&["v"]));
+        server
+            .cache
+            .lock()
+            .unwrap()
+            .get_mut("s")
+            .unwrap()
+            .access
+            .last_access -= 10_000;
+
+        assert_eq!(
+            object(&mut server, &["IDLETIME", "s"]),
+            Some(Value::Integer(10))
+        );
+        assert_eq!(
+            object(&mut server, &["FREQ", "s"]),
+            Some(Value::Integer(LFU_INIT_VAL as i64))
+        );
+        assert_eq!(
+            object(&mut server, &["REFCOUNT", "s"]),
+            Some(Value::Integer(1))
+        );
+
+        server.touch_key("s");
+        assert_eq!(
+            object(&mut server, &["IDLETIME", "s"]),
+            Some(Value::Integer(0))
+        );
+    }
+
+    #[test]
+    fn test_object_frequency_grows_with_access() {
+        let mut server = setup_server();
+        set_handler(&mut server, "s".to_string(), bulk(&["v"]));
+        for _ in 0..1000 {
+            server.touch_key("s");
+        }
+        match object(&mut server, &["FREQ", "s"]) {
+            Some(Value::Integer(freq)) => assert!(freq > LFU_INIT_VAL as i64 && freq < 255),
+            other => panic!("Expected integer, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_object_unknown_subcommand() {
+        let mut server = setup_server();
+        assert_eq!(
+            object(&mut server, &["nope", "s"]),
+            Some(Value::Error(
+                "ERR unknown subcommand or wrong number of arguments for 'nope'. Try OBJECT HELP."
+                    .to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,25 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{randomkey_handler, set_handler};
+    use redis_starter_rust::models::value::Value;
+
+    use crate::setup::setup_server;
+
+    #[test]
+    fn test_randomkey_handler() {
+        let mut server = setup_server();
+        assert_eq!(randomkey_handler(&mut server), Some(Value::NullBulkString));
+
+        for key in ["a", "b", "c"] {
+            set_handler(
+                &mut server,
+                key.to_string(),
+                vec![Value::BulkString("v".to_string())],
+            );
+        }
+        match randomkey_handler(&mut server) {
+            Some(Value::BulkString(key)) => assert!(["a", "b", "c"].contains(&key.as_str())),
+            other => panic!("Expected a key, got {:?}", other),
+        }
+    }
+}
@@ -22,6 +22,8 @@ mod tests {
                 created_at: fixed_instant.elapsed().as_secs() as i64,
                 expiration: None,
                 redis_type: RedisType::String,
+                field_expirations: Default::default(),
+                access: Default::default(),
             },
         );
 
@@ -36,7 +38,7 @@ mod tests {
     fn test_rename_success() {
         let mut server = setup();
 
-        let args = vec![bulk_string("old_key"), bulk_string("new_key")];
+        let args = vec![bulk_string("new_key")];
         let result = rename_handler(&mut server, "old_key".to_string(), args);
         assert_eq!(result, Some(Value::SimpleString("OK".to_string())));
 
@@ -44,8 +46,8 @@ mod tests {
         assert!(cache.contains_key("new_key"));
         assert!(!cache.contains_key("old_key"));
         assert_eq!(
-            cache.get("new_key").map(|item| &item.value),
-            Some(&Value::BulkString("some string".to_string()))
+            cache.get("new_key").map(|item| item.value.unpacked().into_owned()),
+            Some(Value::BulkString("some string".to_string()))
         );
     }
 
@@ -74,8 +76,63 @@ mod tests {
         assert!(cache.contains_key("new_key"));
         assert!(!cache.contains_key("old_key"));
         assert_eq!(
-            cache.get("new_key").map(|item| &item.value),
-            Some(&Value::BulkString("some string".to_string()))
+            cache.get("new_key").map(|item| item.value.unpacked().into_owned()),
+            Some(Value::BulkString("some string".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_rename_preserves_ttl() {
+        let mut server = setup();
+        let created_at = {
+            let mut cache = server.cache.lock().unwrap();
+            let item = cache.get_mut("old_key").unwrap();
+            item.expiration = Some(i64::MAX / 2);
+            item.created_at
+        };
+
+        let result = rename_handler(
+            &mut server,
+            "old_key".to_string(),
+            vec![bulk_string("ttl_key")],
+        );
+        assert_eq!(result, Some(Value::SimpleString("OK".to_string())));
+
+        let cache = server.cache.lock().unwrap();
+        let item = cache.get("ttl_key").unwrap();
+        assert_eq!(item.expiration, Some(i64::MAX / 2));
+        assert_eq!(item.created_at, created_at);
+    }
+
+    #[test]
+    fn test_rename_expired_key() {
+        let mut server = setup();
+        server
+            .cache
+            .lock()
+            .unwrap()
+            .get_mut("old_key")
+            .unwrap()
+            .expiration = Some(0);
+
+        let result = rename_handler(
+            &mut server,
+            "old_key".to_string(),
+            vec![bulk_string("new_key")],
+        );
+        assert_eq!(result, Some(Value::Error("ERR no such key".to_string())));
+        assert!(!server.cache.lock().unwrap().contains_key("new_key"));
+    }
+
+    #[test]
+    fn test_rename_missing_argument() {
+        let mut server = setup();
+        let result = rename_handler(&mut server, "old_key".to_string(), vec![]);
+        assert_eq!(
+            result,
+            Some(Value::Error(
+                "ERR wrong number of arguments for 'rename' command".to_string()
+            ))
         );
     }
 }
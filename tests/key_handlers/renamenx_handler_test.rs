@@ -0,0 +1,39 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{get_handler, renamenx_handler, set_handler};
+    use redis_starter_rust::models::value::Value;
+
+    use crate::setup::setup_server;
+
+    fn bulk(value: &str) -> Value {
+        Value::BulkString(value.to_string())
+    }
+
+    #[test]
+    fn test_renamenx_handler() {
+        let mut server = setup_server();
+        set_handler(&mut server, "a".to_string(), vec![bulk("1")]);
+        set_handler(&mut server, "b".to_string(), vec![bulk("2")]);
+
+        let result = renamenx_handler(&mut server, "a".to_string(), vec![bulk("b")]);
+        assert_eq!(result, Some(Value::Integer(0)));
+        assert_eq!(
+            get_handler(&mut server, "b".to_string(), vec![]),
+            Some(bulk("2"))
+        );
+
+        let result = renamenx_handler(&mut server, "a".to_string(), vec![bulk("c")]);
+        assert_eq!(result, Some(Value::Integer(1)));
+        assert_eq!(
+            get_handler(&mut server, "c".to_string(), vec![]),
+            Some(bulk("1"))
+        );
+    }
+
+    #[test]
+    fn test_renamenx_handler_missing_key() {
+        let mut server = setup_server();
+        let result = renamenx_handler(&mut server, "missing".to_string(), vec![bulk("b")]);
+        assert_eq!(result, Some(Value::Error("ERR no such key".to_string())));
+    }
+}
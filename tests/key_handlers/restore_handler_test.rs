@@ -0,0 +1,140 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{dump_handler, get_handler, restore_handler, set_handler};
+    use redis_starter_rust::models::value::Value;
+    use redis_starter_rust::server::Server;
+    use redis_starter_rust::utilities::now_millis;
+
+    use crate::setup::setup_server;
+
+    fn setup() -> (Server, Vec<u8>) {
+        let mut server = setup_server();
+        set_handler(
+            &mut server,
+            "src".to_string(),
+            vec![Value::BulkString("hello".to_string())],
+        );
+        let payload = match dump_handler(&mut server, "src".to_string(), vec![]) {
+            Some(Value::Bytes(payload)) => payload,
+            other => panic!("unexpected reply: {:?}", other),
+        };
+        (server, payload)
+    }
+
+    fn args(ttl: i64, payload: &[u8], options: &[&str]) -> Vec<Value> {
+        let mut args = vec![
+            Value::BulkString(ttl.to_string()),
+            Value::Bytes(payload.to_vec()),
+        ];
+        args.extend(options.iter().map(|o| Value::BulkString(o.to_string())));
+        args
+    }
+
+    #[test]
+    fn test_restore_handler() {
+        let (mut server, payload) = setup();
+
+        let result = restore_handler(&mut server, "dst".to_string(), args(0, &payload, &[]));
+        assert_eq!(result, Some(Value::SimpleString("OK".to_string())));
+        assert_eq!(
+            get_handler(&mut server, "dst".to_string(), vec![]),
+            Some(Value::BulkString("hello".to_string()))
+        );
+        assert_eq!(server.cache.lock().unwrap()["dst"].expiration, None);
+    }
+
+    #[test]
+    fn test_restore_handler_busy_key_and_replace() {
+        let (mut server, payload) = setup();
+
+        assert_eq!(
+            restore_handler(&mut server, "src".to_string(), args(0, &payload, &[])),
+            Some(Value::Error(
+                "BUSYKEY Target key name already exists.".to_string()
+            ))
+        );
+        assert_eq!(
+            restore_handler(
+                &mut server,
+                "src".to_string(),
+                args(0, &payload, &["REPLACE"])
+            ),
+            Some(Value::SimpleString("OK".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_restore_handler_ttl() {
+        let (mut server, payload) = setup();
+
+        restore_handler(&mut server, "rel".to_string(), args(5_500, &payload, &[]));
+        assert_eq!(server.cache.lock().unwrap()["rel"].expiration, Some(6));
+
+        let at = now_millis() + 60_000;
+        restore_handler(
+            &mut server,
+            "abs".to_string(),
+            args(at, &payload, &["ABSTTL"]),
+        );
+        assert_eq!(server.cache.lock().unwrap()["abs"].expiration, Some(60));
+
+        // An absolute TTL in the past does not create the key.
+        let result = restore_handler(
+            &mut server,
+            "past".to_string(),
+            args(1, &payload, &["ABSTTL"]),
+        );
+        assert_eq!(result, Some(Value::SimpleString("OK".to_string())));
+        assert!(!server.cache.lock().unwrap().contains_key("past"));
+    }
+
+    #[test]
+    fn test_restore_handler_idletime_and_freq() {
+        let (mut server, payload) = setup();
+
+        restore_handler(
+            &mut server,
+            "idle".to_string(),
+            args(0, &payload, &["IDLETIME", "100"]),
+        );
+        restore_handler(
+            &mut server,
+            "freq".to_string(),
+            args(0, &payload, &["FREQ", "42"]),
+        );
+
+        let cache = server.cache.lock().unwrap();
+        assert!(cache["idle"].access.idle_seconds() >= 100);
+        assert_eq!(cache["freq"].access.frequency, 42);
+    }
+
+    #[test]
+    fn test_restore_handler_errors() {
+        let (mut server, payload) = setup();
+
+        let cases = [
+            (
+                args(-1, &payload, &[]),
+                "ERR Invalid TTL value, must be >= 0",
+            ),
+            (
+                args(0, &payload, &["IDLETIME", "1", "FREQ", "1"]),
+                "ERR syntax error",
+            ),
+            (
+                args(0, &payload, &["FREQ", "256"]),
+                "ERR Invalid FREQ value, must be >= 0 and <= 255",
+            ),
+            (
+                args(0, b"not a payload", &[]),
+                "ERR DUMP payload version or checksum are wrong",
+            ),
+        ];
+        for (args, error) in cases {
+            assert_eq!(
+                restore_handler(&mut server, "dst".to_string(), args),
+                Some(Value::Error(error.to_string()))
+            );
+        }
+    }
+}
@@ -0,0 +1,160 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use redis_starter_rust::handlers::{hset_handler, scan_handler, set_handler};
+    use redis_starter_rust::models::value::Value;
+    use redis_starter_rust::server::Server;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    fn scan(server: &mut Server, args: Vec<Value>) -> (String, Vec<String>) {
+        match scan_handler(server, "".to_string(), args) {
+            Some(Value::Array(reply)) => match (&reply[0], &reply[1]) {
+                (Value::BulkString(cursor), Value::Array(keys)) => (
+                    cursor.clone(),
+                    keys.iter()
+                        .map(|key| match key {
+                            Value::BulkString(key) => key.clone(),
+                            other => panic!("Expected key, got {:?}", other),
+                        })
+                        .collect(),
+                ),
+                other => panic!("Unexpected reply {:?}", other),
+            },
+            other => panic!("Expected array, got {:?}", other),
+        }
+    }
+
+    fn set(server: &mut Server, key: &str) {
+        set_handler(server, key.to_string(), bulk(&["value"]));
+    }
+
+    #[test]
+    fn test_scan_handler_visits_every_key() {
+        let mut server = setup_server();
+        for i in 0..100 {
+            set(&mut server, &format!("key:{}", i));
+        }
+
+        let mut cursor = "0".to_string();
+        let mut seen = HashSet::new();
+        loop {
+            let (next, keys) = scan(&mut server, bulk(&[&cursor, "COUNT", "9"]));
+            assert!(keys.len() <= 9);
+            seen.extend(keys);
+            if next == "0" {
+                break;
+            }
+            cursor = next;
+        }
+        assert_eq!(seen.len(), 100);
+    }
+
+    #[test]
+    fn test_scan_handler_survives_resize() {
+        let mut server = setup_server();
+        for i in 0..50 {
+            set(&mut server, &format!("key:{}", i));
+        }
+
+        let mut cursor = "0".to_string();
+        let mut seen = HashSet::new();
+        let mut round = 0;
+        loop {
+            let (next, keys) = scan(&mut server, bulk(&[&cursor, "COUNT", "5"]));
+            seen.extend(keys);
+            // Grow the table well past its original capacity mid-iteration.
+            if round < 3 {
+                for i in 0..200 {
+                    set(&mut server, &format!("extra:{}:{}", round, i));
+                }
+            }
+            round += 1;
+            if next == "0" {
+                break;
+            }
+            cursor = next;
+        }
+
+        for i in 0..50 {
+            assert!(seen.contains(&format!("key:{}", i)), "missed key:{}", i);
+        }
+    }
+
+    #[test]
+    fn test_scan_handler_survives_deletes() {
+        let mut server = setup_server();
+        for i in 0..100 {
+            set(&mut server, &format!("key:{}", i));
+        }
+
+        let mut cursor = "0".to_string();
+        let mut seen = HashSet::new();
+        let mut round = 0;
+        loop {
+            let (next, keys) = scan(&mut server, bulk(&[&cursor, "COUNT", "10"]));
+            assert!(keys.len() <= 10);
+            seen.extend(keys);
+            // Deleting moves other keys around the table.
+            for i in 0..5 {
+                server
+                    .cache
+                    .lock()
+                    .unwrap()
+                    .remove(&format!("key:{}", round * 5 + i));
+            }
+            round += 1;
+            if next == "0" {
+                break;
+            }
+            cursor = next;
+        }
+
+        // Every key that was never deleted was returned.
+        for i in round * 5..100 {
+            assert!(seen.contains(&format!("key:{}", i)), "missed key:{}", i);
+        }
+    }
+
+    #[test]
+    fn test_scan_handler_match_and_type() {
+        let mut server = setup_server();
+        set(&mut server, "user:1");
+        set(&mut server, "user:2");
+        set(&mut server, "session:1");
+        hset_handler(&mut server, "user:hash".to_string(), bulk(&["f", "v"]));
+
+        let (cursor, mut keys) = scan(&mut server, bulk(&["0", "MATCH", "user:*", "COUNT", "100"]));
+        keys.sort();
+        assert_eq!(cursor, "0");
+        assert_eq!(keys, vec!["user:1", "user:2", "user:hash"]);
+
+        let (_, keys) = scan(&mut server, bulk(&["0", "COUNT", "100", "TYPE", "hash"]));
+        assert_eq!(keys, vec!["user:hash"]);
+    }
+
+    #[test]
+    fn test_scan_handler_errors() {
+        let mut server = setup_server();
+        assert_eq!(
+            scan_handler(&mut server, "".to_string(), bulk(&["nope"])),
+            Some(Value::Error("ERR invalid cursor".to_string()))
+        );
+        assert_eq!(
+            scan_handler(&mut server, "".to_string(), bulk(&["0", "COUNT", "0"])),
+            Some(Value::Error("ERR syntax error".to_string()))
+        );
+        assert_eq!(
+            scan_handler(&mut server, "".to_string(), bulk(&["0", "NOVALUES"])),
+            Some(Value::Error("ERR syntax error".to_string()))
+        );
+    }
+}
@@ -1,7 +1,6 @@
 #[cfg(test)]
 mod tests {
 
-
     use redis_starter_rust::handlers::set_handler;
     use redis_starter_rust::models::value::Value;
     use redis_starter_rust::server::Server;
@@ -14,11 +13,8 @@ mod tests {
     #[test]
     fn test_set_handler() {
         let mut server = setup();
-        let args = vec![
-            Value::BulkString("key".to_string()),
-            Value::BulkString("value".to_string()),
-        ];
-        let result = set_handler(&mut server, "key".to_string(),  args);
+        let args = vec![Value::BulkString("value".to_string())];
+        let result = set_handler(&mut server, "key".to_string(), args);
         assert_eq!(result, Some(Value::SimpleString("OK".to_string())));
         let cache = server.cache.lock().unwrap();
         assert!(cache.contains_key("key"));
@@ -28,14 +24,85 @@ mod tests {
     fn test_set_handler_with_expiration() {
         let mut server = setup();
         let args = vec![
-            Value::BulkString("key".to_string()),
             Value::BulkString("value".to_string()),
             Value::BulkString("px".to_string()),
             Value::BulkString("10".to_string()),
         ];
-        let result = set_handler(&mut server, "key".to_string(),  args);
+        let result = set_handler(&mut server, "key".to_string(), args);
         assert_eq!(result, Some(Value::SimpleString("OK".to_string())));
         let cache = server.cache.lock().unwrap();
         assert!(cache.contains_key("key"));
     }
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|value| Value::BulkString(value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_set_handler_ttl() {
+        let mut server = setup();
+        set_handler(&mut server, "key".to_string(), bulk(&["v", "EX", "100"]));
+        {
+            let cache = server.cache.lock().unwrap();
+            assert_eq!(cache.get("key").unwrap().expiration, Some(100));
+        }
+
+        // A plain SET clears the TTL, KEEPTTL keeps it.
+        set_handler(&mut server, "key".to_string(), bulk(&["v", "PX", "1500"]));
+        set_handler(&mut server, "key".to_string(), bulk(&["w", "KEEPTTL"]));
+        {
+            let cache = server.cache.lock().unwrap();
+            assert_eq!(cache.get("key").unwrap().expiration, Some(2));
+        }
+        set_handler(&mut server, "key".to_string(), bulk(&["x"]));
+        let cache = server.cache.lock().unwrap();
+        assert_eq!(cache.get("key").unwrap().expiration, None);
+    }
+
+    #[test]
+    fn test_set_handler_nx_xx_get() {
+        let mut server = setup();
+        let result = set_handler(&mut server, "key".to_string(), bulk(&["a", "XX"]));
+        assert_eq!(result, Some(Value::NullBulkString));
+        let result = set_handler(&mut server, "key".to_string(), bulk(&["a", "NX"]));
+        assert_eq!(result, Some(Value::SimpleString("OK".to_string())));
+        let result = set_handler(&mut server, "key".to_string(), bulk(&["b", "NX", "GET"]));
+        assert_eq!(result, Some(Value::BulkString("a".to_string())));
+        let result = set_handler(&mut server, "key".to_string(), bulk(&["c", "GET"]));
+        assert_eq!(result, Some(Value::BulkString("a".to_string())));
+    }
+
+    #[test]
+    fn test_set_handler_invalid_options() {
+        let mut server = setup();
+        for args in [
+            bulk(&["v", "NX", "XX"]),
+            bulk(&["v", "EX", "10", "PX", "10"]),
+            bulk(&["v", "EX", "10", "KEEPTTL"]),
+            bulk(&["v", "EX"]),
+            bulk(&["v", "BOGUS"]),
+        ] {
+            let result = set_handler(&mut server, "key".to_string(), args);
+            assert_eq!(result, Some(Value::Error("ERR syntax error".to_string())));
+        }
+
+        let result = set_handler(&mut server, "key".to_string(), bulk(&["v", "EX", "ten"]));
+        assert_eq!(
+            result,
+            Some(Value::Error(
+                "ERR value is not an integer or out of range".to_string()
+            ))
+        );
+        let result = set_handler(&mut server, "key".to_string(), bulk(&["v", "EX", "0"]));
+        assert_eq!(
+            result,
+            Some(Value::Error(
+                "ERR invalid expire time in 'set' command".to_string()
+            ))
+        );
+        assert!(server.cache.lock().unwrap().is_empty());
+    }
 }
@@ -0,0 +1,193 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{
+        hset_handler, lrange_handler, rpush_handler, sadd_handler, set_handler, sort_handler,
+        zadd_handler,
+    };
+    use redis_starter_rust::models::value::Value;
+    use redis_starter_rust::server::Server;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    fn nullable(values: &[Option<&str>]) -> Value {
+        Value::Array(
+            values
+                .iter()
+                .map(|v| match v {
+                    Some(v) => Value::BulkString(v.to_string()),
+                    None => Value::NullBulkString,
+                })
+                .collect(),
+        )
+    }
+
+    fn sort(server: &mut Server, key: &str, args: &[&str]) -> Option<Value> {
+        sort_handler(server, key.to_string(), bulk(args))
+    }
+
+    fn setup() -> Server {
+        let mut server = setup_server();
+        rpush_handler(&mut server, "ids".to_string(), bulk(&["3", "1", "10", "2"]));
+        for (id, name, age) in [("1", "ann", "40"), ("2", "bob", "25"), ("3", "cy", "31")] {
+            hset_handler(
+                &mut server,
+                format!("user:{}", id),
+                bulk(&["name", name, "age", age]),
+            );
+            set_handler(&mut server, format!("weight_{}", id), bulk(&[age]));
+        }
+        server
+    }
+
+    #[test]
+    fn test_sort_handler_numeric_and_desc() {
+        let mut server = setup();
+
+        assert_eq!(
+            sort(&mut server, "ids", &[]),
+            Some(Value::Array(bulk(&["1", "2", "3", "10"])))
+        );
+        assert_eq!(
+            sort(&mut server, "ids", &["DESC"]),
+            Some(Value::Array(bulk(&["10", "3", "2", "1"])))
+        );
+    }
+
+    #[test]
+    fn test_sort_handler_alpha_and_limit() {
+        let mut server = setup();
+
+        assert_eq!(
+            sort(&mut server, "ids", &["ALPHA"]),
+            Some(Value::Array(bulk(&["1", "10", "2", "3"])))
+        );
+        assert_eq!(
+            sort(&mut server, "ids", &["LIMIT", "1", "2"]),
+            Some(Value::Array(bulk(&["2", "3"])))
+        );
+        assert_eq!(
+            sort(&mut server, "ids", &["LIMIT", "3", "-1"]),
+            Some(Value::Array(bulk(&["10"])))
+        );
+    }
+
+    #[test]
+    fn test_sort_handler_by_and_get_patterns() {
+        let mut server = setup();
+
+        // id 10 has no weight and sorts first with a weight of 0.
+        assert_eq!(
+            sort(&mut server, "ids", &["BY", "weight_*"]),
+            Some(Value::Array(bulk(&["10", "2", "3", "1"])))
+        );
+        assert_eq!(
+            sort(
+                &mut server,
+                "ids",
+                &["BY", "user:*->age", "GET", "#", "GET", "user:*->name"]
+            ),
+            Some(nullable(&[
+                Some("10"),
+                None,
+                Some("2"),
+                Some("bob"),
+                Some("3"),
+                Some("cy"),
+                Some("1"),
+                Some("ann"),
+            ]))
+        );
+        assert_eq!(
+            sort(&mut server, "ids", &["BY", "user:*->name", "ALPHA", "DESC"]),
+            Some(Value::Array(bulk(&["3", "2", "1", "10"])))
+        );
+    }
+
+    #[test]
+    fn test_sort_handler_by_without_star_keeps_order() {
+        let mut server = setup();
+
+        assert_eq!(
+            sort(&mut server, "ids", &["BY", "nosort"]),
+            Some(Value::Array(bulk(&["3", "1", "10", "2"])))
+        );
+    }
+
+    #[test]
+    fn test_sort_handler_sets_and_zsets() {
+        let mut server = setup_server();
+        sadd_handler(&mut server, "set".to_string(), bulk(&["5", "-1", "2.5"]));
+        zadd_handler(
+            &mut server,
+            "zset".to_string(),
+            bulk(&["1", "b", "2", "a", "3", "c"]),
+        );
+
+        assert_eq!(
+            sort(&mut server, "set", &[]),
+            Some(Value::Array(bulk(&["-1", "2.5", "5"])))
+        );
+        assert_eq!(
+            sort(&mut server, "zset", &["ALPHA"]),
+            Some(Value::Array(bulk(&["a", "b", "c"])))
+        );
+        assert_eq!(
+            sort(&mut server, "zset", &["BY", "nosort", "DESC"]),
+            Some(Value::Array(bulk(&["c", "a", "b"])))
+        );
+    }
+
+    #[test]
+    fn test_sort_handler_store() {
+        let mut server = setup();
+
+        let result = sort(
+            &mut server,
+            "ids",
+            &["BY", "weight_*", "GET", "user:*->name", "STORE", "names"],
+        );
+        assert_eq!(result, Some(Value::Integer(4)));
+        assert_eq!(
+            lrange_handler(&mut server, "names".to_string(), bulk(&["0", "-1"])),
+            Some(Value::Array(bulk(&["", "bob", "cy", "ann"])))
+        );
+
+        let result = sort(&mut server, "missing", &["STORE", "names"]);
+        assert_eq!(result, Some(Value::Integer(0)));
+        assert!(!server.cache.lock().unwrap().contains_key("names"));
+    }
+
+    #[test]
+    fn test_sort_handler_errors() {
+        let mut server = setup();
+        rpush_handler(&mut server, "words".to_string(), bulk(&["a", "b"]));
+
+        assert_eq!(
+            sort(&mut server, "words", &[]),
+            Some(Value::Error(
+                "ERR One or more scores can't be converted into double".to_string()
+            ))
+        );
+        assert_eq!(
+            sort(&mut server, "user:1", &[]),
+            Some(Value::Error(
+                "WRONGTYPE Operation against a key holding the wrong kind of value".to_string()
+            ))
+        );
+        assert_eq!(
+            sort(&mut server, "ids", &["LIMIT", "1"]),
+            Some(Value::Error("ERR syntax error".to_string()))
+        );
+        assert_eq!(
+            sort(&mut server, "missing", &[]),
+            Some(Value::Array(vec![]))
+        );
+    }
+}
@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{rpush_handler, set_handler, sort_ro_handler};
+    use redis_starter_rust::models::value::Value;
+    use redis_starter_rust::server::Server;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_sort_ro_handler() {
+        let mut server = setup_server();
+        rpush_handler(&mut server, "list".to_string(), bulk(&["b", "c", "a"]));
+
+        let result = sort_ro_handler(&mut server, "list".to_string(), bulk(&["ALPHA", "DESC"]));
+        assert_eq!(result, Some(Value::Array(bulk(&["c", "b", "a"]))));
+    }
+
+    #[test]
+    fn test_sort_ro_handler_rejects_store() {
+        let mut server = setup_server();
+        rpush_handler(&mut server, "list".to_string(), bulk(&["1"]));
+
+        let result = sort_ro_handler(&mut server, "list".to_string(), bulk(&["STORE", "dst"]));
+        assert_eq!(result, Some(Value::Error("ERR syntax error".to_string())));
+        assert!(!server.cache.lock().unwrap().contains_key("dst"));
+    }
+
+    #[test]
+    fn test_sort_ro_handler_patterns_need_full_key_access() {
+        let mut server = setup_server();
+        rpush_handler(&mut server, "report:ids".to_string(), bulk(&["1"]));
+        set_handler(&mut server, "secret:1".to_string(), bulk(&["topsecret"]));
+        let rules: Vec<String> = ["on", ">pw", "~report:*", "+@read"]
+            .iter()
+            .map(|rule| rule.to_string())
+            .collect();
+        server
+            .acl
+            .lock()
+            .unwrap()
+            .set_user("analytics", &rules)
+            .unwrap();
+        server.user = "analytics".to_string();
+
+        let sort_ro = |server: &mut Server, args: &[&str]| {
+            sort_ro_handler(server, "report:ids".to_string(), bulk(args))
+        };
+        assert_eq!(
+            sort_ro(&mut server, &["BY", "nosort", "GET", "secret:*"]),
+            Some(Value::Error(
+                "ERR GET option of SORT denied due to insufficient ACL permissions.".to_string()
+            ))
+        );
+        assert_eq!(
+            sort_ro(&mut server, &["BY", "secret:*"]),
+            Some(Value::Error(
+                "ERR BY option of SORT denied due to insufficient ACL permissions.".to_string()
+            ))
+        );
+        assert_eq!(
+            sort_ro(&mut server, &["BY", "nosort"]),
+            Some(Value::Array(bulk(&["1"])))
+        );
+
+        server.user = "default".to_string();
+        assert_eq!(
+            sort_ro(&mut server, &["BY", "nosort", "GET", "secret:*"]),
+            Some(Value::Array(bulk(&["topsecret"])))
+        );
+    }
+}
@@ -0,0 +1,32 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{set_handler, touch_handler};
+    use redis_starter_rust::models::value::Value;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_touch_handler() {
+        let mut server = setup_server();
+        set_handler(&mut server, "a".to_string(), bulk(&["1"]));
+        server
+            .cache
+            .lock()
+            .unwrap()
+            .get_mut("a")
+            .unwrap()
+            .access
+            .last_access = 0;
+
+        let result = touch_handler(&mut server, "".to_string(), bulk(&["a", "missing"]));
+        assert_eq!(result, Some(Value::Integer(1)));
+        assert!(server.cache.lock().unwrap()["a"].access.last_access > 0);
+    }
+}
@@ -1,7 +1,6 @@
 #[cfg(test)]
 mod tests {
 
-
     use redis_starter_rust::handlers::{set_handler, type_handler};
     use redis_starter_rust::models::value::Value;
     use redis_starter_rust::server::Server;
@@ -14,10 +13,7 @@ mod tests {
     #[test]
     fn test_type_handler() {
         let mut server = setup();
-        let args = vec![
-            Value::BulkString("key".to_string()),
-            Value::BulkString("value".to_string()),
-        ];
+        let args = vec![Value::BulkString("value".to_string())];
         set_handler(&mut server, "key".to_string(), args);
         let args = vec![Value::BulkString("key".to_string())];
         let result = type_handler(&mut server, "key".to_string(), args);
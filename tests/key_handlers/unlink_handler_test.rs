@@ -2,6 +2,7 @@
 mod tests {
 
     use redis_starter_rust::handlers::{get_handler, set_handler, unlink_handler};
+    use redis_starter_rust::models::redis_item::RedisItem;
     use redis_starter_rust::models::value::Value;
     use redis_starter_rust::server::Server;
 
@@ -13,15 +14,9 @@ mod tests {
     #[test]
     fn test_unlink_handler() {
         let mut server = setup();
-        let args = vec![
-            Value::BulkString("key1".to_string()),
-            Value::BulkString("value1".to_string()),
-        ];
+        let args = vec![Value::BulkString("value1".to_string())];
         set_handler(&mut server, "key1".to_string(), args);
-        let args = vec![
-            Value::BulkString("key2".to_string()),
-            Value::BulkString("value2".to_string()),
-        ];
+        let args = vec![Value::BulkString("value2".to_string())];
         set_handler(&mut server, "key2".to_string(), args);
 
         let args = vec![
@@ -41,4 +36,22 @@ mod tests {
         let result = get_handler(&mut server, "key".to_string(), args);
         assert_eq!(result, Some(Value::NullBulkString));
     }
+
+    #[test]
+    fn test_unlink_handler_large_value() {
+        let mut server = setup();
+        let values: Vec<Value> = (0..1000)
+            .map(|i| Value::BulkString(i.to_string()))
+            .collect();
+        server
+            .cache
+            .lock()
+            .unwrap()
+            .insert("list".to_string(), RedisItem::new_list(values));
+
+        let args = vec![Value::BulkString("list".to_string())];
+        let result = unlink_handler(&mut server, "".to_string(), args);
+        assert_eq!(result, Some(Value::Integer(1)));
+        assert!(!server.cache.lock().unwrap().contains_key("list"));
+    }
 }
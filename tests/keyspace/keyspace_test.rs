@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::commands::dispatch;
+    use redis_starter_rust::keyspace::Keyspace;
+    use redis_starter_rust::models::redis_item::RedisItem;
+    use redis_starter_rust::models::value::Value;
+
+    use crate::setup::setup_server;
+
+    fn item(ttl: Option<i64>) -> RedisItem {
+        let mut item = RedisItem::new_string("value".to_string());
+        item.expiration = ttl;
+        item
+    }
+
+    fn volatile_keys(keyspace: &Keyspace) -> Vec<String> {
+        let mut keys: Vec<String> = (0..keyspace.expires())
+            .map(|index| keyspace.get_volatile_index(index).unwrap().0.clone())
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    #[test]
+    fn test_keyspace_tracks_keys_with_a_ttl() {
+        let mut keyspace = Keyspace::new();
+        keyspace.insert("a".to_string(), item(Some(10)));
+        keyspace.insert("b".to_string(), item(None));
+        keyspace.insert("c".to_string(), item(Some(10)));
+        assert_eq!(keyspace.expires(), 2);
+        assert_eq!(volatile_keys(&keyspace), ["a", "c"]);
+
+        // Overwriting without a TTL, and clearing one, make a key persistent.
+        keyspace.insert("a".to_string(), item(None));
+        assert!(keyspace.set_expiration("b", Some(5)));
+        assert!(keyspace.set_expiration("c", None));
+        assert!(!keyspace.set_expiration("missing", Some(5)));
+        assert_eq!(volatile_keys(&keyspace), ["b"]);
+
+        keyspace.remove("b");
+        assert_eq!(keyspace.expires(), 0);
+
+        keyspace.insert("d".to_string(), item(Some(1)));
+        keyspace.insert("e".to_string(), item(Some(1)));
+        keyspace.retain(|key, _| key != "d");
+        assert_eq!(volatile_keys(&keyspace), ["e"]);
+        assert_eq!(keyspace.len(), 3);
+
+        keyspace.clear();
+        assert_eq!(keyspace.expires(), 0);
+    }
+
+    #[test]
+    fn test_keyspace_remove_keeps_positions_dense() {
+        let mut keyspace: Keyspace = (0..5).map(|i| (i.to_string(), item(None))).collect();
+        keyspace.remove("1");
+        // The last key fills the hole.
+        let keys: Vec<&String> = (0..keyspace.len())
+            .map(|index| keyspace.get_index(index).unwrap().0)
+            .collect();
+        assert_eq!(keys, ["0", "4", "2", "3"]);
+        assert!(keyspace.get_index(4).is_none());
+    }
+
+    #[test]
+    fn test_keyspace_serde_rebuilds_ttl_index() {
+        let keyspace: Keyspace = [("a", Some(10)), ("b", None)]
+            .into_iter()
+            .map(|(key, ttl)| (key.to_string(), item(ttl)))
+            .collect();
+        let bytes = bincode::serialize(&keyspace).unwrap();
+        let restored: Keyspace = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(restored.len(), 2);
+        assert_eq!(volatile_keys(&restored), ["a"]);
+    }
+
+    #[test]
+    fn test_expire_counts_keys_with_a_ttl() {
+        let mut server = setup_server();
+        let args = |args: &[&str]| -> Vec<Value> {
+            args.iter()
+                .map(|arg| Value::BulkString(arg.to_string()))
+                .collect()
+        };
+        dispatch(&mut server, "SET", args(&["a", "1"]));
+        dispatch(&mut server, "SET", args(&["b", "1", "EX", "100"]));
+        dispatch(&mut server, "EXPIRE", args(&["a", "100"]));
+        assert_eq!(server.cache.lock().unwrap().expires(), 2);
+
+        dispatch(&mut server, "SET", args(&["a", "2"]));
+        dispatch(&mut server, "DEL", args(&["b"]));
+        assert_eq!(server.cache.lock().unwrap().expires(), 0);
+    }
+}
@@ -0,0 +1 @@
+pub mod keyspace_test;
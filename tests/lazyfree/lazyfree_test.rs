@@ -0,0 +1,52 @@
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use redis_starter_rust::keyspace::Keyspace;
+    use redis_starter_rust::lazyfree::{LazyFree, LAZYFREE_THRESHOLD};
+    use redis_starter_rust::models::redis_item::RedisItem;
+    use redis_starter_rust::models::value::Value;
+
+    fn list_item(len: usize) -> RedisItem {
+        RedisItem::new_list(
+            (0..len)
+                .map(|i| Value::BulkString(i.to_string()))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    fn wait_until_idle(lazyfree: &LazyFree) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while lazyfree.pending_objects() > 0 {
+            assert!(Instant::now() < deadline, "lazy-free worker stalled");
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn test_free_item_drains() {
+        let lazyfree = LazyFree::default();
+        for _ in 0..10 {
+            lazyfree.free_item(list_item(LAZYFREE_THRESHOLD * 10));
+        }
+        wait_until_idle(&lazyfree);
+        assert_eq!(lazyfree.pending_objects(), 0);
+    }
+
+    #[test]
+    fn test_small_items_are_freed_inline() {
+        let lazyfree = LazyFree::default();
+        lazyfree.free_item(list_item(1));
+        assert_eq!(lazyfree.pending_objects(), 0);
+    }
+
+    #[test]
+    fn test_free_keyspace_drains() {
+        let lazyfree = LazyFree::default();
+        let keyspace: Keyspace = (0..100)
+            .map(|i| (i.to_string(), RedisItem::new_string("v".to_string())))
+            .collect();
+        lazyfree.free_keyspace(keyspace);
+        wait_until_idle(&lazyfree);
+    }
+}
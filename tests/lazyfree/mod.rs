@@ -0,0 +1 @@
+pub mod lazyfree_test;
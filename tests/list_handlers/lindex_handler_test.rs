@@ -1,5 +1,6 @@
 #[cfg(test)]
 mod tests {
+    use std::collections::VecDeque;
 
     use std::time::Instant;
 
@@ -21,10 +22,12 @@ mod tests {
             Value::BulkString("value3".to_string()),
         ];
         let redis_item = RedisItem {
-            value: Value::Array(list),
+            value: Value::List(VecDeque::from(list)),
             created_at: Instant::now().elapsed().as_secs() as i64,
             expiration: None,
             redis_type: RedisType::List,
+            field_expirations: Default::default(),
+            access: Default::default(),
         };
         server.cache.lock().unwrap().insert(key.clone(), redis_item);
 
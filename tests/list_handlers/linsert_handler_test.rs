@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{linsert_handler, lrange_handler, rpush_handler};
+    use redis_starter_rust::models::value::Value;
+    use redis_starter_rust::server::Server;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    fn setup() -> Server {
+        let mut server = setup_server();
+        rpush_handler(&mut server, "key".to_string(), bulk(&["a", "c"]));
+        server
+    }
+
+    #[test]
+    fn test_linsert_handler_before() {
+        let mut server = setup();
+        let result = linsert_handler(&mut server, "key".to_string(), bulk(&["BEFORE", "c", "b"]));
+        assert_eq!(result, Some(Value::Integer(3)));
+
+        let result = lrange_handler(&mut server, "key".to_string(), bulk(&["0", "-1"]));
+        assert_eq!(result, Some(Value::Array(bulk(&["a", "b", "c"]))));
+    }
+
+    #[test]
+    fn test_linsert_handler_after() {
+        let mut server = setup();
+        let result = linsert_handler(&mut server, "key".to_string(), bulk(&["after", "c", "d"]));
+        assert_eq!(result, Some(Value::Integer(3)));
+
+        let result = lrange_handler(&mut server, "key".to_string(), bulk(&["0", "-1"]));
+        assert_eq!(result, Some(Value::Array(bulk(&["a", "c", "d"]))));
+    }
+
+    #[test]
+    fn test_linsert_handler_pivot_not_found() {
+        let mut server = setup();
+        let result = linsert_handler(&mut server, "key".to_string(), bulk(&["BEFORE", "z", "b"]));
+        assert_eq!(result, Some(Value::Integer(-1)));
+    }
+
+    #[test]
+    fn test_linsert_handler_missing_key() {
+        let mut server = setup();
+        let result = linsert_handler(
+            &mut server,
+            "missing".to_string(),
+            bulk(&["BEFORE", "a", "b"]),
+        );
+        assert_eq!(result, Some(Value::Integer(0)));
+    }
+
+    #[test]
+    fn test_linsert_handler_syntax_error() {
+        let mut server = setup();
+        let result = linsert_handler(&mut server, "key".to_string(), bulk(&["NEAR", "a", "b"]));
+        assert_eq!(result, Some(Value::Error("ERR syntax error".to_string())));
+    }
+}
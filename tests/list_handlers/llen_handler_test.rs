@@ -1,5 +1,6 @@
 #[cfg(test)]
 mod tests {
+    use std::collections::VecDeque;
     use std::time::Instant;
 
     use redis_starter_rust::handlers::llen_handler;
@@ -18,10 +19,12 @@ mod tests {
             Value::BulkString("value3".to_string()),
         ];
         let redis_item = RedisItem {
-            value: Value::Array(list),
+            value: Value::List(VecDeque::from(list)),
             created_at: Instant::now().elapsed().as_secs() as i64,
             expiration: None,
             redis_type: RedisType::List,
+            field_expirations: Default::default(),
+            access: Default::default(),
         };
         server.cache.lock().unwrap().insert(key.clone(), redis_item);
         server
@@ -54,6 +57,8 @@ mod tests {
             created_at: Instant::now().elapsed().as_secs() as i64,
             expiration: None,
             redis_type: RedisType::String,
+            field_expirations: Default::default(),
+            access: Default::default(),
         };
         server.cache.lock().unwrap().insert(key.clone(), redis_item);
         let args = vec![Value::BulkString(key.clone())];
@@ -61,7 +66,7 @@ mod tests {
         assert_eq!(
             result,
             Some(Value::Error(
-                "ERR operation against a key holding the wrong kind of value".to_string()
+                "WRONGTYPE Operation against a key holding the wrong kind of value".to_string()
             ))
         );
     }
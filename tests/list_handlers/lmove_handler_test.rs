@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{lmove_handler, lrange_handler, rpush_handler, set_handler};
+    use redis_starter_rust::models::value::Value;
+    use redis_starter_rust::server::Server;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    fn setup() -> Server {
+        let mut server = setup_server();
+        rpush_handler(&mut server, "source".to_string(), bulk(&["a", "b", "c"]));
+        server
+    }
+
+    #[test]
+    fn test_lmove_handler_to_new_list() {
+        let mut server = setup();
+        let result = lmove_handler(
+            &mut server,
+            "source".to_string(),
+            bulk(&["destination", "LEFT", "RIGHT"]),
+        );
+        assert_eq!(result, Some(Value::BulkString("a".to_string())));
+
+        let result = lrange_handler(&mut server, "destination".to_string(), bulk(&["0", "-1"]));
+        assert_eq!(result, Some(Value::Array(bulk(&["a"]))));
+        let result = lrange_handler(&mut server, "source".to_string(), bulk(&["0", "-1"]));
+        assert_eq!(result, Some(Value::Array(bulk(&["b", "c"]))));
+    }
+
+    #[test]
+    fn test_lmove_handler_rotate_same_list() {
+        let mut server = setup();
+        let result = lmove_handler(
+            &mut server,
+            "source".to_string(),
+            bulk(&["source", "RIGHT", "LEFT"]),
+        );
+        assert_eq!(result, Some(Value::BulkString("c".to_string())));
+
+        let result = lrange_handler(&mut server, "source".to_string(), bulk(&["0", "-1"]));
+        assert_eq!(result, Some(Value::Array(bulk(&["c", "a", "b"]))));
+    }
+
+    #[test]
+    fn test_lmove_handler_missing_source() {
+        let mut server = setup();
+        let result = lmove_handler(
+            &mut server,
+            "missing".to_string(),
+            bulk(&["destination", "LEFT", "LEFT"]),
+        );
+        assert_eq!(result, Some(Value::NullBulkString));
+        assert!(!server.cache.lock().unwrap().contains_key("destination"));
+    }
+
+    #[test]
+    fn test_lmove_handler_wrong_type_destination() {
+        let mut server = setup();
+        set_handler(&mut server, "string".to_string(), bulk(&["value"]));
+        let result = lmove_handler(
+            &mut server,
+            "source".to_string(),
+            bulk(&["string", "LEFT", "LEFT"]),
+        );
+        assert_eq!(
+            result,
+            Some(Value::Error(
+                "WRONGTYPE Operation against a key holding the wrong kind of value".to_string()
+            ))
+        );
+
+        let result = lrange_handler(&mut server, "source".to_string(), bulk(&["0", "-1"]));
+        assert_eq!(result, Some(Value::Array(bulk(&["a", "b", "c"]))));
+    }
+
+    #[test]
+    fn test_lmove_handler_syntax_error() {
+        let mut server = setup();
+        let result = lmove_handler(
+            &mut server,
+            "source".to_string(),
+            bulk(&["destination", "UP", "LEFT"]),
+        );
+        assert_eq!(result, Some(Value::Error("ERR syntax error".to_string())));
+    }
+}
@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{lmpop_handler, rpush_handler};
+    use redis_starter_rust::models::value::Value;
+    use redis_starter_rust::server::Server;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    fn setup() -> Server {
+        let mut server = setup_server();
+        rpush_handler(&mut server, "second".to_string(), bulk(&["a", "b", "c"]));
+        server
+    }
+
+    #[test]
+    fn test_lmpop_handler_first_non_empty() {
+        let mut server = setup();
+        let result = lmpop_handler(
+            &mut server,
+            "".to_string(),
+            bulk(&["2", "first", "second", "LEFT"]),
+        );
+        assert_eq!(
+            result,
+            Some(Value::Array(vec![
+                Value::BulkString("second".to_string()),
+                Value::Array(bulk(&["a"])),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_lmpop_handler_count() {
+        let mut server = setup();
+        let result = lmpop_handler(
+            &mut server,
+            "".to_string(),
+            bulk(&["1", "second", "RIGHT", "COUNT", "5"]),
+        );
+        assert_eq!(
+            result,
+            Some(Value::Array(vec![
+                Value::BulkString("second".to_string()),
+                Value::Array(bulk(&["c", "b", "a"])),
+            ]))
+        );
+        assert!(!server.cache.lock().unwrap().contains_key("second"));
+    }
+
+    #[test]
+    fn test_lmpop_handler_all_empty() {
+        let mut server = setup();
+        let result = lmpop_handler(
+            &mut server,
+            "".to_string(),
+            bulk(&["2", "first", "third", "LEFT"]),
+        );
+        assert_eq!(result, Some(Value::NullBulkString));
+    }
+
+    #[test]
+    fn test_lmpop_handler_invalid_numkeys() {
+        let mut server = setup();
+        let result = lmpop_handler(&mut server, "".to_string(), bulk(&["0", "second", "LEFT"]));
+        assert_eq!(
+            result,
+            Some(Value::Error(
+                "ERR numkeys should be greater than 0".to_string()
+            ))
+        );
+
+        let result = lmpop_handler(&mut server, "".to_string(), bulk(&["3", "second", "LEFT"]));
+        assert_eq!(result, Some(Value::Error("ERR syntax error".to_string())));
+    }
+}
@@ -1,7 +1,6 @@
 #[cfg(test)]
 mod tests {
 
-
     use redis_starter_rust::handlers::{lpop_handler, rpush_handler};
     use redis_starter_rust::models::value::Value;
     use redis_starter_rust::server::Server;
@@ -24,4 +23,47 @@ mod tests {
         let result = lpop_handler(&mut server, key.clone(), args);
         assert_eq!(result, Some(Value::BulkString("initial".to_string())));
     }
+
+    #[test]
+    fn test_lpop_handler_with_count() {
+        let mut server = setup();
+        let key = "key".to_string();
+        let initial_list = vec![
+            Value::BulkString("a".to_string()),
+            Value::BulkString("b".to_string()),
+            Value::BulkString("c".to_string()),
+        ];
+        rpush_handler(&mut server, key.clone(), initial_list);
+
+        let result = lpop_handler(
+            &mut server,
+            key.clone(),
+            vec![Value::BulkString("2".to_string())],
+        );
+        assert_eq!(
+            result,
+            Some(Value::Array(vec![
+                Value::BulkString("a".to_string()),
+                Value::BulkString("b".to_string()),
+            ]))
+        );
+
+        let result = lpop_handler(
+            &mut server,
+            key.clone(),
+            vec![Value::BulkString("5".to_string())],
+        );
+        assert_eq!(
+            result,
+            Some(Value::Array(vec![Value::BulkString("c".to_string())]))
+        );
+        assert!(!server.cache.lock().unwrap().contains_key(&key));
+    }
+
+    #[test]
+    fn test_lpop_handler_missing_key() {
+        let mut server = setup();
+        let result = lpop_handler(&mut server, "missing".to_string(), vec![]);
+        assert_eq!(result, Some(Value::NullBulkString));
+    }
 }
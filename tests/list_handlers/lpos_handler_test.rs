@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{lpos_handler, rpush_handler};
+    use redis_starter_rust::models::value::Value;
+    use redis_starter_rust::server::Server;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    fn setup() -> Server {
+        let mut server = setup_server();
+        rpush_handler(
+            &mut server,
+            "key".to_string(),
+            bulk(&["a", "b", "c", "1", "2", "3", "c", "c"]),
+        );
+        server
+    }
+
+    #[test]
+    fn test_lpos_handler_first_match() {
+        let mut server = setup();
+        let result = lpos_handler(&mut server, "key".to_string(), bulk(&["c"]));
+        assert_eq!(result, Some(Value::Integer(2)));
+    }
+
+    #[test]
+    fn test_lpos_handler_rank() {
+        let mut server = setup();
+        let result = lpos_handler(&mut server, "key".to_string(), bulk(&["c", "RANK", "2"]));
+        assert_eq!(result, Some(Value::Integer(6)));
+
+        let result = lpos_handler(&mut server, "key".to_string(), bulk(&["c", "RANK", "-1"]));
+        assert_eq!(result, Some(Value::Integer(7)));
+    }
+
+    #[test]
+    fn test_lpos_handler_count() {
+        let mut server = setup();
+        let result = lpos_handler(&mut server, "key".to_string(), bulk(&["c", "COUNT", "2"]));
+        assert_eq!(
+            result,
+            Some(Value::Array(vec![Value::Integer(2), Value::Integer(6)]))
+        );
+
+        let result = lpos_handler(&mut server, "key".to_string(), bulk(&["c", "COUNT", "0"]));
+        assert_eq!(
+            result,
+            Some(Value::Array(vec![
+                Value::Integer(2),
+                Value::Integer(6),
+                Value::Integer(7)
+            ]))
+        );
+
+        let result = lpos_handler(
+            &mut server,
+            "key".to_string(),
+            bulk(&["c", "RANK", "-1", "COUNT", "2"]),
+        );
+        assert_eq!(
+            result,
+            Some(Value::Array(vec![Value::Integer(7), Value::Integer(6)]))
+        );
+    }
+
+    #[test]
+    fn test_lpos_handler_maxlen() {
+        let mut server = setup();
+        let result = lpos_handler(
+            &mut server,
+            "key".to_string(),
+            bulk(&["c", "COUNT", "0", "MAXLEN", "5"]),
+        );
+        assert_eq!(result, Some(Value::Array(vec![Value::Integer(2)])));
+    }
+
+    #[test]
+    fn test_lpos_handler_no_match() {
+        let mut server = setup();
+        let result = lpos_handler(&mut server, "key".to_string(), bulk(&["z"]));
+        assert_eq!(result, Some(Value::NullBulkString));
+
+        let result = lpos_handler(&mut server, "key".to_string(), bulk(&["z", "COUNT", "1"]));
+        assert_eq!(result, Some(Value::Array(vec![])));
+    }
+
+    #[test]
+    fn test_lpos_handler_invalid_rank() {
+        let mut server = setup();
+        let result = lpos_handler(&mut server, "key".to_string(), bulk(&["c", "RANK", "0"]));
+        assert!(matches!(result, Some(Value::Error(e)) if e.starts_with("ERR RANK can't be zero")));
+    }
+}
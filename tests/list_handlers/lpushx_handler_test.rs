@@ -0,0 +1,34 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{lpushx_handler, lrange_handler, rpush_handler};
+    use redis_starter_rust::models::value::Value;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_lpushx_handler_existing_list() {
+        let mut server = setup_server();
+        rpush_handler(&mut server, "key".to_string(), bulk(&["c"]));
+
+        let result = lpushx_handler(&mut server, "key".to_string(), bulk(&["b", "a"]));
+        assert_eq!(result, Some(Value::Integer(3)));
+
+        let result = lrange_handler(&mut server, "key".to_string(), bulk(&["0", "-1"]));
+        assert_eq!(result, Some(Value::Array(bulk(&["a", "b", "c"]))));
+    }
+
+    #[test]
+    fn test_lpushx_handler_missing_key() {
+        let mut server = setup_server();
+        let result = lpushx_handler(&mut server, "key".to_string(), bulk(&["a"]));
+        assert_eq!(result, Some(Value::Integer(0)));
+        assert!(!server.cache.lock().unwrap().contains_key("key"));
+    }
+}
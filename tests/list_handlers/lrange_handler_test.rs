@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{lrange_handler, rpush_handler, set_handler};
+    use redis_starter_rust::models::value::Value;
+    use redis_starter_rust::server::Server;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    fn setup() -> Server {
+        let mut server = setup_server();
+        rpush_handler(&mut server, "key".to_string(), bulk(&["a", "b", "c", "d"]));
+        server
+    }
+
+    #[test]
+    fn test_lrange_handler_full_range() {
+        let mut server = setup();
+        let result = lrange_handler(&mut server, "key".to_string(), bulk(&["0", "-1"]));
+        assert_eq!(result, Some(Value::Array(bulk(&["a", "b", "c", "d"]))));
+    }
+
+    #[test]
+    fn test_lrange_handler_negative_indices() {
+        let mut server = setup();
+        let result = lrange_handler(&mut server, "key".to_string(), bulk(&["-3", "-2"]));
+        assert_eq!(result, Some(Value::Array(bulk(&["b", "c"]))));
+    }
+
+    #[test]
+    fn test_lrange_handler_out_of_range() {
+        let mut server = setup();
+        let result = lrange_handler(&mut server, "key".to_string(), bulk(&["2", "100"]));
+        assert_eq!(result, Some(Value::Array(bulk(&["c", "d"]))));
+
+        let result = lrange_handler(&mut server, "key".to_string(), bulk(&["5", "10"]));
+        assert_eq!(result, Some(Value::Array(vec![])));
+
+        let result = lrange_handler(&mut server, "key".to_string(), bulk(&["3", "1"]));
+        assert_eq!(result, Some(Value::Array(vec![])));
+    }
+
+    #[test]
+    fn test_lrange_handler_missing_key() {
+        let mut server = setup();
+        let result = lrange_handler(&mut server, "missing".to_string(), bulk(&["0", "-1"]));
+        assert_eq!(result, Some(Value::Array(vec![])));
+    }
+
+    #[test]
+    fn test_lrange_handler_wrong_type() {
+        let mut server = setup();
+        set_handler(&mut server, "string".to_string(), bulk(&["value"]));
+        let result = lrange_handler(&mut server, "string".to_string(), bulk(&["0", "-1"]));
+        assert_eq!(
+            result,
+            Some(Value::Error(
+                "WRONGTYPE Operation against a key holding the wrong kind of value".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_lrange_handler_invalid_index() {
+        let mut server = setup();
+        let result = lrange_handler(&mut server, "key".to_string(), bulk(&["zero", "-1"]));
+        assert_eq!(
+            result,
+            Some(Value::Error("ERR value is not an integer or out of range".to_string()))
+        );
+    }
+}
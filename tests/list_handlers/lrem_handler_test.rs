@@ -1,9 +1,10 @@
 #[cfg(test)]
 mod tests {
+    use std::collections::VecDeque;
 
     use std::time::Instant;
 
-    use redis_starter_rust::handlers::{lrem_handler, lset_handler};
+    use redis_starter_rust::handlers::{lpush_handler, lrem_handler, lset_handler};
     use redis_starter_rust::models::redis_type::RedisType;
     use redis_starter_rust::models::value::Value;
     use redis_starter_rust::{models::redis_item::RedisItem, server::Server};
@@ -21,10 +22,12 @@ mod tests {
             Value::BulkString("value3".to_string()),
         ];
         let redis_item = RedisItem {
-            value: Value::Array(list),
+            value: Value::List(VecDeque::from(list)),
             created_at: Instant::now().elapsed().as_secs() as i64,
             expiration: None,
             redis_type: RedisType::List,
+            field_expirations: Default::default(),
+            access: Default::default(),
         };
         server.cache.lock().unwrap().insert(key.clone(), redis_item);
 
@@ -86,7 +89,9 @@ mod tests {
         let result = lrem_handler(&mut server, key.clone(), lrem_args);
         assert_eq!(
             result,
-            Some(Value::Error("ERR value is not an integer".to_string()))
+            Some(Value::Error(
+                "ERR value is not an integer or out of range".to_string()
+            ))
         );
     }
 
@@ -107,4 +112,57 @@ mod tests {
             Some(Value::Error("ERR value is not a bulk string".to_string()))
         );
     }
+
+    fn list(server: &Server) -> Vec<Value> {
+        match &server.cache.lock().unwrap().get("key").unwrap().value {
+            Value::List(list) => list.iter().cloned().collect(),
+            other => panic!("unexpected value: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_lrem_handler_count_from_bulk_string() {
+        let mut server = setup();
+        for element in ["value1", "value2"] {
+            lpush_handler(
+                &mut server,
+                "key".to_string(),
+                vec![Value::BulkString(element.to_string())],
+            );
+        }
+
+        // value2 value1 value1 value2 value2 value3: drop the last value2.
+        let args = vec![
+            Value::BulkString("-1".to_string()),
+            Value::BulkString("value2".to_string()),
+        ];
+        let result = lrem_handler(&mut server, "key".to_string(), args);
+        assert_eq!(result, Some(Value::Integer(1)));
+        assert_eq!(
+            list(&server),
+            ["value2", "value1", "value1", "value2", "value3"]
+                .iter()
+                .map(|s| Value::BulkString(s.to_string()))
+                .collect::<Vec<Value>>()
+        );
+
+        let args = vec![
+            Value::BulkString("0".to_string()),
+            Value::BulkString("value2".to_string()),
+        ];
+        let result = lrem_handler(&mut server, "key".to_string(), args);
+        assert_eq!(result, Some(Value::Integer(2)));
+        assert_eq!(list(&server).len(), 3);
+    }
+
+    #[test]
+    fn test_lrem_handler_missing_key() {
+        let mut server = setup_server();
+        let args = vec![
+            Value::BulkString("1".to_string()),
+            Value::BulkString("value".to_string()),
+        ];
+        let result = lrem_handler(&mut server, "nope".to_string(), args);
+        assert_eq!(result, Some(Value::Integer(0)));
+    }
 }
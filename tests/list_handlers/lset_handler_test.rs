@@ -4,6 +4,7 @@ mod tests {
     use redis_starter_rust::models::redis_type::RedisType;
     use redis_starter_rust::models::value::Value;
     use redis_starter_rust::{models::redis_item::RedisItem, server::Server};
+    use std::collections::VecDeque;
     use std::time::Instant;
 
     use crate::setup::setup_server;
@@ -24,10 +25,12 @@ mod tests {
             Value::BulkString("value3".to_string()),
         ];
         let redis_item = RedisItem {
-            value: Value::Array(list),
+            value: Value::List(VecDeque::from(list)),
             created_at: Instant::now().elapsed().as_secs() as i64,
             expiration: None,
             redis_type: RedisType::List,
+            field_expirations: Default::default(),
+            access: Default::default(),
         };
         server.cache.lock().unwrap().insert(key.clone(), redis_item);
 
@@ -42,7 +45,7 @@ mod tests {
         // Verify the value was set correctly
         let cache = server.cache.lock().unwrap();
         let item = cache.get(&key).unwrap();
-        if let Value::Array(ref list) = item.value {
+        if let Value::List(ref list) = item.value {
             assert_eq!(list[1], Value::BulkString("new_value".to_string()));
         } else {
             panic!("Expected list value");
@@ -61,10 +64,12 @@ mod tests {
             Value::BulkString("value3".to_string()),
         ];
         let redis_item = RedisItem {
-            value: Value::Array(list),
+            value: Value::List(VecDeque::from(list)),
             created_at: Instant::now().elapsed().as_secs() as i64,
             expiration: None,
             redis_type: RedisType::List,
+            field_expirations: Default::default(),
+            access: Default::default(),
         };
         server.cache.lock().unwrap().insert(key.clone(), redis_item);
 
@@ -104,6 +109,8 @@ mod tests {
             created_at: Instant::now().elapsed().as_secs() as i64,
             expiration: None,
             redis_type: RedisType::String,
+            field_expirations: Default::default(),
+            access: Default::default(),
         };
         server.cache.lock().unwrap().insert(key.clone(), redis_item);
 
@@ -116,7 +123,7 @@ mod tests {
         assert_eq!(
             result,
             Some(Value::Error(
-                "ERR operation against a key holding the wrong kind of value".to_string()
+                "WRONGTYPE Operation against a key holding the wrong kind of value".to_string()
             ))
         );
     }
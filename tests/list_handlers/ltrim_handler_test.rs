@@ -0,0 +1,60 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{
+        lpush_handler, lrange_handler, ltrim_handler, rpush_handler,
+    };
+    use redis_starter_rust::models::value::Value;
+    use redis_starter_rust::server::Server;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    fn setup() -> Server {
+        let mut server = setup_server();
+        rpush_handler(&mut server, "key".to_string(), bulk(&["a", "b", "c", "d"]));
+        server
+    }
+
+    #[test]
+    fn test_ltrim_handler_keeps_range() {
+        let mut server = setup();
+        let result = ltrim_handler(&mut server, "key".to_string(), bulk(&["1", "-2"]));
+        assert_eq!(result, Some(Value::SimpleString("OK".to_string())));
+
+        let result = lrange_handler(&mut server, "key".to_string(), bulk(&["0", "-1"]));
+        assert_eq!(result, Some(Value::Array(bulk(&["b", "c"]))));
+    }
+
+    #[test]
+    fn test_ltrim_handler_empty_range_removes_key() {
+        let mut server = setup();
+        let result = ltrim_handler(&mut server, "key".to_string(), bulk(&["5", "10"]));
+        assert_eq!(result, Some(Value::SimpleString("OK".to_string())));
+        assert!(!server.cache.lock().unwrap().contains_key("key"));
+    }
+
+    #[test]
+    fn test_ltrim_handler_capped_feed() {
+        let mut server = setup_server();
+        for i in 0..10 {
+            lpush_handler(&mut server, "feed".to_string(), bulk(&[&i.to_string()]));
+            ltrim_handler(&mut server, "feed".to_string(), bulk(&["0", "2"]));
+        }
+
+        let result = lrange_handler(&mut server, "feed".to_string(), bulk(&["0", "-1"]));
+        assert_eq!(result, Some(Value::Array(bulk(&["9", "8", "7"]))));
+    }
+
+    #[test]
+    fn test_ltrim_handler_missing_key() {
+        let mut server = setup();
+        let result = ltrim_handler(&mut server, "missing".to_string(), bulk(&["0", "1"]));
+        assert_eq!(result, Some(Value::SimpleString("OK".to_string())));
+    }
+}
@@ -1,8 +1,17 @@
 pub mod lindex_handler_test;
+pub mod linsert_handler_test;
 pub mod llen_handler_test;
+pub mod lmove_handler_test;
+pub mod lmpop_handler_test;
 pub mod lpop_handler_test;
+pub mod lpos_handler_test;
 pub mod lpush_handler_test;
+pub mod lpushx_handler_test;
+pub mod lrange_handler_test;
 pub mod lrem_handler_test;
 pub mod lset_handler_test;
+pub mod ltrim_handler_test;
 pub mod rpop_handler_test;
+pub mod rpoplpush_handler_test;
 pub mod rpush_handler_test;
+pub mod rpushx_handler_test;
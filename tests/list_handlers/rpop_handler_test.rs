@@ -1,9 +1,10 @@
 #[cfg(test)]
 mod tests {
+    use std::collections::VecDeque;
 
     use std::time::Instant;
 
-    use redis_starter_rust::handlers::rpop_handler;
+    use redis_starter_rust::handlers::{rpop_handler, rpush_handler};
     use redis_starter_rust::models::redis_type::RedisType;
     use redis_starter_rust::models::value::Value;
     use redis_starter_rust::{models::redis_item::RedisItem, server::Server};
@@ -24,10 +25,12 @@ mod tests {
         ];
 
         let redis_item = RedisItem {
-            value: Value::Array(initial_list),
+            value: Value::List(VecDeque::from(initial_list)),
             expiration: None,
             created_at: Instant::now().elapsed().as_secs() as i64,
             redis_type: RedisType::List,
+            field_expirations: Default::default(),
+            access: Default::default(),
         };
 
         server.cache.lock().unwrap().insert(key.clone(), redis_item);
@@ -37,4 +40,45 @@ mod tests {
         let result = rpop_handler(&mut server, key.clone(), args);
         assert_eq!(result, Some(Value::BulkString("second".to_string())));
     }
+
+    #[test]
+    fn test_rpop_handler_with_count() {
+        let mut server = setup();
+        let key = "key".to_string();
+        let initial_list = vec![
+            Value::BulkString("a".to_string()),
+            Value::BulkString("b".to_string()),
+            Value::BulkString("c".to_string()),
+        ];
+        rpush_handler(&mut server, key.clone(), initial_list);
+
+        let result = rpop_handler(
+            &mut server,
+            key.clone(),
+            vec![Value::BulkString("2".to_string())],
+        );
+        assert_eq!(
+            result,
+            Some(Value::Array(vec![
+                Value::BulkString("c".to_string()),
+                Value::BulkString("b".to_string()),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_rpop_handler_negative_count() {
+        let mut server = setup();
+        let result = rpop_handler(
+            &mut server,
+            "key".to_string(),
+            vec![Value::BulkString("-1".to_string())],
+        );
+        assert_eq!(
+            result,
+            Some(Value::Error(
+                "ERR value is out of range, must be positive".to_string()
+            ))
+        );
+    }
 }
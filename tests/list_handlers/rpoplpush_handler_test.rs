@@ -0,0 +1,44 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{lrange_handler, rpoplpush_handler, rpush_handler};
+    use redis_starter_rust::models::value::Value;
+    use redis_starter_rust::server::Server;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    fn setup() -> Server {
+        let mut server = setup_server();
+        rpush_handler(&mut server, "source".to_string(), bulk(&["a", "b", "c"]));
+        rpush_handler(&mut server, "destination".to_string(), bulk(&["x"]));
+        server
+    }
+
+    #[test]
+    fn test_rpoplpush_handler() {
+        let mut server = setup();
+        let result = rpoplpush_handler(&mut server, "source".to_string(), bulk(&["destination"]));
+        assert_eq!(result, Some(Value::BulkString("c".to_string())));
+
+        let result = lrange_handler(&mut server, "destination".to_string(), bulk(&["0", "-1"]));
+        assert_eq!(result, Some(Value::Array(bulk(&["c", "x"]))));
+    }
+
+    #[test]
+    fn test_rpoplpush_handler_drains_source() {
+        let mut server = setup();
+        for _ in 0..3 {
+            rpoplpush_handler(&mut server, "source".to_string(), bulk(&["destination"]));
+        }
+        assert!(!server.cache.lock().unwrap().contains_key("source"));
+
+        let result = rpoplpush_handler(&mut server, "source".to_string(), bulk(&["destination"]));
+        assert_eq!(result, Some(Value::NullBulkString));
+    }
+}
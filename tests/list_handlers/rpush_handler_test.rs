@@ -1,5 +1,6 @@
 #[cfg(test)]
 mod tests {
+    use std::collections::VecDeque;
     use std::time::Instant;
 
     use redis_starter_rust::handlers::rpush_handler;
@@ -19,10 +20,12 @@ mod tests {
         let key = "key".to_string();
         let initial_list = vec![Value::BulkString("initial".to_string())];
         let redis_item = RedisItem {
-            value: Value::Array(initial_list),
+            value: Value::List(VecDeque::from(initial_list)),
             expiration: None,
             created_at: Instant::now().elapsed().as_secs() as i64,
             redis_type: RedisType::List,
+            field_expirations: Default::default(),
+            access: Default::default(),
         };
 
         server.cache.lock().unwrap().insert(key.clone(), redis_item);
@@ -33,12 +36,12 @@ mod tests {
 
         let cache = server.cache.lock().unwrap();
         if let Some(item) = cache.get(&key) {
-            if let Value::Array(list) = &item.value {
+            if let Value::List(list) = item.value.unpacked().as_ref() {
                 assert_eq!(list.len(), 2);
                 assert_eq!(list[0], Value::BulkString("initial".to_string()));
                 assert_eq!(list[1], Value::BulkString("new_item".to_string()));
             } else {
-                panic!("Value is not an array");
+                panic!("Value is not a list");
             }
         } else {
             panic!("Key not found in cache");
@@ -55,11 +58,11 @@ mod tests {
 
         let cache = server.cache.lock().unwrap();
         if let Some(item) = cache.get(&key) {
-            if let Value::Array(list) = &item.value {
+            if let Value::List(list) = item.value.unpacked().as_ref() {
                 assert_eq!(list.len(), 1);
                 assert_eq!(list[0], Value::BulkString("new_item".to_string()));
             } else {
-                panic!("Value is not an array");
+                panic!("Value is not a list");
             }
         } else {
             panic!("Key not found in cache");
@@ -75,6 +78,8 @@ mod tests {
             expiration: None,
             created_at: Instant::now().elapsed().as_secs() as i64,
             redis_type: RedisType::String,
+            field_expirations: Default::default(),
+            access: Default::default(),
         };
 
         server.cache.lock().unwrap().insert(key.clone(), redis_item);
@@ -84,7 +89,7 @@ mod tests {
         assert_eq!(
             result,
             Some(Value::Error(
-                "ERR operation against a key holding the wrong kind of value".to_string()
+                "WRONGTYPE Operation against a key holding the wrong kind of value".to_string()
             ))
         );
     }
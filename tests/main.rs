@@ -1,4 +1,26 @@
+mod acl;
+mod basic_handlers;
+mod commands;
+mod config;
+mod connection_handlers;
+mod database;
+mod eviction;
+mod expiration;
+mod glob;
 mod hash_handlers;
 mod key_handlers;
+mod keyspace;
+mod lazyfree;
+mod memory;
 mod list_handlers;
+mod network;
+mod packed;
+mod resp;
+mod set_handlers;
+mod tls;
+mod zset_handlers;
 pub mod setup;
+
+#[global_allocator]
+static ALLOCATOR: redis_starter_rust::memory::CountingAllocator =
+    redis_starter_rust::memory::CountingAllocator;
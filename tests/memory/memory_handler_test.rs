@@ -0,0 +1,174 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use indexmap::IndexMap;
+    use redis_starter_rust::handlers::{hset_handler, memory_handler};
+    use redis_starter_rust::memory::bytes_to_human;
+    use redis_starter_rust::models::redis_item::RedisItem;
+    use redis_starter_rust::models::value::Value;
+    use redis_starter_rust::server::Server;
+
+    use crate::setup::setup_server;
+
+    fn args(args: &[&str]) -> Vec<Value> {
+        args.iter()
+            .map(|arg| Value::BulkString(arg.to_string()))
+            .collect()
+    }
+
+    fn memory(server: &mut Server, command_args: &[&str]) -> Option<Value> {
+        memory_handler(server, String::new(), args(command_args))
+    }
+
+    fn usage(server: &mut Server, command_args: &[&str]) -> i64 {
+        match memory(server, command_args) {
+            Some(Value::Integer(bytes)) => bytes,
+            other => panic!("unexpected reply: {:?}", other),
+        }
+    }
+
+    fn insert(server: &Server, key: &str, item: RedisItem) {
+        server.cache.lock().unwrap().insert(key.to_string(), item);
+    }
+
+    #[test]
+    fn test_memory_usage() {
+        let mut server = setup_server();
+        assert_eq!(
+            memory(&mut server, &["USAGE", "missing"]),
+            Some(Value::NullBulkString)
+        );
+
+        insert(&server, "small", RedisItem::new_string("x".to_string()));
+        insert(&server, "large", RedisItem::new_string("x".repeat(4096)));
+        let small = usage(&mut server, &["USAGE", "small"]);
+        let large = usage(&mut server, &["USAGE", "large"]);
+        assert!(large > small + 4000);
+    }
+
+    #[test]
+    fn test_memory_usage_counts_nested_values() {
+        let mut server = setup_server();
+        let nested: IndexMap<String, Value> = (0..10)
+            .map(|i| (format!("field{}", i), Value::BulkString("v".repeat(1000))))
+            .collect();
+        insert(
+            &server,
+            "array",
+            RedisItem::new_list(vec![Value::Hash(nested), Value::Array(args(&["a", "b"]))]),
+        );
+        assert!(usage(&mut server, &["USAGE", "array", "SAMPLES", "0"]) > 10_000);
+
+        for i in 0..100 {
+            hset_handler(
+                &mut server,
+                "hash".to_string(),
+                args(&[&format!("field{}", i), &"v".repeat(100)]),
+            );
+        }
+        let exact = usage(&mut server, &["USAGE", "hash", "SAMPLES", "0"]);
+        let sampled = usage(&mut server, &["USAGE", "hash"]);
+        assert!(exact > 100 * 100);
+        // Every field is the same size, so sampling extrapolates exactly.
+        assert!((sampled - exact).abs() < exact / 10);
+    }
+
+    #[test]
+    fn test_memory_usage_options() {
+        let mut server = setup_server();
+        insert(&server, "key", RedisItem::new_string("value".to_string()));
+        assert_eq!(
+            memory(&mut server, &["USAGE", "key", "COUNT", "5"]),
+            Some(Value::Error("ERR syntax error".to_string()))
+        );
+        assert_eq!(
+            memory(&mut server, &["USAGE", "key", "SAMPLES", "-1"]),
+            Some(Value::Error("ERR syntax error".to_string()))
+        );
+        assert_eq!(
+            memory(&mut server, &["USAGE", "key", "SAMPLES", "many"]),
+            Some(Value::Error(
+                "ERR value is not an integer or out of range".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_memory_stats() {
+        let mut server = setup_server();
+        insert(&server, "a", RedisItem::new_string("x".repeat(100)));
+        insert(&server, "b", RedisItem::new_string("y".repeat(100)));
+
+        let reply = match memory(&mut server, &["STATS"]) {
+            Some(Value::Array(reply)) => reply,
+            other => panic!("unexpected reply: {:?}", other),
+        };
+        let stats: HashMap<String, Value> = reply
+            .chunks(2)
+            .map(|pair| match &pair[0] {
+                Value::BulkString(name) => (name.clone(), pair[1].clone()),
+                other => panic!("unexpected field name: {:?}", other),
+            })
+            .collect();
+
+        assert_eq!(stats["keys.count"], Value::Integer(2));
+        assert!(matches!(stats["total.allocated"], Value::Integer(used) if used > 0));
+        let db = match &stats["db.0"] {
+            Value::Array(db) => db,
+            other => panic!("unexpected db stats: {:?}", other),
+        };
+        assert_eq!(
+            db[0],
+            Value::BulkString("overhead.hashtable.main".to_string())
+        );
+        assert_eq!(db[2], Value::BulkString("dataset.bytes".to_string()));
+        assert!(matches!(db[3], Value::Integer(bytes) if bytes > 200));
+        assert_eq!(db[5], Value::Integer(2));
+        assert!(!stats.contains_key("db.1"));
+    }
+
+    #[test]
+    fn test_memory_doctor_reports_big_keys() {
+        let mut server = setup_server();
+        insert(&server, "huge", RedisItem::new_string("x".repeat(32 << 20)));
+        match memory(&mut server, &["DOCTOR"]) {
+            Some(Value::BulkString(report)) => {
+                assert!(report.contains("Big keys"), "{}", report);
+                assert!(report.contains("'huge' in db0"), "{}", report);
+            }
+            other => panic!("unexpected reply: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_memory_other_subcommands() {
+        let mut server = setup_server();
+        assert_eq!(
+            memory(&mut server, &["PURGE"]),
+            Some(Value::SimpleString("OK".to_string()))
+        );
+        assert!(matches!(
+            memory(&mut server, &["MALLOC-STATS"]),
+            Some(Value::BulkString(_))
+        ));
+        assert!(matches!(
+            memory(&mut server, &["HELP"]),
+            Some(Value::Array(_))
+        ));
+        assert_eq!(
+            memory(&mut server, &["STATS", "extra"]),
+            Some(Value::Error(
+                "ERR unknown subcommand or wrong number of arguments for 'STATS'. Try MEMORY HELP."
+                    .to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_bytes_to_human() {
+        assert_eq!(bytes_to_human(512), "512B");
+        assert_eq!(bytes_to_human(1536), "1.50K");
+        assert_eq!(bytes_to_human(3 << 30), "3.00G");
+    }
+}
@@ -0,0 +1 @@
+pub mod memory_handler_test;
@@ -0,0 +1 @@
+pub mod network_test;
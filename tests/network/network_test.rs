@@ -0,0 +1,104 @@
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+    use std::os::unix::fs::PermissionsExt;
+    use std::os::unix::net::UnixStream;
+    use std::time::Duration;
+
+    use redis_starter_rust::network::{
+        bind_tcp, bind_unix, spawn_unix_listener, BindAddr, BindAddrs,
+    };
+
+    use crate::setup::setup_server;
+
+    fn ip(value: &str) -> IpAddr {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_bind_addresses() {
+        let BindAddrs(addrs) = "127.0.0.1 -::1".parse().unwrap();
+        assert_eq!(
+            addrs,
+            vec![
+                BindAddr {
+                    ip: ip("127.0.0.1"),
+                    optional: false
+                },
+                BindAddr {
+                    ip: ip("::1"),
+                    optional: true
+                },
+            ]
+        );
+
+        let BindAddrs(addrs) = "* -::*".parse().unwrap();
+        assert_eq!(addrs[0].ip, IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+        assert_eq!(addrs[1].ip, IpAddr::V6(Ipv6Addr::UNSPECIFIED));
+        assert!(addrs[1].optional);
+
+        assert!("localhost".parse::<BindAddrs>().is_err());
+        assert!("".parse::<BindAddrs>().is_err());
+    }
+
+    #[test]
+    fn test_bind_tcp_skips_optional_addresses() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            // TEST-NET-1 is never assigned to a local interface.
+            let BindAddrs(addrs) = "127.0.0.1 -192.0.2.1".parse().unwrap();
+            let listeners = bind_tcp(&addrs, 0).await.unwrap();
+            assert_eq!(listeners.len(), 1);
+            assert!(listeners[0].local_addr().unwrap().ip().is_loopback());
+
+            let BindAddrs(addrs) = "127.0.0.1 192.0.2.1".parse().unwrap();
+            assert!(bind_tcp(&addrs, 0).await.is_err());
+        });
+    }
+
+    #[test]
+    fn test_unix_socket() {
+        let path = std::env::temp_dir().join(format!("network-test-{}.sock", std::process::id()));
+        let server = setup_server();
+        let (ready_tx, ready_rx) = std::sync::mpsc::channel();
+        let socket = path.clone();
+        std::thread::spawn(move || {
+            let runtime = tokio::runtime::Runtime::new().unwrap();
+            runtime.block_on(async move {
+                let listener = bind_unix(&socket, 0o700).unwrap();
+                spawn_unix_listener(server, listener);
+                ready_tx.send(()).unwrap();
+                std::future::pending::<()>().await;
+            });
+        });
+        ready_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o700);
+
+        let mut stream = UnixStream::connect(&path).unwrap();
+        stream.write_all(b"*1\r\n$4\r\nPING\r\n").unwrap();
+        let mut buffer = [0; 64];
+        let n = stream.read(&mut buffer).unwrap();
+        assert_eq!(&buffer[..n], b"+PONG\r\n");
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_protected_mode() {
+        let server = setup_server();
+        assert!(server.denies_protected(ip("10.0.0.1")));
+        assert!(!server.denies_protected(ip("127.0.0.1")));
+        assert!(!server.denies_protected(ip("::1")));
+        assert!(!server.denies_protected(ip("::ffff:127.0.0.1")));
+
+        server.acl.lock().unwrap().set_requirepass("secret");
+        assert!(!server.denies_protected(ip("10.0.0.1")));
+
+        let server = setup_server();
+        server.config_mut().args.protected_mode = false;
+        assert!(!server.denies_protected(ip("10.0.0.1")));
+    }
+}
@@ -0,0 +1 @@
+pub mod packed_test;
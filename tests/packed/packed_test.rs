@@ -0,0 +1,321 @@
+#[cfg(test)]
+mod tests {
+    use indexmap::{IndexMap, IndexSet};
+    use redis_starter_rust::commands::dispatch;
+    use redis_starter_rust::config;
+    use redis_starter_rust::models::packed::{Listpack, Packed};
+    use redis_starter_rust::models::value::Value;
+    use redis_starter_rust::server::Server;
+
+    use crate::setup::setup_server;
+
+    fn args(args: &[&str]) -> Vec<Value> {
+        args.iter()
+            .map(|arg| Value::BulkString(arg.to_string()))
+            .collect()
+    }
+
+    fn run(server: &mut Server, command: &str, command_args: &[&str]) -> Option<Value> {
+        dispatch(server, command, args(command_args))
+    }
+
+    fn encoding(server: &mut Server, key: &str) -> String {
+        match run(server, "OBJECT", &["ENCODING", key]) {
+            Some(Value::BulkString(encoding)) => encoding,
+            other => panic!("unexpected reply: {:?}", other),
+        }
+    }
+
+    fn set_config(server: &Server, name: &str, value: &str) {
+        config::set(server, &[(name.to_string(), value.to_string())]).unwrap();
+    }
+
+    #[test]
+    fn test_hash_converts_past_max_entries() {
+        let mut server = setup_server();
+        set_config(&server, "hash-max-listpack-entries", "4");
+        for i in 0..4 {
+            run(&mut server, "HSET", &["h", &format!("f{}", i), "v"]);
+        }
+        assert_eq!(encoding(&mut server, "h"), "listpack");
+        assert_eq!(
+            run(&mut server, "HGET", &["h", "f2"]),
+            Some(Value::BulkString("v".to_string()))
+        );
+
+        run(&mut server, "HSET", &["h", "f4", "v"]);
+        assert_eq!(encoding(&mut server, "h"), "hashtable");
+        // Like Redis, a hash that shrinks back keeps its full encoding.
+        run(&mut server, "HDEL", &["h", "f4", "f3"]);
+        assert_eq!(encoding(&mut server, "h"), "hashtable");
+        assert_eq!(run(&mut server, "HLEN", &["h"]), Some(Value::Integer(3)));
+    }
+
+    #[test]
+    fn test_hash_converts_on_long_values_and_field_ttls() {
+        let mut server = setup_server();
+        run(&mut server, "HSET", &["long", "f", &"x".repeat(65)]);
+        assert_eq!(encoding(&mut server, "long"), "hashtable");
+
+        run(&mut server, "HSET", &["ttl", "f", "v", "g", "w"]);
+        assert_eq!(encoding(&mut server, "ttl"), "listpack");
+        run(&mut server, "HEXPIRE", &["ttl", "100", "FIELDS", "1", "f"]);
+        assert_eq!(encoding(&mut server, "ttl"), "hashtable");
+        assert!(matches!(
+            run(&mut server, "HTTL", &["ttl", "FIELDS", "1", "f"]),
+            Some(Value::Array(ttls)) if matches!(ttls[0], Value::Integer(ttl) if ttl > 0)
+        ));
+    }
+
+    #[test]
+    fn test_hincrby_on_a_packed_hash() {
+        let mut server = setup_server();
+        run(&mut server, "HSET", &["h", "count", "10", "name", "x"]);
+        assert_eq!(
+            run(&mut server, "HINCRBY", &["h", "count", "5"]),
+            Some(Value::Integer(15))
+        );
+        assert_eq!(encoding(&mut server, "h"), "listpack");
+        assert_eq!(
+            run(&mut server, "HGET", &["h", "count"]),
+            Some(Value::BulkString("15".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_set_encodings() {
+        let mut server = setup_server();
+        set_config(&server, "set-max-listpack-entries", "3");
+        run(&mut server, "SADD", &["s", "1", "-200", "70000"]);
+        assert_eq!(encoding(&mut server, "s"), "intset");
+
+        // Not an integer the way Redis prints them.
+        run(&mut server, "SADD", &["s", "007"]);
+        assert_eq!(encoding(&mut server, "s"), "hashtable");
+
+        run(&mut server, "SADD", &["t", "1", "a"]);
+        assert_eq!(encoding(&mut server, "t"), "listpack");
+        run(&mut server, "SADD", &["t", "b", "c"]);
+        assert_eq!(encoding(&mut server, "t"), "hashtable");
+
+        set_config(&server, "set-max-intset-entries", "2");
+        run(&mut server, "SADD", &["u", "1", "2", "3"]);
+        assert_eq!(encoding(&mut server, "u"), "listpack");
+    }
+
+    #[test]
+    fn test_zset_converts_past_max_entries() {
+        let mut server = setup_server();
+        set_config(&server, "zset-max-listpack-entries", "2");
+        run(&mut server, "ZADD", &["z", "1.5", "a", "-2", "b"]);
+        assert_eq!(encoding(&mut server, "z"), "listpack");
+        assert_eq!(
+            run(&mut server, "ZADD", &["z", "2.5", "a"]),
+            Some(Value::Integer(0))
+        );
+
+        run(&mut server, "ZADD", &["z", "3", "c"]);
+        assert_eq!(encoding(&mut server, "z"), "skiplist");
+        assert_eq!(
+            run(&mut server, "SORT", &["z", "BY", "nosort"]),
+            Some(Value::Array(args(&["b", "a", "c"])))
+        );
+    }
+
+    #[test]
+    fn test_list_size_limits() {
+        let mut server = setup_server();
+        run(&mut server, "RPUSH", &["bytes", "a", "b", "c"]);
+        assert_eq!(encoding(&mut server, "bytes"), "listpack");
+        run(&mut server, "RPUSH", &["bytes", &"x".repeat(9000)]);
+        assert_eq!(encoding(&mut server, "bytes"), "quicklist");
+
+        set_config(&server, "list-max-listpack-size", "3");
+        run(&mut server, "LPUSH", &["count", "a", "b", "c"]);
+        assert_eq!(encoding(&mut server, "count"), "listpack");
+        run(&mut server, "LPUSH", &["count", "d"]);
+        assert_eq!(encoding(&mut server, "count"), "quicklist");
+        assert_eq!(
+            run(&mut server, "LRANGE", &["count", "0", "-1"]),
+            Some(Value::Array(args(&["d", "c", "b", "a"])))
+        );
+    }
+
+    #[test]
+    fn test_listpack_ends() {
+        let long = "y".repeat(300);
+        let mut listpack = Listpack::default();
+        listpack
+            .push_back(&Value::BulkString("b".to_string()))
+            .unwrap();
+        listpack
+            .push_front(&Value::BulkString(long.clone()))
+            .unwrap();
+        listpack.push_back(&Value::Integer(-7)).unwrap();
+        listpack
+            .push_front(&Value::BulkString("12".to_string()))
+            .unwrap();
+        assert_eq!(listpack.len(), 4);
+        assert_eq!(
+            listpack.get(1).map(|entry| entry.to_value()),
+            Some(Value::BulkString(long.clone()))
+        );
+        assert_eq!(
+            listpack
+                .iter()
+                .rev()
+                .map(|entry| entry.to_value())
+                .collect::<Vec<_>>(),
+            vec![
+                Value::Integer(-7),
+                Value::BulkString("b".to_string()),
+                Value::BulkString(long.clone()),
+                Value::BulkString("12".to_string()),
+            ]
+        );
+        assert!(listpack.get(0).unwrap().is_str("12"));
+
+        assert_eq!(listpack.pop_back(), Some(Value::Integer(-7)));
+        assert_eq!(
+            listpack.pop_front(),
+            Some(Value::BulkString("12".to_string()))
+        );
+        assert_eq!(listpack.pop_front(), Some(Value::BulkString(long)));
+        assert_eq!(
+            listpack.pop_back(),
+            Some(Value::BulkString("b".to_string()))
+        );
+        assert_eq!(listpack.pop_back(), None);
+        assert!(listpack.is_empty());
+    }
+
+    #[test]
+    fn test_commands_on_packed_lists_and_hashes() {
+        let mut server = setup_server();
+        run(&mut server, "RPUSH", &["l", "a", "b", "c", "b"]);
+        run(&mut server, "LPUSH", &["l", "z"]);
+        assert_eq!(
+            run(&mut server, "RPOP", &["l"]),
+            Some(Value::BulkString("b".to_string()))
+        );
+        assert_eq!(
+            run(&mut server, "LPOP", &["l"]),
+            Some(Value::BulkString("z".to_string()))
+        );
+        assert_eq!(encoding(&mut server, "l"), "listpack");
+        assert_eq!(run(&mut server, "LLEN", &["l"]), Some(Value::Integer(3)));
+        assert_eq!(
+            dispatch(
+                &mut server,
+                "LINDEX",
+                vec![Value::BulkString("l".to_string()), Value::Integer(-1)]
+            ),
+            Some(Value::BulkString("c".to_string()))
+        );
+        assert_eq!(
+            run(&mut server, "LRANGE", &["l", "1", "-1"]),
+            Some(Value::Array(args(&["b", "c"])))
+        );
+        assert_eq!(
+            run(&mut server, "LPOS", &["l", "c", "RANK", "-1"]),
+            Some(Value::Integer(2))
+        );
+        assert_eq!(
+            run(&mut server, "LMOVE", &["l", "m", "LEFT", "RIGHT"]),
+            Some(Value::BulkString("a".to_string()))
+        );
+        assert_eq!(encoding(&mut server, "m"), "listpack");
+        assert_eq!(
+            run(
+                &mut server,
+                "LMPOP",
+                &["2", "x", "l", "RIGHT", "COUNT", "5"]
+            ),
+            Some(Value::Array(vec![
+                Value::BulkString("l".to_string()),
+                Value::Array(args(&["c", "b"])),
+            ]))
+        );
+        assert_eq!(run(&mut server, "EXISTS", &["l"]), Some(Value::Integer(0)));
+
+        run(&mut server, "HSET", &["h", "1", "one", "name", "x"]);
+        assert_eq!(
+            run(&mut server, "HGET", &["h", "1"]),
+            Some(Value::BulkString("one".to_string()))
+        );
+        assert_eq!(
+            run(&mut server, "HEXISTS", &["h", "01"]),
+            Some(Value::Integer(0))
+        );
+        assert_eq!(
+            run(&mut server, "HSTRLEN", &["h", "name"]),
+            Some(Value::Integer(1))
+        );
+        assert_eq!(
+            run(&mut server, "HGETALL", &["h"]),
+            Some(Value::Array(args(&["1", "one", "name", "x"])))
+        );
+        assert_eq!(encoding(&mut server, "h"), "listpack");
+    }
+
+    #[test]
+    fn test_packing_round_trips() {
+        let limits = setup_server().pack_limits();
+        let hash = Value::Hash(IndexMap::from([
+            ("text".to_string(), Value::BulkString("hello".to_string())),
+            ("digits".to_string(), Value::BulkString("-42".to_string())),
+            ("padded".to_string(), Value::BulkString("042".to_string())),
+            ("integer".to_string(), Value::Integer(42)),
+            ("empty".to_string(), Value::BulkString(String::new())),
+        ]));
+        let zset = Value::ZSet(IndexMap::from([
+            ("a".to_string(), 1.5),
+            ("b".to_string(), f64::NEG_INFINITY),
+        ]));
+        let set = Value::Set(IndexSet::from([
+            i64::MIN.to_string(),
+            "0".to_string(),
+            i64::MAX.to_string(),
+        ]));
+        let list = Value::List(args(&["x", "1", "", "x"]).into());
+        for value in [hash, zset, set, list] {
+            let packed = Packed::pack(&value, &limits).unwrap();
+            assert_eq!(packed.unpack(), value);
+        }
+
+        // Nested values have no packed form.
+        let nested = Value::List(vec![Value::Array(args(&["a"]))].into());
+        assert_eq!(Packed::pack(&nested, &limits), None);
+    }
+
+    #[test]
+    fn test_packed_hash_is_smaller() {
+        let limits = setup_server().pack_limits();
+        let hash = Value::Hash(
+            (0..20)
+                .map(|i| (format!("field{}", i), Value::BulkString(i.to_string())))
+                .collect(),
+        );
+        let packed = Value::Packed(Packed::pack(&hash, &limits).unwrap());
+        assert!(packed.heap_size() * 5 < hash.heap_size());
+    }
+
+    #[test]
+    fn test_config_params() {
+        let server = setup_server();
+        set_config(&server, "list-max-listpack-size", "-5");
+        assert_eq!(server.pack_limits().list_size, -5);
+        assert_eq!(
+            server.config().get(&["hash-max-listpack-*".to_string()]),
+            vec![
+                ("hash-max-listpack-entries", "128".to_string()),
+                ("hash-max-listpack-value", "64".to_string()),
+            ]
+        );
+        assert!(config::set(
+            &server,
+            &[("zset-max-listpack-value".to_string(), "big".to_string())]
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1 @@
+pub mod resp_test;
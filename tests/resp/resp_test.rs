@@ -0,0 +1,168 @@
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+
+    use redis_starter_rust::models::value::Value;
+    use redis_starter_rust::resp::RespHandler;
+    use redis_starter_rust::server::Server;
+    use redis_starter_rust::utilities::parse_message;
+
+    use crate::setup::setup_server;
+
+    fn command(args: &[&str]) -> String {
+        let frame = Value::Array(
+            args.iter()
+                .map(|arg| Value::BulkString(arg.to_string()))
+                .collect(),
+        )
+        .serialize();
+        String::from_utf8(frame).unwrap()
+    }
+
+    // Serves `server` on an ephemeral port and connects a client to it.
+    fn connect(server: Server) -> TcpStream {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        listener.set_nonblocking(true).unwrap();
+        std::thread::spawn(move || {
+            let runtime = tokio::runtime::Runtime::new().unwrap();
+            runtime.block_on(async move {
+                let listener = tokio::net::TcpListener::from_std(listener).unwrap();
+                let (stream, _) = listener.accept().await.unwrap();
+                let _ = RespHandler::new(stream).handle_client(server).await;
+            });
+        });
+        TcpStream::connect(("127.0.0.1", port)).unwrap()
+    }
+
+    // Reads until `expected` bytes have arrived.
+    fn read_reply(stream: &mut TcpStream, expected: usize) -> String {
+        let mut reply = vec![0; expected];
+        stream.read_exact(&mut reply).unwrap();
+        String::from_utf8(reply).unwrap()
+    }
+
+    #[test]
+    fn test_parse_message_incomplete() {
+        let frame = command(&["SET", "key", "value"]);
+        for end in 0..frame.len() {
+            assert!(parse_message(&frame.as_bytes()[..end]).unwrap().is_none());
+        }
+
+        let pipelined = format!("{}{}", frame, command(&["GET", "key"]));
+        let (value, len) = parse_message(pipelined.as_bytes()).unwrap().unwrap();
+        assert_eq!(len, frame.len());
+        assert_eq!(value.serialize(), frame.as_bytes());
+
+        assert!(parse_message(b"?oops\r\n").is_err());
+        assert!(parse_message(b"*1\r\n$-5\r\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_message_limits() {
+        let nested = "*1\r\n".repeat(2000);
+        assert!(parse_message(nested.as_bytes()).is_err());
+        let shallow = format!("{}:1\r\n", "*1\r\n".repeat(8));
+        assert!(parse_message(shallow.as_bytes()).unwrap().is_some());
+
+        assert!(parse_message(b"*2000000\r\n").is_err());
+        assert!(parse_message(b"$1000000000\r\n").is_err());
+    }
+
+    #[test]
+    fn test_deeply_nested_frame_is_a_protocol_error() {
+        let mut stream = connect(setup_server());
+        stream.write_all("*1\r\n".repeat(2000).as_bytes()).unwrap();
+        let expected = "-ERR Protocol error: too many nested arrays\r\n";
+        assert_eq!(read_reply(&mut stream, expected.len()), expected);
+    }
+
+    #[test]
+    fn test_binary_bulk_strings() {
+        let frame = b"$3\r\na\xff\x80\r\n";
+        let (value, len) = parse_message(frame).unwrap().unwrap();
+        assert_eq!(value, Value::Bytes(b"a\xff\x80".to_vec()));
+        assert_eq!(len, frame.len());
+        assert_eq!(value.serialize(), frame);
+    }
+
+    #[test]
+    fn test_dump_payload_travels_as_raw_bytes() {
+        let mut stream = connect(setup_server());
+        stream
+            .write_all(command(&["SET", "key", "hello"]).as_bytes())
+            .unwrap();
+        assert_eq!(read_reply(&mut stream, 5), "+OK\r\n");
+
+        // Type, length-prefixed string, version and CRC64: 17 bytes, whatever
+        // their values.
+        stream
+            .write_all(command(&["DUMP", "key"]).as_bytes())
+            .unwrap();
+        let mut reply = vec![0; 24];
+        stream.read_exact(&mut reply).unwrap();
+        assert_eq!(&reply[..5], b"$17\r\n");
+        assert_eq!(&reply[22..], b"\r\n");
+
+        let mut restore = b"*4\r\n$7\r\nRESTORE\r\n$4\r\ncopy\r\n$1\r\n0\r\n".to_vec();
+        restore.extend_from_slice(&reply);
+        stream.write_all(&restore).unwrap();
+        assert_eq!(read_reply(&mut stream, 5), "+OK\r\n");
+        stream
+            .write_all(command(&["GET", "copy"]).as_bytes())
+            .unwrap();
+        assert_eq!(read_reply(&mut stream, 11), "$5\r\nhello\r\n");
+    }
+
+    #[test]
+    fn test_pipelined_commands() {
+        let mut stream = connect(setup_server());
+        let pipeline = [
+            command(&["SET", "a", "1"]),
+            command(&["INCR", "a"]),
+            command(&["GET", "a"]),
+            command(&["PING"]),
+        ]
+        .concat();
+        stream.write_all(pipeline.as_bytes()).unwrap();
+
+        let expected = "+OK\r\n:2\r\n$1\r\n2\r\n+PONG\r\n";
+        assert_eq!(read_reply(&mut stream, expected.len()), expected);
+    }
+
+    #[test]
+    fn test_large_value_in_split_writes() {
+        let mut stream = connect(setup_server());
+        let value = "x".repeat(100_000);
+        let set = command(&["SET", "big", &value]);
+        for chunk in set.as_bytes().chunks(1000) {
+            stream.write_all(chunk).unwrap();
+            stream.flush().unwrap();
+        }
+        assert_eq!(read_reply(&mut stream, 5), "+OK\r\n");
+
+        stream
+            .write_all(command(&["GET", "big"]).as_bytes())
+            .unwrap();
+        let expected = String::from_utf8(Value::BulkString(value).serialize()).unwrap();
+        assert_eq!(read_reply(&mut stream, expected.len()), expected);
+    }
+
+    #[test]
+    fn test_query_buffer_limit() {
+        let server = setup_server();
+        server.config_mut().args.client_query_buffer_limit = 1024;
+        let mut stream = connect(server);
+
+        stream
+            .write_all(command(&["SET", "small", "v"]).as_bytes())
+            .unwrap();
+        assert_eq!(read_reply(&mut stream, 5), "+OK\r\n");
+
+        // The server closes the connection instead of buffering the rest.
+        let _ = stream.write_all(command(&["SET", "big", &"x".repeat(64 * 1024)]).as_bytes());
+        let mut buffer = [0; 16];
+        assert!(matches!(stream.read(&mut buffer), Ok(0) | Err(_)));
+    }
+}
@@ -0,0 +1,2 @@
+pub mod sadd_handler_test;
+pub mod sscan_handler_test;
@@ -0,0 +1,45 @@
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexSet;
+    use redis_starter_rust::handlers::{sadd_handler, set_handler};
+    use redis_starter_rust::models::value::Value;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_sadd_handler() {
+        let mut server = setup_server();
+        let result = sadd_handler(&mut server, "myset".to_string(), bulk(&["a", "b", "a"]));
+        assert_eq!(result, Some(Value::Integer(2)));
+
+        let result = sadd_handler(&mut server, "myset".to_string(), bulk(&["b", "c"]));
+        assert_eq!(result, Some(Value::Integer(1)));
+
+        let cache = server.cache.lock().unwrap();
+        let expected: IndexSet<String> = ["a", "b", "c"].iter().map(|m| m.to_string()).collect();
+        assert_eq!(
+            *cache.get("myset").unwrap().value.unpacked(),
+            Value::Set(expected)
+        );
+    }
+
+    #[test]
+    fn test_sadd_handler_wrong_type() {
+        let mut server = setup_server();
+        set_handler(&mut server, "mykey".to_string(), bulk(&["value"]));
+        let result = sadd_handler(&mut server, "mykey".to_string(), bulk(&["a"]));
+        assert_eq!(
+            result,
+            Some(Value::Error(
+                "WRONGTYPE Operation against a key holding the wrong kind of value".to_string()
+            ))
+        );
+    }
+}
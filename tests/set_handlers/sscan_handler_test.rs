@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use redis_starter_rust::handlers::{sadd_handler, sscan_handler};
+    use redis_starter_rust::models::value::Value;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_sscan_handler_visits_every_member() {
+        let mut server = setup_server();
+        // Large enough not to be packed.
+        let members: Vec<String> = (0..200).map(|i| format!("member:{}", i)).collect();
+        let refs: Vec<&str> = members.iter().map(String::as_str).collect();
+        sadd_handler(&mut server, "myset".to_string(), bulk(&refs));
+
+        let mut cursor = "0".to_string();
+        let mut seen = HashSet::new();
+        loop {
+            match sscan_handler(
+                &mut server,
+                "myset".to_string(),
+                bulk(&[&cursor, "COUNT", "4"]),
+            ) {
+                Some(Value::Array(reply)) => {
+                    if let Value::Array(page) = &reply[1] {
+                        assert!(page.len() <= 4);
+                        seen.extend(page.iter().map(|member| format!("{:?}", member)));
+                    }
+                    match &reply[0] {
+                        Value::BulkString(next) if next == "0" => break,
+                        Value::BulkString(next) => cursor = next.clone(),
+                        other => panic!("Expected cursor, got {:?}", other),
+                    }
+                }
+                other => panic!("Expected array, got {:?}", other),
+            }
+        }
+        assert_eq!(seen.len(), 200);
+    }
+
+    #[test]
+    fn test_sscan_handler_match() {
+        let mut server = setup_server();
+        sadd_handler(
+            &mut server,
+            "myset".to_string(),
+            bulk(&["apple", "avocado", "banana"]),
+        );
+
+        match sscan_handler(
+            &mut server,
+            "myset".to_string(),
+            bulk(&["0", "MATCH", "a*", "COUNT", "10"]),
+        ) {
+            Some(Value::Array(reply)) => {
+                assert_eq!(reply[0], Value::BulkString("0".to_string()));
+                match &reply[1] {
+                    Value::Array(page) => {
+                        let mut page = page.clone();
+                        page.sort_by_key(|v| format!("{:?}", v));
+                        assert_eq!(page, bulk(&["apple", "avocado"]));
+                    }
+                    other => panic!("Expected members, got {:?}", other),
+                }
+            }
+            other => panic!("Expected array, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_sscan_handler_returns_packed_sets_whole() {
+        let mut server = setup_server();
+        sadd_handler(&mut server, "myset".to_string(), bulk(&["a", "b", "c"]));
+
+        match sscan_handler(&mut server, "myset".to_string(), bulk(&["0", "COUNT", "1"])) {
+            Some(Value::Array(reply)) => {
+                assert_eq!(reply[0], Value::BulkString("0".to_string()));
+                assert!(matches!(&reply[1], Value::Array(page) if page.len() == 3));
+            }
+            other => panic!("Expected array, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_sscan_handler_missing_key() {
+        let mut server = setup_server();
+        let result = sscan_handler(&mut server, "missing".to_string(), bulk(&["0"]));
+        assert_eq!(
+            result,
+            Some(Value::Array(vec![
+                Value::BulkString("0".to_string()),
+                Value::Array(vec![]),
+            ]))
+        );
+    }
+}
@@ -1,21 +1,28 @@
-use std::{
-    collections::HashMap,
-    sync::{Arc, Mutex},
-};
+use std::sync::Arc;
 
 use redis_starter_rust::{
+    lazyfree::LazyFree,
     server::{Role, Server},
+    stats::Stats,
     utilities::ServerState,
 };
 
 pub fn setup_server() -> Server {
-    let server = Server {
-        cache: Arc::new(Mutex::new(HashMap::new())),
+    let databases = Server::create_databases(16);
+    Server {
+        cache: databases[0].clone(),
+        databases,
+        db_index: 0,
+        lazyfree: Arc::new(LazyFree::default()),
+        stats: Arc::new(Stats::default()),
         role: Role::Main,
         port: 6379,
         sync: false,
         server_state: ServerState::Initialising,
-    };
-
-    server
+        acl: Default::default(),
+        user: "default".to_string(),
+        tls: None,
+        config: Default::default(),
+        eviction_pool: Default::default(),
+    }
 }
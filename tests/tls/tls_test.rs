@@ -0,0 +1,221 @@
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+    use std::sync::Arc;
+
+    use rcgen::{
+        BasicConstraints, Certificate, CertificateParams, DnType, ExtendedKeyUsagePurpose, IsCa,
+        KeyPair,
+    };
+    use redis_starter_rust::resp::RespHandler;
+    use redis_starter_rust::tls::{AuthClients, TlsConfig, TlsContext, TlsError};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    use crate::setup::setup_server;
+
+    struct Ca {
+        cert: Certificate,
+        key: KeyPair,
+    }
+
+    impl Ca {
+        fn new() -> Self {
+            let key = KeyPair::generate().unwrap();
+            let mut params = CertificateParams::new(Vec::<String>::new()).unwrap();
+            params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+            params
+                .distinguished_name
+                .push(DnType::CommonName, "Test CA");
+            Ca {
+                cert: params.self_signed(&key).unwrap(),
+                key,
+            }
+        }
+
+        // Issues a certificate for 127.0.0.1, returning its PEM and its key's.
+        fn issue(&self, usage: ExtendedKeyUsagePurpose) -> (Certificate, String) {
+            let key = KeyPair::generate().unwrap();
+            let mut params = CertificateParams::new(vec!["127.0.0.1".to_string()]).unwrap();
+            params.extended_key_usages = vec![usage];
+            let cert = params.signed_by(&key, &self.cert, &self.key).unwrap();
+            (cert, key.serialize_pem())
+        }
+    }
+
+    // A directory holding ca.pem, server.pem/server.key and
+    // client.pem/client.key.
+    fn write_certs(name: &str, ca: &Ca) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("tls-test-{}-{}", std::process::id(), name));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("ca.pem"), ca.cert.pem()).unwrap();
+        for (name, usage) in [
+            ("server", ExtendedKeyUsagePurpose::ServerAuth),
+            ("client", ExtendedKeyUsagePurpose::ClientAuth),
+        ] {
+            let (cert, key) = ca.issue(usage);
+            std::fs::write(dir.join(format!("{}.pem", name)), cert.pem()).unwrap();
+            std::fs::write(dir.join(format!("{}.key", name)), key).unwrap();
+        }
+        dir
+    }
+
+    fn server_config(dir: &Path, auth_clients: AuthClients) -> TlsConfig {
+        TlsConfig {
+            port: 1,
+            cert_file: Some(dir.join("server.pem")),
+            key_file: Some(dir.join("server.key")),
+            ca_cert_file: Some(dir.join("ca.pem")),
+            auth_clients,
+            replication: false,
+        }
+    }
+
+    // The configuration of a replica connecting to the server, with or
+    // without a client certificate.
+    fn client_context(dir: &Path, with_cert: bool) -> TlsContext {
+        TlsContext::new(TlsConfig {
+            port: 0,
+            cert_file: with_cert.then(|| dir.join("client.pem")),
+            key_file: with_cert.then(|| dir.join("client.key")),
+            ca_cert_file: Some(dir.join("ca.pem")),
+            auth_clients: AuthClients::No,
+            replication: true,
+        })
+        .unwrap()
+    }
+
+    // Serves a fresh server over TLS on an ephemeral port.
+    async fn spawn_tls_server(tls: Arc<TlsContext>) -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = setup_server();
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let acceptor = tls.acceptor().unwrap();
+                let server = server.clone();
+                tokio::spawn(async move {
+                    if let Ok(stream) = acceptor.accept(stream).await {
+                        let _ = RespHandler::new(stream).handle_client(server).await;
+                    }
+                });
+            }
+        });
+        port
+    }
+
+    // Sends PING over a TLS connection, returning the reply or None when the
+    // server drops the connection.
+    async fn ping(client: &TlsContext, port: u16) -> Option<String> {
+        let stream = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+        let mut stream = client.connect("127.0.0.1", stream).await.ok()?;
+        stream.write_all(b"*1\r\n$4\r\nPING\r\n").await.ok()?;
+        let mut buffer = [0; 64];
+        match stream.read(&mut buffer).await {
+            Ok(n) if n > 0 => Some(String::from_utf8_lossy(&buffer[..n]).to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_tls_mutual_authentication() {
+        let dir = write_certs("mtls", &Ca::new());
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            let tls = Arc::new(TlsContext::new(server_config(&dir, AuthClients::Yes)).unwrap());
+            let port = spawn_tls_server(tls).await;
+
+            assert_eq!(
+                ping(&client_context(&dir, true), port).await.as_deref(),
+                Some("+PONG\r\n")
+            );
+            assert_eq!(ping(&client_context(&dir, false), port).await, None);
+        });
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_tls_optional_client_certificate() {
+        let dir = write_certs("optional", &Ca::new());
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            let config = server_config(&dir, AuthClients::Optional);
+            let port = spawn_tls_server(Arc::new(TlsContext::new(config).unwrap())).await;
+
+            assert_eq!(
+                ping(&client_context(&dir, false), port).await.as_deref(),
+                Some("+PONG\r\n")
+            );
+        });
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_tls_rejects_untrusted_server() {
+        let dir = write_certs("untrusted", &Ca::new());
+        let other = write_certs("untrusted-other", &Ca::new());
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            let config = server_config(&dir, AuthClients::No);
+            let port = spawn_tls_server(Arc::new(TlsContext::new(config).unwrap())).await;
+
+            assert_eq!(ping(&client_context(&other, true), port).await, None);
+        });
+        std::fs::remove_dir_all(dir).unwrap();
+        std::fs::remove_dir_all(other).unwrap();
+    }
+
+    #[test]
+    fn test_tls_reload() {
+        let ca = Ca::new();
+        let dir = write_certs("reload", &ca);
+        let tls = Arc::new(TlsContext::new(server_config(&dir, AuthClients::No)).unwrap());
+        assert!(!tls.reload_if_changed().unwrap());
+
+        let (cert, key) = ca.issue(ExtendedKeyUsagePurpose::ServerAuth);
+        std::fs::write(dir.join("server.pem"), cert.pem()).unwrap();
+        std::fs::write(dir.join("server.key"), key).unwrap();
+        assert!(tls.reload_if_changed().unwrap());
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            let port = spawn_tls_server(tls.clone()).await;
+            let stream = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+            let stream = client_context(&dir, false)
+                .connect("127.0.0.1", stream)
+                .await
+                .unwrap();
+            let presented = stream.get_ref().1.peer_certificates().unwrap()[0].clone();
+            assert_eq!(presented.as_ref(), cert.der().as_ref());
+        });
+
+        // A broken file keeps the previous certificate in use.
+        std::fs::write(dir.join("server.pem"), "not a certificate").unwrap();
+        assert!(matches!(
+            tls.reload_if_changed(),
+            Err(TlsError::NoCertificate(_))
+        ));
+        assert!(tls.acceptor().is_some());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_tls_config_errors() {
+        let dir = write_certs("errors", &Ca::new());
+        let mut config = server_config(&dir, AuthClients::Yes);
+        config.ca_cert_file = None;
+        assert!(matches!(
+            TlsContext::new(config),
+            Err(TlsError::Missing("tls-ca-cert-file"))
+        ));
+
+        let mut config = server_config(&dir, AuthClients::No);
+        config.key_file = None;
+        assert!(matches!(
+            TlsContext::new(config),
+            Err(TlsError::Missing("tls-cert-file and tls-key-file"))
+        ));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
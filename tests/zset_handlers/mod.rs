@@ -0,0 +1,2 @@
+pub mod zadd_handler_test;
+pub mod zscan_handler_test;
@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::zadd_handler;
+    use redis_starter_rust::models::value::Value;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    fn score(server: &redis_starter_rust::server::Server, member: &str) -> Option<f64> {
+        match server
+            .cache
+            .lock()
+            .unwrap()
+            .get("myzset")?
+            .value
+            .unpacked()
+            .as_ref()
+        {
+            Value::ZSet(zset) => zset.get(member).copied(),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_zadd_handler() {
+        let mut server = setup_server();
+        let result = zadd_handler(
+            &mut server,
+            "myzset".to_string(),
+            bulk(&["1", "one", "2.5", "two"]),
+        );
+        assert_eq!(result, Some(Value::Integer(2)));
+
+        let result = zadd_handler(&mut server, "myzset".to_string(), bulk(&["3", "two"]));
+        assert_eq!(result, Some(Value::Integer(0)));
+        assert_eq!(score(&server, "two"), Some(3.0));
+    }
+
+    #[test]
+    fn test_zadd_handler_flags() {
+        let mut server = setup_server();
+        zadd_handler(&mut server, "myzset".to_string(), bulk(&["1", "one"]));
+
+        let nx = zadd_handler(
+            &mut server,
+            "myzset".to_string(),
+            bulk(&["NX", "5", "one", "2", "two"]),
+        );
+        assert_eq!(nx, Some(Value::Integer(1)));
+        assert_eq!(score(&server, "one"), Some(1.0));
+
+        let xx = zadd_handler(
+            &mut server,
+            "myzset".to_string(),
+            bulk(&["XX", "CH", "7", "one", "3", "three"]),
+        );
+        assert_eq!(xx, Some(Value::Integer(1)));
+        assert_eq!(score(&server, "one"), Some(7.0));
+        assert_eq!(score(&server, "three"), None);
+    }
+
+    #[test]
+    fn test_zadd_handler_invalid_score() {
+        let mut server = setup_server();
+        let result = zadd_handler(&mut server, "myzset".to_string(), bulk(&["abc", "one"]));
+        assert_eq!(
+            result,
+            Some(Value::Error("ERR value is not a valid float".to_string()))
+        );
+    }
+}
@@ -0,0 +1,48 @@
+#[cfg(test)]
+mod tests {
+    use redis_starter_rust::handlers::{zadd_handler, zscan_handler};
+    use redis_starter_rust::models::value::Value;
+
+    use crate::setup::setup_server;
+
+    fn bulk(values: &[&str]) -> Vec<Value> {
+        values
+            .iter()
+            .map(|v| Value::BulkString(v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_zscan_handler() {
+        let mut server = setup_server();
+        zadd_handler(&mut server, "myzset".to_string(), bulk(&["1.5", "one"]));
+
+        let result = zscan_handler(&mut server, "myzset".to_string(), bulk(&["0"]));
+        assert_eq!(
+            result,
+            Some(Value::Array(vec![
+                Value::BulkString("0".to_string()),
+                Value::Array(bulk(&["one", "1.5"])),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_zscan_handler_noscores() {
+        let mut server = setup_server();
+        zadd_handler(
+            &mut server,
+            "myzset".to_string(),
+            bulk(&["1", "one", "2", "two"]),
+        );
+
+        match zscan_handler(
+            &mut server,
+            "myzset".to_string(),
+            bulk(&["0", "MATCH", "t*", "NOSCORES"]),
+        ) {
+            Some(Value::Array(reply)) => assert_eq!(reply[1], Value::Array(bulk(&["two"]))),
+            other => panic!("Expected array, got {:?}", other),
+        }
+    }
+}